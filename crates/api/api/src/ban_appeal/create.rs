@@ -0,0 +1,79 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{check_local_user_deleted, slur_regex},
+};
+use lemmy_db_schema::source::{
+  ban_appeal::{BanAppeal, BanAppealInsertForm},
+  community::Community,
+  modlog::Modlog,
+};
+use lemmy_db_schema_file::enums::ModlogKind;
+use lemmy_db_views_ban_appeal::{
+  BanAppealView,
+  api::{BanAppealResponse, CreateBanAppeal},
+};
+use lemmy_db_views_community_moderator::CommunityPersonBanView;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::{slurs::check_slurs, validation::is_valid_body_field},
+};
+
+pub async fn create_ban_appeal(
+  Json(data): Json<CreateBanAppeal>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<BanAppealResponse>> {
+  // Banned users need to be able to appeal, so only check for deletion here
+  check_local_user_deleted(&local_user_view)?;
+  let person = &local_user_view.person;
+
+  let reason = data.reason.trim();
+  is_valid_body_field(reason, false)?;
+  check_slurs(reason, &slur_regex(&context).await?)?;
+
+  let ban = Modlog::read(&mut context.pool(), data.modlog_id).await?;
+  if ban.is_revert || ban.target_person_id != Some(person.id) {
+    return Err(LemmyErrorType::InvalidBanAppeal.into());
+  }
+
+  // The ban must still be in effect
+  let community_id = match ban.kind {
+    ModlogKind::AdminBan => {
+      if !local_user_view.banned || ban.target_instance_id != Some(person.instance_id) {
+        return Err(LemmyErrorType::InvalidBanAppeal.into());
+      }
+      None
+    }
+    ModlogKind::ModBanFromCommunity => {
+      let community_id = ban
+        .target_community_id
+        .ok_or(LemmyErrorType::InvalidBanAppeal)?;
+      let community = Community::read(&mut context.pool(), community_id).await?;
+      let banned = CommunityPersonBanView::check(&mut context.pool(), person.id, community_id)
+        .await
+        .is_err();
+      if !community.local || !banned {
+        return Err(LemmyErrorType::InvalidBanAppeal.into());
+      }
+      Some(community_id)
+    }
+    _ => return Err(LemmyErrorType::InvalidBanAppeal.into()),
+  };
+
+  if BanAppeal::read_by_modlog(&mut context.pool(), ban.id)
+    .await?
+    .is_some()
+  {
+    return Err(LemmyErrorType::AlreadyExists.into());
+  }
+
+  let form = BanAppealInsertForm::new(ban.id, person.id, community_id, reason.to_string());
+  let appeal = BanAppeal::create(&mut context.pool(), &form).await?;
+
+  let ban_appeal = BanAppealView::read(&mut context.pool(), appeal.id).await?;
+  Ok(Json(BanAppealResponse { ban_appeal }))
+}
@@ -0,0 +1,31 @@
+use crate::ban_appeal::check_ban_appeal_resolver;
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::check_local_user_deleted};
+use lemmy_db_views_ban_appeal::{
+  BanAppealView,
+  api::{BanAppealResponse, GetBanAppeal},
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+/// Gets the appeal against a ban, either for the banned user or for those who can resolve it.
+pub async fn get_ban_appeal(
+  Query(data): Query<GetBanAppeal>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<BanAppealResponse>> {
+  check_local_user_deleted(&local_user_view)?;
+  let ban_appeal = BanAppealView::read_by_modlog(&mut context.pool(), data.modlog_id).await?;
+
+  if ban_appeal.ban_appeal.creator_id != local_user_view.person.id {
+    check_ban_appeal_resolver(
+      &local_user_view,
+      ban_appeal.ban_appeal.community_id,
+      &context,
+    )
+    .await?;
+  }
+
+  Ok(Json(BanAppealResponse { ban_appeal }))
+}
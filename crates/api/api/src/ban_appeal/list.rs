@@ -0,0 +1,28 @@
+use crate::ban_appeal::check_ban_appeal_resolver;
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_views_ban_appeal::{BanAppealView, api::ListBanAppeals, impls::BanAppealQuery};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::pagination::PagedResponse;
+use lemmy_utils::error::LemmyResult;
+
+/// Lists the appeals against bans from a community for its mods, or against site bans for admins.
+pub async fn list_ban_appeals(
+  Query(data): Query<ListBanAppeals>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PagedResponse<BanAppealView>>> {
+  check_ban_appeal_resolver(&local_user_view, data.community_id, &context).await?;
+
+  let ban_appeals = BanAppealQuery {
+    community_id: data.community_id,
+    unresolved_only: data.unresolved_only,
+    page_cursor: data.page_cursor,
+    limit: data.limit,
+  }
+  .list(&mut context.pool())
+  .await?;
+
+  Ok(Json(ban_appeals))
+}
@@ -0,0 +1,29 @@
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{check_community_mod_action, is_admin},
+};
+use lemmy_db_schema::{newtypes::CommunityId, source::community::Community};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+
+pub mod create;
+pub mod get;
+pub mod list;
+pub mod resolve;
+
+/// Appeals against community bans are handled by the mods of the community, and appeals against
+/// site bans by the admins.
+async fn check_ban_appeal_resolver(
+  local_user_view: &LocalUserView,
+  community_id: Option<CommunityId>,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  match community_id {
+    Some(community_id) => {
+      let community = Community::read(&mut context.pool(), community_id).await?;
+      check_community_mod_action(local_user_view, &community, true, &mut context.pool()).await
+    }
+    None => is_admin(local_user_view),
+  }
+}
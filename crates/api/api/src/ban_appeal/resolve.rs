@@ -0,0 +1,164 @@
+use crate::ban_appeal::check_ban_appeal_resolver;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  notify::{notify_ban_appeal_denied, notify_mod_action},
+  send_activity::{ActivityChannel, SendActivityData},
+};
+use lemmy_db_schema::{
+  source::{
+    ban_appeal::{BanAppeal, BanAppealUpdateForm},
+    community::{CommunityActions, CommunityPersonBanForm},
+    instance::{InstanceActions, InstanceBanForm},
+    modlog::{Modlog, ModlogInsertForm},
+    person::Person,
+  },
+  traits::Bannable,
+};
+use lemmy_db_views_ban_appeal::{
+  BanAppealView,
+  api::{BanAppealResponse, ResolveBanAppeal},
+};
+use lemmy_db_views_community::api::BanFromCommunity;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  traits::Crud,
+  utils::diesel_string_update,
+};
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::validation::is_valid_body_field,
+};
+
+/// The modlog reason for bans which are reverted by approving an appeal.
+const APPEAL_APPROVED_REASON: &str = "Ban appeal approved";
+
+/// Approves or denies a ban appeal. Approving it reverts the ban, and in both cases the banned user
+/// is notified.
+pub async fn resolve_ban_appeal(
+  Json(data): Json<ResolveBanAppeal>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<BanAppealResponse>> {
+  let appeal = BanAppeal::read(&mut context.pool(), data.id).await?;
+  check_ban_appeal_resolver(&local_user_view, appeal.community_id, &context).await?;
+  if appeal.resolved_at.is_some() {
+    return Err(LemmyErrorType::InvalidBanAppeal.into());
+  }
+  if let Some(deny_reason) = &data.deny_reason {
+    is_valid_body_field(deny_reason, false)?;
+  }
+
+  let deny_reason = if data.approve {
+    None
+  } else {
+    data.deny_reason.clone()
+  };
+  let form = BanAppealUpdateForm {
+    resolver_id: Some(Some(local_user_view.person.id)),
+    approved: Some(data.approve),
+    deny_reason: diesel_string_update(deny_reason.as_deref()),
+    resolved_at: Some(Some(Utc::now())),
+  };
+
+  // Resolving the appeal and lifting the ban happen together, so that two mods resolving the same
+  // appeal at once can't both act on it.
+  let approve = data.approve;
+  let moderator = local_user_view.person.clone();
+  let pool = &mut context.pool();
+  let conn = &mut get_conn(pool).await?;
+  let (appeal, unban_action) = conn
+    .run_transaction(|conn| {
+      async move {
+        let appeal = BanAppeal::resolve(&mut conn.into(), appeal.id, &form).await?;
+        let unban_action = if approve {
+          Some(revert_ban(&appeal, &moderator, &mut conn.into()).await?)
+        } else {
+          None
+        };
+        Ok((appeal, unban_action))
+      }
+      .scope_boxed()
+    })
+    .await?;
+
+  match unban_action {
+    Some(action) => send_unban(&appeal, action, local_user_view.person, &context).await?,
+    None => notify_ban_appeal_denied(appeal.clone(), &context),
+  }
+
+  let ban_appeal = BanAppealView::read(&mut context.pool(), appeal.id).await?;
+  Ok(Json(BanAppealResponse { ban_appeal }))
+}
+
+/// Lifts the ban in the same way as an unban by the mod or admin, and writes the modlog entry.
+async fn revert_ban(
+  appeal: &BanAppeal,
+  moderator: &Person,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<Vec<Modlog>> {
+  let form = if let Some(community_id) = appeal.community_id {
+    let form = CommunityPersonBanForm::new(community_id, appeal.creator_id);
+    CommunityActions::unban(pool, &form).await?;
+    ModlogInsertForm::mod_ban_from_community(
+      moderator.id,
+      community_id,
+      appeal.creator_id,
+      false,
+      None,
+      APPEAL_APPROVED_REASON,
+    )
+  } else {
+    let form = InstanceBanForm::new(appeal.creator_id, moderator.instance_id, None);
+    InstanceActions::unban(pool, &form).await?;
+    ModlogInsertForm::admin_ban(
+      moderator,
+      appeal.creator_id,
+      false,
+      None,
+      APPEAL_APPROVED_REASON,
+    )
+  };
+  Modlog::create(pool, &[form]).await
+}
+
+/// Notifies the user about the lifted ban and federates it.
+async fn send_unban(
+  appeal: &BanAppeal,
+  action: Vec<Modlog>,
+  moderator: Person,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  notify_mod_action(action, context);
+  let target = Person::read(&mut context.pool(), appeal.creator_id).await?;
+
+  let data = if let Some(community_id) = appeal.community_id {
+    SendActivityData::BanFromCommunity {
+      moderator,
+      community_id,
+      data: BanFromCommunity {
+        community_id,
+        person_id: target.id,
+        ban: false,
+        remove_or_restore_data: None,
+        reason: APPEAL_APPROVED_REASON.to_string(),
+        expires_at: None,
+      },
+      target,
+    }
+  } else {
+    SendActivityData::BanFromSite {
+      moderator,
+      banned_user: target,
+      reason: APPEAL_APPROVED_REASON.to_string(),
+      remove_or_restore_data: None,
+      ban: false,
+      expires_at: None,
+    }
+  };
+  ActivityChannel::submit_activity(data, context)
+}
@@ -0,0 +1,151 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{check_private_instance, is_mod_or_admin_opt, text_diff},
+};
+use lemmy_db_schema::source::comment_history::CommentHistory;
+use lemmy_db_views_comment::{
+  CommentView,
+  api::{CommentRevision, GetCommentHistory, GetCommentHistoryResponse},
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::SiteView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+/// Lists all versions of a comment, with the changes made by each edit.
+pub async fn get_comment_history(
+  Query(data): Query<GetCommentHistory>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<GetCommentHistoryResponse>> {
+  let SiteView {
+    site, local_site, ..
+  } = SiteView::read_local(&mut context.pool()).await?;
+  check_private_instance(&local_user_view, &local_site)?;
+
+  // Reading the comment view also checks that the comment is visible
+  let comment_view = CommentView::read(
+    &mut context.pool(),
+    data.comment_id,
+    local_user_view.as_ref().map(|l| &l.local_user),
+    site.instance_id,
+  )
+  .await?;
+  let is_mod_or_admin = is_mod_or_admin_opt(
+    &mut context.pool(),
+    local_user_view.as_ref(),
+    Some(comment_view.community.id),
+  )
+  .await
+  .is_ok();
+  if !local_site.public_edit_history && !is_mod_or_admin {
+    return Err(LemmyErrorType::NotAModOrAdmin.into());
+  }
+
+  // Like the comment content, earlier versions of removed or deleted comments are only visible to
+  // mods and the creator
+  let comment = comment_view.comment;
+  let is_creator = local_user_view
+    .as_ref()
+    .is_some_and(|l| l.person.id == comment.creator_id);
+  if (comment.removed || comment.deleted) && !is_mod_or_admin && !is_creator {
+    return Ok(Json(GetCommentHistoryResponse { revisions: vec![] }));
+  }
+
+  let mut versions = CommentHistory::list_for_comment(&mut context.pool(), comment.id)
+    .await?
+    .into_iter()
+    .map(|h| (h.content, h.published_at))
+    .collect::<Vec<_>>();
+  versions.push((
+    comment.content,
+    comment.updated_at.unwrap_or(comment.published_at),
+  ));
+
+  let mut revisions: Vec<CommentRevision> = Vec::with_capacity(versions.len());
+  for (content, published_at) in versions {
+    let content_diff = revisions
+      .last()
+      .and_then(|prev| text_diff(&prev.content, &content));
+    revisions.push(CommentRevision {
+      content,
+      published_at,
+      content_diff,
+    });
+  }
+
+  Ok(Json(GetCommentHistoryResponse { revisions }))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use lemmy_db_schema::{
+    source::{
+      comment::{Comment, CommentInsertForm, CommentUpdateForm},
+      community::{Community, CommunityInsertForm},
+      local_site::{LocalSite, LocalSiteUpdateForm},
+      post::{Post, PostInsertForm},
+    },
+    test_data::TestData,
+  };
+  use lemmy_diesel_utils::traits::Crud;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_removed_comment_history() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+    let local_site_form = LocalSiteUpdateForm {
+      public_edit_history: Some(true),
+      ..Default::default()
+    };
+    LocalSite::update(pool, &local_site_form).await?;
+
+    let community = Community::create(
+      pool,
+      &CommunityInsertForm::new(data.instance.id, "test".to_string(), "pubkey".to_string()),
+    )
+    .await?;
+    let post_form = PostInsertForm::new("Test".to_string(), data.person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+    let comment_form =
+      CommentInsertForm::new(data.person.id, post.id, community.id, "first".to_string());
+    let comment = Comment::create(pool, &comment_form, None).await?;
+    let edit_form = CommentUpdateForm {
+      content: Some("second".to_string()),
+      ..Default::default()
+    };
+    Comment::update(pool, comment.id, &edit_form).await?;
+
+    let query = || {
+      Query(GetCommentHistory {
+        comment_id: comment.id,
+      })
+    };
+    let Json(res) = get_comment_history(query(), context.clone(), None).await?;
+    let contents = res
+      .revisions
+      .into_iter()
+      .map(|r| r.content)
+      .collect::<Vec<_>>();
+    assert_eq!(vec!["first".to_string(), "second".to_string()], contents);
+
+    // Earlier versions of a removed comment are hidden from anonymous users
+    let remove_form = CommentUpdateForm {
+      removed: Some(true),
+      ..Default::default()
+    };
+    Comment::update(pool, comment.id, &remove_form).await?;
+    let Json(res) = get_comment_history(query(), context.clone(), None).await?;
+    assert!(res.revisions.is_empty());
+
+    LocalSite::delete(pool).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+}
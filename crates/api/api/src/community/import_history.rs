@@ -0,0 +1,35 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{context::LemmyContext, utils::check_community_mod_action};
+use lemmy_apub_objects::objects::community::IMPORT_COMMUNITY_HISTORY;
+use lemmy_db_schema::source::community::Community;
+use lemmy_db_views_community::api::ImportCommunityHistory;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::SuccessResponse;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn import_community_history(
+  Json(data): Json<ImportCommunityHistory>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let community = Community::read(&mut context.pool(), data.community_id).await?;
+  check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+  if !community.local {
+    return Err(LemmyErrorType::InvalidCommunityMove.into());
+  }
+
+  // Only communities whose move to this community was verified can be imported
+  let moved = Community::list_moved_to(&mut context.pool(), &community.ap_id).await?;
+  if moved.is_empty() {
+    return Err(LemmyErrorType::InvalidCommunityMove.into());
+  }
+  if let Some(import_fn) = IMPORT_COMMUNITY_HISTORY.get() {
+    for old_community in moved {
+      import_fn(old_community.into(), context.reset_request_count());
+    }
+  }
+
+  Ok(Json(SuccessResponse::default()))
+}
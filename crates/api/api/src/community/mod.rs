@@ -17,6 +17,8 @@ pub mod add_mod;
 pub mod ban;
 pub mod block;
 pub mod follow;
+pub mod import_history;
+pub mod move_community;
 pub mod multi_community_follow;
 pub mod pending_follows;
 pub mod random;
@@ -0,0 +1,52 @@
+use crate::federation::fetcher::resolve_community;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::{check_community_user_action, is_admin, is_top_mod},
+};
+use lemmy_apub_objects::{
+  objects::community::ApubCommunity,
+  utils::functions::{apply_community_move, verify_community_move_target},
+};
+use lemmy_db_schema::source::community::Community;
+use lemmy_db_views_community::api::MoveCommunity;
+use lemmy_db_views_community_moderator::CommunityModeratorView;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::SuccessResponse;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn move_community(
+  Json(data): Json<MoveCommunity>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let community = Community::read(&mut context.pool(), data.community_id).await?;
+  check_community_user_action(&local_user_view, &community, &mut context.pool()).await?;
+  if !community.local {
+    return Err(LemmyErrorType::InvalidCommunityMove.into());
+  }
+
+  // Only the top mod or an admin can move the community
+  let community_mods =
+    CommunityModeratorView::for_community(&mut context.pool(), community.id).await?;
+  if !(is_top_mod(&local_user_view, &community_mods).is_ok() || is_admin(&local_user_view).is_ok())
+  {
+    return Err(LemmyErrorType::NotAModOrAdmin.into());
+  }
+
+  let target = resolve_community(&data.target, &context, &local_user_view).await?;
+  let old_community: ApubCommunity = community.into();
+  let new_community =
+    verify_community_move_target(&old_community, &target.ap_id.clone().into(), &context).await?;
+  apply_community_move(&old_community, &new_community, &context).await?;
+
+  ActivityChannel::submit_activity(
+    SendActivityData::MoveCommunity(old_community.0, new_community.0),
+    &context,
+  )?;
+
+  Ok(Json(SuccessResponse::default()))
+}
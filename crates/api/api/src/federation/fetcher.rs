@@ -104,6 +104,36 @@ pub(crate) async fn resolve_person_identifier(
   })
 }
 
+/// Resolve a person identifier like `name@example.com`, fetching it if necessary.
+pub(crate) async fn resolve_person(
+  identifier: &str,
+  context: &Data<LemmyContext>,
+  local_user_view: &LocalUserView,
+) -> LemmyResult<ApubPerson> {
+  resolve_ap_identifier::<ApubPerson, Person>(
+    identifier,
+    context,
+    &Some(local_user_view.clone()),
+    false,
+  )
+  .await
+}
+
+/// Resolve a community identifier like `news@example.com`, fetching it if necessary.
+pub(crate) async fn resolve_community(
+  identifier: &str,
+  context: &Data<LemmyContext>,
+  local_user_view: &LocalUserView,
+) -> LemmyResult<ApubCommunity> {
+  resolve_ap_identifier::<ApubCommunity, Community>(
+    identifier,
+    context,
+    &Some(local_user_view.clone()),
+    false,
+  )
+  .await
+}
+
 pub(crate) async fn resolve_multi_community_identifier(
   name: &Option<String>,
   id: Option<MultiCommunityId>,
@@ -92,6 +92,7 @@ async fn list_comments_common(
     post_id,
     local_user,
     search_term,
+    search_sort_by_relevance: None,
     page_cursor,
     limit,
   }
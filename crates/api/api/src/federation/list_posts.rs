@@ -115,6 +115,7 @@ pub async fn list_posts(
     search_term,
     search_title_only,
     search_url_only,
+    search_sort_by_relevance: None,
     tag_id,
     page_cursor,
   }
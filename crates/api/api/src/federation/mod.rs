@@ -5,7 +5,7 @@ use lemmy_db_schema::{
 };
 use lemmy_db_schema_file::enums::{CommentSortType, ListingType, PostSortType};
 
-mod fetcher;
+pub(crate) mod fetcher;
 pub mod list_comments;
 pub mod list_person_content;
 pub mod list_persons;
@@ -12,9 +12,10 @@ use lemmy_db_schema::{
   MultiCommunitySortType,
   PersonListingType,
   PersonSortType,
+  SearchSortType,
   SearchType,
 };
-use lemmy_db_schema_file::enums::{CommentSortType, ListingType};
+use lemmy_db_schema_file::enums::{CommentSortType, ListingType, PostSortType};
 use lemmy_db_views_comment::impls::CommentQuery;
 use lemmy_db_views_community::impls::{CommunityQuery, MultiCommunityQuery};
 use lemmy_db_views_local_user::LocalUserView;
@@ -48,6 +49,7 @@ pub async fn search(
   let search_title_only = data.title_only;
   let time_range_seconds = data.time_range_seconds;
   let search_url_only = data.post_url_only;
+  let search_sort_by_relevance = (data.sort == Some(SearchSortType::Relevance)).then_some(true);
   let show_nsfw = data.show_nsfw;
   let page_cursors = from_single_cursor(data.page_cursor.clone(), search_type);
   let limit = data.limit;
@@ -80,6 +82,8 @@ pub async fn search(
     creator_id,
     time_range_seconds,
     search_url_only,
+    search_sort_by_relevance,
+    sort: Some(PostSortType::New),
     show_nsfw,
     page_cursor: page_cursors[0].clone(),
     limit,
@@ -95,6 +99,7 @@ pub async fn search(
     community_id,
     creator_id,
     time_range_seconds,
+    search_sort_by_relevance,
     sort: Some(CommentSortType::New),
     page_cursor: page_cursors[1].clone(),
     limit,
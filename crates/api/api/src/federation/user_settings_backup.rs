@@ -83,6 +83,8 @@ pub async fn import_user_settings(
       .settings
       .as_ref()
       .map(|s| s.send_notifications_to_email),
+    email_digest_frequency: data.settings.as_ref().map(|s| s.email_digest_frequency),
+    email_digest_top_posts: data.settings.as_ref().map(|s| s.email_digest_top_posts),
     show_bot_accounts: data.settings.as_ref().map(|s| s.show_bot_accounts),
     show_read_posts: data.settings.as_ref().map(|s| s.show_read_posts),
     open_links_in_new_tab: data.settings.as_ref().map(|s| s.open_links_in_new_tab),
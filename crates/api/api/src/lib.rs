@@ -1,6 +1,8 @@
 use lemmy_api_utils::{context::LemmyContext, utils::is_mod_or_admin_opt};
-use lemmy_db_schema::newtypes::CommunityId;
+use lemmy_db_schema::{newtypes::CommunityId, source::local_site::LocalSite};
+use lemmy_db_views_community_moderator::CommunityModeratorView;
 use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::connection::DbPool;
 use lemmy_utils::{
   error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
   utils::slurs::check_slurs,
@@ -8,6 +10,7 @@ use lemmy_utils::{
 use regex::Regex;
 use totp_rs::{Secret, TOTP};
 
+pub mod ban_appeal;
 pub mod comment;
 pub mod community;
 pub mod federation;
@@ -54,6 +57,27 @@ pub(crate) fn check_totp_2fa_valid(
   Ok(())
 }
 
+/// Moderators and admins can be required to set up totp or a passkey before logging in. This is
+/// only called for users who have neither.
+pub(crate) async fn check_2fa_not_required(
+  local_user_view: &LocalUserView,
+  local_site: &LocalSite,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  if !local_site.require_2fa_for_mods_and_admins {
+    return Ok(());
+  }
+  let is_mod_or_admin = local_user_view.local_user.admin
+    || CommunityModeratorView::is_community_moderator_of_any(pool, local_user_view.person.id)
+      .await
+      .is_ok();
+  if is_mod_or_admin {
+    Err(LemmyErrorType::TwoFactorRequired.into())
+  } else {
+    Ok(())
+  }
+}
+
 pub(crate) fn generate_totp_2fa_secret() -> String {
   Secret::generate_secret().to_string()
 }
@@ -1,4 +1,5 @@
-use actix_web::web::{Data, Json};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
 use lemmy_api_utils::{context::LemmyContext, notify::notify_mod_action, utils::is_admin};
 use lemmy_db_schema::source::{
   local_user::{LocalUser, LocalUserUpdateForm},
@@ -0,0 +1,205 @@
+use crate::local_user::unread_counts::read_unread_counts;
+use actix_web::{
+  HttpRequest,
+  HttpResponse,
+  http::header::CACHE_CONTROL,
+  web::{Bytes, Data, Query},
+};
+use futures::stream::unfold;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  realtime::RealtimeEvent,
+  utils::{
+    API_TOKEN_PREFIX,
+    check_local_user_banned_or_deleted,
+    local_user_view_from_api_token,
+    local_user_view_from_jwt,
+    read_auth_token,
+  },
+};
+use lemmy_db_schema::source::{community::CommunityActions, person::PersonActions};
+use lemmy_db_schema_file::InstanceId;
+use lemmy_db_views_comment::CommentView;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_notification::NotificationView;
+use lemmy_db_views_post::PostView;
+use lemmy_db_views_site::{
+  SiteView,
+  api::{ListenEvents, StreamEvent},
+};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use std::{convert::Infallible, time::Duration};
+use tokio::{
+  select,
+  sync::broadcast::{Receiver, error::RecvError},
+  time::{Interval, interval},
+};
+
+/// Comments are sent this often, so that proxies don't close idle connections. The session is
+/// checked again at the same interval.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Streams new notifications, unread counts and optionally new content as server-sent events.
+/// Works with the `EventSource` API of browsers, which sends the auth cookie. The stream ends once
+/// the session or token isn't valid anymore, or the user is banned.
+pub async fn listen_events(
+  Query(data): Query<ListenEvents>,
+  req: HttpRequest,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<HttpResponse> {
+  check_local_user_banned_or_deleted(&local_user_view)?;
+  let auth_token = read_auth_token(&req)?.ok_or(LemmyErrorType::NotLoggedIn)?;
+  let local_instance_id = SiteView::read_local(&mut context.pool())
+    .await?
+    .site
+    .instance_id;
+  let listener = EventListener {
+    data,
+    receiver: context.realtime().subscribe(),
+    context,
+    auth_token,
+    local_user_view,
+    local_instance_id,
+  };
+
+  let stream = unfold(
+    (listener, interval(KEEP_ALIVE_INTERVAL)),
+    |(mut listener, mut keep_alive): (EventListener, Interval)| async move {
+      let bytes = select! {
+        events = listener.next() => format_events(&events?),
+        _ = keep_alive.tick() => {
+          listener.refresh_user().await.ok()?;
+          Bytes::from_static(b": keep-alive\n\n")
+        }
+      };
+      Some((Ok::<_, Infallible>(bytes), (listener, keep_alive)))
+    },
+  );
+
+  Ok(
+    HttpResponse::Ok()
+      .content_type("text/event-stream")
+      .insert_header((CACHE_CONTROL, "no-cache"))
+      .streaming(stream),
+  )
+}
+
+struct EventListener {
+  data: ListenEvents,
+  receiver: Receiver<RealtimeEvent>,
+  context: Data<LemmyContext>,
+  auth_token: String,
+  local_user_view: LocalUserView,
+  local_instance_id: InstanceId,
+}
+
+impl EventListener {
+  /// Waits for the next events which should be sent to this user. Returns `None` once the server
+  /// shuts down.
+  async fn next(&mut self) -> Option<Vec<StreamEvent>> {
+    loop {
+      let events = match self.receiver.recv().await {
+        Ok(event) => self.stream_events(event).await,
+        // Events were skipped because the client is too slow, so at least send the current counts
+        Err(RecvError::Lagged(_)) => self.unread_counts().await,
+        Err(RecvError::Closed) => return None,
+      };
+      if !events.is_empty() {
+        return Some(events);
+      }
+    }
+  }
+
+  /// Reads the user again in the same way as the session middleware. Fails after logout, when the
+  /// token was revoked, or when the user was banned or deleted in the meantime.
+  async fn refresh_user(&mut self) -> LemmyResult<()> {
+    let local_user_view = if self.auth_token.starts_with(API_TOKEN_PREFIX) {
+      local_user_view_from_api_token(&self.auth_token, &self.context)
+        .await?
+        .0
+    } else {
+      local_user_view_from_jwt(&self.auth_token, &self.context).await?
+    };
+    check_local_user_banned_or_deleted(&local_user_view)?;
+    self.local_user_view = local_user_view;
+    Ok(())
+  }
+
+  /// Objects which can't be read are not visible to the user, so they are skipped.
+  async fn stream_events(&self, event: RealtimeEvent) -> Vec<StreamEvent> {
+    let person = &self.local_user_view.person;
+    let local_user = Some(&self.local_user_view.local_user);
+    let pool = &mut self.context.pool();
+    let followed_communities = self.data.followed_communities.unwrap_or_default();
+
+    match event {
+      RealtimeEvent::Notification { id, recipient_id } if recipient_id == person.id => {
+        let Ok(notification) = NotificationView::read(pool, id, person).await else {
+          return vec![];
+        };
+        let mut events = vec![StreamEvent::Notification(Box::new(notification))];
+        events.append(&mut self.unread_counts().await);
+        events
+      }
+      RealtimeEvent::UnreadCounts { recipient_id } if recipient_id == person.id => {
+        self.unread_counts().await
+      }
+      RealtimeEvent::Post { id, .. } if followed_communities => {
+        match PostView::read(pool, id, local_user, self.local_instance_id, false).await {
+          Ok(post)
+            if is_followed(post.community_actions.as_ref())
+              && !is_blocked(post.person_actions.as_ref()) =>
+          {
+            vec![StreamEvent::Post(Box::new(post))]
+          }
+          _ => vec![],
+        }
+      }
+      RealtimeEvent::Comment { id, post_id }
+        if followed_communities || self.data.post_id == Some(post_id) =>
+      {
+        match CommentView::read(pool, id, local_user, self.local_instance_id).await {
+          Ok(comment)
+            if (self.data.post_id == Some(post_id)
+              || is_followed(comment.community_actions.as_ref()))
+              && !is_blocked(comment.person_actions.as_ref()) =>
+          {
+            vec![StreamEvent::Comment(Box::new(comment))]
+          }
+          _ => vec![],
+        }
+      }
+      _ => vec![],
+    }
+  }
+
+  async fn unread_counts(&self) -> Vec<StreamEvent> {
+    read_unread_counts(&self.local_user_view, &self.context)
+      .await
+      .map(StreamEvent::UnreadCounts)
+      .into_iter()
+      .collect()
+  }
+}
+
+fn is_followed(community_actions: Option<&CommunityActions>) -> bool {
+  community_actions.is_some_and(|a| a.followed_at.is_some())
+}
+
+fn is_blocked(person_actions: Option<&PersonActions>) -> bool {
+  person_actions.is_some_and(|a| a.blocked_at.is_some())
+}
+
+/// Each event becomes a message with a single `data` line, which contains the event as JSON.
+fn format_events(events: &[StreamEvent]) -> Bytes {
+  let mut out = String::new();
+  for event in events {
+    if let Ok(json) = serde_json::to_string(event) {
+      out.push_str("data: ");
+      out.push_str(&json);
+      out.push_str("\n\n");
+    }
+  }
+  Bytes::from(out)
+}
@@ -55,8 +55,10 @@ pub async fn export_user_data(
     NotificationData::Post(p) => Some(Post(p.post)),
     NotificationData::Comment(c) => Some(Comment(c.comment)),
     NotificationData::PrivateMessage(pm) => Some(PrivateMessage(pm.private_message)),
-    // skip modlog items
-    NotificationData::ModAction(_) => None,
+    // skip modlog, modmail and ban appeal items
+    NotificationData::ModAction(_)
+    | NotificationData::Modmail(_)
+    | NotificationData::BanAppeal(_) => None,
   })
   .collect();
 
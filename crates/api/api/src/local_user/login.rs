@@ -1,4 +1,8 @@
-use crate::check_totp_2fa_valid;
+use crate::{
+  check_2fa_not_required,
+  check_totp_2fa_valid,
+  local_user::passkey::check_passkey_login,
+};
 use actix_web::{
   HttpRequest,
   web::{Data, Json},
@@ -9,6 +13,7 @@ use lemmy_api_utils::{
   context::LemmyContext,
   utils::{check_email_verified, check_local_user_deleted, check_registration_application},
 };
+use lemmy_db_schema::source::passkey::Passkey;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_site::{
   SiteView,
@@ -61,13 +66,20 @@ pub async fn login(
   check_registration_application(&local_user_view, &site_view.local_site, &mut context.pool())
     .await?;
 
-  // Check the totp if enabled
-  if local_user_view.local_user.totp_2fa_enabled {
+  // Check the second factor. If both totp and passkeys are set up, either of them can be used.
+  let local_user_id = local_user_view.local_user.id;
+  if let Some(passkey) = &data.passkey {
+    check_passkey_login(passkey, Some(local_user_id), &context).await?;
+  } else if local_user_view.local_user.totp_2fa_enabled {
     check_totp_2fa_valid(
       &local_user_view,
       &data.totp_2fa_token,
       &context.settings().hostname,
     )?;
+  } else if Passkey::exists_for_user(&mut context.pool(), local_user_id).await? {
+    return Err(LemmyErrorType::MissingPasskey.into());
+  } else {
+    check_2fa_not_required(&local_user_view, &site_view.local_site, &mut context.pool()).await?;
   }
 
   let jwt = Claims::generate(
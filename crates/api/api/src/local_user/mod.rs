@@ -3,6 +3,7 @@ pub mod ban_person;
 pub mod block;
 pub mod change_password;
 pub mod change_password_after_reset;
+pub mod events;
 pub mod export_data;
 pub mod generate_totp_secret;
 pub mod get_captcha;
@@ -15,12 +16,16 @@ pub mod list_saved;
 pub mod login;
 pub mod logout;
 pub mod mark_donation_dialog_shown;
+pub mod move_account;
 pub mod note_person;
 pub mod notifications;
+pub mod passkey;
+pub mod push;
 pub mod resend_verification_email;
 pub mod reset_password;
 pub mod save_settings;
 pub mod unread_counts;
+pub mod unsubscribe_email;
 pub mod update_totp;
 pub mod user_block_instance;
 pub mod validate_auth;
@@ -0,0 +1,48 @@
+use crate::federation::fetcher::resolve_person;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use bcrypt::verify;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::check_local_user_banned_or_deleted,
+};
+use lemmy_apub_objects::{objects::person::ApubPerson, utils::functions::verify_move_target};
+use lemmy_db_schema::source::person::Person;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{MoveAccount, SuccessResponse};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn move_account(
+  Json(data): Json<MoveAccount>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  check_local_user_banned_or_deleted(&local_user_view)?;
+  if local_user_view.person.moved_to.is_some() {
+    return Err(LemmyErrorType::InvalidAccountMove.into());
+  }
+
+  // Verify the password
+  let valid: bool = local_user_view
+    .local_user
+    .password_encrypted
+    .as_ref()
+    .and_then(|password_encrypted| verify(&data.password, password_encrypted).ok())
+    .unwrap_or(false);
+  if !valid {
+    return Err(LemmyErrorType::IncorrectLogin.into());
+  }
+
+  let target = resolve_person(&data.target, &context, &local_user_view).await?;
+  let old_person: ApubPerson = local_user_view.person.clone().into();
+  let new_person = verify_move_target(&old_person, &target.ap_id.clone().into(), &context).await?;
+  Person::apply_move(&mut context.pool(), old_person.id, &new_person).await?;
+
+  ActivityChannel::submit_activity(
+    SendActivityData::MoveUser(local_user_view.person, new_person.0),
+    &context,
+  )?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -20,6 +20,7 @@ pub async fn list_notifications(
     hide_modlog_names: Some(hide_modlog_names),
     creator_id: data.creator_id,
     limit: data.limit,
+    published_after: None,
     no_limit: None,
   }
   .list(&mut context.pool(), &local_user_view.person)
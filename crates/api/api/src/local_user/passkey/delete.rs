@@ -0,0 +1,20 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::passkey::Passkey;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{DeletePasskey, SuccessResponse};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn delete_passkey(
+  Json(data): Json<DeletePasskey>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let deleted =
+    Passkey::delete(&mut context.pool(), data.id, local_user_view.local_user.id).await?;
+  if deleted == 0 {
+    return Err(LemmyErrorType::NotFound.into());
+  }
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -0,0 +1,24 @@
+use crate::local_user::passkey::check_passkey_name;
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::passkey::Passkey;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{EditPasskey, PasskeyResponse};
+use lemmy_utils::error::LemmyResult;
+
+pub async fn edit_passkey(
+  Json(data): Json<EditPasskey>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PasskeyResponse>> {
+  let name = check_passkey_name(&data.name)?;
+  let passkey = Passkey::update_name(
+    &mut context.pool(),
+    data.id,
+    local_user_view.local_user.id,
+    &name,
+  )
+  .await?;
+
+  Ok(Json(PasskeyResponse { passkey }))
+}
@@ -0,0 +1,15 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::passkey::Passkey;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::ListPasskeysResponse;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_passkeys(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListPasskeysResponse>> {
+  let passkeys = Passkey::list(&mut context.pool(), local_user_view.local_user.id).await?;
+
+  Ok(Json(ListPasskeysResponse { passkeys }))
+}
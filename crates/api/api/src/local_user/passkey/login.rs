@@ -0,0 +1,100 @@
+use crate::local_user::passkey::{
+  PASSKEY_TIMEOUT,
+  RelyingParty,
+  check_passkey_login,
+  create_challenge,
+  credential_descriptors,
+};
+use actix_web::{
+  HttpRequest,
+  web::{Data, Json},
+};
+use lemmy_api_utils::{
+  claims::Claims,
+  context::LemmyContext,
+  utils::{check_email_verified, check_local_user_deleted, check_registration_application},
+};
+use lemmy_db_schema::source::passkey::Passkey;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::{
+  SiteView,
+  api::{
+    BeginPasskeyLogin,
+    BeginPasskeyLoginResponse,
+    LoginResponse,
+    PasskeyLogin,
+    PasskeyRequestOptions,
+  },
+};
+use lemmy_utils::error::LemmyResult;
+
+/// Returns the options for logging in with a passkey in the browser. With a user, the result is
+/// used as second factor for the normal login. Otherwise it is passed to [passkey_login].
+pub async fn begin_passkey_login(
+  Json(data): Json<BeginPasskeyLogin>,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<BeginPasskeyLoginResponse>> {
+  let rp = RelyingParty::new(&context)?;
+
+  // Unknown users get an empty list like users without passkeys, so that this can't be used to
+  // find out which accounts exist.
+  let local_user_view = match &data.username_or_email {
+    Some(username_or_email) => {
+      LocalUserView::find_by_email_or_name(&mut context.pool(), username_or_email)
+        .await
+        .ok()
+    }
+    None => None,
+  };
+  let local_user_id = local_user_view.map(|l| l.local_user.id);
+  let passkeys = match local_user_id {
+    Some(local_user_id) => Passkey::list(&mut context.pool(), local_user_id).await?,
+    None => vec![],
+  };
+  let challenge = create_challenge(local_user_id, &context).await?;
+  let user_verification = if data.username_or_email.is_some() {
+    "preferred"
+  } else {
+    "required"
+  };
+
+  Ok(Json(BeginPasskeyLoginResponse {
+    public_key: PasskeyRequestOptions {
+      challenge,
+      rp_id: rp.id,
+      timeout: PASSKEY_TIMEOUT.num_milliseconds(),
+      allow_credentials: credential_descriptors(&passkeys),
+      user_verification: user_verification.to_string(),
+    },
+  }))
+}
+
+/// Passwordless login. The passkey has to verify the user, so it counts as two factors.
+pub async fn passkey_login(
+  Json(data): Json<PasskeyLogin>,
+  req: HttpRequest,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<LoginResponse>> {
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  let passkey = check_passkey_login(&data.credential, None, &context).await?;
+
+  let local_user_view = LocalUserView::read(&mut context.pool(), passkey.local_user_id).await?;
+  check_local_user_deleted(&local_user_view)?;
+  check_email_verified(&local_user_view, &site_view)?;
+  check_registration_application(&local_user_view, &site_view.local_site, &mut context.pool())
+    .await?;
+
+  let jwt = Claims::generate(
+    local_user_view.local_user.id,
+    data.stay_logged_in,
+    req,
+    &context,
+  )
+  .await?;
+
+  Ok(Json(LoginResponse {
+    jwt: Some(jwt),
+    verify_email_sent: false,
+    registration_created: false,
+  }))
+}
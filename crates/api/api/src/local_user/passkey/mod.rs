@@ -0,0 +1,481 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{Duration, Utc};
+use ciborium::Value;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::{
+  newtypes::LocalUserId,
+  source::passkey::{Passkey, PasskeyChallenge, PasskeyChallengeInsertForm},
+};
+use lemmy_db_views_site::api::{PasskeyAssertion, PasskeyAttestation, PasskeyCredentialDescriptor};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+use ring::signature::{
+  ECDSA_P256_SHA256_ASN1,
+  ED25519,
+  RSA_PKCS1_2048_8192_SHA256,
+  RsaPublicKeyComponents,
+  UnparsedPublicKey,
+};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+pub mod delete;
+pub mod edit;
+pub mod list;
+pub mod login;
+pub mod register;
+
+/// How long the user has to complete the passkey prompt of the browser.
+const PASSKEY_TIMEOUT: Duration = Duration::minutes(5);
+
+/// COSE identifiers of the supported signature algorithms.
+const COSE_ALG_ES256: i64 = -7;
+const COSE_ALG_EDDSA: i64 = -8;
+const COSE_ALG_RS256: i64 = -257;
+
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_USER_VERIFIED: u8 = 0x04;
+const FLAG_ATTESTED_CREDENTIAL: u8 = 0x40;
+
+const MAX_PASSKEY_NAME_LENGTH: usize = 100;
+
+/// The domain which passkeys are bound to, and the origin of the frontend which uses them.
+struct RelyingParty {
+  id: String,
+  origin: String,
+}
+
+impl RelyingParty {
+  fn new(context: &LemmyContext) -> LemmyResult<Self> {
+    Ok(RelyingParty {
+      id: context.settings().get_hostname_without_port()?,
+      origin: context.settings().get_protocol_and_hostname(),
+    })
+  }
+}
+
+#[derive(Deserialize)]
+struct ClientData {
+  #[serde(rename = "type")]
+  type_: String,
+  challenge: String,
+  origin: String,
+}
+
+struct AuthenticatorData {
+  flags: u8,
+  sign_count: u32,
+  /// Credential id and COSE public key, only present during registration.
+  attested_credential: Option<(Vec<u8>, Value)>,
+}
+
+enum PublicKey {
+  /// Uncompressed P-256 point.
+  Es256(Vec<u8>),
+  Ed25519(Vec<u8>),
+  Rs256 {
+    n: Vec<u8>,
+    e: Vec<u8>,
+  },
+}
+
+impl PublicKey {
+  fn from_cose(key: &Value) -> LemmyResult<Self> {
+    let map = key.as_map().ok_or(LemmyErrorType::InvalidPasskey)?;
+    let get = |label: i64| {
+      map
+        .iter()
+        .find(|(k, _)| k.as_integer() == Some(label.into()))
+        .map(|(_, v)| v)
+    };
+    let bytes = |label: i64| {
+      get(label)
+        .and_then(Value::as_bytes)
+        .cloned()
+        .ok_or(LemmyErrorType::InvalidPasskey)
+    };
+    let alg = get(3)
+      .and_then(Value::as_integer)
+      .and_then(|a| i64::try_from(a).ok());
+
+    match alg {
+      Some(COSE_ALG_ES256) => {
+        let mut point = vec![0x04];
+        point.extend(bytes(-2)?);
+        point.extend(bytes(-3)?);
+        Ok(PublicKey::Es256(point))
+      }
+      Some(COSE_ALG_EDDSA) => Ok(PublicKey::Ed25519(bytes(-2)?)),
+      Some(COSE_ALG_RS256) => Ok(PublicKey::Rs256 {
+        n: bytes(-1)?,
+        e: bytes(-2)?,
+      }),
+      _ => Err(LemmyErrorType::InvalidPasskey.into()),
+    }
+  }
+
+  fn verify(&self, message: &[u8], signature: &[u8]) -> LemmyResult<()> {
+    match self {
+      PublicKey::Es256(point) => {
+        UnparsedPublicKey::new(&ECDSA_P256_SHA256_ASN1, point).verify(message, signature)
+      }
+      PublicKey::Ed25519(key) => UnparsedPublicKey::new(&ED25519, key).verify(message, signature),
+      PublicKey::Rs256 { n, e } => {
+        RsaPublicKeyComponents { n, e }.verify(&RSA_PKCS1_2048_8192_SHA256, message, signature)
+      }
+    }
+    .with_lemmy_type(LemmyErrorType::InvalidPasskey)
+  }
+}
+
+/// A passkey which passed the registration checks.
+struct NewCredential {
+  credential_id: String,
+  public_key: Vec<u8>,
+  sign_count: u32,
+}
+
+fn check_passkey_name(name: &str) -> LemmyResult<String> {
+  let name = name.trim();
+  if name.is_empty() || name.chars().count() > MAX_PASSKEY_NAME_LENGTH {
+    return Err(LemmyErrorType::InvalidPasskey.into());
+  }
+  Ok(name.to_string())
+}
+
+fn decode(data: &str) -> LemmyResult<Vec<u8>> {
+  URL_SAFE_NO_PAD
+    .decode(data.trim_end_matches('='))
+    .with_lemmy_type(LemmyErrorType::InvalidPasskey)
+}
+
+fn encode(data: &[u8]) -> String {
+  URL_SAFE_NO_PAD.encode(data)
+}
+
+/// The user handle which is stored on the authenticator, and returned on passwordless login.
+fn user_handle(local_user_id: LocalUserId) -> String {
+  encode(&local_user_id.0.to_be_bytes())
+}
+
+fn credential_descriptors(passkeys: &[Passkey]) -> Vec<PasskeyCredentialDescriptor> {
+  passkeys
+    .iter()
+    .map(|p| PasskeyCredentialDescriptor {
+      type_: "public-key".to_string(),
+      id: p.credential_id.clone(),
+    })
+    .collect()
+}
+
+async fn create_challenge(
+  local_user_id: Option<LocalUserId>,
+  context: &LemmyContext,
+) -> LemmyResult<String> {
+  let challenge = encode(&rand::random::<[u8; 32]>());
+  let form = PasskeyChallengeInsertForm::new(
+    challenge.clone(),
+    local_user_id,
+    Utc::now() + PASSKEY_TIMEOUT,
+  );
+  PasskeyChallenge::create(&mut context.pool(), &form).await?;
+  Ok(challenge)
+}
+
+/// Checks the type and origin of the client data, and returns the challenge.
+fn verify_client_data(
+  client_data_json: &[u8],
+  expected_type: &str,
+  rp: &RelyingParty,
+) -> LemmyResult<String> {
+  let client_data: ClientData =
+    serde_json::from_slice(client_data_json).with_lemmy_type(LemmyErrorType::InvalidPasskey)?;
+  if client_data.type_ != expected_type || client_data.origin != rp.origin {
+    return Err(LemmyErrorType::InvalidPasskey.into());
+  }
+  Ok(client_data.challenge)
+}
+
+fn parse_authenticator_data(data: &[u8], rp: &RelyingParty) -> LemmyResult<AuthenticatorData> {
+  let (rp_id_hash, rest) = data
+    .split_at_checked(32)
+    .ok_or(LemmyErrorType::InvalidPasskey)?;
+  if rp_id_hash != Sha256::digest(rp.id.as_bytes()).as_slice() {
+    return Err(LemmyErrorType::InvalidPasskey.into());
+  }
+  let (&flags, rest) = rest.split_first().ok_or(LemmyErrorType::InvalidPasskey)?;
+  if flags & FLAG_USER_PRESENT == 0 {
+    return Err(LemmyErrorType::InvalidPasskey.into());
+  }
+  let (sign_count, rest) = rest
+    .split_first_chunk::<4>()
+    .ok_or(LemmyErrorType::InvalidPasskey)?;
+
+  let attested_credential = if flags & FLAG_ATTESTED_CREDENTIAL != 0 {
+    // Skip the AAGUID, which identifies the authenticator model
+    let rest = rest.get(16..).ok_or(LemmyErrorType::InvalidPasskey)?;
+    let (len, rest) = rest
+      .split_first_chunk::<2>()
+      .ok_or(LemmyErrorType::InvalidPasskey)?;
+    let (credential_id, rest) = rest
+      .split_at_checked(u16::from_be_bytes(*len).into())
+      .ok_or(LemmyErrorType::InvalidPasskey)?;
+    let public_key: Value =
+      ciborium::from_reader(rest).with_lemmy_type(LemmyErrorType::InvalidPasskey)?;
+    Some((credential_id.to_vec(), public_key))
+  } else {
+    None
+  };
+
+  Ok(AuthenticatorData {
+    flags,
+    sign_count: u32::from_be_bytes(*sign_count),
+    attested_credential,
+  })
+}
+
+/// Verifies the result of `navigator.credentials.create()`, and returns its challenge together
+/// with the new credential. Attestation statements are ignored, as only `none` attestation is
+/// requested.
+fn verify_registration(
+  credential: &PasskeyAttestation,
+  rp: &RelyingParty,
+) -> LemmyResult<(String, NewCredential)> {
+  let client_data_json = decode(&credential.response.client_data_json)?;
+  let challenge = verify_client_data(&client_data_json, "webauthn.create", rp)?;
+
+  let attestation: Value =
+    ciborium::from_reader(decode(&credential.response.attestation_object)?.as_slice())
+      .with_lemmy_type(LemmyErrorType::InvalidPasskey)?;
+  let auth_data = attestation
+    .as_map()
+    .and_then(|m| m.iter().find(|(k, _)| k.as_text() == Some("authData")))
+    .and_then(|(_, v)| v.as_bytes())
+    .ok_or(LemmyErrorType::InvalidPasskey)?;
+  let auth_data = parse_authenticator_data(auth_data, rp)?;
+  let (credential_id, public_key) = auth_data
+    .attested_credential
+    .ok_or(LemmyErrorType::InvalidPasskey)?;
+
+  // Only store keys which can be used for login later
+  PublicKey::from_cose(&public_key)?;
+  let mut public_key_bytes = Vec::new();
+  ciborium::into_writer(&public_key, &mut public_key_bytes)
+    .with_lemmy_type(LemmyErrorType::InvalidPasskey)?;
+
+  Ok((
+    challenge,
+    NewCredential {
+      credential_id: encode(&credential_id),
+      public_key: public_key_bytes,
+      sign_count: auth_data.sign_count,
+    },
+  ))
+}
+
+/// Verifies the result of `navigator.credentials.get()` for the given passkey, and returns its
+/// challenge together with the new signature counter.
+fn verify_assertion(
+  credential: &PasskeyAssertion,
+  passkey: &Passkey,
+  require_user_verification: bool,
+  rp: &RelyingParty,
+) -> LemmyResult<(String, i64)> {
+  let client_data_json = decode(&credential.response.client_data_json)?;
+  let challenge = verify_client_data(&client_data_json, "webauthn.get", rp)?;
+
+  let mut message = decode(&credential.response.authenticator_data)?;
+  let auth_data = parse_authenticator_data(&message, rp)?;
+  if require_user_verification && auth_data.flags & FLAG_USER_VERIFIED == 0 {
+    return Err(LemmyErrorType::InvalidPasskey.into());
+  }
+
+  let public_key: Value = ciborium::from_reader(passkey.public_key.as_slice())
+    .with_lemmy_type(LemmyErrorType::InvalidPasskey)?;
+  message.extend_from_slice(&Sha256::digest(&client_data_json));
+  PublicKey::from_cose(&public_key)?.verify(&message, &decode(&credential.response.signature)?)?;
+
+  // A counter which doesn't increase indicates a cloned authenticator. Authenticators without a
+  // counter always return zero.
+  let sign_count = i64::from(auth_data.sign_count);
+  if (sign_count != 0 || passkey.sign_count != 0) && sign_count <= passkey.sign_count {
+    return Err(LemmyErrorType::InvalidPasskey.into());
+  }
+  Ok((challenge, sign_count))
+}
+
+/// Checks a passkey login, and returns the passkey. As second factor, `local_user_id` is the user
+/// who entered their password. For passwordless login it is empty, and the passkey needs to
+/// verify the user instead.
+pub(crate) async fn check_passkey_login(
+  credential: &PasskeyAssertion,
+  local_user_id: Option<LocalUserId>,
+  context: &LemmyContext,
+) -> LemmyResult<Passkey> {
+  let rp = RelyingParty::new(context)?;
+  let passkey = Passkey::read_by_credential_id(&mut context.pool(), &credential.id).await?;
+  if local_user_id.is_some_and(|id| id != passkey.local_user_id)
+    || credential
+      .response
+      .user_handle
+      .as_ref()
+      .is_some_and(|h| h != &user_handle(passkey.local_user_id))
+  {
+    return Err(LemmyErrorType::InvalidPasskey.into());
+  }
+
+  let (challenge, sign_count) =
+    verify_assertion(credential, &passkey, local_user_id.is_none(), &rp)?;
+  let challenge = PasskeyChallenge::consume(&mut context.pool(), &challenge).await?;
+  if challenge
+    .local_user_id
+    .is_some_and(|id| id != passkey.local_user_id)
+  {
+    return Err(LemmyErrorType::InvalidPasskey.into());
+  }
+
+  Passkey::mark_as_used(&mut context.pool(), passkey.id, sign_count).await?;
+  Ok(passkey)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use lemmy_db_schema::newtypes::PasskeyId;
+  use lemmy_db_views_site::api::{PasskeyAssertionResponse, PasskeyAttestationResponse};
+  use ring::{
+    rand::SystemRandom,
+    signature::{ECDSA_P256_SHA256_ASN1_SIGNING, EcdsaKeyPair, KeyPair},
+  };
+
+  fn relying_party() -> RelyingParty {
+    RelyingParty {
+      id: "example.com".to_string(),
+      origin: "https://example.com".to_string(),
+    }
+  }
+
+  fn client_data(type_: &str, challenge: &str, origin: &str) -> Vec<u8> {
+    serde_json::json!({ "type": type_, "challenge": challenge, "origin": origin })
+      .to_string()
+      .into_bytes()
+  }
+
+  fn authenticator_data(flags: u8, sign_count: u32) -> Vec<u8> {
+    let mut data = Sha256::digest(b"example.com").to_vec();
+    data.push(flags);
+    data.extend(sign_count.to_be_bytes());
+    data
+  }
+
+  #[test]
+  fn test_passkey_registration_and_login() -> LemmyResult<()> {
+    let rp = relying_party();
+    let rng = SystemRandom::new();
+    let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng)?;
+    let key_pair = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref(), &rng)?;
+    let point = key_pair.public_key().as_ref();
+    let cose_key = Value::Map(vec![
+      (Value::from(1), Value::from(2)),
+      (Value::from(3), Value::from(COSE_ALG_ES256)),
+      (Value::from(-1), Value::from(1)),
+      (
+        Value::from(-2),
+        Value::Bytes(point.get(1..33).unwrap_or_default().to_vec()),
+      ),
+      (
+        Value::from(-3),
+        Value::Bytes(point.get(33..).unwrap_or_default().to_vec()),
+      ),
+    ]);
+
+    // Register the passkey
+    let credential_id = b"credential";
+    let mut auth_data = authenticator_data(
+      FLAG_USER_PRESENT | FLAG_USER_VERIFIED | FLAG_ATTESTED_CREDENTIAL,
+      0,
+    );
+    auth_data.extend([0; 16]);
+    auth_data.extend(u16::try_from(credential_id.len())?.to_be_bytes());
+    auth_data.extend(credential_id);
+    ciborium::into_writer(&cose_key, &mut auth_data)?;
+    let attestation = Value::Map(vec![
+      (Value::from("fmt"), Value::from("none")),
+      (Value::from("attStmt"), Value::Map(vec![])),
+      (Value::from("authData"), Value::Bytes(auth_data)),
+    ]);
+    let mut attestation_object = Vec::new();
+    ciborium::into_writer(&attestation, &mut attestation_object)?;
+    let registration = PasskeyAttestation {
+      id: encode(credential_id),
+      response: PasskeyAttestationResponse {
+        client_data_json: encode(&client_data(
+          "webauthn.create",
+          "challenge1",
+          "https://example.com",
+        )),
+        attestation_object: encode(&attestation_object),
+      },
+    };
+    let (challenge, new_credential) = verify_registration(&registration, &rp)?;
+    assert_eq!("challenge1", challenge);
+    assert_eq!(encode(credential_id), new_credential.credential_id);
+
+    // Registration from another site is rejected
+    let mut other_origin = registration.clone();
+    other_origin.response.client_data_json = encode(&client_data(
+      "webauthn.create",
+      "challenge1",
+      "https://evil.example",
+    ));
+    assert!(verify_registration(&other_origin, &rp).is_err());
+
+    // Log in with the passkey
+    let mut passkey = Passkey {
+      id: PasskeyId(1),
+      local_user_id: LocalUserId(1),
+      name: "Laptop".to_string(),
+      credential_id: new_credential.credential_id,
+      public_key: new_credential.public_key,
+      sign_count: new_credential.sign_count.into(),
+      last_used_at: None,
+      published_at: Utc::now(),
+    };
+    let sign = |flags: u8, sign_count: u32| -> LemmyResult<PasskeyAssertion> {
+      let client_data_json = client_data("webauthn.get", "challenge2", "https://example.com");
+      let auth_data = authenticator_data(flags, sign_count);
+      let mut message = auth_data.clone();
+      message.extend_from_slice(&Sha256::digest(&client_data_json));
+      let signature = key_pair.sign(&rng, &message)?;
+      Ok(PasskeyAssertion {
+        id: passkey.credential_id.clone(),
+        response: PasskeyAssertionResponse {
+          client_data_json: encode(&client_data_json),
+          authenticator_data: encode(&auth_data),
+          signature: encode(signature.as_ref()),
+          user_handle: None,
+        },
+      })
+    };
+    let assertion = sign(FLAG_USER_PRESENT | FLAG_USER_VERIFIED, 1)?;
+    assert_eq!(
+      ("challenge2".to_string(), 1),
+      verify_assertion(&assertion, &passkey, true, &rp)?
+    );
+
+    // Passwordless login needs user verification
+    let assertion = sign(FLAG_USER_PRESENT, 1)?;
+    assert!(verify_assertion(&assertion, &passkey, true, &rp).is_err());
+    assert!(verify_assertion(&assertion, &passkey, false, &rp).is_ok());
+
+    // The signature needs to match
+    let mut tampered = assertion.clone();
+    tampered.response.authenticator_data = encode(&authenticator_data(FLAG_USER_PRESENT, 2));
+    assert!(verify_assertion(&tampered, &passkey, false, &rp).is_err());
+
+    // The counter needs to increase
+    passkey.sign_count = 1;
+    assert!(verify_assertion(&assertion, &passkey, false, &rp).is_err());
+
+    Ok(())
+  }
+}
@@ -0,0 +1,105 @@
+use crate::local_user::passkey::{
+  COSE_ALG_EDDSA,
+  COSE_ALG_ES256,
+  COSE_ALG_RS256,
+  PASSKEY_TIMEOUT,
+  RelyingParty,
+  check_passkey_name,
+  create_challenge,
+  credential_descriptors,
+  user_handle,
+  verify_registration,
+};
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::{context::LemmyContext, utils::check_local_user_banned_or_deleted};
+use lemmy_db_schema::source::passkey::{Passkey, PasskeyChallenge, PasskeyInsertForm};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::{
+  SiteView,
+  api::{
+    BeginPasskeyRegistrationResponse,
+    FinishPasskeyRegistration,
+    PasskeyAuthenticatorSelection,
+    PasskeyCreationOptions,
+    PasskeyCredentialParameters,
+    PasskeyRelyingParty,
+    PasskeyResponse,
+    PasskeyUser,
+  },
+};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+/// Returns the options for registering a new passkey in the browser. The result is passed to
+/// [finish_passkey_registration].
+pub async fn begin_passkey_registration(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<BeginPasskeyRegistrationResponse>> {
+  check_local_user_banned_or_deleted(&local_user_view)?;
+  let rp = RelyingParty::new(&context)?;
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  let local_user_id = local_user_view.local_user.id;
+  let passkeys = Passkey::list(&mut context.pool(), local_user_id).await?;
+  let challenge = create_challenge(Some(local_user_id), &context).await?;
+
+  let person = local_user_view.person;
+  let pub_key_cred_params = [COSE_ALG_ES256, COSE_ALG_EDDSA, COSE_ALG_RS256]
+    .into_iter()
+    .map(|alg| PasskeyCredentialParameters {
+      type_: "public-key".to_string(),
+      alg,
+    })
+    .collect();
+
+  Ok(Json(BeginPasskeyRegistrationResponse {
+    public_key: PasskeyCreationOptions {
+      rp: PasskeyRelyingParty {
+        id: rp.id,
+        name: site_view.site.name,
+      },
+      user: PasskeyUser {
+        id: user_handle(local_user_id),
+        display_name: person.display_name.unwrap_or_else(|| person.name.clone()),
+        name: person.name,
+      },
+      challenge,
+      pub_key_cred_params,
+      timeout: PASSKEY_TIMEOUT.num_milliseconds(),
+      // Prevents registering the same authenticator twice
+      exclude_credentials: credential_descriptors(&passkeys),
+      authenticator_selection: PasskeyAuthenticatorSelection {
+        resident_key: "preferred".to_string(),
+        user_verification: "preferred".to_string(),
+      },
+      attestation: "none".to_string(),
+    },
+  }))
+}
+
+pub async fn finish_passkey_registration(
+  Json(data): Json<FinishPasskeyRegistration>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PasskeyResponse>> {
+  check_local_user_banned_or_deleted(&local_user_view)?;
+  let name = check_passkey_name(&data.name)?;
+  let rp = RelyingParty::new(&context)?;
+  let (challenge, credential) = verify_registration(&data.credential, &rp)?;
+
+  let local_user_id = local_user_view.local_user.id;
+  let challenge = PasskeyChallenge::consume(&mut context.pool(), &challenge).await?;
+  if challenge.local_user_id != Some(local_user_id) {
+    return Err(LemmyErrorType::InvalidPasskey.into());
+  }
+
+  let form = PasskeyInsertForm::new(
+    local_user_id,
+    name,
+    credential.credential_id,
+    credential.public_key,
+    credential.sign_count.into(),
+  );
+  let passkey = Passkey::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(PasskeyResponse { passkey }))
+}
@@ -0,0 +1,36 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::{context::LemmyContext, push::decode_subscription_key};
+use lemmy_db_schema::source::push_subscription::{PushSubscription, PushSubscriptionInsertForm};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{CreatePushSubscription, SuccessResponse};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+/// Length of an uncompressed P-256 public key.
+const P256DH_LENGTH: usize = 65;
+
+/// Length of the authentication secret.
+const AUTH_LENGTH: usize = 16;
+
+pub async fn create_push_subscription(
+  Json(data): Json<CreatePushSubscription>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  // Push messages contain notification previews, so they are only sent over https
+  if data.endpoint.scheme() != "https"
+    || decode_subscription_key(&data.p256dh)?.len() != P256DH_LENGTH
+    || decode_subscription_key(&data.auth)?.len() != AUTH_LENGTH
+  {
+    return Err(LemmyErrorType::InvalidPushSubscription.into());
+  }
+
+  let form = PushSubscriptionInsertForm::new(
+    local_user_view.local_user.id,
+    data.endpoint.to_string(),
+    data.p256dh,
+    data.auth,
+  );
+  PushSubscription::upsert(&mut context.pool(), &form).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -0,0 +1,24 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::push_subscription::PushSubscription;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{DeletePushSubscription, SuccessResponse};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn delete_push_subscription(
+  Json(data): Json<DeletePushSubscription>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let deleted = PushSubscription::delete(
+    &mut context.pool(),
+    data.endpoint.as_str(),
+    local_user_view.local_user.id,
+  )
+  .await?;
+  if deleted == 0 {
+    return Err(LemmyErrorType::NotFound.into());
+  }
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -0,0 +1,13 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::{context::LemmyContext, push::vapid_public_key};
+use lemmy_db_views_site::api::GetVapidPublicKeyResponse;
+use lemmy_utils::error::LemmyResult;
+
+#[expect(clippy::unused_async, reason = "request handlers have to be async")]
+pub async fn get_vapid_public_key(
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<GetVapidPublicKeyResponse>> {
+  Ok(Json(GetVapidPublicKeyResponse {
+    public_key: vapid_public_key(context.secret())?,
+  }))
+}
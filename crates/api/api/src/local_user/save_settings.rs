@@ -1,5 +1,7 @@
+use crate::federation::fetcher::resolve_person;
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
+use chrono::Utc;
 use lemmy_api_utils::{
   context::LemmyContext,
   utils::{
@@ -31,6 +33,7 @@ use lemmy_email::account::send_verification_email;
 use lemmy_utils::{
   error::{LemmyErrorType, LemmyResult},
   utils::validation::{
+    check_also_known_as_count,
     check_blocking_keywords_are_valid,
     is_valid_bio_field,
     is_valid_display_name,
@@ -124,11 +127,30 @@ pub async fn save_user_settings(
 
   let default_comment_sort_type = data.default_comment_sort_type;
 
+  // Notifications which were already emailed immediately shouldn't be repeated in the first digest
+  let last_email_digest_at = data
+    .email_digest_frequency
+    .filter(|f| f != &local_user_view.local_user.email_digest_frequency)
+    .map(|_| Utc::now());
+
+  let also_known_as = if let Some(identifiers) = &data.also_known_as {
+    check_also_known_as_count(identifiers.len())?;
+    let mut also_known_as = vec![];
+    for identifier in identifiers {
+      let person = resolve_person(identifier, &context, &local_user_view).await?;
+      also_known_as.push(Some(person.ap_id.clone()));
+    }
+    Some(also_known_as)
+  } else {
+    None
+  };
+
   let person_form = PersonUpdateForm {
     display_name,
     bio,
     matrix_user_id,
     bot_account: data.bot_account,
+    also_known_as,
     ..Default::default()
   };
 
@@ -162,6 +184,9 @@ pub async fn save_user_settings(
     show_media: data.show_media,
     show_read_posts: data.show_read_posts,
     send_notifications_to_email: data.send_notifications_to_email,
+    email_digest_frequency: data.email_digest_frequency,
+    email_digest_top_posts: data.email_digest_top_posts,
+    last_email_digest_at,
     show_nsfw: data.show_nsfw,
     blur_nsfw: data.blur_nsfw,
     show_bot_accounts: data.show_bot_accounts,
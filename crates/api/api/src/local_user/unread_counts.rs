@@ -16,6 +16,13 @@ pub async fn get_unread_counts(
   context: Data<LemmyContext>,
   local_user_view: LocalUserView,
 ) -> LemmyResult<Json<UnreadCountsResponse>> {
+  Ok(Json(read_unread_counts(&local_user_view, &context).await?))
+}
+
+pub(crate) async fn read_unread_counts(
+  local_user_view: &LocalUserView,
+  context: &LemmyContext,
+) -> LemmyResult<UnreadCountsResponse> {
   let person = &local_user_view.person;
   let show_bot_accounts = local_user_view.local_user.show_bot_accounts;
 
@@ -24,13 +31,13 @@ pub async fn get_unread_counts(
 
   // Community mods get additional counts for reports and pending follows for private communities.
   let (report_count, pending_follow_count) =
-    if check_community_mod_of_any_or_admin_action(&local_user_view, &mut context.pool())
+    if check_community_mod_of_any_or_admin_action(local_user_view, &mut context.pool())
       .await
       .is_ok()
     {
       (
         Some(
-          ReportCombinedViewInternal::get_report_count(&mut context.pool(), &local_user_view)
+          ReportCombinedViewInternal::get_report_count(&mut context.pool(), local_user_view)
             .await?,
         ),
         Some(PendingFollowerView::count_approval_required(&mut context.pool(), person.id).await?),
@@ -40,7 +47,7 @@ pub async fn get_unread_counts(
     };
 
   // Admins also get the number of unread registration applications.
-  let registration_application_count = if is_admin(&local_user_view).is_ok() {
+  let registration_application_count = if is_admin(local_user_view).is_ok() {
     let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
     let verified_email_only = local_site.email_verification_required;
     Some(
@@ -51,10 +58,10 @@ pub async fn get_unread_counts(
     None
   };
 
-  Ok(Json(UnreadCountsResponse {
+  Ok(UnreadCountsResponse {
     notification_count,
     report_count,
     pending_follow_count,
     registration_application_count,
-  }))
+  })
 }
@@ -0,0 +1,20 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::{context::LemmyContext, email_digest::verify_unsubscribe_token};
+use lemmy_db_schema::source::local_user::{LocalUser, LocalUserUpdateForm};
+use lemmy_db_views_site::api::{SuccessResponse, UnsubscribeEmail};
+use lemmy_utils::error::LemmyResult;
+
+pub async fn unsubscribe_email(
+  Json(data): Json<UnsubscribeEmail>,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let local_user_id = verify_unsubscribe_token(&data.token, context.secret())?;
+
+  let form = LocalUserUpdateForm {
+    send_notifications_to_email: Some(false),
+    ..Default::default()
+  };
+  LocalUser::update(&mut context.pool(), local_user_id, &form).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -4,7 +4,12 @@ use actix_web::{
 };
 use lemmy_api_utils::{
   context::LemmyContext,
-  utils::{local_user_view_from_jwt, read_auth_token},
+  utils::{
+    API_TOKEN_PREFIX,
+    local_user_view_from_api_token,
+    local_user_view_from_jwt,
+    read_auth_token,
+  },
 };
 use lemmy_db_views_site::api::SuccessResponse;
 use lemmy_utils::error::{LemmyErrorType, LemmyResult};
@@ -17,7 +22,11 @@ pub async fn validate_auth(
 ) -> LemmyResult<Json<SuccessResponse>> {
   let jwt = read_auth_token(&req)?;
   if let Some(jwt) = jwt {
-    local_user_view_from_jwt(&jwt, &context).await?;
+    if jwt.starts_with(API_TOKEN_PREFIX) {
+      local_user_view_from_api_token(&jwt, &context).await?;
+    } else {
+      local_user_view_from_jwt(&jwt, &context).await?;
+    }
   } else {
     return Err(LemmyErrorType::NotLoggedIn.into());
   }
@@ -0,0 +1,165 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{check_private_instance, is_mod_or_admin_opt, text_diff},
+};
+use lemmy_db_schema::source::{post::Post, post_history::PostHistory};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_post::{
+  PostView,
+  api::{GetPostHistory, GetPostHistoryResponse, PostRevision},
+};
+use lemmy_db_views_site::SiteView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+/// Lists all versions of a post, with the changes made by each edit.
+pub async fn get_post_history(
+  Query(data): Query<GetPostHistory>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<GetPostHistoryResponse>> {
+  let SiteView {
+    site, local_site, ..
+  } = SiteView::read_local(&mut context.pool()).await?;
+  check_private_instance(&local_user_view, &local_site)?;
+
+  let community_id = Post::read(&mut context.pool(), data.post_id)
+    .await?
+    .community_id;
+  let is_mod_or_admin = is_mod_or_admin_opt(
+    &mut context.pool(),
+    local_user_view.as_ref(),
+    Some(community_id),
+  )
+  .await
+  .is_ok();
+  if !local_site.public_edit_history && !is_mod_or_admin {
+    return Err(LemmyErrorType::NotAModOrAdmin.into());
+  }
+
+  // Make sure the post itself is visible
+  let post = PostView::read(
+    &mut context.pool(),
+    data.post_id,
+    local_user_view.as_ref().map(|l| &l.local_user),
+    site.instance_id,
+    is_mod_or_admin,
+  )
+  .await?
+  .post;
+
+  // Like the post body, earlier versions of removed or deleted posts are only visible to mods and
+  // the creator
+  let is_creator = local_user_view
+    .as_ref()
+    .is_some_and(|l| l.person.id == post.creator_id);
+  if (post.removed || post.deleted) && !is_mod_or_admin && !is_creator {
+    return Ok(Json(GetPostHistoryResponse { revisions: vec![] }));
+  }
+
+  let mut versions = PostHistory::list_for_post(&mut context.pool(), post.id)
+    .await?
+    .into_iter()
+    .map(|h| (h.name, h.body, h.url, h.published_at))
+    .collect::<Vec<_>>();
+  versions.push((
+    post.name,
+    post.body,
+    post.url,
+    post.updated_at.unwrap_or(post.published_at),
+  ));
+
+  let mut revisions: Vec<PostRevision> = Vec::with_capacity(versions.len());
+  for (name, body, url, published_at) in versions {
+    let (name_diff, body_diff) = match revisions.last() {
+      Some(prev) => (
+        text_diff(&prev.name, &name),
+        text_diff(
+          prev.body.as_deref().unwrap_or_default(),
+          body.as_deref().unwrap_or_default(),
+        ),
+      ),
+      None => (None, None),
+    };
+    revisions.push(PostRevision {
+      name,
+      body,
+      url,
+      published_at,
+      name_diff,
+      body_diff,
+    });
+  }
+
+  Ok(Json(GetPostHistoryResponse { revisions }))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use lemmy_db_schema::{
+    source::{
+      comment::{Comment, CommentInsertForm},
+      community::{Community, CommunityInsertForm},
+      local_site::{LocalSite, LocalSiteUpdateForm},
+      post::{PostInsertForm, PostUpdateForm},
+    },
+    test_data::TestData,
+  };
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_deleted_post_history() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+    let local_site_form = LocalSiteUpdateForm {
+      public_edit_history: Some(true),
+      ..Default::default()
+    };
+    LocalSite::update(pool, &local_site_form).await?;
+
+    let community = Community::create(
+      pool,
+      &CommunityInsertForm::new(data.instance.id, "test".to_string(), "pubkey".to_string()),
+    )
+    .await?;
+    let post_form = PostInsertForm::new("first".to_string(), data.person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+    let edit_form = PostUpdateForm {
+      name: Some("second".to_string()),
+      ..Default::default()
+    };
+    Post::update(pool, post.id, &edit_form).await?;
+    // Posts deleted by the creator stay visible if they have comments
+    let comment_form =
+      CommentInsertForm::new(data.person.id, post.id, community.id, "comment".to_string());
+    Comment::create(pool, &comment_form, None).await?;
+
+    let query = || Query(GetPostHistory { post_id: post.id });
+    let Json(res) = get_post_history(query(), context.clone(), None).await?;
+    let names = res
+      .revisions
+      .into_iter()
+      .map(|r| r.name)
+      .collect::<Vec<_>>();
+    assert_eq!(vec!["first".to_string(), "second".to_string()], names);
+
+    // Earlier versions of a deleted post are hidden from anonymous users
+    let delete_form = PostUpdateForm {
+      deleted: Some(true),
+      ..Default::default()
+    };
+    Post::update(pool, post.id, &delete_form).await?;
+    let Json(res) = get_post_history(query(), context.clone(), None).await?;
+    assert!(res.revisions.is_empty());
+
+    LocalSite::delete(pool).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+}
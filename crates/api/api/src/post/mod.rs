@@ -1,6 +1,7 @@
 pub mod feature;
 pub mod get_link_metadata;
 pub mod hide;
+pub mod history;
 pub mod like;
 pub mod list_post_likes;
 pub mod lock;
@@ -9,4 +10,5 @@ pub mod mark_read;
 pub mod mod_update;
 pub mod save;
 pub mod update_notifications;
+pub mod vote_poll;
 pub mod warning;
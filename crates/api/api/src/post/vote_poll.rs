@@ -0,0 +1,96 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use itertools::Itertools;
+use lemmy_api_utils::{
+  build_response::build_post_response,
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::{
+    check_bot_account,
+    check_community_user_action,
+    check_local_user_banned_or_deleted,
+    check_post_deleted_or_removed,
+  },
+};
+use lemmy_db_schema::source::{
+  poll::{PollVote, PollVoteForm},
+  post::PostActions,
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_post::{
+  PostView,
+  api::{PostResponse, VotePoll},
+};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn vote_poll(
+  Json(data): Json<VotePoll>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PostResponse>> {
+  check_local_user_banned_or_deleted(&local_user_view)?;
+  check_bot_account(&local_user_view.person)?;
+  let post_id = data.post_id;
+  let my_person_id = local_user_view.person.id;
+
+  let orig_post = PostView::read(
+    &mut context.pool(),
+    post_id,
+    Some(&local_user_view.local_user),
+    local_user_view.person.instance_id,
+    false,
+  )
+  .await?;
+  check_community_user_action(&local_user_view, &orig_post.community, &mut context.pool()).await?;
+  check_post_deleted_or_removed(&orig_post.post)?;
+  if orig_post.post.locked {
+    return Err(LemmyErrorType::Locked.into());
+  }
+
+  let poll = orig_post.poll.ok_or(LemmyErrorType::NotFound)?;
+  if poll.poll.is_closed() {
+    return Err(LemmyErrorType::PollEnded.into());
+  }
+  if !poll.my_votes.is_empty() {
+    return Err(LemmyErrorType::AlreadyVotedInPoll.into());
+  }
+
+  // All choices must belong to this poll, and only one is allowed unless it is multiple choice
+  let choices = poll
+    .choices
+    .into_iter()
+    .filter(|c| data.choice_ids.contains(&c.id))
+    .collect::<Vec<_>>();
+  let valid_count = if poll.poll.multiple_choice {
+    !choices.is_empty()
+  } else {
+    choices.len() == 1
+  };
+  if !valid_count || choices.len() != data.choice_ids.len() || !data.choice_ids.iter().all_unique()
+  {
+    return Err(LemmyErrorType::InvalidPollVote.into());
+  }
+
+  let forms = choices
+    .iter()
+    .map(|c| PollVoteForm {
+      poll_choice_id: c.id,
+      person_id: my_person_id,
+      post_id,
+    })
+    .collect::<Vec<_>>();
+  PollVote::vote(&mut context.pool(), &forms).await?;
+
+  PostActions::mark_as_read(&mut context.pool(), my_person_id, &[post_id]).await?;
+
+  ActivityChannel::submit_activity(
+    SendActivityData::VotePoll {
+      post: orig_post.post,
+      actor: local_user_view.person.clone(),
+      choices,
+    },
+    &context,
+  )?;
+
+  build_post_response(&context, orig_post.community.id, local_user_view, post_id).await
+}
@@ -12,6 +12,7 @@ use lemmy_api_utils::{
     check_local_user_banned_or_deleted,
     slur_regex,
   },
+  webhooks::{WebhookData, send_webhooks},
 };
 use lemmy_db_schema::{
   source::comment_report::{CommentReport, CommentReportForm},
@@ -20,6 +21,7 @@ use lemmy_db_schema::{
 use lemmy_db_views_comment::CommentView;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_report_combined::{
+  ReportCombinedView,
   ReportCombinedViewInternal,
   api::{CommentReportResponse, CreateCommentReport},
 };
@@ -72,6 +74,13 @@ pub async fn create_comment_report(
   let comment_report_view =
     ReportCombinedViewInternal::read_comment_report(&mut context.pool(), report.id, person).await?;
   plugin_hook_after("comment_report_after_create", &comment_report_view);
+  send_webhooks(
+    WebhookData::NewReport(Box::new(ReportCombinedView::Comment(
+      comment_report_view.clone(),
+    ))),
+    Some(comment_report_view.community.id),
+    &context,
+  );
 
   // Email the admins
   let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
@@ -7,6 +7,7 @@ use lemmy_api_utils::{
   plugins::plugin_hook_after,
   send_activity::{ActivityChannel, SendActivityData},
   utils::{check_local_user_banned_or_deleted, slur_regex},
+  webhooks::{WebhookData, send_webhooks},
 };
 use lemmy_db_schema::{
   source::{
@@ -18,6 +19,7 @@ use lemmy_db_schema::{
 };
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_report_combined::{
+  ReportCombinedView,
   ReportCombinedViewInternal,
   api::{CommunityReportResponse, CreateCommunityReport},
 };
@@ -59,6 +61,13 @@ pub async fn create_community_report(
     ReportCombinedViewInternal::read_community_report(&mut context.pool(), report.id, person)
       .await?;
   plugin_hook_after("community_report_after_create", &community_report_view);
+  send_webhooks(
+    WebhookData::NewReport(Box::new(ReportCombinedView::Community(
+      community_report_view.clone(),
+    ))),
+    Some(community_report_view.community.id),
+    &context,
+  );
 
   // Email the admins
   let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
@@ -12,6 +12,7 @@ use lemmy_api_utils::{
     check_post_deleted_or_removed,
     slur_regex,
   },
+  webhooks::{WebhookData, send_webhooks},
 };
 use lemmy_db_schema::{
   source::post_report::{PostReport, PostReportForm},
@@ -20,6 +21,7 @@ use lemmy_db_schema::{
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_post::PostView;
 use lemmy_db_views_report_combined::{
+  ReportCombinedView,
   ReportCombinedViewInternal,
   api::{CreatePostReport, PostReportResponse},
 };
@@ -69,6 +71,11 @@ pub async fn create_post_report(
   let post_report_view =
     ReportCombinedViewInternal::read_post_report(&mut context.pool(), report.id, person).await?;
   plugin_hook_after("post_report_after_create", &post_report_view);
+  send_webhooks(
+    WebhookData::NewReport(Box::new(ReportCombinedView::Post(post_report_view.clone()))),
+    Some(post_report_view.community.id),
+    &context,
+  );
 
   // Email the admins
   let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
@@ -0,0 +1,26 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::source::queued_email::QueuedEmail;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::ListQueuedEmails;
+use lemmy_diesel_utils::pagination::PagedResponse;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn admin_list_emails(
+  Query(data): Query<ListQueuedEmails>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PagedResponse<QueuedEmail>>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let emails = QueuedEmail::list(
+    &mut context.pool(),
+    data.status.unwrap_or_default(),
+    data.page_cursor,
+    data.limit,
+  )
+  .await?;
+
+  Ok(Json(emails))
+}
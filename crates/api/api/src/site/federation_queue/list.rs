@@ -0,0 +1,54 @@
+use actix_web::web::{Data, Json, Query};
+use chrono::Utc;
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::{newtypes::ActivityId, source::activity::SentActivity};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::{
+  FederationQueueView,
+  api::{FederationQueueStatus, ListFederationQueues},
+};
+use lemmy_diesel_utils::pagination::PagedResponse;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_federation_queues(
+  Query(data): Query<ListFederationQueues>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PagedResponse<FederationQueueStatus>>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let queues = FederationQueueView::list(&mut context.pool(), data).await?;
+  let latest_id = SentActivity::read_latest_id(&mut context.pool())
+    .await?
+    .unwrap_or(ActivityId(0));
+  let now = Utc::now();
+  let items = queues
+    .items
+    .into_iter()
+    .map(|queue| {
+      // Queues without a sent activity start at the latest activity
+      let last_sent_id = queue.queue_state.last_successful_id.unwrap_or(latest_id);
+      let activities_behind = (latest_id.0 - last_sent_id.0).max(0);
+      let seconds_behind = if activities_behind > 0 {
+        queue
+          .queue_state
+          .last_successful_published_time_at
+          .map(|published| (now - published).num_seconds())
+      } else {
+        None
+      };
+      FederationQueueStatus {
+        queue,
+        activities_behind,
+        seconds_behind,
+      }
+    })
+    .collect();
+
+  Ok(Json(PagedResponse {
+    items,
+    next_page: queues.next_page,
+    prev_page: queues.prev_page,
+  }))
+}
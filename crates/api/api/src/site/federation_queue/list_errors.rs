@@ -0,0 +1,19 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::source::federation_send_error::FederationSendError;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{ListFederationSendErrors, ListFederationSendErrorsResponse};
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_federation_send_errors(
+  Query(data): Query<ListFederationSendErrors>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListFederationSendErrorsResponse>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let errors = FederationSendError::list(&mut context.pool(), data.instance_id, data.limit).await?;
+
+  Ok(Json(ListFederationSendErrorsResponse { errors }))
+}
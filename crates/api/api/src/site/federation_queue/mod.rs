@@ -0,0 +1,5 @@
+pub mod list;
+pub mod list_errors;
+pub mod pause;
+pub mod retry;
+pub mod skip;
@@ -0,0 +1,24 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::source::{
+  federation_queue_control::FederationQueueControl,
+  instance::Instance,
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::AdminRetryFederationQueue;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn retry_federation_queue(
+  Json(data): Json<AdminRetryFederationQueue>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<FederationQueueControl>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let instance = Instance::read(&mut context.pool(), data.instance_id).await?;
+  // The federation workers check for requests periodically, so this takes effect within a minute
+  let control = FederationQueueControl::request_retry(&mut context.pool(), instance.id).await?;
+
+  Ok(Json(control))
+}
@@ -0,0 +1,31 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::source::{
+  activity::SentActivity,
+  federation_queue_control::FederationQueueControl,
+  instance::Instance,
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::AdminSkipFederationQueue;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn skip_federation_queue(
+  Json(data): Json<AdminSkipFederationQueue>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<FederationQueueControl>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let instance = Instance::read(&mut context.pool(), data.instance_id).await?;
+  // Can't skip activities which don't exist yet
+  let latest_id = SentActivity::read_latest_id(&mut context.pool()).await?;
+  if latest_id.is_none_or(|latest_id| data.activity_id > latest_id) {
+    return Err(LemmyErrorType::NotFound.into());
+  }
+  let control =
+    FederationQueueControl::request_skip(&mut context.pool(), instance.id, data.activity_id)
+      .await?;
+
+  Ok(Json(control))
+}
@@ -1,8 +1,11 @@
 pub mod admin_allow_instance;
 pub mod admin_block_instance;
+pub mod admin_list_emails;
 pub mod admin_list_users;
 pub mod federated_instances;
+pub mod federation_queue;
 pub mod list_all_media;
 pub mod mod_log;
 pub mod purge;
+pub mod received_activities;
 pub mod registration_applications;
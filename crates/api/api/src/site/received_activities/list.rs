@@ -0,0 +1,29 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::source::received_activity_log::ReceivedActivityLog;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::ListReceivedActivities;
+use lemmy_diesel_utils::pagination::PagedResponse;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_received_activities(
+  Query(data): Query<ListReceivedActivities>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PagedResponse<ReceivedActivityLog>>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let activities = ReceivedActivityLog::list(
+    &mut context.pool(),
+    data.actor_id,
+    data.domain,
+    data.kind,
+    data.failed_only.unwrap_or_default(),
+    data.page_cursor,
+    data.limit,
+  )
+  .await?;
+
+  Ok(Json(activities))
+}
@@ -0,0 +1,41 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_apub_objects::utils::functions::REPLAY_RECEIVED_ACTIVITY;
+use lemmy_db_schema::source::received_activity_log::{
+  ReceivedActivityLog,
+  ReceivedActivityLogUpdateForm,
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::ReplayReceivedActivity;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn replay_received_activity(
+  Json(data): Json<ReplayReceivedActivity>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ReceivedActivityLog>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let entry = ReceivedActivityLog::read(&mut context.pool(), data.id).await?;
+  // Without a valid signature there is no proof that the actor really sent this activity
+  let (Some(activity), true) = (entry.data, entry.signature_verified) else {
+    return Err(LemmyErrorType::CantReplayActivity.into());
+  };
+  let Some(replay_fn) = REPLAY_RECEIVED_ACTIVITY.get() else {
+    return Err(LemmyErrorType::CantReplayActivity.into());
+  };
+  let res = replay_fn(activity, context.reset_request_count()).await;
+
+  let form = ReceivedActivityLogUpdateForm {
+    success: Some(res.is_ok()),
+    error: Some(res.err().map(|e| e.to_string())),
+    replayed_at: Some(Some(Utc::now())),
+  };
+  let entry = ReceivedActivityLog::update(&mut context.pool(), entry.id, &form).await?;
+
+  Ok(Json(entry))
+}
@@ -62,13 +62,20 @@ pub async fn approve_registration_application(
   if approved_local_user_view.local_user.email.is_some() {
     // Email sending may fail, but this won't revert the application approval
     if data.approve {
-      send_application_approved_email(&approved_local_user_view, context.settings())?;
+      send_application_approved_email(
+        &approved_local_user_view,
+        &mut context.pool(),
+        context.settings(),
+      )
+      .await?;
     } else {
       send_application_denied_email(
         &approved_local_user_view,
         data.deny_reason.clone(),
+        &mut context.pool(),
         context.settings(),
-      )?;
+      )
+      .await?;
     }
   }
 
@@ -1,25 +1,55 @@
+pub use lemmy_db_schema_file::enums::EmailDigestFrequency;
 pub use lemmy_db_views_person_content_combined::api::{ListPersonHidden, ListPersonRead};
 pub use lemmy_db_views_person_liked_combined::ListPersonLiked;
 pub use lemmy_db_views_person_saved_combined::ListPersonSaved;
 pub use lemmy_db_views_post_comment_combined::PostCommentCombinedView;
-pub use lemmy_db_views_site::api::{DeleteAccount, MyUserInfo, SaveUserSettings};
+pub use lemmy_db_views_site::api::{DeleteAccount, MoveAccount, MyUserInfo, SaveUserSettings};
 pub mod auth {
-  pub use lemmy_db_schema::source::login_token::LoginToken;
+  pub use lemmy_db_schema::{
+    newtypes::{ApiTokenId, PasskeyId},
+    source::{api_token::ApiToken, login_token::LoginToken, passkey::Passkey},
+  };
+  pub use lemmy_db_schema_file::enums::ApiTokenScope;
   pub use lemmy_db_views_registration_applications::api::{CaptchaAnswer, Register};
   pub use lemmy_db_views_site::api::{
+    BeginPasskeyLogin,
+    BeginPasskeyLoginResponse,
+    BeginPasskeyRegistrationResponse,
     CaptchaResponse,
     ChangePassword,
     ChangePasswordAfterReset,
+    CreateApiToken,
+    CreateApiTokenResponse,
+    DeleteApiToken,
+    DeletePasskey,
+    EditPasskey,
     EditTotp,
     EditTotpResponse,
     ExportDataResponse,
+    FinishPasskeyRegistration,
     GenerateTotpSecretResponse,
     GetCaptchaResponse,
+    ListApiTokensResponse,
     ListLoginsResponse,
+    ListPasskeysResponse,
     Login,
     LoginResponse,
+    PasskeyAssertion,
+    PasskeyAssertionResponse,
+    PasskeyAttestation,
+    PasskeyAttestationResponse,
+    PasskeyAuthenticatorSelection,
+    PasskeyCreationOptions,
+    PasskeyCredentialDescriptor,
+    PasskeyCredentialParameters,
+    PasskeyLogin,
+    PasskeyRelyingParty,
+    PasskeyRequestOptions,
+    PasskeyResponse,
+    PasskeyUser,
     ResendVerificationEmail,
     ResetPassword,
+    UnsubscribeEmail,
     UserSettingsBackup,
     VerifyEmail,
   };
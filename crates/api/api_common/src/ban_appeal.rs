@@ -0,0 +1,11 @@
+pub use lemmy_db_schema::{newtypes::BanAppealId, source::ban_appeal::BanAppeal};
+pub use lemmy_db_views_ban_appeal::{BanAppealView, api::BanAppealResponse};
+
+pub mod actions {
+  pub use lemmy_db_views_ban_appeal::api::{
+    CreateBanAppeal,
+    GetBanAppeal,
+    ListBanAppeals,
+    ResolveBanAppeal,
+  };
+}
@@ -1,11 +1,21 @@
 pub use lemmy_db_schema::{
   newtypes::CommentId,
-  source::comment::{Comment, CommentActions, CommentInsertForm},
+  source::{
+    comment::{Comment, CommentActions, CommentInsertForm},
+    comment_history::CommentHistory,
+  },
 };
 pub use lemmy_db_views_comment::{
   CommentSlimView,
   CommentView,
-  api::{CommentResponse, GetComment, GetComments},
+  api::{
+    CommentResponse,
+    CommentRevision,
+    GetComment,
+    GetCommentHistory,
+    GetCommentHistoryResponse,
+    GetComments,
+  },
 };
 
 pub mod actions {
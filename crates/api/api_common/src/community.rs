@@ -38,19 +38,41 @@ pub mod actions {
   };
 
   pub mod moderation {
-    pub use lemmy_db_schema_file::enums::CommunityFollowerState;
+    pub use lemmy_db_schema::{
+      newtypes::{AutomodRuleId, RecurringPostId},
+      source::{automod_rule::AutomodRule, recurring_post::RecurringPost},
+    };
+    pub use lemmy_db_schema_file::enums::{
+      AutomodAction,
+      CommunityFollowerState,
+      RecurringPostFrequency,
+    };
     pub use lemmy_db_views_community::api::{
       AddModToCommunity,
       AddModToCommunityResponse,
       ApproveCommunityPendingFollower,
+      AutomodRuleResponse,
       BanFromCommunity,
       CommunityIdQuery,
+      CreateAutomodRule,
       CreateCommunityTag,
+      CreateRecurringPost,
+      DeleteAutomodRule,
       DeleteCommunity,
       DeleteCommunityTag,
+      DeleteRecurringPost,
+      EditAutomodRule,
       EditCommunity,
       EditCommunityTag,
+      EditRecurringPost,
+      ImportCommunityHistory,
+      ListAutomodRules,
+      ListAutomodRulesResponse,
+      ListRecurringPosts,
+      ListRecurringPostsResponse,
+      MoveCommunity,
       PurgeCommunity,
+      RecurringPostResponse,
       RemoveCommunity,
       TransferCommunity,
     };
@@ -0,0 +1,16 @@
+pub use lemmy_db_schema::{newtypes::DraftId, source::draft::Draft};
+pub use lemmy_db_views_draft::{
+  DraftView,
+  api::{DraftResponse, PublishDraftResponse},
+};
+
+pub mod actions {
+  pub use lemmy_db_views_draft::api::{
+    CreateDraft,
+    DeleteDraft,
+    EditDraft,
+    GetDraft,
+    ListDrafts,
+    PublishDraft,
+  };
+}
@@ -0,0 +1,13 @@
+pub use lemmy_db_schema::{newtypes::EmailTemplateId, source::email_template::EmailTemplate};
+pub use lemmy_db_schema_file::enums::EmailTemplateType;
+
+pub mod administration {
+  pub use lemmy_db_views_site::api::{
+    DeleteEmailTemplate,
+    EmailTemplateResponse,
+    ListEmailTemplatesResponse,
+    PreviewEmailTemplate,
+    PreviewEmailTemplateResponse,
+    SaveEmailTemplate,
+  };
+}
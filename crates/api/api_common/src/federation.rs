@@ -20,5 +20,28 @@ pub use lemmy_db_views_site::{
 };
 
 pub mod administration {
-  pub use lemmy_db_views_site::api::{AdminAllowInstanceParams, AdminBlockInstanceParams};
+  pub use lemmy_db_schema::{
+    newtypes::{FederationSendErrorId, ReceivedActivityLogId},
+    source::{
+      federation_queue_control::FederationQueueControl,
+      federation_send_error::FederationSendError,
+      received_activity_log::ReceivedActivityLog,
+    },
+  };
+  pub use lemmy_db_views_site::{
+    FederationQueueView,
+    api::{
+      AdminAllowInstanceParams,
+      AdminBlockInstanceParams,
+      AdminPauseFederationQueue,
+      AdminRetryFederationQueue,
+      AdminSkipFederationQueue,
+      FederationQueueStatus,
+      ListFederationQueues,
+      ListFederationSendErrors,
+      ListFederationSendErrorsResponse,
+      ListReceivedActivities,
+      ReplayReceivedActivity,
+    },
+  };
 }
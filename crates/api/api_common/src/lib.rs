@@ -1,12 +1,16 @@
 pub mod account;
+pub mod ban_appeal;
 pub mod comment;
 pub mod community;
 pub mod custom_emoji;
+pub mod draft;
+pub mod email_template;
 pub mod error;
 pub mod federation;
 pub mod language;
 pub mod media;
 pub mod modlog;
+pub mod modmail;
 pub mod notification;
 pub mod oauth;
 pub mod person;
@@ -16,6 +20,7 @@ pub mod private_message;
 pub mod report;
 pub mod site;
 pub mod tagline;
+pub mod webhook;
 
 pub use lemmy_db_schema_file::enums::VoteShow;
 pub use lemmy_db_views_site::api::SuccessResponse;
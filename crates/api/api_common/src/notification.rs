@@ -8,3 +8,11 @@ pub use lemmy_db_views_notification::{
   NotificationView,
   api::MarkNotificationAsRead,
 };
+pub use lemmy_db_views_site::api::{
+  CreatePushSubscription,
+  DeletePushSubscription,
+  GetVapidPublicKeyResponse,
+  ListenEvents,
+  PushNotification,
+  StreamEvent,
+};
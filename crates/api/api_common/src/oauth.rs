@@ -2,6 +2,7 @@ pub use lemmy_db_schema::{
   newtypes::OAuthProviderId,
   source::{
     oauth_account::OAuthAccount,
+    oauth_client::OAuthClient,
     oauth_provider::{AdminOAuthProvider, PublicOAuthProvider},
   },
 };
@@ -10,4 +11,13 @@ pub use lemmy_db_views_site::api::{
   CreateOAuthProvider,
   DeleteOAuthProvider,
   EditOAuthProvider,
+  OAuthAuthorize,
+  OAuthAuthorizeResponse,
+  OAuthConsent,
+  OAuthConsentResponse,
+  OAuthRevoke,
+  OAuthToken,
+  OAuthTokenResponse,
+  RegisterOAuthClient,
+  RegisterOAuthClientResponse,
 };
@@ -1,7 +1,11 @@
 pub use lemmy_db_schema::{
   PostFeatureType,
-  newtypes::PostId,
-  source::post::{Post, PostActions, PostInsertForm, PostLikeForm},
+  newtypes::{PollChoiceId, PostId},
+  source::{
+    poll::{Poll, PollChoice, PollView},
+    post::{Post, PostActions, PostInsertForm, PostLikeForm},
+    post_history::PostHistory,
+  },
 };
 pub use lemmy_db_schema_file::enums::{PostListingMode, PostNotificationsMode};
 pub use lemmy_db_views_post::{
@@ -9,6 +13,8 @@ pub use lemmy_db_views_post::{
   api::{
     CreatePostWarning,
     GetPost,
+    GetPostHistory,
+    GetPostHistoryResponse,
     GetPostResponse,
     GetPosts,
     GetSiteMetadata,
@@ -16,6 +22,7 @@ pub use lemmy_db_views_post::{
     LinkMetadata,
     OpenGraphData,
     PostResponse,
+    PostRevision,
   },
 };
 pub mod actions {
@@ -29,6 +36,7 @@ pub mod actions {
     MarkManyPostsAsRead,
     MarkPostAsRead,
     SavePost,
+    VotePoll,
   };
 
   pub mod moderation {
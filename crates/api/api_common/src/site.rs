@@ -14,11 +14,22 @@ pub use lemmy_db_views_site::{
 };
 
 pub mod administration {
+  pub use lemmy_db_schema::{
+    QueuedEmailStatus,
+    newtypes::QueuedEmailId,
+    source::queued_email::QueuedEmail,
+  };
   pub use lemmy_db_views_local_user::api::AdminListUsers;
   pub use lemmy_db_views_person::api::{AddAdmin, AddAdminResponse};
   pub use lemmy_db_views_registration_applications::api::{
     ApproveRegistrationApplication,
     ListRegistrationApplications,
   };
-  pub use lemmy_db_views_site::api::{CreateSite, EditSite, Search, SearchResponse};
+  pub use lemmy_db_views_site::api::{
+    CreateSite,
+    EditSite,
+    ListQueuedEmails,
+    Search,
+    SearchResponse,
+  };
 }
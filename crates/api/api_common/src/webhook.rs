@@ -0,0 +1,15 @@
+pub use lemmy_db_schema::{
+  newtypes::{WebhookDeliveryId, WebhookId},
+  source::webhook::{Webhook, WebhookDelivery},
+};
+pub use lemmy_db_schema_file::enums::WebhookEventType;
+pub use lemmy_db_views_site::api::{
+  CreateWebhook,
+  CreateWebhookResponse,
+  DeleteWebhook,
+  EditWebhook,
+  ListWebhookDeliveries,
+  ListWebhooks,
+  ListWebhooksResponse,
+  WebhookResponse,
+};
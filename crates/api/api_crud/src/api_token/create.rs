@@ -0,0 +1,64 @@
+use actix_web::web::{Data, Json};
+use chrono::Utc;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{API_TOKEN_PREFIX, hash_api_token, is_admin},
+};
+use lemmy_db_schema::source::api_token::{ApiToken, ApiTokenInsertForm};
+use lemmy_db_schema_file::enums::ApiTokenScope;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{CreateApiToken, CreateApiTokenResponse};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use rand::{RngExt, distr::Alphanumeric};
+
+const MAX_API_TOKEN_NAME_LENGTH: usize = 100;
+
+pub async fn create_api_token(
+  Json(data): Json<CreateApiToken>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CreateApiTokenResponse>> {
+  let name = data.name.trim();
+  if name.is_empty() || name.chars().count() > MAX_API_TOKEN_NAME_LENGTH || data.scopes.is_empty() {
+    return Err(LemmyErrorType::InvalidApiToken.into());
+  }
+  if data.expires_at.is_some_and(|e| e < Utc::now()) {
+    return Err(LemmyErrorType::InvalidApiToken.into());
+  }
+  if data.scopes.contains(&ApiTokenScope::Admin) {
+    is_admin(&local_user_view)?;
+  }
+
+  let mut scopes = Vec::new();
+  for scope in data.scopes {
+    if !scopes.contains(&Some(scope)) {
+      scopes.push(Some(scope));
+    }
+  }
+
+  let token = generate_api_token();
+  let form = ApiTokenInsertForm {
+    expires_at: data.expires_at,
+    ..ApiTokenInsertForm::new(
+      local_user_view.local_user.id,
+      name.to_string(),
+      hash_api_token(&token),
+      scopes,
+    )
+  };
+  let api_token = ApiToken::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(CreateApiTokenResponse {
+    api_token,
+    token: token.into(),
+  }))
+}
+
+fn generate_api_token() -> String {
+  let random: String = rand::rng()
+    .sample_iter(Alphanumeric)
+    .take(40)
+    .map(char::from)
+    .collect();
+  format!("{API_TOKEN_PREFIX}{random}")
+}
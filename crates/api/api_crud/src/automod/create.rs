@@ -0,0 +1,37 @@
+use crate::automod::{check_automod_permission, check_automod_rule, non_empty, to_db_array};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::automod_rule::{AutomodRule, AutomodRuleInsertForm};
+use lemmy_db_views_community::api::{AutomodRuleResponse, CreateAutomodRule};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{error::LemmyResult, utils::validation::check_api_elements_count};
+
+pub async fn create_automod_rule(
+  Json(data): Json<CreateAutomodRule>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<AutomodRuleResponse>> {
+  check_automod_permission(&local_user_view, data.community_id, &context).await?;
+  let rules = AutomodRule::list(&mut context.pool(), data.community_id).await?;
+  check_api_elements_count(rules.len())?;
+
+  let form = AutomodRuleInsertForm {
+    title_regex: non_empty(data.title_regex),
+    body_regex: non_empty(data.body_regex),
+    url_regex: non_empty(data.url_regex),
+    max_account_age_days: data.max_account_age_days,
+    max_post_score: data.max_post_score,
+    max_comment_score: data.max_comment_score,
+    domains: to_db_array(data.domains),
+    language_ids: to_db_array(data.language_ids),
+    community_tag_id: data.community_tag_id,
+    reply_text: non_empty(data.reply_text),
+    ..AutomodRuleInsertForm::new(data.community_id, data.name, data.action)
+  };
+  check_automod_rule(&form, &context).await?;
+  let rule = AutomodRule::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(AutomodRuleResponse { rule }))
+}
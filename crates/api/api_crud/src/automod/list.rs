@@ -0,0 +1,20 @@
+use crate::automod::check_automod_permission;
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::automod_rule::AutomodRule;
+use lemmy_db_views_community::api::{ListAutomodRules, ListAutomodRulesResponse};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_automod_rules(
+  Query(data): Query<ListAutomodRules>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListAutomodRulesResponse>> {
+  check_automod_permission(&local_user_view, data.community_id, &context).await?;
+
+  let rules = AutomodRule::list(&mut context.pool(), data.community_id).await?;
+
+  Ok(Json(ListAutomodRulesResponse { rules }))
+}
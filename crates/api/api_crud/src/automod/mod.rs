@@ -0,0 +1,103 @@
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{check_community_mod_action, slur_regex},
+};
+use lemmy_db_schema::{
+  newtypes::CommunityId,
+  source::{
+    automod_rule::AutomodRuleInsertForm,
+    community::Community,
+    community_tag::CommunityTag,
+  },
+};
+use lemmy_db_schema_file::enums::AutomodAction;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::{
+    slurs::check_slurs,
+    validation::{build_and_check_regex, check_api_elements_count, is_valid_body_field},
+  },
+};
+
+pub mod create;
+pub mod delete;
+pub mod list;
+pub mod update;
+
+/// Automod rules are managed by the mods of a community. They only exist for local communities, as
+/// remote content is moderated by its own instance.
+async fn check_automod_permission(
+  local_user_view: &LocalUserView,
+  community_id: CommunityId,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let community = Community::read(&mut context.pool(), community_id).await?;
+  if !community.local {
+    return Err(LemmyErrorType::InvalidAutomodRule.into());
+  }
+  check_community_mod_action(local_user_view, &community, false, &mut context.pool()).await
+}
+
+/// Checks that a rule has at least one condition, valid regexes, and the field which is required
+/// by its action.
+async fn check_automod_rule(
+  form: &AutomodRuleInsertForm,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  if form.name.trim().is_empty() {
+    return Err(LemmyErrorType::InvalidAutomodRule.into());
+  }
+  is_valid_body_field(&form.name, false)?;
+
+  let regexes = [&form.title_regex, &form.body_regex, &form.url_regex];
+  for regex in regexes.iter().copied().flatten() {
+    build_and_check_regex(Some(regex))?;
+  }
+  let domains = form.domains.as_deref().unwrap_or_default();
+  let language_ids = form.language_ids.as_deref().unwrap_or_default();
+  check_api_elements_count(domains.len())?;
+  check_api_elements_count(language_ids.len())?;
+
+  let has_condition = regexes.iter().any(|r| r.is_some())
+    || form.max_account_age_days.is_some()
+    || form.max_post_score.is_some()
+    || form.max_comment_score.is_some()
+    || !domains.is_empty()
+    || !language_ids.is_empty();
+  if !has_condition {
+    return Err(LemmyErrorType::InvalidAutomodRule.into());
+  }
+
+  match form.action {
+    AutomodAction::ApplyTag => {
+      let tag_id = form
+        .community_tag_id
+        .ok_or(LemmyErrorType::InvalidAutomodRule)?;
+      let tag = CommunityTag::read(&mut context.pool(), tag_id).await?;
+      if tag.community_id != form.community_id || tag.deleted {
+        return Err(LemmyErrorType::TagNotInCommunity.into());
+      }
+    }
+    AutomodAction::Reply => {
+      let reply_text = form
+        .reply_text
+        .as_deref()
+        .ok_or(LemmyErrorType::InvalidAutomodRule)?;
+      is_valid_body_field(reply_text, false)?;
+      check_slurs(reply_text, &slur_regex(context).await?)?;
+    }
+    AutomodAction::Remove | AutomodAction::Report | AutomodAction::Lock => {}
+  }
+  Ok(())
+}
+
+/// Empty strings from the API mean that a condition is not used.
+fn non_empty(text: Option<String>) -> Option<String> {
+  text.filter(|t| !t.trim().is_empty())
+}
+
+fn to_db_array<T>(items: Option<Vec<T>>) -> Option<Vec<Option<T>>> {
+  items.map(|items| items.into_iter().map(Some).collect())
+}
@@ -0,0 +1,59 @@
+use crate::automod::{check_automod_permission, check_automod_rule, non_empty, to_db_array};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::automod_rule::{
+  AutomodRule,
+  AutomodRuleInsertForm,
+  AutomodRuleUpdateForm,
+};
+use lemmy_db_views_community::api::{AutomodRuleResponse, EditAutomodRule};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn edit_automod_rule(
+  Json(data): Json<EditAutomodRule>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<AutomodRuleResponse>> {
+  let rule = AutomodRule::read(&mut context.pool(), data.id).await?;
+  check_automod_permission(&local_user_view, rule.community_id, &context).await?;
+
+  // The whole rule is replaced, so it is validated the same way as a new one
+  let rule = AutomodRuleInsertForm {
+    title_regex: non_empty(data.title_regex),
+    body_regex: non_empty(data.body_regex),
+    url_regex: non_empty(data.url_regex),
+    max_account_age_days: data.max_account_age_days,
+    max_post_score: data.max_post_score,
+    max_comment_score: data.max_comment_score,
+    domains: to_db_array(data.domains),
+    language_ids: to_db_array(data.language_ids),
+    community_tag_id: data.community_tag_id,
+    reply_text: non_empty(data.reply_text),
+    ..AutomodRuleInsertForm::new(rule.community_id, data.name, data.action)
+  };
+  check_automod_rule(&rule, &context).await?;
+
+  let form = AutomodRuleUpdateForm {
+    name: Some(rule.name),
+    title_regex: Some(rule.title_regex),
+    body_regex: Some(rule.body_regex),
+    url_regex: Some(rule.url_regex),
+    max_account_age_days: Some(rule.max_account_age_days),
+    max_post_score: Some(rule.max_post_score),
+    max_comment_score: Some(rule.max_comment_score),
+    domains: Some(rule.domains.unwrap_or_default()),
+    language_ids: Some(rule.language_ids.unwrap_or_default()),
+    action: Some(rule.action),
+    community_tag_id: Some(rule.community_tag_id),
+    reply_text: Some(rule.reply_text),
+    enabled: data.enabled,
+    updated_at: Some(Some(Utc::now())),
+  };
+  let rule = AutomodRule::update(&mut context.pool(), data.id, &form).await?;
+
+  Ok(Json(AutomodRuleResponse { rule }))
+}
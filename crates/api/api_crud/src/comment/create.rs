@@ -2,6 +2,7 @@ use crate::community_use_pending;
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use lemmy_api_utils::{
+  automod::{AutomodContent, run_automod},
   build_response::build_comment_response,
   context::LemmyContext,
   notify::NotifyData,
@@ -17,6 +18,7 @@ use lemmy_api_utils::{
     slur_regex,
     update_read_comments,
   },
+  webhooks::{WebhookData, send_webhooks},
 };
 use lemmy_db_schema::{
   impls::actor_language::validate_post_language,
@@ -117,6 +119,11 @@ pub async fn create_comment(
   let inserted_comment =
     Comment::create(&mut context.pool(), &comment_form, parent_path.as_ref()).await?;
   plugin_hook_after("local_comment_after_create", &inserted_comment);
+  send_webhooks(
+    WebhookData::NewComment(inserted_comment.id),
+    Some(community_id),
+    &context,
+  );
 
   NotifyData {
     comment: Some(inserted_comment.clone()),
@@ -138,6 +145,7 @@ pub async fn create_comment(
     SendActivityData::CreateComment(inserted_comment.clone()),
     &context,
   )?;
+  run_automod(AutomodContent::Comment(inserted_comment.clone()), &context);
 
   // Update the read comments, so your own new comment doesn't appear as a +1 unread
   update_read_comments(
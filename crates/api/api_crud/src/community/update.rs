@@ -14,6 +14,7 @@ use lemmy_api_utils::{
     slur_regex,
   },
 };
+use lemmy_apub_objects::utils::functions::check_apub_id_valid_with_strictness;
 use lemmy_db_schema::source::{
   actor_language::{CommunityLanguage, SiteLanguage},
   community::{Community, CommunityUpdateForm},
@@ -24,12 +25,13 @@ use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_site::SiteView;
 use lemmy_diesel_utils::{traits::Crud, utils::diesel_string_update};
 use lemmy_utils::{
-  error::{LemmyErrorType, LemmyResult},
+  error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
   utils::{
     slurs::{check_slurs, check_slurs_opt},
-    validation::{is_valid_body_field, is_valid_display_name},
+    validation::{check_also_known_as_count, is_valid_body_field, is_valid_display_name},
   },
 };
+use url::Url;
 
 pub async fn edit_community(
   Json(data): Json<EditCommunity>,
@@ -86,6 +88,19 @@ pub async fn edit_community(
     CommunityLanguage::update(&mut context.pool(), languages, community_id).await?;
   }
 
+  let also_known_as = if let Some(ids) = &data.also_known_as {
+    check_also_known_as_count(ids.len())?;
+    let mut also_known_as = vec![];
+    for id in ids {
+      let url = Url::parse(id).with_lemmy_type(LemmyErrorType::InvalidUrl)?;
+      check_apub_id_valid_with_strictness(&url, true, &context).await?;
+      also_known_as.push(Some(url.into()));
+    }
+    Some(also_known_as)
+  } else {
+    None
+  };
+
   let community_form = CommunityUpdateForm {
     title,
     sidebar,
@@ -93,6 +108,7 @@ pub async fn edit_community(
     nsfw: data.nsfw,
     posting_restricted_to_mods: data.posting_restricted_to_mods,
     visibility: data.visibility,
+    also_known_as,
     updated_at: Some(Some(Utc::now())),
     ..Default::default()
   };
@@ -0,0 +1,57 @@
+use crate::draft::check_draft_fields;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{context::LemmyContext, utils::check_local_user_banned_or_deleted};
+use lemmy_db_schema::source::draft::{Draft, DraftInsertForm};
+use lemmy_db_views_draft::{
+  DraftView,
+  api::{CreateDraft, DraftResponse},
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn create_draft(
+  Json(data): Json<CreateDraft>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<DraftResponse>> {
+  check_local_user_banned_or_deleted(&local_user_view)?;
+
+  let targets = [
+    data.community_id.is_some(),
+    data.post_id.is_some(),
+    data.recipient_id.is_some(),
+  ];
+  if targets.into_iter().filter(|t| *t).count() != 1
+    || (data.parent_id.is_some() && data.post_id.is_none())
+  {
+    return Err(LemmyErrorType::InvalidDraft.into());
+  }
+  check_draft_fields(&[
+    &data.name,
+    &data.url,
+    &data.body,
+    &data.alt_text,
+    &data.custom_thumbnail,
+  ])?;
+
+  let form = DraftInsertForm {
+    community_id: data.community_id,
+    post_id: data.post_id,
+    parent_id: data.parent_id,
+    recipient_id: data.recipient_id,
+    name: data.name,
+    url: data.url,
+    body: data.body,
+    alt_text: data.alt_text,
+    nsfw: data.nsfw,
+    language_id: data.language_id,
+    custom_thumbnail: data.custom_thumbnail,
+    ..DraftInsertForm::new(local_user_view.person.id)
+  };
+  let draft = Draft::create(&mut context.pool(), &form).await?;
+
+  let draft_view = DraftView::read(&mut context.pool(), draft.id).await?;
+  Ok(Json(DraftResponse { draft_view }))
+}
@@ -0,0 +1,22 @@
+use crate::draft::read_own_draft;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::draft::Draft;
+use lemmy_db_views_draft::api::DeleteDraft;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::SuccessResponse;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn delete_draft(
+  Json(data): Json<DeleteDraft>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let draft = read_own_draft(data.draft_id, &local_user_view, &context).await?;
+
+  Draft::delete(&mut context.pool(), draft.id).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
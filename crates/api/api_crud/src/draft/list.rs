@@ -0,0 +1,23 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_views_draft::{DraftView, api::ListDrafts, impls::DraftQuery};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::pagination::PagedResponse;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_drafts(
+  Query(data): Query<ListDrafts>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PagedResponse<DraftView>>> {
+  let drafts = DraftQuery {
+    creator_id: local_user_view.person.id,
+    page_cursor: data.page_cursor,
+    limit: data.limit,
+  }
+  .list(&mut context.pool())
+  .await?;
+
+  Ok(Json(drafts))
+}
@@ -0,0 +1,37 @@
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::{newtypes::DraftId, source::draft::Draft};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::validation::is_valid_body_field,
+};
+
+pub mod create;
+pub mod delete;
+pub mod list;
+pub mod publish;
+pub mod read;
+pub mod update;
+
+/// Drafts are private, so drafts of other users are treated as nonexistent.
+async fn read_own_draft(
+  draft_id: DraftId,
+  local_user_view: &LocalUserView,
+  context: &LemmyContext,
+) -> LemmyResult<Draft> {
+  let draft = Draft::read(&mut context.pool(), draft_id).await?;
+  if draft.creator_id != local_user_view.person.id {
+    return Err(LemmyErrorType::NotFound.into());
+  }
+  Ok(draft)
+}
+
+/// Drafts may be incomplete, so only the length is checked here. Everything else is validated
+/// when the draft gets published.
+fn check_draft_fields(fields: &[&Option<String>]) -> LemmyResult<()> {
+  for field in fields.iter().copied().flatten() {
+    is_valid_body_field(field, true)?;
+  }
+  Ok(())
+}
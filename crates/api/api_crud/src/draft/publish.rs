@@ -0,0 +1,83 @@
+use crate::{
+  comment::create::create_comment,
+  post::create::create_post,
+  private_message::create::create_private_message,
+};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::draft::Draft;
+use lemmy_db_views_comment::api::CreateComment;
+use lemmy_db_views_draft::api::{PublishDraft, PublishDraftResponse};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_post::api::CreatePost;
+use lemmy_db_views_private_message::api::CreatePrivateMessage;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+/// Publishes a draft through the normal create endpoint of its type, so that it goes through the
+/// same validation and federation. The draft is claimed first so that it can't be published twice
+/// by concurrent requests, and put back if publishing fails.
+pub async fn publish_draft(
+  Json(data): Json<PublishDraft>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PublishDraftResponse>> {
+  let draft = Draft::claim(
+    &mut context.pool(),
+    data.draft_id,
+    local_user_view.person.id,
+  )
+  .await?;
+
+  let res = Box::pin(publish(draft.clone(), context.clone(), local_user_view)).await;
+  if res.is_err() {
+    draft.restore(&mut context.pool()).await?;
+  }
+  Ok(Json(res?))
+}
+
+async fn publish(
+  draft: Draft,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<PublishDraftResponse> {
+  let mut res = PublishDraftResponse {
+    post_view: None,
+    comment_view: None,
+    private_message_view: None,
+  };
+  if let Some(community_id) = draft.community_id {
+    let form = CreatePost {
+      name: draft.name.unwrap_or_default(),
+      community_id,
+      url: draft.url,
+      body: draft.body,
+      alt_text: draft.alt_text,
+      nsfw: draft.nsfw,
+      language_id: draft.language_id,
+      custom_thumbnail: draft.custom_thumbnail,
+      ..Default::default()
+    };
+    let post = create_post(Json(form), context.clone(), local_user_view).await?;
+    res.post_view = Some(post.0.post_view);
+  } else if let Some(post_id) = draft.post_id {
+    let form = CreateComment {
+      content: draft.body.unwrap_or_default(),
+      post_id,
+      parent_id: draft.parent_id,
+      language_id: draft.language_id,
+    };
+    let comment = create_comment(Json(form), context.clone(), local_user_view).await?;
+    res.comment_view = Some(comment.0.comment_view);
+  } else if let Some(recipient_id) = draft.recipient_id {
+    let form = CreatePrivateMessage {
+      content: draft.body.unwrap_or_default(),
+      recipient_id,
+    };
+    let message = create_private_message(Json(form), context.clone(), local_user_view).await?;
+    res.private_message_view = Some(message.0.private_message_view);
+  } else {
+    return Err(LemmyErrorType::InvalidDraft.into());
+  }
+  Ok(res)
+}
@@ -0,0 +1,21 @@
+use crate::draft::read_own_draft;
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_views_draft::{
+  DraftView,
+  api::{DraftResponse, GetDraft},
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn get_draft(
+  Query(data): Query<GetDraft>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<DraftResponse>> {
+  let draft = read_own_draft(data.draft_id, &local_user_view, &context).await?;
+
+  let draft_view = DraftView::read(&mut context.pool(), draft.id).await?;
+  Ok(Json(DraftResponse { draft_view }))
+}
@@ -0,0 +1,43 @@
+use crate::draft::{check_draft_fields, read_own_draft};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::draft::{Draft, DraftUpdateForm};
+use lemmy_db_views_draft::{
+  DraftView,
+  api::{DraftResponse, EditDraft},
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::{traits::Crud, utils::diesel_string_update};
+use lemmy_utils::error::LemmyResult;
+
+pub async fn edit_draft(
+  Json(data): Json<EditDraft>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<DraftResponse>> {
+  let draft = read_own_draft(data.draft_id, &local_user_view, &context).await?;
+  check_draft_fields(&[
+    &data.name,
+    &data.url,
+    &data.body,
+    &data.alt_text,
+    &data.custom_thumbnail,
+  ])?;
+
+  let form = DraftUpdateForm {
+    name: diesel_string_update(data.name.as_deref()),
+    url: diesel_string_update(data.url.as_deref()),
+    body: diesel_string_update(data.body.as_deref()),
+    alt_text: diesel_string_update(data.alt_text.as_deref()),
+    nsfw: data.nsfw.map(Some),
+    language_id: data.language_id.map(Some),
+    custom_thumbnail: diesel_string_update(data.custom_thumbnail.as_deref()),
+    updated_at: Some(Some(Utc::now())),
+  };
+  Draft::update(&mut context.pool(), draft.id, &form).await?;
+
+  let draft_view = DraftView::read(&mut context.pool(), draft.id).await?;
+  Ok(Json(DraftResponse { draft_view }))
+}
@@ -0,0 +1,19 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::source::email_template::EmailTemplate;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::ListEmailTemplatesResponse;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_email_templates(
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListEmailTemplatesResponse>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let email_templates = EmailTemplate::list(&mut context.pool()).await?;
+
+  Ok(Json(ListEmailTemplatesResponse { email_templates }))
+}
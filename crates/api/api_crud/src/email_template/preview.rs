@@ -0,0 +1,26 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{PreviewEmailTemplate, PreviewEmailTemplateResponse};
+use lemmy_email::template::render_template_preview;
+use lemmy_utils::error::LemmyResult;
+
+#[expect(clippy::unused_async, reason = "request handlers have to be async")]
+pub async fn preview_email_template(
+  Json(data): Json<PreviewEmailTemplate>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PreviewEmailTemplateResponse>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let (subject, body) = render_template_preview(
+    data.template_type,
+    &data.subject,
+    &data.body,
+    context.settings(),
+  )?;
+
+  Ok(Json(PreviewEmailTemplateResponse { subject, body }))
+}
@@ -0,0 +1,31 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::source::email_template::{EmailTemplate, EmailTemplateInsertForm};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{EmailTemplateResponse, SaveEmailTemplate};
+use lemmy_email::template::validate_email_template;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn save_email_template(
+  Json(data): Json<SaveEmailTemplate>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<EmailTemplateResponse>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let language = data.language.filter(|l| !l.is_empty());
+  validate_email_template(
+    data.template_type,
+    language.as_deref(),
+    &data.subject,
+    &data.body,
+    context.settings(),
+  )?;
+
+  let form = EmailTemplateInsertForm::new(data.template_type, language, data.subject, data.body);
+  let email_template = EmailTemplate::upsert(&mut context.pool(), &form).await?;
+
+  Ok(Json(EmailTemplateResponse { email_template }))
+}
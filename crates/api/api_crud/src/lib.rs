@@ -1,17 +1,25 @@
 use lemmy_api_utils::context::LemmyContext;
 use lemmy_db_schema::source::community::{Community, CommunityActions};
 
+pub mod api_token;
+pub mod automod;
 pub mod comment;
 pub mod community;
 pub mod custom_emoji;
+pub mod draft;
+pub mod email_template;
 pub mod invite;
+pub mod modmail;
 pub mod multi_community;
+pub mod oauth_client;
 pub mod oauth_provider;
 pub mod post;
 pub mod private_message;
+pub mod recurring_post;
 pub mod site;
 pub mod tagline;
 pub mod user;
+pub mod webhook;
 
 /// Only mark new posts/comments to remote community as pending if it has any local followers.
 /// Otherwise it could never get updated to be marked as published.
@@ -0,0 +1,35 @@
+use crate::modmail::check_modmail_access;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::modmail::{ModmailThread, ModmailThreadUpdateForm};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_modmail::{
+  ModmailThreadView,
+  api::{ArchiveModmailThread, ModmailThreadResponse},
+};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+/// The archive state is only stored locally, it is not federated.
+pub async fn archive_modmail_thread(
+  Json(data): Json<ArchiveModmailThread>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ModmailThreadResponse>> {
+  let thread = ModmailThread::read(&mut context.pool(), data.thread_id).await?;
+  if !check_modmail_access(&local_user_view, &thread, &context).await? {
+    return Err(LemmyErrorType::NotAModerator.into());
+  }
+
+  let form = ModmailThreadUpdateForm {
+    archived: Some(data.archived),
+    updated_at: Some(Some(Utc::now())),
+  };
+  ModmailThread::update(&mut context.pool(), thread.id, &form).await?;
+
+  Ok(Json(ModmailThreadResponse {
+    thread_view: ModmailThreadView::read(&mut context.pool(), thread.id).await?,
+  }))
+}
@@ -0,0 +1,92 @@
+use crate::modmail::process_modmail_content;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  notify::notify_modmail,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::{check_community_deleted_removed, check_local_user_banned_or_deleted, slur_regex},
+};
+use lemmy_db_schema::source::{
+  comment::Comment,
+  community::Community,
+  modlog::Modlog,
+  modmail::{ModmailMessage, ModmailMessageInsertForm, ModmailThread, ModmailThreadInsertForm},
+  post::Post,
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_modmail::{
+  ModmailMessageView,
+  ModmailThreadView,
+  api::{CreateModmailThread, GetModmailThreadResponse},
+};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::{slurs::check_slurs, validation::is_valid_post_title},
+};
+
+pub async fn create_modmail_thread(
+  Json(data): Json<CreateModmailThread>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<GetModmailThreadResponse>> {
+  // Users who are banned from the community can still contact its mods, eg to appeal the ban.
+  check_local_user_banned_or_deleted(&local_user_view)?;
+  let community = Community::read(&mut context.pool(), data.community_id).await?;
+  check_community_deleted_removed(&community)?;
+
+  let subject = data.subject.trim().to_string();
+  is_valid_post_title(&subject)?;
+  check_slurs(&subject, &slur_regex(&context).await?)?;
+  let content = process_modmail_content(&data.content, &context).await?;
+  check_modmail_links(&data, &context).await?;
+
+  let thread_form = ModmailThreadInsertForm {
+    post_id: data.post_id,
+    comment_id: data.comment_id,
+    modlog_id: data.modlog_id,
+    ..ModmailThreadInsertForm::new(community.id, local_user_view.person.id, subject)
+  };
+  let thread = ModmailThread::create(&mut context.pool(), &thread_form).await?;
+  let message_form =
+    ModmailMessageInsertForm::new(thread.id, local_user_view.person.id, false, content);
+  let message = ModmailMessage::create(&mut context.pool(), &message_form).await?;
+
+  notify_modmail(&thread, &message, &context);
+  let message_view = ModmailMessageView::new(message.clone(), local_user_view.person, false);
+  ActivityChannel::submit_activity(
+    SendActivityData::CreateModmailMessage(thread.clone(), message),
+    &context,
+  )?;
+
+  Ok(Json(GetModmailThreadResponse {
+    thread_view: ModmailThreadView::read(&mut context.pool(), thread.id).await?,
+    messages: vec![message_view],
+  }))
+}
+
+/// The linked post, comment and mod action need to belong to the same community as the thread.
+async fn check_modmail_links(
+  data: &CreateModmailThread,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let mut community_ids = vec![];
+  if let Some(post_id) = data.post_id {
+    community_ids.push(Some(
+      Post::read(&mut context.pool(), post_id).await?.community_id,
+    ));
+  }
+  if let Some(comment_id) = data.comment_id {
+    let comment = Comment::read(&mut context.pool(), comment_id).await?;
+    community_ids.push(Some(comment.community_id));
+  }
+  if let Some(modlog_id) = data.modlog_id {
+    let modlog = Modlog::read(&mut context.pool(), modlog_id).await?;
+    community_ids.push(modlog.target_community_id);
+  }
+  if community_ids.iter().any(|c| *c != Some(data.community_id)) {
+    return Err(LemmyErrorType::InvalidModmailThread.into());
+  }
+  Ok(())
+}
@@ -0,0 +1,45 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::check_local_user_banned_or_deleted};
+use lemmy_db_views_community_moderator::CommunityModeratorView;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_modmail::{
+  ModmailThreadView,
+  api::ListModmailThreads,
+  impls::ModmailThreadQuery,
+};
+use lemmy_diesel_utils::pagination::PagedResponse;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_modmail_threads(
+  Query(data): Query<ListModmailThreads>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PagedResponse<ModmailThreadView>>> {
+  check_local_user_banned_or_deleted(&local_user_view)?;
+
+  // Mods list all threads of their community, everyone else only their own threads
+  let creator_id = if let Some(community_id) = data.community_id {
+    CommunityModeratorView::check_is_community_moderator(
+      &mut context.pool(),
+      community_id,
+      local_user_view.person.id,
+    )
+    .await?;
+    None
+  } else {
+    Some(local_user_view.person.id)
+  };
+
+  let threads = ModmailThreadQuery {
+    community_id: data.community_id,
+    creator_id,
+    archived: data.archived,
+    page_cursor: data.page_cursor,
+    limit: data.limit,
+  }
+  .list(&mut context.pool())
+  .await?;
+
+  Ok(Json(threads))
+}
@@ -0,0 +1,50 @@
+use activitypub_federation::config::Data;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{check_local_user_banned_or_deleted, get_url_blocklist, process_markdown, slur_regex},
+};
+use lemmy_db_schema::source::modmail::ModmailThread;
+use lemmy_db_views_community_moderator::CommunityModeratorView;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::SiteView;
+use lemmy_utils::{error::LemmyResult, utils::validation::is_valid_body_field};
+
+pub mod archive;
+pub mod create;
+pub mod list;
+pub mod read;
+pub mod reply;
+
+/// Only the user who opened a thread and the moderators of its community have access to it.
+///
+/// Returns true if the user acts as the mod team.
+async fn check_modmail_access(
+  local_user_view: &LocalUserView,
+  thread: &ModmailThread,
+  context: &LemmyContext,
+) -> LemmyResult<bool> {
+  check_local_user_banned_or_deleted(local_user_view)?;
+  if thread.creator_id == local_user_view.person.id {
+    return Ok(false);
+  }
+  CommunityModeratorView::check_is_community_moderator(
+    &mut context.pool(),
+    thread.community_id,
+    local_user_view.person.id,
+  )
+  .await?;
+  Ok(true)
+}
+
+async fn process_modmail_content(
+  content: &str,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<String> {
+  let slur_regex = slur_regex(context).await?;
+  let url_blocklist = get_url_blocklist(context).await?;
+  let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+  let content =
+    process_markdown(content, &slur_regex, &url_blocklist, &local_site, context).await?;
+  is_valid_body_field(&content, false)?;
+  Ok(content)
+}
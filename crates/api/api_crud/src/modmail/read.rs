@@ -0,0 +1,38 @@
+use crate::modmail::check_modmail_access;
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::{modmail::ModmailThread, notification::Notification};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_modmail::{
+  ModmailMessageView,
+  ModmailThreadView,
+  api::{GetModmailThread, GetModmailThreadResponse},
+};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn get_modmail_thread(
+  Query(data): Query<GetModmailThread>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<GetModmailThreadResponse>> {
+  let thread = ModmailThread::read(&mut context.pool(), data.thread_id).await?;
+  let is_mod = check_modmail_access(&local_user_view, &thread, &context).await?;
+
+  let thread_view = ModmailThreadView::read(&mut context.pool(), thread.id).await?;
+  let messages =
+    ModmailMessageView::list_for_thread(&mut context.pool(), thread.id, !is_mod).await?;
+
+  Notification::mark_read_by_modmail_thread_and_recipient(
+    &mut context.pool(),
+    thread.id,
+    local_user_view.person.id,
+  )
+  .await?;
+
+  Ok(Json(GetModmailThreadResponse {
+    thread_view,
+    messages,
+  }))
+}
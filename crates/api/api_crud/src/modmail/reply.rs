@@ -0,0 +1,52 @@
+use crate::modmail::{check_modmail_access, process_modmail_content};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  notify::notify_modmail,
+  send_activity::{ActivityChannel, SendActivityData},
+};
+use lemmy_db_schema::source::modmail::{
+  ModmailMessage,
+  ModmailMessageInsertForm,
+  ModmailThread,
+  ModmailThreadUpdateForm,
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_modmail::{
+  ModmailMessageView,
+  api::{CreateModmailMessage, ModmailMessageResponse},
+};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn create_modmail_message(
+  Json(data): Json<CreateModmailMessage>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ModmailMessageResponse>> {
+  let thread = ModmailThread::read(&mut context.pool(), data.thread_id).await?;
+  let from_mod_team = check_modmail_access(&local_user_view, &thread, &context).await?;
+  let content = process_modmail_content(&data.content, &context).await?;
+
+  let form =
+    ModmailMessageInsertForm::new(thread.id, local_user_view.person.id, from_mod_team, content);
+  let message = ModmailMessage::create(&mut context.pool(), &form).await?;
+
+  // A new message from the user brings an archived thread back to the attention of the mods.
+  let form = ModmailThreadUpdateForm {
+    archived: (!from_mod_team).then_some(false),
+    updated_at: Some(Some(Utc::now())),
+  };
+  let thread = ModmailThread::update(&mut context.pool(), thread.id, &form).await?;
+
+  notify_modmail(&thread, &message, &context);
+  let message_view = ModmailMessageView::new(message.clone(), local_user_view.person, false);
+  ActivityChannel::submit_activity(
+    SendActivityData::CreateModmailMessage(thread, message),
+    &context,
+  )?;
+
+  Ok(Json(ModmailMessageResponse { message_view }))
+}
@@ -0,0 +1,71 @@
+use crate::oauth_client::{generate_secret, validate_authorize};
+use actix_web::web::{Data, Json, Query};
+use chrono::{Duration, Utc};
+use lemmy_api_utils::{context::LemmyContext, utils::hash_api_token};
+use lemmy_db_schema::source::oauth_client::{
+  OAuthAuthorizationCode,
+  OAuthAuthorizationCodeInsertForm,
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{
+  OAuthAuthorize,
+  OAuthAuthorizeResponse,
+  OAuthConsent,
+  OAuthConsentResponse,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+use url::Url;
+
+/// How long the client has to exchange the authorization code for tokens.
+const AUTHORIZATION_CODE_EXPIRY: Duration = Duration::minutes(10);
+
+/// Validates an authorization request, and returns what the frontend should show on the consent
+/// screen.
+pub async fn get_oauth_authorize(
+  Query(data): Query<OAuthAuthorize>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<OAuthAuthorizeResponse>> {
+  let (client, scopes) = validate_authorize(&data, &local_user_view, &context).await?;
+  Ok(Json(OAuthAuthorizeResponse { client, scopes }))
+}
+
+/// Stores the decision of the user, and returns the uri to redirect back to the client.
+pub async fn oauth_consent(
+  Json(data): Json<OAuthConsent>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<OAuthConsentResponse>> {
+  let (client, scopes) = validate_authorize(&data.authorize, &local_user_view, &context).await?;
+  let mut redirect_uri = Url::parse(&data.authorize.redirect_uri)
+    .with_lemmy_type(LemmyErrorType::OauthInvalidRequest)?;
+
+  let code = if data.approve {
+    let code = generate_secret();
+    let form = OAuthAuthorizationCodeInsertForm {
+      code_hash: hash_api_token(&code),
+      oauth_client_id: client.id,
+      local_user_id: local_user_view.local_user.id,
+      redirect_uri: data.authorize.redirect_uri.clone(),
+      scopes: scopes.into_iter().map(Some).collect(),
+      code_challenge: data.authorize.code_challenge.clone(),
+      expires_at: Utc::now() + AUTHORIZATION_CODE_EXPIRY,
+    };
+    OAuthAuthorizationCode::create(&mut context.pool(), &form).await?;
+    Some(code)
+  } else {
+    None
+  };
+
+  let mut query = redirect_uri.query_pairs_mut();
+  match &code {
+    Some(code) => query.append_pair("code", code),
+    None => query.append_pair("error", "access_denied"),
+  };
+  if let Some(state) = &data.authorize.state {
+    query.append_pair("state", state);
+  }
+  drop(query);
+
+  Ok(Json(OAuthConsentResponse { redirect_uri }))
+}
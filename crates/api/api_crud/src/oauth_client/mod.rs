@@ -0,0 +1,149 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use lemmy_api_utils::{context::LemmyContext, utils::hash_api_token};
+use lemmy_db_schema::source::oauth_client::OAuthClient;
+use lemmy_db_schema_file::enums::ApiTokenScope;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::OAuthAuthorize;
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+use rand::{RngExt, distr::Alphanumeric};
+use sha2::{Digest, Sha256};
+use std::str::FromStr;
+
+pub mod authorize;
+pub mod register;
+pub mod revoke;
+pub mod token;
+
+/// Parses space separated OAuth scopes, ignoring duplicates.
+fn parse_scopes(scope: &str) -> LemmyResult<Vec<ApiTokenScope>> {
+  let mut scopes = Vec::new();
+  for s in scope.split_whitespace() {
+    let s = ApiTokenScope::from_str(s).with_lemmy_type(LemmyErrorType::OauthInvalidScope)?;
+    if !scopes.contains(&s) {
+      scopes.push(s);
+    }
+  }
+  if scopes.is_empty() {
+    return Err(LemmyErrorType::OauthInvalidScope.into());
+  }
+  Ok(scopes)
+}
+
+fn format_scopes(scopes: &[Option<ApiTokenScope>]) -> String {
+  scopes
+    .iter()
+    .flatten()
+    .map(ToString::to_string)
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+fn generate_secret() -> String {
+  rand::rng()
+    .sample_iter(Alphanumeric)
+    .take(40)
+    .map(char::from)
+    .collect()
+}
+
+/// Reads the client of a token or revocation request. Confidential clients also need to send
+/// their secret.
+async fn authenticate_client(
+  client_id: &str,
+  client_secret: Option<&str>,
+  context: &LemmyContext,
+) -> LemmyResult<OAuthClient> {
+  let client = OAuthClient::read_by_client_id(&mut context.pool(), client_id).await?;
+  if let Some(secret_hash) = &client.client_secret_hash {
+    let secret = client_secret.ok_or(LemmyErrorType::OauthInvalidClient)?;
+    if &hash_api_token(secret) != secret_hash {
+      return Err(LemmyErrorType::OauthInvalidClient.into());
+    }
+  }
+  Ok(client)
+}
+
+/// Checks an authorization request against the registered client, and returns the scopes which
+/// the user is asked to grant.
+async fn validate_authorize(
+  data: &OAuthAuthorize,
+  local_user_view: &LocalUserView,
+  context: &LemmyContext,
+) -> LemmyResult<(OAuthClient, Vec<ApiTokenScope>)> {
+  let client = OAuthClient::read_by_client_id(&mut context.pool(), &data.client_id).await?;
+  if !client
+    .redirect_uris
+    .iter()
+    .flatten()
+    .any(|r| r == &data.redirect_uri)
+  {
+    return Err(LemmyErrorType::OauthInvalidRequest.into());
+  }
+  if data.response_type != "code"
+    || data.code_challenge_method != "S256"
+    || !(43..=128).contains(&data.code_challenge.len())
+  {
+    return Err(LemmyErrorType::OauthInvalidRequest.into());
+  }
+
+  let scopes = match &data.scope {
+    Some(scope) => parse_scopes(scope)?,
+    None => client.scopes.iter().flatten().copied().collect(),
+  };
+  if scopes.iter().any(|s| !client.scopes.contains(&Some(*s))) {
+    return Err(LemmyErrorType::OauthInvalidScope.into());
+  }
+  if scopes.contains(&ApiTokenScope::Admin) && !local_user_view.local_user.admin {
+    return Err(LemmyErrorType::OauthInvalidScope.into());
+  }
+  Ok((client, scopes))
+}
+
+/// Checks the PKCE code verifier against the S256 challenge of the authorization request.
+fn verify_code_challenge(code_verifier: &str, code_challenge: &str) -> LemmyResult<()> {
+  if !(43..=128).contains(&code_verifier.len()) {
+    return Err(LemmyErrorType::InvalidCodeVerifier.into());
+  }
+  let hash = URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()));
+  if hash != code_challenge {
+    return Err(LemmyErrorType::OauthInvalidGrant.into());
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_verify_code_challenge() {
+    // Example from RFC 7636, appendix B
+    let verifier = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+    let challenge = "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM";
+    assert!(verify_code_challenge(verifier, challenge).is_ok());
+    assert!(
+      verify_code_challenge(verifier, "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cm").is_err()
+    );
+    assert!(verify_code_challenge("short", challenge).is_err());
+  }
+
+  #[test]
+  fn test_parse_scopes() -> LemmyResult<()> {
+    let scopes = parse_scopes("read vote read private_message")?;
+    assert_eq!(
+      vec![
+        ApiTokenScope::Read,
+        ApiTokenScope::Vote,
+        ApiTokenScope::PrivateMessage
+      ],
+      scopes
+    );
+    assert_eq!(
+      "read vote private_message",
+      format_scopes(&scopes.into_iter().map(Some).collect::<Vec<_>>())
+    );
+    assert!(parse_scopes("read write").is_err());
+    assert!(parse_scopes(" ").is_err());
+    Ok(())
+  }
+}
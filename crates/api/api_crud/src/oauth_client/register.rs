@@ -0,0 +1,81 @@
+use crate::oauth_client::{format_scopes, generate_secret, parse_scopes};
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{hash_api_token, slur_regex},
+};
+use lemmy_db_schema::source::oauth_client::{OAuthClient, OAuthClientInsertForm};
+use lemmy_db_views_site::api::{RegisterOAuthClient, RegisterOAuthClientResponse};
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::slurs::check_slurs,
+};
+
+const MAX_CLIENT_NAME_LENGTH: usize = 100;
+const MAX_REDIRECT_URIS: usize = 10;
+
+/// Dynamic client registration, so that apps work with any instance without asking the admin
+/// first. Users still need to give consent before an app gets access to their account.
+pub async fn register_oauth_client(
+  Json(data): Json<RegisterOAuthClient>,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<RegisterOAuthClientResponse>> {
+  let name = data.client_name.trim();
+  if name.is_empty() || name.chars().count() > MAX_CLIENT_NAME_LENGTH {
+    return Err(LemmyErrorType::OauthInvalidRequest.into());
+  }
+  check_slurs(name, &slur_regex(&context).await?)?;
+
+  // Redirect uris may use custom schemes for mobile apps, but plain http is only allowed for
+  // apps running on the same device.
+  if data.redirect_uris.is_empty()
+    || data.redirect_uris.len() > MAX_REDIRECT_URIS
+    || data.redirect_uris.iter().any(|r| {
+      r.fragment().is_some()
+        || (r.scheme() == "http" && !matches!(r.host_str(), Some("localhost" | "127.0.0.1")))
+    })
+  {
+    return Err(LemmyErrorType::OauthInvalidRequest.into());
+  }
+
+  let auth_method = data
+    .token_endpoint_auth_method
+    .unwrap_or_else(|| "none".to_string());
+  let client_secret = match auth_method.as_str() {
+    "none" => None,
+    "client_secret_post" => Some(generate_secret()),
+    _ => return Err(LemmyErrorType::OauthInvalidRequest.into()),
+  };
+
+  let scopes = parse_scopes(data.scope.as_deref().unwrap_or("read"))?
+    .into_iter()
+    .map(Some)
+    .collect::<Vec<_>>();
+  let redirect_uris = data
+    .redirect_uris
+    .into_iter()
+    .map(|r| Some(r.to_string()))
+    .collect();
+
+  let form = OAuthClientInsertForm {
+    client_uri: data.client_uri.map(Into::into),
+    ..OAuthClientInsertForm::new(
+      generate_secret(),
+      client_secret.as_deref().map(hash_api_token),
+      name.to_string(),
+      redirect_uris,
+      scopes,
+    )
+  };
+  let client = OAuthClient::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(RegisterOAuthClientResponse {
+    client_id: client.client_id,
+    client_secret: client_secret.map(Into::into),
+    client_name: client.name,
+    redirect_uris: client.redirect_uris.into_iter().flatten().collect(),
+    client_uri: client.client_uri.map(|u| u.to_string()),
+    scope: format_scopes(&client.scopes),
+    token_endpoint_auth_method: auth_method,
+  }))
+}
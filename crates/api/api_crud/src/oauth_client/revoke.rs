@@ -0,0 +1,18 @@
+use crate::oauth_client::authenticate_client;
+use actix_web::web::{Data, Form, Json};
+use lemmy_api_utils::{context::LemmyContext, utils::hash_api_token};
+use lemmy_db_schema::source::api_token::ApiToken;
+use lemmy_db_views_site::api::{OAuthRevoke, SuccessResponse};
+use lemmy_utils::error::LemmyResult;
+
+/// Revokes the grant which the given access or refresh token belongs to. Unknown tokens are
+/// ignored, as required by RFC 7009.
+pub async fn oauth_revoke(
+  Form(data): Form<OAuthRevoke>,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let client =
+    authenticate_client(&data.client_id, data.client_secret.as_deref(), &context).await?;
+  ApiToken::revoke_oauth(&mut context.pool(), &hash_api_token(&data.token), client.id).await?;
+  Ok(Json(SuccessResponse::default()))
+}
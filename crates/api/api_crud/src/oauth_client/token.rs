@@ -0,0 +1,94 @@
+use crate::oauth_client::{
+  authenticate_client,
+  format_scopes,
+  generate_secret,
+  verify_code_challenge,
+};
+use actix_web::web::{Data, Form, Json};
+use chrono::{Duration, Utc};
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{API_TOKEN_PREFIX, hash_api_token},
+};
+use lemmy_db_schema::source::{
+  api_token::{ApiToken, ApiTokenInsertForm, ApiTokenRefreshForm},
+  oauth_client::OAuthAuthorizationCode,
+};
+use lemmy_db_views_site::api::{OAuthToken, OAuthTokenResponse};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+/// Access tokens are short-lived, clients use the refresh token to get a new one.
+const ACCESS_TOKEN_EXPIRY: Duration = Duration::hours(1);
+
+/// Exchanges an authorization code or a refresh token for a new access token.
+pub async fn oauth_token(
+  Form(data): Form<OAuthToken>,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<OAuthTokenResponse>> {
+  let client =
+    authenticate_client(&data.client_id, data.client_secret.as_deref(), &context).await?;
+
+  let access_token = format!("{API_TOKEN_PREFIX}{}", generate_secret());
+  let refresh_token = generate_secret();
+  let expires_at = Utc::now() + ACCESS_TOKEN_EXPIRY;
+
+  let api_token = match data.grant_type.as_str() {
+    "authorization_code" => {
+      let code = data
+        .code
+        .as_deref()
+        .ok_or(LemmyErrorType::OauthInvalidRequest)?;
+      let code_verifier = data
+        .code_verifier
+        .as_deref()
+        .ok_or(LemmyErrorType::OauthInvalidRequest)?;
+      let code =
+        OAuthAuthorizationCode::consume(&mut context.pool(), &hash_api_token(code)).await?;
+      if code.oauth_client_id != client.id || data.redirect_uri.as_ref() != Some(&code.redirect_uri)
+      {
+        return Err(LemmyErrorType::OauthInvalidGrant.into());
+      }
+      verify_code_challenge(code_verifier, &code.code_challenge)?;
+
+      let form = ApiTokenInsertForm {
+        expires_at: Some(expires_at),
+        oauth_client_id: Some(client.id),
+        refresh_token_hash: Some(hash_api_token(&refresh_token)),
+        ..ApiTokenInsertForm::new(
+          code.local_user_id,
+          client.name,
+          hash_api_token(&access_token),
+          code.scopes,
+        )
+      };
+      ApiToken::create(&mut context.pool(), &form).await?
+    }
+    "refresh_token" => {
+      let old_refresh_token = data
+        .refresh_token
+        .as_deref()
+        .ok_or(LemmyErrorType::OauthInvalidRequest)?;
+      let form = ApiTokenRefreshForm {
+        token_hash: hash_api_token(&access_token),
+        refresh_token_hash: hash_api_token(&refresh_token),
+        expires_at,
+      };
+      ApiToken::refresh(
+        &mut context.pool(),
+        &hash_api_token(old_refresh_token),
+        client.id,
+        &form,
+      )
+      .await?
+    }
+    _ => return Err(LemmyErrorType::OauthUnsupportedGrantType.into()),
+  };
+
+  Ok(Json(OAuthTokenResponse {
+    access_token: access_token.into(),
+    token_type: "Bearer".to_string(),
+    expires_in: ACCESS_TOKEN_EXPIRY.num_seconds(),
+    refresh_token: refresh_token.into(),
+    scope: format_scopes(&api_token.scopes),
+  }))
+}
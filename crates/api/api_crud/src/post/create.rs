@@ -2,7 +2,9 @@ use super::convert_published_time;
 use crate::community_use_pending;
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
+use chrono::{TimeZone, Utc};
 use lemmy_api_utils::{
+  automod::{AutomodContent, run_automod},
   build_response::build_post_response,
   context::LemmyContext,
   notify::NotifyData,
@@ -19,10 +21,14 @@ use lemmy_api_utils::{
     slur_regex,
     update_post_tags,
   },
+  webhooks::{WebhookData, send_webhooks},
 };
 use lemmy_db_schema::{
   impls::actor_language::validate_post_language,
-  source::post::{Post, PostActions, PostInsertForm, PostLikeForm},
+  source::{
+    poll::Poll,
+    post::{Post, PostActions, PostInsertForm, PostLikeForm},
+  },
   traits::Likeable,
 };
 use lemmy_db_views_community::CommunityView;
@@ -32,13 +38,14 @@ use lemmy_db_views_post::api::{CreatePost, PostResponse};
 use lemmy_db_views_site::SiteView;
 use lemmy_diesel_utils::{traits::Crud, utils::diesel_url_create};
 use lemmy_utils::{
-  error::LemmyResult,
+  error::{LemmyErrorType, LemmyResult},
   utils::{
     slurs::check_slurs,
     validation::{
       is_url_blocked,
       is_valid_alt_text_field,
       is_valid_body_field,
+      is_valid_poll_choices,
       is_valid_post_title,
       is_valid_url,
     },
@@ -88,6 +95,31 @@ pub async fn create_post(
     is_valid_body_field(body, true)?;
   }
 
+  let poll_choices = data.poll_choices.as_ref().map(|choices| {
+    choices
+      .iter()
+      .map(|c| c.trim().to_string())
+      .collect::<Vec<_>>()
+  });
+  if let Some(poll_choices) = &poll_choices {
+    is_valid_poll_choices(poll_choices)?;
+    for choice in poll_choices {
+      check_slurs(choice, &slur_regex)?;
+    }
+  }
+  let poll_end_at = data
+    .poll_end_at
+    .map(|t| {
+      Utc
+        .timestamp_opt(t, 0)
+        .single()
+        .ok_or(LemmyErrorType::InvalidUnixTime)
+    })
+    .transpose()?;
+  if poll_end_at.is_some_and(|e| e < Utc::now()) {
+    return Err(LemmyErrorType::PollEnded.into());
+  }
+
   let community_view = CommunityView::read(
     &mut context.pool(),
     data.community_id,
@@ -140,7 +172,19 @@ pub async fn create_post(
   )
   .await?;
 
-  let inserted_post = Post::create(&mut context.pool(), &post_form).await?;
+  let inserted_post = if let Some(poll_choices) = &poll_choices {
+    Poll::create(
+      &mut context.pool(),
+      &post_form,
+      data.poll_multiple_choice,
+      poll_end_at,
+      poll_choices,
+    )
+    .await?
+    .0
+  } else {
+    Post::create(&mut context.pool(), &post_form).await?
+  };
 
   plugin_hook_after("local_post_after_create", &inserted_post);
 
@@ -151,6 +195,12 @@ pub async fn create_post(
   let community_id = community.id;
   let federate_post = if scheduled_publish_time_at.is_none() {
     send_webmention(inserted_post.clone(), community, context.clone());
+    send_webhooks(
+      WebhookData::NewPost(inserted_post.id),
+      Some(community_id),
+      &context,
+    );
+    run_automod(AutomodContent::Post(inserted_post.clone()), &context);
     |post| Some(SendActivityData::CreatePost(post))
   } else {
     |_| None
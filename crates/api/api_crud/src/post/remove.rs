@@ -71,7 +71,7 @@ pub async fn remove_post(
     None,
   );
   let action = Modlog::create(&mut context.pool(), &[form]).await?;
-  notify_mod_action(action, context.app_data());
+  notify_mod_action(action, &context);
 
   if let Some(remove_children) = data.remove_children {
     let updated_comments: Vec<Comment> =
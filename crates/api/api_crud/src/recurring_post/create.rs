@@ -0,0 +1,41 @@
+use crate::recurring_post::{
+  check_recurring_post,
+  check_recurring_post_permission,
+  convert_publish_time,
+};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::recurring_post::{RecurringPost, RecurringPostInsertForm};
+use lemmy_db_views_community::api::{CreateRecurringPost, RecurringPostResponse};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{error::LemmyResult, utils::validation::check_api_elements_count};
+
+pub async fn create_recurring_post(
+  Json(data): Json<CreateRecurringPost>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<RecurringPostResponse>> {
+  check_recurring_post_permission(&local_user_view, data.community_id, &context).await?;
+  let recurring_posts = RecurringPost::list(&mut context.pool(), data.community_id).await?;
+  check_api_elements_count(recurring_posts.len())?;
+
+  let form = RecurringPostInsertForm {
+    body: data.body,
+    nsfw: data.nsfw,
+    language_id: data.language_id,
+    feature: data.feature,
+    ..RecurringPostInsertForm::new(
+      data.community_id,
+      local_user_view.person.id,
+      data.name.trim().to_string(),
+      data.frequency,
+      convert_publish_time(data.first_publish_at)?,
+    )
+  };
+  check_recurring_post(&form, &context).await?;
+  let recurring_post = RecurringPost::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(RecurringPostResponse { recurring_post }))
+}
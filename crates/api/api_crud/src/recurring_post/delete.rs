@@ -0,0 +1,23 @@
+use crate::recurring_post::check_recurring_post_permission;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::recurring_post::RecurringPost;
+use lemmy_db_views_community::api::DeleteRecurringPost;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::SuccessResponse;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn delete_recurring_post(
+  Json(data): Json<DeleteRecurringPost>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let recurring_post = RecurringPost::read(&mut context.pool(), data.id).await?;
+  check_recurring_post_permission(&local_user_view, recurring_post.community_id, &context).await?;
+
+  RecurringPost::delete(&mut context.pool(), data.id).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
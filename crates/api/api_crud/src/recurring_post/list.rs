@@ -0,0 +1,20 @@
+use crate::recurring_post::check_recurring_post_permission;
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::recurring_post::RecurringPost;
+use lemmy_db_views_community::api::{ListRecurringPosts, ListRecurringPostsResponse};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_recurring_posts(
+  Query(data): Query<ListRecurringPosts>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListRecurringPostsResponse>> {
+  check_recurring_post_permission(&local_user_view, data.community_id, &context).await?;
+
+  let recurring_posts = RecurringPost::list(&mut context.pool(), data.community_id).await?;
+
+  Ok(Json(ListRecurringPostsResponse { recurring_posts }))
+}
@@ -0,0 +1,182 @@
+use chrono::{DateTime, Datelike, Days, Months, TimeZone, Utc};
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{check_community_mod_action, slur_regex},
+};
+use lemmy_db_schema::{
+  impls::actor_language::validate_post_language,
+  newtypes::CommunityId,
+  source::{community::Community, recurring_post::RecurringPostInsertForm},
+};
+use lemmy_db_schema_file::enums::RecurringPostFrequency;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::{
+    slurs::check_slurs,
+    validation::{is_valid_body_field, is_valid_post_title},
+  },
+};
+
+pub mod create;
+pub mod delete;
+pub mod list;
+pub mod publish;
+pub mod update;
+
+/// Recurring posts are managed by the mods of a community. They only exist for local communities,
+/// as the posts are published by the creator's local account.
+async fn check_recurring_post_permission(
+  local_user_view: &LocalUserView,
+  community_id: CommunityId,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let community = Community::read(&mut context.pool(), community_id).await?;
+  if !community.local {
+    return Err(LemmyErrorType::InvalidRecurringPost.into());
+  }
+  check_community_mod_action(local_user_view, &community, false, &mut context.pool()).await
+}
+
+/// Checks the template with the placeholders filled in, so that the published posts pass the
+/// same validation. Monthly posts need to start on a day which exists in every month.
+async fn check_recurring_post(
+  form: &RecurringPostInsertForm,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let slur_regex = slur_regex(context).await?;
+  let name = render_template(&form.name, form.next_publish_at);
+  is_valid_post_title(&name)?;
+  check_slurs(&name, &slur_regex)?;
+  if let Some(body) = &form.body {
+    let body = render_template(body, form.next_publish_at);
+    is_valid_body_field(&body, true)?;
+    check_slurs(&body, &slur_regex)?;
+  }
+  validate_post_language(&mut context.pool(), form.language_id, form.community_id).await?;
+
+  if form.frequency == RecurringPostFrequency::Monthly && form.next_publish_at.day() > 28 {
+    return Err(LemmyErrorType::InvalidRecurringPost.into());
+  }
+  Ok(())
+}
+
+/// Converts a publish time from the API, which has to be in the future.
+fn convert_publish_time(publish_at: i64) -> LemmyResult<DateTime<Utc>> {
+  let publish_at = Utc
+    .timestamp_opt(publish_at, 0)
+    .single()
+    .ok_or(LemmyErrorType::InvalidUnixTime)?;
+  if publish_at < Utc::now() {
+    return Err(LemmyErrorType::PostScheduleTimeMustBeInFuture.into());
+  }
+  Ok(publish_at)
+}
+
+/// Fills in the placeholders of a title or body for a post which is published at the given time.
+fn render_template(template: &str, publish_at: DateTime<Utc>) -> String {
+  template
+    .replace("{date}", &publish_at.format("%Y-%m-%d").to_string())
+    .replace("{week}", &publish_at.iso_week().week().to_string())
+    .replace("{week_year}", &publish_at.iso_week().year().to_string())
+    .replace("{month}", &publish_at.format("%B").to_string())
+    .replace("{year}", &publish_at.year().to_string())
+}
+
+/// The first scheduled time after `now`. Occurrences which were missed, for example because the
+/// server was down, are skipped.
+fn next_publish_time(
+  frequency: RecurringPostFrequency,
+  mut publish_at: DateTime<Utc>,
+  now: DateTime<Utc>,
+) -> LemmyResult<DateTime<Utc>> {
+  while publish_at <= now {
+    publish_at = match frequency {
+      RecurringPostFrequency::Daily => publish_at.checked_add_days(Days::new(1)),
+      RecurringPostFrequency::Weekly => publish_at.checked_add_days(Days::new(7)),
+      RecurringPostFrequency::Monthly => publish_at.checked_add_months(Months::new(1)),
+    }
+    .ok_or(LemmyErrorType::InvalidRecurringPost)?;
+  }
+  Ok(publish_at)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::recurring_post::{next_publish_time, render_template};
+  use chrono::{TimeZone, Utc};
+  use lemmy_db_schema_file::enums::RecurringPostFrequency;
+  use lemmy_utils::error::LemmyResult;
+
+  #[test]
+  fn test_render_template() -> LemmyResult<()> {
+    let publish_at = Utc
+      .with_ymd_and_hms(2026, 1, 1, 9, 0, 0)
+      .single()
+      .ok_or(anyhow::anyhow!("invalid date"))?;
+    assert_eq!(
+      "Week 1 of 2026 (January, 2026-01-01)",
+      render_template("Week {week} of {year} ({month}, {date})", publish_at)
+    );
+    assert_eq!(
+      "No placeholders",
+      render_template("No placeholders", publish_at)
+    );
+
+    // Around New Year the ISO week can belong to a different year
+    let publish_at = Utc
+      .with_ymd_and_hms(2025, 12, 29, 9, 0, 0)
+      .single()
+      .ok_or(anyhow::anyhow!("invalid date"))?;
+    assert_eq!(
+      "Week 1 of 2026 (2025)",
+      render_template("Week {week} of {week_year} ({year})", publish_at)
+    );
+    let publish_at = Utc
+      .with_ymd_and_hms(2027, 1, 1, 9, 0, 0)
+      .single()
+      .ok_or(anyhow::anyhow!("invalid date"))?;
+    assert_eq!(
+      "Week 53 of 2026 (2027)",
+      render_template("Week {week} of {week_year} ({year})", publish_at)
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn test_next_publish_time() -> LemmyResult<()> {
+    let date = |month, day, hour| {
+      Utc
+        .with_ymd_and_hms(2026, month, day, hour, 0, 0)
+        .single()
+        .ok_or(anyhow::anyhow!("invalid date"))
+    };
+    let publish_at = date(3, 28, 9)?;
+
+    assert_eq!(
+      date(3, 29, 9)?,
+      next_publish_time(RecurringPostFrequency::Daily, publish_at, publish_at)?
+    );
+    assert_eq!(
+      date(4, 4, 9)?,
+      next_publish_time(RecurringPostFrequency::Weekly, publish_at, publish_at)?
+    );
+    assert_eq!(
+      date(4, 28, 9)?,
+      next_publish_time(RecurringPostFrequency::Monthly, publish_at, publish_at)?
+    );
+
+    // Missed posts are skipped
+    assert_eq!(
+      date(4, 18, 9)?,
+      next_publish_time(RecurringPostFrequency::Weekly, publish_at, date(4, 13, 12)?)?
+    );
+    // Posts which are not due yet are kept
+    assert_eq!(
+      publish_at,
+      next_publish_time(RecurringPostFrequency::Daily, publish_at, date(3, 1, 0)?)?
+    );
+    Ok(())
+  }
+}
@@ -0,0 +1,130 @@
+use crate::{
+  post::create::create_post,
+  recurring_post::{next_publish_time, render_template},
+};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::check_community_mod_action,
+};
+use lemmy_db_schema::{
+  newtypes::PostId,
+  source::{
+    community::Community,
+    modlog::{Modlog, ModlogInsertForm},
+    person::Person,
+    post::{Post, PostUpdateForm},
+    recurring_post::{RecurringPost, RecurringPostUpdateForm},
+  },
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_post::api::CreatePost;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+use tracing::warn;
+
+/// Publishes a post for each recurring post which is due. This is called by the scheduled task
+/// runner.
+pub async fn publish_recurring_posts(context: &Data<LemmyContext>) -> LemmyResult<()> {
+  for recurring_post in RecurringPost::list_due(&mut context.pool()).await? {
+    let id = recurring_post.id;
+    publish_recurring_post(recurring_post, context)
+      .await
+      .inspect_err(|e| warn!("Failed to publish recurring post {id:?}: {e}"))
+      .ok();
+  }
+  Ok(())
+}
+
+async fn publish_recurring_post(
+  recurring_post: RecurringPost,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let publish_at = recurring_post.next_publish_at;
+
+  // Move on to the next time first, so that a failing post is skipped instead of retried
+  let form = RecurringPostUpdateForm {
+    next_publish_at: Some(next_publish_time(
+      recurring_post.frequency,
+      publish_at,
+      Utc::now(),
+    )?),
+    ..Default::default()
+  };
+  RecurringPost::update(&mut context.pool(), recurring_post.id, &form).await?;
+
+  // The creator needs to be a mod of the community still
+  let local_user_view =
+    LocalUserView::read_person(&mut context.pool(), recurring_post.creator_id).await?;
+  let community = Community::read(&mut context.pool(), recurring_post.community_id).await?;
+  check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+
+  // Go through the normal create endpoint for validation, federation and notifications
+  let data = CreatePost {
+    name: render_template(&recurring_post.name, publish_at),
+    community_id: community.id,
+    body: recurring_post
+      .body
+      .as_deref()
+      .map(|body| render_template(body, publish_at)),
+    nsfw: Some(recurring_post.nsfw),
+    language_id: recurring_post.language_id,
+    ..Default::default()
+  };
+  let person = local_user_view.person.clone();
+  let post_view = Box::pin(create_post(Json(data), context.clone(), local_user_view))
+    .await?
+    .0
+    .post_view;
+
+  // Store the new post first, so that the next run still unfeatures it if featuring fails below
+  let form = RecurringPostUpdateForm {
+    last_post_id: Some(Some(post_view.post.id)),
+    ..Default::default()
+  };
+  RecurringPost::update(&mut context.pool(), recurring_post.id, &form).await?;
+
+  if recurring_post.feature {
+    if let Some(last_post_id) = recurring_post.last_post_id {
+      // The previous post may have been deleted in the meantime, which shouldn't prevent featuring
+      // the new one
+      feature_post(last_post_id, &person, false, context)
+        .await
+        .inspect_err(|e| warn!("Failed to unfeature recurring post {last_post_id:?}: {e}"))
+        .ok();
+    }
+    feature_post(post_view.post.id, &person, true, context).await?;
+  }
+  Ok(())
+}
+
+/// Features or unfeatures a post in its community in the name of the recurring post creator.
+async fn feature_post(
+  post_id: PostId,
+  person: &Person,
+  featured: bool,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let post = Post::read(&mut context.pool(), post_id).await?;
+  if post.featured_community == featured {
+    return Ok(());
+  }
+
+  let form = PostUpdateForm {
+    featured_community: Some(featured),
+    ..Default::default()
+  };
+  let post = Post::update(&mut context.pool(), post_id, &form).await?;
+
+  let form = ModlogInsertForm::mod_feature_post_community(person.id, &post, featured);
+  Modlog::create(&mut context.pool(), &[form]).await?;
+
+  ActivityChannel::submit_activity(
+    SendActivityData::FeaturePost(post, person.clone(), featured),
+    context,
+  )?;
+  Ok(())
+}
@@ -0,0 +1,61 @@
+use crate::recurring_post::{
+  check_recurring_post,
+  check_recurring_post_permission,
+  convert_publish_time,
+};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::recurring_post::{
+  RecurringPost,
+  RecurringPostInsertForm,
+  RecurringPostUpdateForm,
+};
+use lemmy_db_views_community::api::{EditRecurringPost, RecurringPostResponse};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn edit_recurring_post(
+  Json(data): Json<EditRecurringPost>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<RecurringPostResponse>> {
+  let orig = RecurringPost::read(&mut context.pool(), data.id).await?;
+  check_recurring_post_permission(&local_user_view, orig.community_id, &context).await?;
+
+  // Validate the template as it is after the edit
+  let next_publish_at = data.next_publish_at.map(convert_publish_time).transpose()?;
+  let recurring_post = RecurringPostInsertForm {
+    // An empty body removes it
+    body: data
+      .body
+      .map_or(orig.body, |b| Some(b).filter(|b| !b.trim().is_empty())),
+    language_id: data.language_id.or(orig.language_id),
+    ..RecurringPostInsertForm::new(
+      orig.community_id,
+      orig.creator_id,
+      data.name.map_or(orig.name, |n| n.trim().to_string()),
+      data.frequency.unwrap_or(orig.frequency),
+      next_publish_at.unwrap_or(orig.next_publish_at),
+    )
+  };
+  check_recurring_post(&recurring_post, &context).await?;
+
+  let form = RecurringPostUpdateForm {
+    name: Some(recurring_post.name),
+    body: Some(recurring_post.body),
+    nsfw: data.nsfw,
+    language_id: Some(recurring_post.language_id),
+    frequency: data.frequency,
+    next_publish_at,
+    feature: data.feature,
+    enabled: data.enabled,
+    updated_at: Some(Some(Utc::now())),
+    ..Default::default()
+  };
+  let recurring_post = RecurringPost::update(&mut context.pool(), data.id, &form).await?;
+
+  Ok(Json(RecurringPostResponse { recurring_post }))
+}
@@ -134,6 +134,10 @@ pub async fn create_site(
     image_allow_video_uploads: data.image_allow_video_uploads,
     image_upload_disabled: data.image_upload_disabled,
     max_invites_per_user_allowed: data.max_invites_per_user_allowed,
+    full_text_search: data.full_text_search,
+    public_edit_history: data.public_edit_history,
+    require_2fa_for_mods_and_admins: data.require_2fa_for_mods_and_admins,
+    federation_authorized_fetch: data.federation_authorized_fetch,
   };
 
   LocalSite::update(&mut context.pool(), &local_site_form).await?;
@@ -21,6 +21,7 @@ use lemmy_db_schema::{
     local_site_rate_limit::{LocalSiteRateLimit, LocalSiteRateLimitUpdateForm},
     local_site_url_blocklist::LocalSiteUrlBlocklist,
     local_user::LocalUser,
+    passkey::Passkey,
     site::{Site, SiteUpdateForm},
   },
 };
@@ -35,7 +36,7 @@ use lemmy_diesel_utils::{
   utils::{diesel_opt_number_update, diesel_string_update},
 };
 use lemmy_utils::{
-  error::LemmyResult,
+  error::{LemmyErrorType, LemmyResult},
   utils::{
     slurs::check_slurs_opt,
     validation::{
@@ -62,6 +63,14 @@ pub async fn edit_site(
 
   validate_update_payload(&local_site, &data)?;
 
+  // Admins need 2FA themselves before requiring it, so that they don't lock themselves out
+  if data.require_2fa_for_mods_and_admins == Some(true)
+    && !local_user_view.local_user.totp_2fa_enabled
+    && !Passkey::exists_for_user(&mut context.pool(), local_user_view.local_user.id).await?
+  {
+    return Err(LemmyErrorType::TwoFactorRequired.into());
+  }
+
   if let Some(discussion_languages) = data.discussion_languages.clone() {
     SiteLanguage::update(&mut context.pool(), discussion_languages.clone(), &site).await?;
   }
@@ -141,6 +150,10 @@ pub async fn edit_site(
     image_allow_video_uploads: data.image_allow_video_uploads,
     image_upload_disabled: data.image_upload_disabled,
     max_invites_per_user_allowed: data.max_invites_per_user_allowed,
+    full_text_search: data.full_text_search,
+    public_edit_history: data.public_edit_history,
+    require_2fa_for_mods_and_admins: data.require_2fa_for_mods_and_admins,
+    federation_authorized_fetch: data.federation_authorized_fetch,
   };
 
   let update_local_site = LocalSite::update(&mut context.pool(), &local_site_form)
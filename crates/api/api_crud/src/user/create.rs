@@ -21,6 +21,7 @@ use lemmy_api_utils::{
     password_length_check,
     slur_regex,
   },
+  webhooks::{WebhookData, send_webhooks},
 };
 use lemmy_apub_objects::objects::community::ApubCommunity;
 use lemmy_db_schema::{
@@ -222,6 +223,14 @@ pub async fn register(
     })
     .await?;
 
+  if local_site.site_setup && require_registration_application {
+    send_webhooks(
+      WebhookData::NewRegistrationApplication(user.person.id),
+      None,
+      &context,
+    );
+  }
+
   // Email the admins, only if email verification is not required
   if local_site.application_email_admins && !local_site.email_verification_required {
     send_new_applicant_email_to_admins(&data.username, pool, context.settings()).await?;
@@ -469,6 +478,14 @@ pub async fn authenticate_with_oauth(
         })
         .await?;
 
+      if login_response.registration_created {
+        send_webhooks(
+          WebhookData::NewRegistrationApplication(user.person.id),
+          None,
+          &context,
+        );
+      }
+
       // Check email is verified when required
       login_response.verify_email_sent = send_verification_email_if_required(
         &local_site,
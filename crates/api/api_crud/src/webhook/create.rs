@@ -0,0 +1,41 @@
+use crate::webhook::{check_webhook_permission, check_webhook_url};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::webhook::{Webhook, WebhookInsertForm};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{CreateWebhook, CreateWebhookResponse};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+use rand::{RngExt, distr::Alphanumeric};
+
+pub async fn create_webhook(
+  Json(data): Json<CreateWebhook>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CreateWebhookResponse>> {
+  check_webhook_permission(&local_user_view, data.community_id, &context).await?;
+  check_webhook_url(&data.url, &context).await?;
+
+  let form = WebhookInsertForm::new(
+    data.community_id,
+    local_user_view.person.id,
+    data.url.into(),
+    generate_webhook_secret().into(),
+    data.events.into_iter().map(Some).collect(),
+  );
+  let webhook = Webhook::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(CreateWebhookResponse {
+    secret: webhook.secret.clone(),
+    webhook,
+  }))
+}
+
+fn generate_webhook_secret() -> String {
+  rand::rng()
+    .sample_iter(Alphanumeric)
+    .take(32)
+    .map(char::from)
+    .collect()
+}
@@ -0,0 +1,29 @@
+use crate::webhook::check_webhook_permission;
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::webhook::Webhook;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{ListWebhooks, ListWebhooksResponse};
+use lemmy_utils::error::LemmyResult;
+
+/// Lists the webhooks of a community. Without community_id, lists the webhooks which were created
+/// by the user.
+pub async fn list_webhooks(
+  Query(data): Query<ListWebhooks>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListWebhooksResponse>> {
+  if data.community_id.is_some() {
+    check_webhook_permission(&local_user_view, data.community_id, &context).await?;
+  }
+
+  let webhooks = Webhook::list(
+    &mut context.pool(),
+    data.community_id,
+    local_user_view.person.id,
+  )
+  .await?;
+
+  Ok(Json(ListWebhooksResponse { webhooks }))
+}
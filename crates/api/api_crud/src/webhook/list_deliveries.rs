@@ -0,0 +1,29 @@
+use crate::webhook::check_webhook_permission;
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::webhook::{Webhook, WebhookDelivery};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::ListWebhookDeliveries;
+use lemmy_diesel_utils::{pagination::PagedResponse, traits::Crud};
+use lemmy_utils::error::LemmyResult;
+
+/// The delivery log of a webhook, newest first.
+pub async fn list_webhook_deliveries(
+  Query(data): Query<ListWebhookDeliveries>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PagedResponse<WebhookDelivery>>> {
+  let webhook = Webhook::read(&mut context.pool(), data.webhook_id).await?;
+  check_webhook_permission(&local_user_view, webhook.community_id, &context).await?;
+
+  let deliveries = WebhookDelivery::list(
+    &mut context.pool(),
+    webhook.id,
+    data.page_cursor,
+    data.limit,
+  )
+  .await?;
+
+  Ok(Json(deliveries))
+}
@@ -0,0 +1,37 @@
+use activitypub_federation::config::Data;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{is_admin, is_mod_or_admin},
+};
+use lemmy_db_schema::newtypes::CommunityId;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use url::Url;
+
+pub mod create;
+pub mod delete;
+pub mod list;
+pub mod list_deliveries;
+pub mod update;
+
+/// Site-wide webhooks can only be managed by admins, community webhooks also by the mods of the
+/// community.
+async fn check_webhook_permission(
+  local_user_view: &LocalUserView,
+  community_id: Option<CommunityId>,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  match community_id {
+    Some(community_id) => is_mod_or_admin(&mut context.pool(), local_user_view, community_id).await,
+    None => is_admin(local_user_view),
+  }
+}
+
+/// Webhooks must use https, and can't point to a private address.
+async fn check_webhook_url(url: &Url, context: &Data<LemmyContext>) -> LemmyResult<()> {
+  if url.scheme() != "https" {
+    return Err(LemmyErrorType::InvalidUrlScheme.into());
+  }
+  context.is_valid_ip(url).await?;
+  Ok(())
+}
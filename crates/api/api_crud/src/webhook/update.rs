@@ -0,0 +1,34 @@
+use crate::webhook::{check_webhook_permission, check_webhook_url};
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::webhook::{Webhook, WebhookUpdateForm};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::{EditWebhook, WebhookResponse};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn edit_webhook(
+  Json(data): Json<EditWebhook>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<WebhookResponse>> {
+  let webhook = Webhook::read(&mut context.pool(), data.id).await?;
+  check_webhook_permission(&local_user_view, webhook.community_id, &context).await?;
+  if let Some(url) = &data.url {
+    check_webhook_url(url, &context).await?;
+  }
+
+  let form = WebhookUpdateForm {
+    url: data.url.map(Into::into),
+    events: data
+      .events
+      .map(|events| events.into_iter().map(Some).collect()),
+    enabled: data.enabled,
+    updated_at: Some(Some(Utc::now())),
+  };
+  let webhook = Webhook::update(&mut context.pool(), data.id, &form).await?;
+
+  Ok(Json(WebhookResponse { webhook }))
+}
@@ -0,0 +1,490 @@
+use crate::{
+  context::LemmyContext,
+  notify::notify_mod_action,
+  send_activity::{ActivityChannel, SendActivityData},
+};
+use activitypub_federation::config::Data;
+use chrono::{DateTime, Days, Utc};
+use lemmy_db_schema::{
+  newtypes::{AutomodRuleId, CommunityId, LanguageId},
+  source::{
+    automod_rule::AutomodRule,
+    comment::{Comment, CommentInsertForm, CommentUpdateForm},
+    comment_report::{CommentReport, CommentReportForm},
+    community::Community,
+    community_tag::{CommunityTag, PostCommunityTag},
+    modlog::{Modlog, ModlogInsertForm},
+    person::Person,
+    post::{Post, PostUpdateForm},
+    post_report::{PostReport, PostReportForm},
+  },
+  traits::Reportable,
+};
+use lemmy_db_schema_file::enums::AutomodAction;
+use lemmy_db_views_community_moderator::CommunityModeratorView;
+use lemmy_db_views_site::SiteView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  spawn_try_task,
+  utils::validation::build_and_check_regex,
+};
+use moka::future::Cache;
+use regex::Regex;
+use std::{sync::LazyLock, time::Duration};
+use tracing::warn;
+use url::Url;
+
+/// New content which is checked against the automod rules of its community.
+pub enum AutomodContent {
+  Post(Post),
+  Comment(Comment),
+}
+
+impl AutomodContent {
+  fn community_id(&self) -> CommunityId {
+    match self {
+      AutomodContent::Post(post) => post.community_id,
+      AutomodContent::Comment(comment) => comment.community_id,
+    }
+  }
+}
+
+/// The properties of a post or comment which rule conditions are matched against.
+struct AutomodInput<'a> {
+  title: Option<&'a str>,
+  body: Option<&'a str>,
+  url: Option<&'a Url>,
+  language_id: LanguageId,
+  creator_published_at: DateTime<Utc>,
+  creator_post_score: i32,
+  creator_comment_score: i32,
+}
+
+/// Evaluates the automod rules of a local community against new content, and takes the action of
+/// each matching rule. Content by community moderators is never checked. Runs in a background
+/// task.
+pub fn run_automod(content: AutomodContent, context: &Data<LemmyContext>) {
+  let context = context.reset_request_count();
+  spawn_try_task(async move {
+    let pool = &mut context.pool();
+    let rules = AutomodRule::list_enabled(pool, content.community_id()).await?;
+    if rules.is_empty() {
+      return Ok(());
+    }
+    let community = Community::read(pool, content.community_id()).await?;
+    if !community.local {
+      return Ok(());
+    }
+    let creator_id = match &content {
+      AutomodContent::Post(post) => post.creator_id,
+      AutomodContent::Comment(comment) => comment.creator_id,
+    };
+    if CommunityModeratorView::check_is_community_moderator(pool, community.id, creator_id)
+      .await
+      .is_ok()
+    {
+      return Ok(());
+    }
+    let creator = Person::read(pool, creator_id).await?;
+    let input = match &content {
+      AutomodContent::Post(post) => AutomodInput {
+        title: Some(&post.name),
+        body: post.body.as_deref(),
+        url: post.url.as_deref(),
+        language_id: post.language_id,
+        creator_published_at: creator.published_at,
+        creator_post_score: creator.post_score,
+        creator_comment_score: creator.comment_score,
+      },
+      AutomodContent::Comment(comment) => AutomodInput {
+        title: None,
+        body: Some(&comment.content),
+        url: None,
+        language_id: comment.language_id,
+        creator_published_at: creator.published_at,
+        creator_post_score: creator.post_score,
+        creator_comment_score: creator.comment_score,
+      },
+    };
+
+    let mut matching = vec![];
+    for rule in rules {
+      // Regexes are validated when the rule is saved, so this should never fail
+      match rule_regexes(&rule).await {
+        Ok(regexes) if rule_matches(&rule, &regexes, &input, Utc::now()) => matching.push(rule),
+        Ok(_) => {}
+        Err(e) => warn!("Invalid regex in automod rule {}: {e}", rule.id.0),
+      }
+    }
+    if matching.is_empty() {
+      return Ok(());
+    }
+    let automod = SiteView::read_system_account(pool).await?;
+    for rule in matching {
+      take_action(&rule, &content, &community, &automod, &context).await?;
+    }
+    Ok(())
+  });
+}
+
+/// The compiled regexes of a rule.
+#[derive(Clone)]
+struct RuleRegexes {
+  title: Option<Regex>,
+  body: Option<Regex>,
+  url: Option<Regex>,
+}
+
+impl RuleRegexes {
+  fn new(rule: &AutomodRule) -> LemmyResult<Self> {
+    let build = |regex: &Option<String>| {
+      regex
+        .as_deref()
+        .map(|r| build_and_check_regex(Some(r)))
+        .transpose()
+    };
+    Ok(RuleRegexes {
+      title: build(&rule.title_regex)?,
+      body: build(&rule.body_regex)?,
+      url: build(&rule.url_regex)?,
+    })
+  }
+}
+
+/// Compiling regexes is slow, so they are cached for each version of a rule.
+async fn rule_regexes(rule: &AutomodRule) -> LemmyResult<RuleRegexes> {
+  static CACHE: LazyLock<Cache<(AutomodRuleId, DateTime<Utc>), RuleRegexes>> =
+    LazyLock::new(|| {
+      Cache::builder()
+        .max_capacity(1000)
+        .time_to_idle(Duration::from_secs(60 * 60))
+        .build()
+    });
+  let version = rule.updated_at.unwrap_or(rule.published_at);
+  CACHE
+    .try_get_with((rule.id, version), async { RuleRegexes::new(rule) })
+    .await
+    .map_err(|e| anyhow::anyhow!("Failed to construct regex: {e}").into())
+}
+
+/// A rule matches if all of its conditions match. Rules without any condition never match.
+fn rule_matches(
+  rule: &AutomodRule,
+  regexes: &RuleRegexes,
+  input: &AutomodInput,
+  now: DateTime<Utc>,
+) -> bool {
+  let has_condition = rule.title_regex.is_some()
+    || rule.body_regex.is_some()
+    || rule.url_regex.is_some()
+    || rule.max_account_age_days.is_some()
+    || rule.max_post_score.is_some()
+    || rule.max_comment_score.is_some()
+    || !rule.domains.is_empty()
+    || !rule.language_ids.is_empty();
+  if !has_condition {
+    return false;
+  }
+
+  let url = input.url.map(Url::as_str);
+  if !regex_matches(regexes.title.as_ref(), input.title)
+    || !regex_matches(regexes.body.as_ref(), input.body)
+    || !regex_matches(regexes.url.as_ref(), url)
+  {
+    return false;
+  }
+
+  if let Some(days) = rule.max_account_age_days {
+    let days = Days::new(days.try_into().unwrap_or_default());
+    if now.checked_sub_days(days).unwrap_or(now) > input.creator_published_at {
+      return false;
+    }
+  }
+  if rule
+    .max_post_score
+    .is_some_and(|max| input.creator_post_score > max)
+    || rule
+      .max_comment_score
+      .is_some_and(|max| input.creator_comment_score > max)
+  {
+    return false;
+  }
+
+  if !rule.domains.is_empty() {
+    let host = input.url.and_then(Url::host_str).unwrap_or_default();
+    let on_domain = rule
+      .domains
+      .iter()
+      .flatten()
+      .any(|d| host == d || host.ends_with(&format!(".{d}")));
+    if !on_domain {
+      return false;
+    }
+  }
+  rule.language_ids.is_empty() || rule.language_ids.contains(&Some(input.language_id))
+}
+
+fn regex_matches(regex: Option<&Regex>, text: Option<&str>) -> bool {
+  match (regex, text) {
+    (None, _) => true,
+    (Some(regex), Some(text)) => regex.is_match(text),
+    (Some(_), None) => false,
+  }
+}
+
+async fn take_action(
+  rule: &AutomodRule,
+  content: &AutomodContent,
+  community: &Community,
+  automod: &Person,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let pool = &mut context.pool();
+  let reason = format!("Automod: {}", rule.name);
+  match (rule.action, content) {
+    (AutomodAction::Remove, AutomodContent::Post(post)) => {
+      let form = PostUpdateForm {
+        removed: Some(true),
+        ..Default::default()
+      };
+      let post = Post::update(pool, post.id, &form).await?;
+      let form = ModlogInsertForm::mod_remove_post(automod.id, &post, true, &reason, None);
+      notify_mod_action(Modlog::create(pool, &[form]).await?, context);
+      ActivityChannel::submit_activity(
+        SendActivityData::RemovePost {
+          post,
+          moderator: automod.clone(),
+          reason,
+          removed: true,
+          with_replies: false,
+        },
+        context,
+      )?;
+    }
+    (AutomodAction::Remove, AutomodContent::Comment(comment)) => {
+      let form = CommentUpdateForm {
+        removed: Some(true),
+        ..Default::default()
+      };
+      let comment = Comment::update(pool, comment.id, &form).await?;
+      let form = ModlogInsertForm::mod_remove_comment(
+        automod.id,
+        &comment,
+        community.id,
+        true,
+        &reason,
+        None,
+      );
+      notify_mod_action(Modlog::create(pool, &[form]).await?, context);
+      ActivityChannel::submit_activity(
+        SendActivityData::RemoveComment {
+          comment,
+          moderator: automod.clone(),
+          community: community.clone(),
+          reason,
+          with_replies: false,
+        },
+        context,
+      )?;
+    }
+    (AutomodAction::Report, AutomodContent::Post(post)) => {
+      let form = PostReportForm {
+        creator_id: automod.id,
+        post_id: post.id,
+        original_post_name: post.name.clone(),
+        original_post_url: post.url.clone(),
+        original_post_body: post.body.clone(),
+        reason: reason.clone(),
+        violates_instance_rules: false,
+      };
+      PostReport::report(pool, &form).await?;
+      let form = ModlogInsertForm::mod_automod_post(automod.id, post, &reason);
+      notify_mod_action(Modlog::create(pool, &[form]).await?, context);
+    }
+    (AutomodAction::Report, AutomodContent::Comment(comment)) => {
+      let form = CommentReportForm {
+        creator_id: automod.id,
+        comment_id: comment.id,
+        original_comment_text: comment.content.clone(),
+        reason: reason.clone(),
+        violates_instance_rules: false,
+      };
+      CommentReport::report(pool, &form).await?;
+      let form = ModlogInsertForm::mod_automod_comment(automod.id, comment, community.id, &reason);
+      notify_mod_action(Modlog::create(pool, &[form]).await?, context);
+    }
+    (AutomodAction::Lock, AutomodContent::Post(post)) => {
+      let form = PostUpdateForm {
+        locked: Some(true),
+        ..Default::default()
+      };
+      let post = Post::update(pool, post.id, &form).await?;
+      let form = ModlogInsertForm::mod_lock_post(automod.id, &post, true, &reason);
+      notify_mod_action(Modlog::create(pool, &[form]).await?, context);
+      ActivityChannel::submit_activity(
+        SendActivityData::LockPost(post, automod.clone(), true, reason),
+        context,
+      )?;
+    }
+    (AutomodAction::Lock, AutomodContent::Comment(comment)) => {
+      let comments =
+        Comment::update_locked_for_comment_and_children(pool, &comment.path, true).await?;
+      // The children are locked too, but only the matching comment is logged and federated
+      let comment = comments
+        .iter()
+        .find(|c| c.id == comment.id)
+        .ok_or(LemmyErrorType::NotFound)?;
+      let form =
+        ModlogInsertForm::mod_lock_comment(automod.id, comment, community.id, true, &reason);
+      notify_mod_action(Modlog::create(pool, &[form]).await?, context);
+      ActivityChannel::submit_activity(
+        SendActivityData::LockComment(comment.clone(), automod.clone(), true, reason),
+        context,
+      )?;
+    }
+    (AutomodAction::ApplyTag, AutomodContent::Post(post)) => {
+      let Some(tag_id) = rule.community_tag_id else {
+        return Ok(());
+      };
+      let mut tag_ids: Vec<_> = CommunityTag::read_for_post(pool, post.id)
+        .await?
+        .into_iter()
+        .map(|t| t.id)
+        .collect();
+      if tag_ids.contains(&tag_id) {
+        return Ok(());
+      }
+      tag_ids.push(tag_id);
+      PostCommunityTag::update(pool, post, &tag_ids).await?;
+      let form = ModlogInsertForm::mod_automod_post(automod.id, post, &reason);
+      notify_mod_action(Modlog::create(pool, &[form]).await?, context);
+      ActivityChannel::submit_activity(SendActivityData::UpdatePost(post.clone()), context)?;
+    }
+    // Comments have no tags
+    (AutomodAction::ApplyTag, AutomodContent::Comment(_)) => {}
+    (AutomodAction::Reply, _) => {
+      let Some(reply_text) = rule.reply_text.clone() else {
+        return Ok(());
+      };
+      let (post_id, parent_path) = match content {
+        AutomodContent::Post(post) => (post.id, None),
+        AutomodContent::Comment(comment) => (comment.post_id, Some(&comment.path)),
+      };
+      let form = CommentInsertForm::new(automod.id, post_id, community.id, reply_text);
+      let reply = Comment::create(pool, &form, parent_path).await?;
+      let form = match content {
+        AutomodContent::Post(post) => ModlogInsertForm::mod_automod_post(automod.id, post, &reason),
+        AutomodContent::Comment(comment) => {
+          ModlogInsertForm::mod_automod_comment(automod.id, comment, community.id, &reason)
+        }
+      };
+      notify_mod_action(Modlog::create(pool, &[form]).await?, context);
+      ActivityChannel::submit_activity(SendActivityData::CreateComment(reply), context)?;
+    }
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use chrono::TimeDelta;
+
+  fn rule() -> AutomodRule {
+    AutomodRule {
+      id: AutomodRuleId(1),
+      community_id: CommunityId(1),
+      name: "test".to_string(),
+      title_regex: None,
+      body_regex: None,
+      url_regex: None,
+      max_account_age_days: None,
+      max_post_score: None,
+      max_comment_score: None,
+      domains: vec![],
+      language_ids: vec![],
+      action: AutomodAction::Remove,
+      community_tag_id: None,
+      reply_text: None,
+      enabled: true,
+      published_at: Utc::now(),
+      updated_at: None,
+    }
+  }
+
+  fn matches(rule: &AutomodRule, input: &AutomodInput, now: DateTime<Utc>) -> LemmyResult<bool> {
+    Ok(rule_matches(rule, &RuleRegexes::new(rule)?, input, now))
+  }
+
+  #[test]
+  fn test_automod_rule_matches() -> LemmyResult<()> {
+    let now = Utc::now();
+    let url = Url::parse("https://spam.example.com/buy")?;
+    let input = AutomodInput {
+      title: Some("Cheap Pills"),
+      body: None,
+      url: Some(&url),
+      language_id: LanguageId(37),
+      creator_published_at: now - TimeDelta::days(2),
+      creator_post_score: 5,
+      creator_comment_score: -3,
+    };
+
+    // A rule without conditions never matches
+    assert!(!matches(&rule(), &input, now)?);
+
+    let title = AutomodRule {
+      title_regex: Some("cheap pills".to_string()),
+      ..rule()
+    };
+    assert!(matches(&title, &input, now)?);
+
+    // All conditions need to match, and a missing body never matches
+    let title_and_body = AutomodRule {
+      body_regex: Some("pills".to_string()),
+      ..title
+    };
+    assert!(!matches(&title_and_body, &input, now)?);
+
+    let new_account = AutomodRule {
+      max_account_age_days: Some(7),
+      max_comment_score: Some(0),
+      ..rule()
+    };
+    assert!(matches(&new_account, &input, now)?);
+    let old_account = AutomodRule {
+      max_account_age_days: Some(1),
+      ..rule()
+    };
+    assert!(!matches(&old_account, &input, now)?);
+    let post_score = AutomodRule {
+      max_post_score: Some(4),
+      ..rule()
+    };
+    assert!(!matches(&post_score, &input, now)?);
+
+    let domain = AutomodRule {
+      domains: vec![Some("example.com".to_string())],
+      ..rule()
+    };
+    assert!(matches(&domain, &input, now)?);
+    let other_domain = AutomodRule {
+      domains: vec![Some("ample.com".to_string())],
+      ..rule()
+    };
+    assert!(!matches(&other_domain, &input, now)?);
+
+    let language = AutomodRule {
+      language_ids: vec![Some(LanguageId(1)), Some(LanguageId(37))],
+      ..rule()
+    };
+    assert!(matches(&language, &input, now)?);
+    let other_language = AutomodRule {
+      language_ids: vec![Some(LanguageId(1))],
+      ..rule()
+    };
+    assert!(!matches(&other_language, &input, now)?);
+    Ok(())
+  }
+}
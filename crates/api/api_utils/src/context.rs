@@ -1,4 +1,7 @@
-use crate::request::client_builder;
+use crate::{
+  realtime::{REALTIME_CAPACITY, RealtimeEvent},
+  request::client_builder,
+};
 use activitypub_federation::config::{Data, FederationConfig};
 use lemmy_db_schema::source::secret::Secret;
 use lemmy_diesel_utils::connection::{ActualDbPool, DbPool, build_db_pool_for_tests};
@@ -8,6 +11,7 @@ use lemmy_utils::{
 };
 use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
 use std::sync::Arc;
+use tokio::sync::broadcast;
 
 #[derive(Clone)]
 pub struct LemmyContext {
@@ -18,6 +22,8 @@ pub struct LemmyContext {
   pictrs_client: Arc<ClientWithMiddleware>,
   secret: Arc<Secret>,
   rate_limit_cell: RateLimit,
+  /// Events from the database which are pushed to connected clients.
+  realtime: broadcast::Sender<RealtimeEvent>,
 }
 
 impl LemmyContext {
@@ -34,6 +40,7 @@ impl LemmyContext {
       pictrs_client: Arc::new(pictrs_client),
       secret: Arc::new(secret),
       rate_limit_cell,
+      realtime: broadcast::channel(REALTIME_CAPACITY).0,
     }
   }
   pub fn pool(&self) -> DbPool<'_> {
@@ -57,6 +64,9 @@ impl LemmyContext {
   pub fn rate_limit_cell(&self) -> &RateLimit {
     &self.rate_limit_cell
   }
+  pub fn realtime(&self) -> &broadcast::Sender<RealtimeEvent> {
+    &self.realtime
+  }
 
   /// Initialize a context for use in tests which blocks federation network calls.
   ///
@@ -72,6 +82,7 @@ impl LemmyContext {
     let secret = Secret {
       id: 0,
       jwt_secret: String::new().into(),
+      vapid_private_key: None,
     };
 
     let rate_limit_cell = RateLimit::with_debug_config();
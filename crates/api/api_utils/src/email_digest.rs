@@ -0,0 +1,233 @@
+use crate::context::LemmyContext;
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::Utc;
+use lemmy_db_schema::{
+  newtypes::LocalUserId,
+  source::{
+    local_user::{LocalUser, LocalUserUpdateForm},
+    secret::Secret,
+  },
+};
+use lemmy_db_schema_file::enums::{ListingType, NotificationType, PostSortType};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_notification::{NotificationData, NotificationView, impls::NotificationQuery};
+use lemmy_db_views_post::impls::PostQuery;
+use lemmy_db_views_site::SiteView;
+use lemmy_email::digest::{DigestNotification, DigestPost, send_digest_email};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+use ring::hmac;
+use tracing::warn;
+
+/// Maximum number of notifications listed in a single digest email.
+const DIGEST_NOTIFICATIONS_LIMIT: i64 = 50;
+
+/// Number of top posts from subscribed communities which are listed in a digest email.
+const DIGEST_TOP_POSTS_LIMIT: i64 = 5;
+
+/// Sends a digest email to each user whose digest period has passed. Unread notifications stay in
+/// the notification table until then, so they serve as the queue of the digest.
+pub async fn send_email_digests(context: &LemmyContext) -> LemmyResult<()> {
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  if site_view.local_site.email_notifications_disabled {
+    return Ok(());
+  }
+
+  for local_user_view in LocalUserView::list_due_for_email_digest(&mut context.pool()).await? {
+    if let Err(e) = send_email_digest(&local_user_view, &site_view, context).await {
+      warn!(
+        "Failed to send email digest to {}: {e}",
+        local_user_view.person.name
+      );
+    }
+  }
+  Ok(())
+}
+
+async fn send_email_digest(
+  local_user_view: &LocalUserView,
+  site_view: &SiteView,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let local_user = &local_user_view.local_user;
+  let since = local_user.last_email_digest_at;
+  let now = Utc::now();
+
+  if !local_user_view.banned {
+    let notifications = NotificationQuery {
+      unread_only: Some(true),
+      published_after: Some(since),
+      show_bot_accounts: Some(local_user.show_bot_accounts),
+      hide_modlog_names: Some(true),
+      limit: Some(DIGEST_NOTIFICATIONS_LIMIT),
+      ..Default::default()
+    }
+    .list(&mut context.pool(), &local_user_view.person)
+    .await?
+    .items
+    .into_iter()
+    .filter_map(|n| digest_notification(n, context).transpose())
+    .collect::<LemmyResult<Vec<_>>>()?;
+
+    let top_posts = if local_user.email_digest_top_posts {
+      PostQuery {
+        listing_type: Some(ListingType::Subscribed),
+        sort: Some(PostSortType::Top),
+        time_range_seconds: i32::try_from((now - since).num_seconds()).ok(),
+        local_user: Some(local_user),
+        limit: Some(DIGEST_TOP_POSTS_LIMIT),
+        ..Default::default()
+      }
+      .list(&mut context.pool(), &site_view.site, &site_view.local_site)
+      .await?
+      .items
+      .into_iter()
+      .map(|p| {
+        Ok(DigestPost {
+          link: p.post.local_url(context.settings())?.to_string(),
+          title: p.post.name,
+          community_name: p.community.title.unwrap_or(p.community.name),
+          score: p.post.score,
+        })
+      })
+      .collect::<LemmyResult<Vec<_>>>()?
+    } else {
+      vec![]
+    };
+
+    let unsubscribe_link = format!(
+      "{}/unsubscribe_email/{}",
+      context.settings().get_protocol_and_hostname(),
+      unsubscribe_token(local_user.id, context.secret())
+    );
+    send_digest_email(
+      local_user_view,
+      &notifications,
+      &top_posts,
+      &unsubscribe_link,
+      &mut context.pool(),
+      context.settings(),
+    )
+    .await?;
+  }
+
+  let form = LocalUserUpdateForm {
+    last_email_digest_at: Some(now),
+    ..Default::default()
+  };
+  LocalUser::update(&mut context.pool(), local_user.id, &form).await?;
+  Ok(())
+}
+
+/// Only replies, mentions and private messages are included in the digest.
+fn digest_notification(
+  view: NotificationView,
+  context: &LemmyContext,
+) -> LemmyResult<Option<DigestNotification>> {
+  let kind = view.notification.kind;
+  if !matches!(
+    kind,
+    NotificationType::Reply | NotificationType::Mention | NotificationType::PrivateMessage
+  ) {
+    return Ok(None);
+  }
+  let settings = context.settings();
+  let (creator_name, content, link) = match view.data {
+    NotificationData::Comment(c) => (
+      c.creator.name,
+      c.comment.content.clone(),
+      c.comment.local_url(settings)?.to_string(),
+    ),
+    NotificationData::Post(p) => (
+      p.creator.name,
+      p.post.body.clone().unwrap_or_else(|| p.post.name.clone()),
+      p.post.local_url(settings)?.to_string(),
+    ),
+    NotificationData::PrivateMessage(pm) => (
+      pm.creator.name,
+      pm.private_message.content.clone(),
+      pm.private_message.local_url(settings)?.to_string(),
+    ),
+    NotificationData::ModAction(_)
+    | NotificationData::Modmail(_)
+    | NotificationData::BanAppeal(_) => return Ok(None),
+  };
+  Ok(Some(DigestNotification {
+    kind,
+    creator_name,
+    content,
+    link,
+  }))
+}
+
+/// Signed token for the unsubscribe link, so that it works without login. It consists of the local
+/// user id and a signature with the JWT secret.
+pub fn unsubscribe_token(local_user_id: LocalUserId, secret: &Secret) -> String {
+  let signature = hmac::sign(
+    &unsubscribe_key(secret),
+    unsubscribe_message(local_user_id).as_bytes(),
+  );
+  format!(
+    "{}.{}",
+    local_user_id.0,
+    URL_SAFE_NO_PAD.encode(signature.as_ref())
+  )
+}
+
+/// Checks the signature of an unsubscribe token, and returns the local user it belongs to.
+pub fn verify_unsubscribe_token(token: &str, secret: &Secret) -> LemmyResult<LocalUserId> {
+  let (local_user_id, signature) = token
+    .split_once('.')
+    .ok_or(LemmyErrorType::InvalidUnsubscribeToken)?;
+  let local_user_id = LocalUserId(
+    local_user_id
+      .parse()
+      .with_lemmy_type(LemmyErrorType::InvalidUnsubscribeToken)?,
+  );
+  let signature = URL_SAFE_NO_PAD
+    .decode(signature)
+    .with_lemmy_type(LemmyErrorType::InvalidUnsubscribeToken)?;
+  hmac::verify(
+    &unsubscribe_key(secret),
+    unsubscribe_message(local_user_id).as_bytes(),
+    &signature,
+  )
+  .with_lemmy_type(LemmyErrorType::InvalidUnsubscribeToken)?;
+  Ok(local_user_id)
+}
+
+fn unsubscribe_key(secret: &Secret) -> hmac::Key {
+  hmac::Key::new(hmac::HMAC_SHA256, secret.jwt_secret.as_ref())
+}
+
+fn unsubscribe_message(local_user_id: LocalUserId) -> String {
+  format!("unsubscribe:{}", local_user_id.0)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_unsubscribe_token() -> LemmyResult<()> {
+    let secret = Secret {
+      id: 1,
+      jwt_secret: "secret".to_string().into(),
+      vapid_private_key: None,
+    };
+    let token = unsubscribe_token(LocalUserId(5), &secret);
+    assert_eq!(LocalUserId(5), verify_unsubscribe_token(&token, &secret)?);
+
+    // Changing the user id invalidates the signature
+    let forged = token.replacen('5', "6", 1);
+    assert!(verify_unsubscribe_token(&forged, &secret).is_err());
+    assert!(verify_unsubscribe_token("5", &secret).is_err());
+
+    let other_secret = Secret {
+      jwt_secret: "other".to_string().into(),
+      ..secret
+    };
+    assert!(verify_unsubscribe_token(&token, &other_secret).is_err());
+    Ok(())
+  }
+}
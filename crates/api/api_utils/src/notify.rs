@@ -1,10 +1,18 @@
-use crate::{context::LemmyContext, plugins::plugin_hook_notification};
+use crate::{
+  context::LemmyContext,
+  plugins::plugin_hook_notification,
+  push::send_push_notifications,
+  webhooks::{WebhookData, send_webhooks},
+};
+use activitypub_federation::config::Data;
 use lemmy_db_schema::{
   source::{
+    ban_appeal::BanAppeal,
     comment::Comment,
     community::{Community, CommunityActions},
     instance::InstanceActions,
     modlog::Modlog,
+    modmail::{ModmailMessage, ModmailThread},
     notification::{Notification, NotificationInsertForm},
     person::{Person, PersonActions},
     post::{Post, PostActions},
@@ -15,6 +23,7 @@ use lemmy_db_schema_file::{
   PersonId,
   enums::{CommunityNotificationsMode, NotificationType, PostNotificationsMode},
 };
+use lemmy_db_views_community_moderator::CommunityModeratorView;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_private_message::PrivateMessageView;
 use lemmy_db_views_site::SiteView;
@@ -115,11 +124,19 @@ impl NotifyData {
       };
 
       if self.do_send_email {
-        send_notification_email(user_view, c.local_url, c.data, context.settings());
+        send_notification_email(
+          user_view,
+          c.local_url,
+          c.data,
+          &mut context.pool(),
+          context.settings(),
+        )
+        .await?;
       }
     }
     if !forms.is_empty() {
       let notifications = Notification::create(&mut context.pool(), &forms).await?;
+      send_push_notifications(&notifications, &context);
       plugin_hook_notification(notifications, &context).await?;
     }
 
@@ -302,6 +319,7 @@ async fn notify_private_message_internal(
   let notifications = Notification::create(&mut context.pool(), &[form]).await?;
 
   if is_create {
+    send_push_notifications(&notifications, context);
     plugin_hook_notification(notifications, context).await?;
     let site_view = SiteView::read_local(&mut context.pool()).await?;
     if !site_view.local_site.email_notifications_disabled {
@@ -313,14 +331,55 @@ async fn notify_private_message_internal(
         local_recipient,
         view.private_message.local_url(context.settings())?,
         d,
+        &mut context.pool(),
         context.settings(),
-      );
+      )
+      .await?;
     }
   }
   Ok(())
 }
 
-pub fn notify_mod_action(actions: Vec<Modlog>, context: &LemmyContext) {
+/// Messages from the user notify the local moderators of the community, and replies from the mod
+/// team notify the user who opened the thread.
+pub fn notify_modmail(thread: &ModmailThread, message: &ModmailMessage, context: &LemmyContext) {
+  let thread = thread.clone();
+  let from_mod_team = message.from_mod_team;
+  let sender_id = message.creator_id;
+  let context = context.clone();
+  spawn_try_task(async move {
+    let recipients: Vec<Person> = if from_mod_team {
+      vec![Person::read(&mut context.pool(), thread.creator_id).await?]
+    } else {
+      CommunityModeratorView::for_community(&mut context.pool(), thread.community_id)
+        .await?
+        .into_iter()
+        .map(|m| m.moderator)
+        .collect()
+    };
+    let forms: Vec<_> = recipients
+      .into_iter()
+      .filter(|r| r.local && r.id != sender_id)
+      .map(|r| NotificationInsertForm::new_modmail(&thread, r.id))
+      .collect();
+    if forms.is_empty() {
+      return Ok(());
+    }
+    let notifications = Notification::create(&mut context.pool(), &forms).await?;
+    send_push_notifications(&notifications, &context);
+    plugin_hook_notification(notifications, &context).await
+  })
+}
+
+pub fn notify_mod_action(actions: Vec<Modlog>, context: &Data<LemmyContext>) {
+  for action in &actions {
+    send_webhooks(
+      WebhookData::ModAction(action.id),
+      action.target_community_id,
+      context,
+    );
+  }
+
   // Mod actions should notify the target person. If there is no target person then also no
   // notification. This means each mod action can only notify a single person (eg it is not possible
   // to notify all community mods when a community gets removed).
@@ -345,6 +404,7 @@ pub fn notify_mod_action(actions: Vec<Modlog>, context: &LemmyContext) {
 
       let form = NotificationInsertForm::new_mod_action(&action, local_recipient.person.id);
       let notifications = Notification::create(&mut context.pool(), &[form]).await?;
+      send_push_notifications(&notifications, &context);
       plugin_hook_notification(notifications, &context).await?;
 
       let modlog_url = format!(
@@ -362,13 +422,48 @@ pub fn notify_mod_action(actions: Vec<Modlog>, context: &LemmyContext) {
         local_recipient,
         Url::parse(&modlog_url)?.into(),
         d,
+        &mut context.pool(),
         context.settings(),
-      );
+      )
+      .await?;
     }
     Ok(())
   })
 }
 
+/// Notifies a user that their appeal against a ban was denied, including the reason given by the
+/// mod or admin.
+pub fn notify_ban_appeal_denied(appeal: BanAppeal, context: &LemmyContext) {
+  let context = context.clone();
+  spawn_try_task(async move {
+    let Ok(local_recipient) =
+      LocalUserView::read_person(&mut context.pool(), appeal.creator_id).await
+    else {
+      return Ok(());
+    };
+
+    let form = NotificationInsertForm::new_ban_appeal(&appeal);
+    let notifications = Notification::create(&mut context.pool(), &[form]).await?;
+    send_push_notifications(&notifications, &context);
+    plugin_hook_notification(notifications, &context).await?;
+
+    let ban = Modlog::read(&mut context.pool(), appeal.modlog_id).await?;
+    let d = NotificationEmailData::BanAppealDenied {
+      kind: ban.kind,
+      reason: appeal.deny_reason.as_deref(),
+    };
+    let inbox_link = format!("{}/inbox", context.settings().get_protocol_and_hostname());
+    send_notification_email(
+      local_recipient,
+      Url::parse(&inbox_link)?.into(),
+      d,
+      &mut context.pool(),
+      context.settings(),
+    )
+    .await
+  })
+}
+
 #[cfg(test)]
 #[expect(clippy::indexing_slicing)]
 mod tests {
@@ -0,0 +1,343 @@
+use crate::context::LemmyContext;
+use base64::{
+  Engine,
+  engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+};
+use chrono::{Duration, Utc};
+use lemmy_db_schema::source::{
+  notification::Notification,
+  person::Person,
+  push_subscription::PushSubscription,
+  secret::Secret,
+};
+use lemmy_db_views_notification::{NotificationData, NotificationView};
+use lemmy_db_views_site::api::PushNotification;
+use lemmy_diesel_utils::{connection::DbPool, traits::Crud};
+use lemmy_utils::{
+  error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
+  spawn_try_task,
+};
+use reqwest::StatusCode;
+use ring::{
+  aead::{AES_128_GCM, Aad, LessSafeKey, Nonce, UnboundKey},
+  agreement::{ECDH_P256, EphemeralPrivateKey, UnparsedPublicKey, agree_ephemeral},
+  hkdf::{self, HKDF_SHA256, KeyType},
+  rand::{SecureRandom, SystemRandom},
+  signature::{ECDSA_P256_SHA256_FIXED_SIGNING, EcdsaKeyPair, KeyPair},
+};
+use serde_json::json;
+use tracing::warn;
+use url::Url;
+
+/// Push services drop messages which couldn't be delivered within this time.
+const PUSH_TTL: Duration = Duration::days(1);
+
+/// Push services reject VAPID tokens which are valid for more than 24 hours.
+const VAPID_EXPIRATION: Duration = Duration::hours(12);
+
+/// Push services accept messages of about 4 KB, so longer texts are cut off.
+const PREVIEW_LENGTH: usize = 200;
+
+/// The encrypted content always consists of a single record of at most this size.
+const RECORD_SIZE: u32 = 4096;
+
+/// Generates the key for signing push requests, if the database doesn't contain one yet.
+pub async fn init_vapid_key(secret: Secret, pool: &mut DbPool<'_>) -> LemmyResult<Secret> {
+  if secret.vapid_private_key.is_some() {
+    return Ok(secret);
+  }
+  let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &SystemRandom::new())?;
+  Secret::set_vapid_private_key(pool, &STANDARD.encode(pkcs8.as_ref())).await
+}
+
+/// The key pair for signing push requests. Missing in tests, where no key is generated.
+fn vapid_key_pair(secret: &Secret) -> LemmyResult<Option<EcdsaKeyPair>> {
+  let Some(private_key) = &secret.vapid_private_key else {
+    return Ok(None);
+  };
+  let key_pair = EcdsaKeyPair::from_pkcs8(
+    &ECDSA_P256_SHA256_FIXED_SIGNING,
+    &STANDARD.decode(private_key.as_bytes())?,
+    &SystemRandom::new(),
+  )?;
+  Ok(Some(key_pair))
+}
+
+/// The public key which browsers need to create a push subscription.
+pub fn vapid_public_key(secret: &Secret) -> LemmyResult<String> {
+  let key_pair = vapid_key_pair(secret)?.ok_or(LemmyErrorType::NotFound)?;
+  Ok(URL_SAFE_NO_PAD.encode(key_pair.public_key().as_ref()))
+}
+
+/// Decodes a key of a push subscription. Some browsers include base64 padding.
+pub fn decode_subscription_key(key: &str) -> LemmyResult<Vec<u8>> {
+  URL_SAFE_NO_PAD
+    .decode(key.trim_end_matches('='))
+    .with_lemmy_type(LemmyErrorType::InvalidPushSubscription)
+}
+
+/// Sends the notifications to the push subscriptions of their recipients. The mute settings of
+/// posts and communities are already applied when the notifications are created.
+pub fn send_push_notifications(notifications: &[Notification], context: &LemmyContext) {
+  let notifications = notifications.to_vec();
+  let context = context.clone();
+  spawn_try_task(async move {
+    let Some(key_pair) = vapid_key_pair(context.secret())? else {
+      return Ok(());
+    };
+    for n in notifications {
+      let subscriptions =
+        PushSubscription::list_for_person(&mut context.pool(), n.recipient_id).await?;
+      if subscriptions.is_empty() {
+        continue;
+      }
+      let person = Person::read(&mut context.pool(), n.recipient_id).await?;
+      let view = NotificationView::read(&mut context.pool(), n.id, &person).await?;
+      let payload = serde_json::to_vec(&push_notification(view, &context)?)?;
+      for subscription in subscriptions {
+        if let Err(e) = send_push(&subscription, &payload, &key_pair, &context).await {
+          warn!("Failed to send push notification: {e}");
+        }
+      }
+    }
+    Ok(())
+  })
+}
+
+fn push_notification(
+  view: NotificationView,
+  context: &LemmyContext,
+) -> LemmyResult<PushNotification> {
+  let settings = context.settings();
+  let (creator_name, preview, url) = match view.data {
+    NotificationData::Comment(c) => (
+      Some(c.creator.name),
+      Some(c.comment.content.clone()),
+      Some(c.comment.local_url(settings)?),
+    ),
+    NotificationData::Post(p) => (
+      Some(p.creator.name),
+      Some(p.post.name.clone()),
+      Some(p.post.local_url(settings)?),
+    ),
+    NotificationData::PrivateMessage(pm) => (
+      Some(pm.creator.name),
+      Some(pm.private_message.content.clone()),
+      Some(pm.private_message.local_url(settings)?.into()),
+    ),
+    NotificationData::ModAction(m) => (m.moderator.map(|m| m.name), m.modlog.reason, None),
+    NotificationData::Modmail(m) => (Some(m.community.name), Some(m.thread.subject), None),
+    NotificationData::BanAppeal(a) => (None, a.deny_reason, None),
+  };
+  Ok(PushNotification {
+    notification_id: view.notification.id,
+    kind: view.notification.kind,
+    creator_name,
+    preview: preview.map(|p| p.chars().take(PREVIEW_LENGTH).collect()),
+    url,
+  })
+}
+
+async fn send_push(
+  subscription: &PushSubscription,
+  payload: &[u8],
+  key_pair: &EcdsaKeyPair,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let endpoint = Url::parse(&subscription.endpoint)?;
+  let body = encrypt(
+    payload,
+    &decode_subscription_key(&subscription.p256dh)?,
+    &decode_subscription_key(&subscription.auth)?,
+  )?;
+  let subject = context.settings().get_protocol_and_hostname();
+  let res = context
+    .client()
+    .post(endpoint.as_str())
+    .header(
+      "Authorization",
+      vapid_authorization(&endpoint, &subject, key_pair)?,
+    )
+    .header("Content-Encoding", "aes128gcm")
+    .header("Content-Type", "application/octet-stream")
+    .header("TTL", PUSH_TTL.num_seconds().to_string())
+    .body(body)
+    .send()
+    .await?;
+
+  // The user unsubscribed or the subscription expired
+  if matches!(res.status(), StatusCode::NOT_FOUND | StatusCode::GONE) {
+    PushSubscription::delete_expired(&mut context.pool(), &subscription.endpoint).await?;
+  } else {
+    res.error_for_status()?;
+  }
+  Ok(())
+}
+
+/// Identifies the server to the push service with a signed JWT (RFC 8292).
+fn vapid_authorization(
+  endpoint: &Url,
+  subject: &str,
+  key_pair: &EcdsaKeyPair,
+) -> LemmyResult<String> {
+  let header = URL_SAFE_NO_PAD.encode(r#"{"typ":"JWT","alg":"ES256"}"#);
+  let claims = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&json!({
+    "aud": endpoint.origin().ascii_serialization(),
+    "exp": (Utc::now() + VAPID_EXPIRATION).timestamp(),
+    "sub": subject,
+  }))?);
+  let unsigned = format!("{header}.{claims}");
+  let signature = key_pair.sign(&SystemRandom::new(), unsigned.as_bytes())?;
+  Ok(format!(
+    "vapid t={unsigned}.{}, k={}",
+    URL_SAFE_NO_PAD.encode(signature.as_ref()),
+    URL_SAFE_NO_PAD.encode(key_pair.public_key().as_ref())
+  ))
+}
+
+/// Encrypts the payload for a subscription with the `aes128gcm` content encoding (RFC 8291).
+fn encrypt(payload: &[u8], ua_public: &[u8], auth_secret: &[u8]) -> LemmyResult<Vec<u8>> {
+  let rng = SystemRandom::new();
+  let as_private = EphemeralPrivateKey::generate(&ECDH_P256, &rng)?;
+  let as_public = as_private.compute_public_key()?;
+  let ecdh_secret = agree_ephemeral(
+    as_private,
+    &UnparsedPublicKey::new(&ECDH_P256, ua_public),
+    <[u8]>::to_vec,
+  )
+  .with_lemmy_type(LemmyErrorType::InvalidPushSubscription)?;
+  let mut salt = [0u8; 16];
+  rng.fill(&mut salt)?;
+  let (cek, nonce) = derive_content_keys(
+    &ecdh_secret,
+    auth_secret,
+    ua_public,
+    as_public.as_ref(),
+    &salt,
+  )?;
+
+  // Delimiter which marks the last record, without further padding
+  let mut record = payload.to_vec();
+  record.push(2);
+  LessSafeKey::new(UnboundKey::new(&AES_128_GCM, &cek)?).seal_in_place_append_tag(
+    Nonce::assume_unique_for_key(nonce),
+    Aad::empty(),
+    &mut record,
+  )?;
+
+  let mut body = salt.to_vec();
+  body.extend_from_slice(&RECORD_SIZE.to_be_bytes());
+  body.push(u8::try_from(as_public.as_ref().len())?);
+  body.extend_from_slice(as_public.as_ref());
+  body.append(&mut record);
+  Ok(body)
+}
+
+/// Derives the content encryption key and the nonce from the shared ECDH secret.
+fn derive_content_keys(
+  ecdh_secret: &[u8],
+  auth_secret: &[u8],
+  ua_public: &[u8],
+  as_public: &[u8],
+  salt: &[u8],
+) -> LemmyResult<([u8; 16], [u8; 12])> {
+  let key_info = [&b"WebPush: info\0"[..], ua_public, as_public].concat();
+  let ikm: [u8; 32] = hkdf_sha256(auth_secret, ecdh_secret, &key_info)?;
+  let cek = hkdf_sha256(salt, &ikm, b"Content-Encoding: aes128gcm\0")?;
+  let nonce = hkdf_sha256(salt, &ikm, b"Content-Encoding: nonce\0")?;
+  Ok((cek, nonce))
+}
+
+fn hkdf_sha256<const N: usize>(salt: &[u8], ikm: &[u8], info: &[u8]) -> LemmyResult<[u8; N]> {
+  let mut out = [0u8; N];
+  hkdf::Salt::new(HKDF_SHA256, salt)
+    .extract(ikm)
+    .expand(&[info], OutputLength(N))?
+    .fill(&mut out)?;
+  Ok(out)
+}
+
+struct OutputLength(usize);
+
+impl KeyType for OutputLength {
+  fn len(&self) -> usize {
+    self.0
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_encrypt_push_payload() -> LemmyResult<()> {
+    // Keys of the subscription, which the browser generates
+    let rng = SystemRandom::new();
+    let ua_private = EphemeralPrivateKey::generate(&ECDH_P256, &rng)?;
+    let ua_public = ua_private.compute_public_key()?;
+    let auth_secret = [7u8; 16];
+
+    let body = encrypt(b"hello", ua_public.as_ref(), &auth_secret)?;
+
+    // Decrypt like the browser
+    let salt = body.get(..16).unwrap_or_default();
+    assert_eq!(Some(&RECORD_SIZE.to_be_bytes()[..]), body.get(16..20));
+    assert_eq!(Some(&65), body.get(20));
+    let as_public = body.get(21..86).unwrap_or_default();
+    let ecdh_secret = agree_ephemeral(
+      ua_private,
+      &UnparsedPublicKey::new(&ECDH_P256, as_public),
+      <[u8]>::to_vec,
+    )?;
+    let (cek, nonce) = derive_content_keys(
+      &ecdh_secret,
+      &auth_secret,
+      ua_public.as_ref(),
+      as_public,
+      salt,
+    )?;
+    let mut record = body.get(86..).unwrap_or_default().to_vec();
+    let plaintext = LessSafeKey::new(UnboundKey::new(&AES_128_GCM, &cek)?).open_in_place(
+      Nonce::assume_unique_for_key(nonce),
+      Aad::empty(),
+      &mut record,
+    )?;
+    assert_eq!(b"hello\x02", plaintext);
+    Ok(())
+  }
+
+  #[test]
+  fn test_vapid_authorization() -> LemmyResult<()> {
+    let pkcs8 =
+      EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &SystemRandom::new())?;
+    let secret = Secret {
+      id: 0,
+      jwt_secret: String::new().into(),
+      vapid_private_key: Some(STANDARD.encode(pkcs8.as_ref()).into()),
+    };
+    let key_pair = vapid_key_pair(&secret)?.ok_or(LemmyErrorType::NotFound)?;
+    let endpoint = Url::parse("https://push.example.com/send/abc")?;
+    let authorization = vapid_authorization(&endpoint, "https://lemmy.tld", &key_pair)?;
+
+    let (token, key) = authorization
+      .strip_prefix("vapid t=")
+      .and_then(|a| a.split_once(", k="))
+      .ok_or(LemmyErrorType::NotFound)?;
+    assert_eq!(vapid_public_key(&secret)?, key);
+    let (unsigned, signature) = token.rsplit_once('.').ok_or(LemmyErrorType::NotFound)?;
+    ring::signature::UnparsedPublicKey::new(
+      &ring::signature::ECDSA_P256_SHA256_FIXED,
+      URL_SAFE_NO_PAD.decode(key)?,
+    )
+    .verify(unsigned.as_bytes(), &URL_SAFE_NO_PAD.decode(signature)?)?;
+
+    let (_, claims) = unsigned.split_once('.').ok_or(LemmyErrorType::NotFound)?;
+    let claims: serde_json::Value = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(claims)?)?;
+    assert_eq!(
+      Some("https://push.example.com"),
+      claims.get("aud").and_then(|a| a.as_str())
+    );
+    Ok(())
+  }
+}
@@ -0,0 +1,94 @@
+use crate::context::LemmyContext;
+use lemmy_db_schema::newtypes::{CommentId, CommunityId, NotificationId, PostId};
+use lemmy_db_schema_file::PersonId;
+use lemmy_diesel_utils::connection::DbListener;
+use serde::Deserialize;
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::warn;
+
+/// Channel on which the database triggers publish real-time events.
+const REALTIME_CHANNEL: &str = "lemmy_realtime";
+
+/// How long to wait before connecting again after the listener connection was lost.
+const RECONNECT_DELAY: Duration = Duration::from_secs(10);
+
+/// Number of events which are buffered for each connected client. Slower clients miss events.
+pub const REALTIME_CAPACITY: usize = 1024;
+
+/// An event published by the database triggers in `replaceable_schema/triggers.sql`. It only
+/// contains ids, so that the objects can be read with the permissions of each connected user.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RealtimeEvent {
+  Notification {
+    id: NotificationId,
+    recipient_id: PersonId,
+  },
+  UnreadCounts {
+    recipient_id: PersonId,
+  },
+  Post {
+    id: PostId,
+    community_id: CommunityId,
+  },
+  Comment {
+    id: CommentId,
+    post_id: PostId,
+  },
+}
+
+/// Forwards the events published with `NOTIFY` to the clients connected to this server process.
+/// This way events from all processes which share the database reach every client.
+pub async fn listen_for_realtime_events(context: LemmyContext) {
+  loop {
+    match DbListener::connect(REALTIME_CHANNEL).await {
+      Ok(mut listener) => {
+        while let Some(payload) = listener.recv().await {
+          match serde_json::from_str::<RealtimeEvent>(&payload) {
+            // Fails if no client is connected, which is fine
+            Ok(event) => context.realtime().send(event).ok(),
+            Err(e) => {
+              warn!("Invalid realtime event {payload}: {e}");
+              None
+            }
+          };
+        }
+        warn!("Lost connection for realtime events");
+      }
+      Err(e) => warn!("Failed to listen for realtime events: {e}"),
+    }
+    sleep(RECONNECT_DELAY).await;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_parse_realtime_event() -> serde_json::Result<()> {
+    // Same format as `json_build_object` in the triggers
+    let event: RealtimeEvent =
+      serde_json::from_str(r#"{"type" : "notification", "id" : 5, "recipient_id" : 2}"#)?;
+    assert_eq!(
+      RealtimeEvent::Notification {
+        id: NotificationId(5),
+        recipient_id: PersonId(2),
+      },
+      event
+    );
+
+    let event: RealtimeEvent =
+      serde_json::from_str(r#"{"type" : "comment", "id" : 7, "post_id" : 3}"#)?;
+    assert_eq!(
+      RealtimeEvent::Comment {
+        id: CommentId(7),
+        post_id: PostId(3),
+      },
+      event
+    );
+    Ok(())
+  }
+}
@@ -6,8 +6,10 @@ use lemmy_db_schema::{
   source::{
     comment::Comment,
     community::Community,
+    modmail::{ModmailMessage, ModmailThread},
     multi_community::MultiCommunity,
     person::Person,
+    poll::PollChoice,
     post::Post,
     private_message::PrivateMessage,
     site::Site,
@@ -63,6 +65,11 @@ pub enum SendActivityData {
     previous_is_upvote: Option<bool>,
     new_is_upvote: Option<bool>,
   },
+  VotePoll {
+    post: Post,
+    actor: Person,
+    choices: Vec<PollChoice>,
+  },
   FollowCommunity(Community, Person, bool),
   FollowMultiCommunity(MultiCommunity, Person, bool),
   PrivateCommunityAcceptFollower {
@@ -106,7 +113,10 @@ pub enum SendActivityData {
   CreatePrivateMessage(PrivateMessageView),
   UpdatePrivateMessage(PrivateMessageView),
   DeletePrivateMessage(Person, PrivateMessage, bool),
+  CreateModmailMessage(ModmailThread, ModmailMessage),
   DeleteUser(Person, bool),
+  MoveUser(Person, Person),
+  MoveCommunity(Community, Community),
   CreateReport {
     object_id: Url,
     actor: Person,
@@ -11,6 +11,7 @@ use enum_map::{EnumMap, enum_map};
 use lemmy_db_schema::{
   newtypes::{CommunityId, CommunityTagId, ModlogId, PostId, PostOrCommentId},
   source::{
+    api_token::ApiToken,
     comment::{Comment, CommentActions, CommentLikeForm},
     community::{Community, CommunityActions, CommunityUpdateForm},
     community_tag::{CommunityTag, PostCommunityTag},
@@ -37,7 +38,7 @@ use lemmy_db_schema_file::{
 use lemmy_db_views_community_follower_approval::PendingFollowerView;
 use lemmy_db_views_community_moderator::{CommunityModeratorView, CommunityPersonBanView};
 use lemmy_db_views_local_image::LocalImageView;
-use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_local_user::{ApiTokenScopes, LocalUserView};
 use lemmy_db_views_site::SiteView;
 use lemmy_diesel_utils::{connection::DbPool, dburl::DbUrl, traits::Crud};
 use lemmy_utils::{
@@ -67,6 +68,7 @@ use lemmy_utils::{
 };
 use moka::future::Cache;
 use regex::{Regex, RegexSet, escape};
+use sha2::{Digest, Sha256};
 use std::{collections::HashSet, sync::LazyLock};
 use tracing::Instrument;
 use url::{ParseError, Url};
@@ -74,6 +76,8 @@ use urlencoding::encode;
 use webmention::{Webmention, WebmentionError};
 
 pub const AUTH_COOKIE_NAME: &str = "jwt";
+/// API tokens start with this prefix, to distinguish them from login tokens.
+pub const API_TOKEN_PREFIX: &str = "lemmy_api_";
 
 pub async fn check_is_mod_or_admin(
   pool: &mut DbPool<'_>,
@@ -786,6 +790,11 @@ pub fn generate_followers_url(ap_id: &DbUrl) -> Result<DbUrl, ParseError> {
   Ok(Url::parse(&format!("{ap_id}/followers"))?.into())
 }
 
+/// Partial followers collection for FEP-8fcf
+pub fn generate_followers_sync_url(ap_id: &DbUrl) -> Result<DbUrl, ParseError> {
+  Ok(Url::parse(&format!("{ap_id}/followers_synchronization"))?.into())
+}
+
 pub fn generate_inbox_url() -> LemmyResult<DbUrl> {
   let url = format!("{}/inbox", SETTINGS.get_protocol_and_hostname());
   Ok(Url::parse(&url)?.into())
@@ -975,6 +984,24 @@ pub async fn local_user_view_from_jwt(
   Ok(local_user_view)
 }
 
+/// Reads the user of an API token, together with the scopes which the token is limited to.
+pub async fn local_user_view_from_api_token(
+  token: &str,
+  context: &LemmyContext,
+) -> LemmyResult<(LocalUserView, ApiTokenScopes)> {
+  let api_token = ApiToken::validate(&mut context.pool(), &hash_api_token(token)).await?;
+  let local_user_view = LocalUserView::read(&mut context.pool(), api_token.local_user_id).await?;
+  check_local_user_deleted(&local_user_view)?;
+
+  let scopes = api_token.scopes.into_iter().flatten().collect();
+  Ok((local_user_view, ApiTokenScopes(scopes)))
+}
+
+/// Only the hash of API tokens is stored, so that they can't be used if the database leaks.
+pub fn hash_api_token(token: &str) -> String {
+  hex::encode(Sha256::digest(token.as_bytes()))
+}
+
 pub fn read_auth_token(req: &HttpRequest) -> LemmyResult<Option<String>> {
   // Try reading jwt from auth header
   if let Ok(header) = Authorization::<Bearer>::parse(req) {
@@ -1046,6 +1073,16 @@ pub async fn update_post_tags(
   Ok(())
 }
 
+/// Unified diff between two versions of a post or comment text. Returns None if the text is
+/// unchanged.
+pub fn text_diff(old: &str, new: &str) -> Option<String> {
+  if old == new {
+    return None;
+  }
+  let diff = unified_diff::diff(old.as_bytes(), "", new.as_bytes(), "", 3);
+  Some(String::from_utf8_lossy(&diff).into_owned())
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -1066,6 +1103,17 @@ mod tests {
     assert!(password_length_check("looooooooooooooooooooooooooooooooooooooooooooooooooooooooooong").is_err());
   }
 
+  #[test]
+  fn test_text_diff() {
+    assert_eq!(None, text_diff("same", "same"));
+    let diff = text_diff("first line\nsecond line\n", "first line\nchanged line\n");
+    assert!(
+      diff
+        .as_deref()
+        .is_some_and(|d| d.contains("-second line\n+changed line"))
+    );
+  }
+
   #[test]
   fn honeypot() {
     assert!(honeypot_check(&None).is_ok());
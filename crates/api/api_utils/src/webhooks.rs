@@ -0,0 +1,249 @@
+use crate::context::LemmyContext;
+use activitypub_federation::config::Data;
+use chrono::{DateTime, Utc};
+use futures::future::join_all;
+use hmac::{Hmac, Mac};
+use lemmy_db_schema::{
+  newtypes::{CommentId, CommunityId, ModlogId, PostId},
+  source::webhook::{
+    Webhook,
+    WebhookDelivery,
+    WebhookDeliveryInsertForm,
+    WebhookDeliveryUpdateForm,
+  },
+};
+use lemmy_db_schema_file::{PersonId, enums::WebhookEventType};
+use lemmy_db_views_comment::CommentView;
+use lemmy_db_views_modlog::ModlogView;
+use lemmy_db_views_post::PostView;
+use lemmy_db_views_registration_applications::RegistrationApplicationView;
+use lemmy_db_views_report_combined::ReportCombinedView;
+use lemmy_db_views_site::SiteView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{
+  error::{LemmyResult, UntranslatedError},
+  spawn_try_task,
+};
+use reqwest::header::CONTENT_TYPE;
+use serde::Serialize;
+use serde_json::Value;
+use sha2::Sha256;
+use std::{cmp::min, time::Duration};
+use tracing::warn;
+
+/// Delivery is given up after this many failed attempts.
+const MAX_ATTEMPTS: i32 = 10;
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+/// While a delivery is being attempted, its next attempt is moved this far into the future so that
+/// it isn't picked up again by [retry_webhook_deliveries]. Must be longer than [WEBHOOK_TIMEOUT].
+const DELIVERY_LEASE: Duration = Duration::from_secs(5 * 60);
+
+/// The object which triggered a webhook event. Views are only read from the database if a webhook
+/// is actually subscribed to the event.
+pub enum WebhookData {
+  NewPost(PostId),
+  NewComment(CommentId),
+  NewReport(Box<ReportCombinedView>),
+  ModAction(ModlogId),
+  /// The registration application of the given person.
+  NewRegistrationApplication(PersonId),
+}
+
+#[derive(Serialize)]
+struct WebhookPayload {
+  event: WebhookEventType,
+  published_at: DateTime<Utc>,
+  data: Value,
+}
+
+impl WebhookData {
+  fn event(&self) -> WebhookEventType {
+    match self {
+      WebhookData::NewPost(_) => WebhookEventType::NewPost,
+      WebhookData::NewComment(_) => WebhookEventType::NewComment,
+      WebhookData::NewReport(_) => WebhookEventType::NewReport,
+      WebhookData::ModAction(_) => WebhookEventType::ModAction,
+      WebhookData::NewRegistrationApplication(_) => WebhookEventType::NewRegistrationApplication,
+    }
+  }
+
+  /// Reads the view for the event, as seen by an anonymous user. This means that content from
+  /// private communities is not sent.
+  async fn read_view(self, context: &LemmyContext) -> LemmyResult<Value> {
+    let pool = &mut context.pool();
+    let local_instance_id = SiteView::read_local(pool).await?.instance.id;
+    let view = match self {
+      WebhookData::NewPost(post_id) => {
+        serde_json::to_value(PostView::read(pool, post_id, None, local_instance_id, false).await?)?
+      }
+      WebhookData::NewComment(comment_id) => {
+        serde_json::to_value(CommentView::read(pool, comment_id, None, local_instance_id).await?)?
+      }
+      WebhookData::NewReport(report) => serde_json::to_value(report)?,
+      WebhookData::ModAction(modlog_id) => {
+        serde_json::to_value(ModlogView::read(pool, modlog_id).await?)?
+      }
+      WebhookData::NewRegistrationApplication(person_id) => {
+        serde_json::to_value(RegistrationApplicationView::read_by_person(pool, person_id).await?)?
+      }
+    };
+    Ok(view)
+  }
+}
+
+/// Sends an event to all site-wide webhooks, and to the webhooks of the given community. Runs in a
+/// background task.
+pub fn send_webhooks(
+  data: WebhookData,
+  community_id: Option<CommunityId>,
+  context: &Data<LemmyContext>,
+) {
+  let context = context.clone();
+  spawn_try_task(async move {
+    let event = data.event();
+    let webhooks = Webhook::list_for_event(&mut context.pool(), event, community_id).await?;
+    if webhooks.is_empty() {
+      return Ok(());
+    }
+
+    let payload = WebhookPayload {
+      event,
+      published_at: Utc::now(),
+      data: data.read_view(&context).await?,
+    };
+    let payload = serde_json::to_string(&payload)?;
+
+    let mut deliveries = vec![];
+    for webhook in webhooks {
+      let form = WebhookDeliveryInsertForm {
+        // The first attempt is made right away, and holds the lease on the delivery. If it doesn't
+        // finish, the delivery is retried by the scheduled task once the lease expires.
+        next_attempt_at: Some(Utc::now() + DELIVERY_LEASE),
+        ..WebhookDeliveryInsertForm::new(webhook.id, event, payload.clone())
+      };
+      let delivery = WebhookDelivery::create(&mut context.pool(), &form).await?;
+      deliveries.push((delivery, webhook));
+    }
+    deliver_all(deliveries, &context).await;
+    Ok(())
+  });
+}
+
+/// Retries all deliveries which previously failed and are now due. The deliveries are claimed
+/// first, so that they aren't sent twice if another server process retries at the same time.
+pub async fn retry_webhook_deliveries(context: &Data<LemmyContext>) -> LemmyResult<()> {
+  let pending =
+    WebhookDelivery::claim_pending(&mut context.pool(), Utc::now() + DELIVERY_LEASE).await?;
+  deliver_all(pending, context).await;
+  Ok(())
+}
+
+async fn deliver_all(deliveries: Vec<(WebhookDelivery, Webhook)>, context: &Data<LemmyContext>) {
+  let tasks = deliveries
+    .into_iter()
+    .map(|(delivery, webhook)| deliver(delivery, webhook, context));
+  for res in join_all(tasks).await {
+    res
+      .inspect_err(|e| warn!("Failed to store webhook delivery: {e}"))
+      .ok();
+  }
+}
+
+/// Posts the payload to the webhook url and stores the result. If it fails, the next attempt is
+/// scheduled with exponential backoff.
+async fn deliver(
+  delivery: WebhookDelivery,
+  webhook: Webhook,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let attempts = delivery.attempts + 1;
+  // The domain may resolve to a different address than when the webhook was created
+  let (status, error) = match context.is_valid_ip(&webhook.url).await {
+    Ok(()) => {
+      let signature = sign_webhook_payload(&webhook.secret, &delivery.payload)?;
+      let res = context
+        .client()
+        .post(webhook.url.as_str())
+        .header(CONTENT_TYPE, "application/json")
+        .header("X-Lemmy-Event", delivery.event.to_string())
+        .header("X-Lemmy-Delivery", delivery.id.0.to_string())
+        .header("X-Lemmy-Signature", format!("sha256={signature}"))
+        .timeout(WEBHOOK_TIMEOUT)
+        .body(delivery.payload)
+        .send()
+        .await;
+      match res {
+        Ok(res) if res.status().is_success() => (Some(res.status().as_u16().into()), None),
+        Ok(res) => (
+          Some(res.status().as_u16().into()),
+          Some(format!("Received status {}", res.status())),
+        ),
+        Err(e) => (None, Some(e.to_string())),
+      }
+    }
+    Err(e) => (None, Some(e.to_string())),
+  };
+  let form = if error.is_none() {
+    WebhookDeliveryUpdateForm {
+      attempts: Some(attempts),
+      next_attempt_at: Some(None),
+      last_status: Some(status),
+      last_error: Some(None),
+      delivered_at: Some(Some(Utc::now())),
+    }
+  } else {
+    let next_attempt_at =
+      (attempts < MAX_ATTEMPTS).then(|| Utc::now() + webhook_retry_delay(attempts));
+    WebhookDeliveryUpdateForm {
+      attempts: Some(attempts),
+      next_attempt_at: Some(next_attempt_at),
+      last_status: Some(status),
+      last_error: Some(error),
+      delivered_at: None,
+    }
+  };
+  WebhookDelivery::update(&mut context.pool(), delivery.id, &form).await?;
+  Ok(())
+}
+
+/// Hex encoded HMAC-SHA256 of the payload, so that receivers can verify that it was sent by Lemmy.
+fn sign_webhook_payload(secret: &str, payload: &str) -> LemmyResult<String> {
+  let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+    .map_err(|_e| UntranslatedError::Unreachable)?;
+  mac.update(payload.as_bytes());
+  Ok(hex::encode(mac.finalize().into_bytes()))
+}
+
+/// How long to wait before retrying a failed delivery. Starts at one minute and doubles after each
+/// attempt, with a maximum of one day.
+fn webhook_retry_delay(attempts: i32) -> Duration {
+  let minutes = 2_u64.saturating_pow(attempts.saturating_sub(1).try_into().unwrap_or(0));
+  min(
+    Duration::from_secs(60 * 60 * 24),
+    Duration::from_secs(60_u64.saturating_mul(minutes)),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_sign_webhook_payload() -> LemmyResult<()> {
+    let signature = sign_webhook_payload("key", "The quick brown fox jumps over the lazy dog")?;
+    assert_eq!(
+      "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8",
+      signature
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn test_webhook_retry_delay() {
+    assert_eq!(Duration::from_secs(60), webhook_retry_delay(1));
+    assert_eq!(Duration::from_secs(4 * 60), webhook_retry_delay(3));
+    assert_eq!(Duration::from_secs(256 * 60), webhook_retry_delay(9));
+    assert_eq!(Duration::from_secs(60 * 60 * 24), webhook_retry_delay(100));
+  }
+}
@@ -1,7 +1,14 @@
 use actix_web::{guard, web::*};
 use lemmy_api::{
+  ban_appeal::{
+    create::create_ban_appeal,
+    get::get_ban_appeal,
+    list::list_ban_appeals,
+    resolve::resolve_ban_appeal,
+  },
   comment::{
     distinguish::distinguish_comment,
+    history::get_comment_history,
     like::like_comment,
     list_comment_likes::list_comment_likes,
     lock::lock_comment,
@@ -13,6 +20,8 @@ use lemmy_api::{
     ban::ban_from_community,
     block::user_block_community,
     follow::follow_community,
+    import_history::import_community_history,
+    move_community::move_community,
     multi_community_follow::follow_multi_community,
     pending_follows::{approve::post_pending_follows_approve, list::get_pending_follows_list},
     random::get_random_community,
@@ -38,6 +47,7 @@ use lemmy_api::{
     block::user_block_person,
     change_password::change_password,
     change_password_after_reset::change_password_after_reset,
+    events::listen_events,
     export_data::export_user_data,
     generate_totp_secret::generate_totp_secret,
     get_captcha::get_captcha,
@@ -50,16 +60,30 @@ use lemmy_api::{
     login::login,
     logout::logout,
     mark_donation_dialog_shown::mark_donation_dialog_shown,
+    move_account::move_account,
     note_person::user_note_person,
     notifications::{
       list::list_notifications,
       mark_all_read::mark_all_notifications_read,
       mark_notification_read::mark_notification_as_read,
     },
+    passkey::{
+      delete::delete_passkey,
+      edit::edit_passkey,
+      list::list_passkeys,
+      login::{begin_passkey_login, passkey_login},
+      register::{begin_passkey_registration, finish_passkey_registration},
+    },
+    push::{
+      create::create_push_subscription,
+      delete::delete_push_subscription,
+      vapid_key::get_vapid_public_key,
+    },
     resend_verification_email::resend_verification_email,
     reset_password::reset_password,
     save_settings::save_user_settings,
     unread_counts::get_unread_counts,
+    unsubscribe_email::unsubscribe_email,
     update_totp::edit_totp,
     user_block_instance::{user_block_instance_communities, user_block_instance_persons},
     validate_auth::validate_auth,
@@ -69,6 +93,7 @@ use lemmy_api::{
     feature::feature_post,
     get_link_metadata::get_link_metadata,
     hide::hide_post,
+    history::get_post_history,
     like::like_post,
     list_post_likes::list_post_likes,
     lock::lock_post,
@@ -77,6 +102,7 @@ use lemmy_api::{
     mod_update::mod_edit_post,
     save::save_post,
     update_notifications::edit_post_notifications,
+    vote_poll::vote_poll,
     warning::create_post_warning,
   },
   reports::{
@@ -89,8 +115,16 @@ use lemmy_api::{
   site::{
     admin_allow_instance::admin_allow_instance,
     admin_block_instance::admin_block_instance,
+    admin_list_emails::admin_list_emails,
     admin_list_users::admin_list_users,
     federated_instances::get_federated_instances,
+    federation_queue::{
+      list::list_federation_queues,
+      list_errors::list_federation_send_errors,
+      pause::pause_federation_queue,
+      retry::retry_federation_queue,
+      skip::skip_federation_queue,
+    },
     list_all_media::list_all_media,
     mod_log::get_mod_log,
     purge::{
@@ -99,6 +133,7 @@ use lemmy_api::{
       person::purge_person,
       post::purge_post,
     },
+    received_activities::{list::list_received_activities, replay::replay_received_activity},
     registration_applications::{
       approve::approve_registration_application,
       get::get_registration_application,
@@ -107,6 +142,13 @@ use lemmy_api::{
   },
 };
 use lemmy_api_crud::{
+  api_token::{create::create_api_token, delete::delete_api_token, list::list_api_tokens},
+  automod::{
+    create::create_automod_rule,
+    delete::delete_automod_rule,
+    list::list_automod_rules,
+    update::edit_automod_rule,
+  },
   comment::{
     create::create_comment,
     delete::delete_comment,
@@ -127,7 +169,28 @@ use lemmy_api_crud::{
     list::list_custom_emojis,
     update::edit_custom_emoji,
   },
+  draft::{
+    create::create_draft,
+    delete::delete_draft,
+    list::list_drafts,
+    publish::publish_draft,
+    read::get_draft,
+    update::edit_draft,
+  },
+  email_template::{
+    delete::delete_email_template,
+    list::list_email_templates,
+    preview::preview_email_template,
+    save::save_email_template,
+  },
   invite::{create::create_invitation, list::list_invitations, revoke::revoke_invitation},
+  modmail::{
+    archive::archive_modmail_thread,
+    create::create_modmail_thread,
+    list::list_modmail_threads,
+    read::get_modmail_thread,
+    reply::create_modmail_message,
+  },
   multi_community::{
     create::create_multi_community,
     create_entry::create_multi_community_entry,
@@ -135,6 +198,12 @@ use lemmy_api_crud::{
     list::list_multi_communities,
     update::edit_multi_community,
   },
+  oauth_client::{
+    authorize::{get_oauth_authorize, oauth_consent},
+    register::register_oauth_client,
+    revoke::oauth_revoke,
+    token::oauth_token,
+  },
   oauth_provider::{
     create::create_oauth_provider,
     delete::delete_oauth_provider,
@@ -152,6 +221,12 @@ use lemmy_api_crud::{
     delete::delete_private_message,
     update::edit_private_message,
   },
+  recurring_post::{
+    create::create_recurring_post,
+    delete::delete_recurring_post,
+    list::list_recurring_posts,
+    update::edit_recurring_post,
+  },
   site::{create::create_site, read::get_site, update::edit_site},
   tagline::{
     create::create_tagline,
@@ -164,29 +239,40 @@ use lemmy_api_crud::{
     delete::delete_account,
     my_user::get_my_user,
   },
-};
-use lemmy_routes::images::{
-  delete::{
-    delete_community_banner,
-    delete_community_icon,
-    delete_image,
-    delete_image_admin,
-    delete_site_banner,
-    delete_site_icon,
-    delete_user_avatar,
-    delete_user_banner,
+  webhook::{
+    create::create_webhook,
+    delete::delete_webhook,
+    list::list_webhooks,
+    list_deliveries::list_webhook_deliveries,
+    update::edit_webhook,
   },
-  download::{get_image, image_proxy},
-  pictrs_health,
-  upload::{
-    upload_community_banner,
-    upload_community_icon,
-    upload_image,
-    upload_site_banner,
-    upload_site_icon,
-    upload_user_avatar,
-    upload_user_banner,
+};
+use lemmy_db_schema_file::enums::ApiTokenScope;
+use lemmy_routes::{
+  images::{
+    delete::{
+      delete_community_banner,
+      delete_community_icon,
+      delete_image,
+      delete_image_admin,
+      delete_site_banner,
+      delete_site_icon,
+      delete_user_avatar,
+      delete_user_banner,
+    },
+    download::{get_image, image_proxy},
+    pictrs_health,
+    upload::{
+      upload_community_banner,
+      upload_community_icon,
+      upload_image,
+      upload_site_banner,
+      upload_site_icon,
+      upload_user_avatar,
+      upload_user_banner,
+    },
   },
+  middleware::api_token_scope::ApiTokenScopeMiddleware,
 };
 use lemmy_utils::rate_limit::RateLimit;
 
@@ -194,9 +280,13 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
   cfg.service(
     scope("/api/v4")
       .wrap(rate_limit.message())
+      // API tokens can only be used for the routes which set a scope for them. Routes without
+      // their own setting can only be read.
+      .wrap(ApiTokenScopeMiddleware::read_only())
       // Site
       .service(
         scope("/site")
+          .wrap(ApiTokenScopeMiddleware::new(ApiTokenScope::Admin))
           .route("", get().to(get_site))
           .route("", post().to(create_site))
           .route("", put().to(edit_site))
@@ -221,21 +311,40 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
         resource("/community")
           .guard(guard::Post())
           .wrap(rate_limit.register())
+          .wrap(ApiTokenScopeMiddleware::new(ApiTokenScope::Post))
           .route(post().to(create_community)),
       )
       .service(
         scope("/community")
+          .wrap(ApiTokenScopeMiddleware::new(ApiTokenScope::Moderate))
           .route("", get().to(get_community))
           .route("", put().to(edit_community))
           .route("", delete().to(delete_community))
           .route("/random", get().to(get_random_community))
           .route("/list", get().to(list_communities))
-          .route("/follow", post().to(follow_community))
-          .route("/report", post().to(create_community_report))
+          .route(
+            "/follow",
+            post()
+              .to(follow_community)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Read)),
+          )
+          .route(
+            "/report",
+            post()
+              .to(create_community_report)
+              .wrap(ApiTokenScopeMiddleware::new(ApiTokenScope::Post)),
+          )
           .route("/report/resolve", put().to(resolve_community_report))
           // Mod Actions
           .route("/remove", post().to(remove_community))
           .route("/transfer", post().to(transfer_community))
+          .route(
+            "/move",
+            post()
+              .to(move_community)
+              .wrap(ApiTokenScopeMiddleware::deny()),
+          )
+          .route("/import_history", post().to(import_community_history))
           .route("/ban_user", post().to(ban_from_community))
           .route("/mod", post().to(add_mod_to_community))
           .route("/icon", post().to(upload_community_icon))
@@ -245,7 +354,20 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .route("/tag", post().to(create_community_tag))
           .route("/tag", put().to(edit_community_tag))
           .route("/tag", delete().to(delete_community_tag))
-          .route("/notifications", put().to(edit_community_notifications))
+          .route("/automod", post().to(create_automod_rule))
+          .route("/automod", put().to(edit_automod_rule))
+          .route("/automod", delete().to(delete_automod_rule))
+          .route("/automod/list", get().to(list_automod_rules))
+          .route("/recurring_post", post().to(create_recurring_post))
+          .route("/recurring_post", put().to(edit_recurring_post))
+          .route("/recurring_post", delete().to(delete_recurring_post))
+          .route("/recurring_post/list", get().to(list_recurring_posts))
+          .route(
+            "/notifications",
+            put()
+              .to(edit_community_notifications)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Read)),
+          )
           .service(
             scope("/pending_follows")
               .route("/list", get().to(get_pending_follows_list))
@@ -254,13 +376,19 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
       )
       .service(
         scope("/multi_community")
+          .wrap(ApiTokenScopeMiddleware::new(ApiTokenScope::Post))
           .route("", post().to(create_multi_community))
           .route("", put().to(edit_multi_community))
           .route("", get().to(read_multi_community))
           .route("/entry", post().to(create_multi_community_entry))
           .route("/entry", delete().to(delete_multi_community_entry))
           .route("/list", get().to(list_multi_communities))
-          .route("/follow", post().to(follow_multi_community)),
+          .route(
+            "/follow",
+            post()
+              .to(follow_multi_community)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Read)),
+          ),
       )
       .route("/federated_instances", get().to(get_federated_instances))
       // Post
@@ -269,6 +397,7 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           // Handle POST to /post separately to add the post() rate limitter
           .guard(guard::Post())
           .wrap(rate_limit.post())
+          .wrap(ApiTokenScopeMiddleware::new(ApiTokenScope::Post))
           .route(post().to(create_post)),
       )
       .service(
@@ -278,24 +407,92 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
       )
       .service(
         scope("/post")
+          .wrap(ApiTokenScopeMiddleware::new(ApiTokenScope::Post))
           .route("", get().to(get_post))
           .route("", put().to(edit_post))
           .route("", delete().to(delete_post))
-          .route("/remove", post().to(remove_post))
-          .route("/mark_as_read", post().to(mark_post_as_read))
-          .route("/mark_as_read/many", post().to(mark_posts_as_read))
-          .route("/hide", post().to(hide_post))
-          .route("/lock", post().to(lock_post))
-          .route("/feature", post().to(feature_post))
+          .route(
+            "/remove",
+            post()
+              .to(remove_post)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Moderate)),
+          )
+          .route(
+            "/mark_as_read",
+            post()
+              .to(mark_post_as_read)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Read)),
+          )
+          .route(
+            "/mark_as_read/many",
+            post()
+              .to(mark_posts_as_read)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Read)),
+          )
+          .route(
+            "/hide",
+            post()
+              .to(hide_post)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Read)),
+          )
+          .route("/history", get().to(get_post_history))
+          .route(
+            "/lock",
+            post()
+              .to(lock_post)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Moderate)),
+          )
+          .route(
+            "/feature",
+            post()
+              .to(feature_post)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Moderate)),
+          )
           .route("/list", get().to(list_posts))
-          .route("/like", post().to(like_post))
+          .route(
+            "/like",
+            post()
+              .to(like_post)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Vote)),
+          )
           .route("/like/list", get().to(list_post_likes))
-          .route("/save", put().to(save_post))
+          .route(
+            "/poll/vote",
+            post()
+              .to(vote_poll)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Vote)),
+          )
+          .route(
+            "/save",
+            put()
+              .to(save_post)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Read)),
+          )
           .route("/report", post().to(create_post_report))
-          .route("/report/resolve", put().to(resolve_post_report))
-          .route("/notifications", put().to(edit_post_notifications))
-          .route("/mod_edit", put().to(mod_edit_post))
-          .route("/warn", post().to(create_post_warning)),
+          .route(
+            "/report/resolve",
+            put()
+              .to(resolve_post_report)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Moderate)),
+          )
+          .route(
+            "/notifications",
+            put()
+              .to(edit_post_notifications)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Read)),
+          )
+          .route(
+            "/mod_edit",
+            put()
+              .to(mod_edit_post)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Moderate)),
+          )
+          .route(
+            "/warn",
+            post()
+              .to(create_post_warning)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Moderate)),
+          ),
       )
       // Comment
       .service(
@@ -303,38 +500,118 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
         resource("/comment")
           .guard(guard::Post())
           .wrap(rate_limit.comment())
+          .wrap(ApiTokenScopeMiddleware::new(ApiTokenScope::Post))
           .route(post().to(create_comment)),
       )
       .service(
         scope("/comment")
+          .wrap(ApiTokenScopeMiddleware::new(ApiTokenScope::Post))
           .route("", get().to(get_comment))
           .route("", put().to(edit_comment))
           .route("", delete().to(delete_comment))
-          .route("/remove", post().to(remove_comment))
-          .route("/distinguish", post().to(distinguish_comment))
-          .route("/like", post().to(like_comment))
+          .route(
+            "/remove",
+            post()
+              .to(remove_comment)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Moderate)),
+          )
+          .route(
+            "/distinguish",
+            post()
+              .to(distinguish_comment)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Moderate)),
+          )
+          .route("/history", get().to(get_comment_history))
+          .route(
+            "/like",
+            post()
+              .to(like_comment)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Vote)),
+          )
           .route("/like/list", get().to(list_comment_likes))
-          .route("/save", put().to(save_comment))
-          .route("/lock", post().to(lock_comment))
+          .route(
+            "/save",
+            put()
+              .to(save_comment)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Read)),
+          )
+          .route(
+            "/lock",
+            post()
+              .to(lock_comment)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Moderate)),
+          )
           .route("/list", get().to(list_comments))
           .route("/list/slim", get().to(list_comments_slim))
-          .route("/warn", post().to(create_comment_warning))
+          .route(
+            "/warn",
+            post()
+              .to(create_comment_warning)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Moderate)),
+          )
           .route("/report", post().to(create_comment_report))
-          .route("/report/resolve", put().to(resolve_comment_report)),
+          .route(
+            "/report/resolve",
+            put()
+              .to(resolve_comment_report)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Moderate)),
+          ),
       )
       // Private Message
       .service(
         scope("/private_message")
+          .wrap(ApiTokenScopeMiddleware::new(ApiTokenScope::PrivateMessage))
           .route("", post().to(create_private_message))
           .route("", put().to(edit_private_message))
           .route("", delete().to(delete_private_message))
           .route("/report", post().to(create_pm_report))
-          .route("/report/resolve", put().to(resolve_pm_report)),
+          .route(
+            "/report/resolve",
+            put()
+              .to(resolve_pm_report)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Admin)),
+          ),
+      )
+      // Modmail
+      .service(
+        scope("/modmail")
+          .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::PrivateMessage))
+          .route("", post().to(create_modmail_thread))
+          .route("", get().to(get_modmail_thread))
+          .route("/message", post().to(create_modmail_message))
+          .route("/archive", put().to(archive_modmail_thread))
+          .route("/list", get().to(list_modmail_threads)),
+      )
+      // Drafts
+      .service(
+        scope("/draft")
+          .wrap(ApiTokenScopeMiddleware::new(ApiTokenScope::Post))
+          .route("", post().to(create_draft))
+          .route("", put().to(edit_draft))
+          .route("", delete().to(delete_draft))
+          .route("", get().to(get_draft))
+          .route("/list", get().to(list_drafts))
+          .route("/publish", post().to(publish_draft)),
+      )
+      // Ban appeals
+      .service(
+        scope("/ban_appeal")
+          .wrap(ApiTokenScopeMiddleware::new(ApiTokenScope::Post))
+          .route("", post().to(create_ban_appeal))
+          .route("", get().to(get_ban_appeal))
+          .route("/list", get().to(list_ban_appeals))
+          .route(
+            "/resolve",
+            put()
+              .to(resolve_ban_appeal)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Moderate)),
+          ),
       )
       // Reports
       .service(
         scope("/report")
           .wrap(rate_limit.message())
+          .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Moderate))
           .route("/list", get().to(list_reports)),
       )
       // User
@@ -342,6 +619,7 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
         scope("/account/auth")
           .guard(guard::Post())
           .wrap(rate_limit.register())
+          .wrap(ApiTokenScopeMiddleware::deny())
           .route("/register", post().to(register))
           .route("/login", post().to(login))
           .route("/logout", post().to(logout))
@@ -350,7 +628,10 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .route("/change_password", put().to(change_password))
           .route("/totp/generate", post().to(generate_totp_secret))
           .route("/totp/edit", post().to(edit_totp))
+          .route("/passkey/begin", post().to(begin_passkey_login))
+          .route("/passkey/login", post().to(passkey_login))
           .route("/verify_email", post().to(verify_email))
+          .route("/unsubscribe_email", post().to(unsubscribe_email))
           .route(
             "/resend_verification_email",
             post().to(resend_verification_email),
@@ -361,6 +642,12 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .route("/auth/get_captcha", get().to(get_captcha))
           .route("", get().to(get_my_user))
           .route("/unread_counts", get().to(get_unread_counts))
+          .route(
+            "/events",
+            get()
+              .to(listen_events)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Read)),
+          )
           .service(
             scope("/media")
               .route("", delete().to(delete_image))
@@ -368,12 +655,29 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           )
           .service(
             scope("/notification")
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Read))
               .route("/list", get().to(list_notifications))
               .route("/mark_as_read/all", post().to(mark_all_notifications_read))
               .route("/mark_as_read", post().to(mark_notification_as_read)),
           )
+          .service(
+            scope("/push_subscription")
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Read))
+              .route("", post().to(create_push_subscription))
+              .route("", delete().to(delete_push_subscription))
+              .route("/vapid_key", get().to(get_vapid_public_key)),
+          )
           .route("", delete().to(delete_account))
-          .route("/login/list", get().to(list_logins))
+          .route(
+            "/move",
+            post()
+              .to(move_account)
+              .wrap(ApiTokenScopeMiddleware::deny()),
+          )
+          .route(
+            "/login/list",
+            get().to(list_logins).wrap(ApiTokenScopeMiddleware::deny()),
+          )
           .route("/validate_auth", get().to(validate_auth))
           .route(
             "/donation_dialog_shown",
@@ -385,6 +689,7 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .route("/banner", delete().to(delete_user_banner))
           .service(
             scope("/block")
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Read))
               .route("/person", post().to(user_block_person))
               .route("/community", post().to(user_block_community))
               .route(
@@ -402,24 +707,45 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .service(
             scope("/settings")
               .wrap(rate_limit.import_user_settings())
+              .wrap(ApiTokenScopeMiddleware::deny())
               .route("/export", get().to(export_user_settings))
               .route("/import", post().to(import_user_settings)),
           )
           .service(
             resource("/data/export")
               .wrap(rate_limit.import_user_settings())
+              .wrap(ApiTokenScopeMiddleware::deny())
               .route(get().to(export_user_data)),
           )
           .service(
             scope("/invite")
+              .wrap(ApiTokenScopeMiddleware::deny())
               .route("", post().to(create_invitation))
               .route("", delete().to(revoke_invitation))
               .route("/list", get().to(list_invitations)),
+          )
+          .service(
+            scope("/passkey")
+              .wrap(ApiTokenScopeMiddleware::deny())
+              .route("/register/begin", post().to(begin_passkey_registration))
+              .route("/register/finish", post().to(finish_passkey_registration))
+              .route("", put().to(edit_passkey))
+              .route("", delete().to(delete_passkey))
+              .route("/list", get().to(list_passkeys)),
+          )
+          // API tokens can't be used to create other tokens
+          .service(
+            scope("/api_token")
+              .wrap(ApiTokenScopeMiddleware::deny())
+              .route("", post().to(create_api_token))
+              .route("", delete().to(delete_api_token))
+              .route("/list", get().to(list_api_tokens)),
           ),
       )
       // Person / User actions
       .service(
         scope("/person")
+          .wrap(ApiTokenScopeMiddleware::new(ApiTokenScope::Read))
           .route("", get().to(read_person))
           .route("/list", get().to(list_persons))
           .route("/content", get().to(list_person_content))
@@ -428,6 +754,7 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
       // Admin Actions
       .service(
         scope("/admin")
+          .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Admin))
           .route("/add", post().to(add_admin))
           .service(
             scope("/registration_application")
@@ -451,21 +778,53 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           )
           .route("/ban", post().to(ban_from_site))
           .route("/users", get().to(admin_list_users))
+          .route("/email/list", get().to(admin_list_emails))
+          .service(
+            scope("/email_template")
+              .route("", put().to(save_email_template))
+              .route("", delete().to(delete_email_template))
+              .route("/list", get().to(list_email_templates))
+              .route("/preview", post().to(preview_email_template)),
+          )
           .service(
             scope("/instance")
               .route("/block", post().to(admin_block_instance))
               .route("/allow", post().to(admin_allow_instance)),
+          )
+          .service(
+            scope("/federation_queue")
+              .route("/list", get().to(list_federation_queues))
+              .route("/pause", post().to(pause_federation_queue))
+              .route("/retry", post().to(retry_federation_queue))
+              .route("/skip", post().to(skip_federation_queue))
+              .route("/errors", get().to(list_federation_send_errors)),
+          )
+          .service(
+            scope("/received_activity")
+              .route("/list", get().to(list_received_activities))
+              .route("/replay", post().to(replay_received_activity)),
           ),
       )
       .service(
         scope("/custom_emoji")
+          .wrap(ApiTokenScopeMiddleware::new(ApiTokenScope::Admin))
           .route("", post().to(create_custom_emoji))
           .route("", put().to(edit_custom_emoji))
           .route("", delete().to(delete_custom_emoji))
           .route("/list", get().to(list_custom_emojis)),
       )
+      .service(
+        scope("/webhook")
+          .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Moderate))
+          .route("", post().to(create_webhook))
+          .route("", put().to(edit_webhook))
+          .route("", delete().to(delete_webhook))
+          .route("/list", get().to(list_webhooks))
+          .route("/delivery/list", get().to(list_webhook_deliveries)),
+      )
       .service(
         scope("/oauth_provider")
+          .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Admin))
           .route("", post().to(create_oauth_provider))
           .route("", put().to(edit_oauth_provider))
           .route("", delete().to(delete_oauth_provider)),
@@ -475,11 +834,38 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .wrap(rate_limit.register())
           .route("/authenticate", post().to(authenticate_with_oauth)),
       )
+      // Lemmy as OAuth2 authorization server for third-party apps
+      .service(
+        scope("/oauth2")
+          .service(
+            resource("/register")
+              .wrap(rate_limit.register())
+              .route(post().to(register_oauth_client)),
+          )
+          .service(
+            resource("/authorize")
+              .wrap(rate_limit.message())
+              .wrap(ApiTokenScopeMiddleware::deny())
+              .route(get().to(get_oauth_authorize))
+              .route(post().to(oauth_consent)),
+          )
+          .service(
+            resource("/token")
+              .wrap(rate_limit.message())
+              .route(post().to(oauth_token)),
+          )
+          .service(
+            resource("/revoke")
+              .wrap(rate_limit.message())
+              .route(post().to(oauth_revoke)),
+          ),
+      )
       .service(
         scope("/image")
           .service(
             resource("")
               .wrap(rate_limit.image())
+              .wrap(ApiTokenScopeMiddleware::new(ApiTokenScope::Post))
               .route(post().to(upload_image))
               .route(delete().to(delete_image_admin)),
           )
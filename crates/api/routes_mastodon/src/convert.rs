@@ -0,0 +1,362 @@
+use crate::entities::{Account, MediaAttachment, Notification, Relationship, Status};
+use lemmy_db_schema::{
+  newtypes::{CommentId, CommunityId, PostId},
+  source::{community::Community, person::Person},
+};
+use lemmy_db_schema_file::{
+  PersonId,
+  enums::{CommunityFollowerState, NotificationType},
+};
+use lemmy_db_views_comment::CommentView;
+use lemmy_db_views_community::CommunityView;
+use lemmy_db_views_notification::{NotificationData, NotificationView};
+use lemmy_db_views_post::PostView;
+use lemmy_db_views_post_comment_combined::PostCommentCombinedView;
+use lemmy_diesel_utils::dburl::DbUrl;
+use lemmy_utils::{error::LemmyErrorType, utils::markdown::markdown_to_html};
+use std::{
+  fmt::{Display, Formatter},
+  str::FromStr,
+};
+
+/// Both persons and communities are exposed as Mastodon accounts, so the id needs a prefix to tell
+/// them apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AccountId {
+  Person(PersonId),
+  Community(CommunityId),
+}
+
+impl Display for AccountId {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      AccountId::Person(id) => write!(f, "u{}", id.0),
+      AccountId::Community(id) => write!(f, "c{}", id.0),
+    }
+  }
+}
+
+impl FromStr for AccountId {
+  type Err = LemmyErrorType;
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    if let Some(id) = s.strip_prefix('u') {
+      Ok(AccountId::Person(PersonId(parse_id(id)?)))
+    } else if let Some(id) = s.strip_prefix('c') {
+      Ok(AccountId::Community(CommunityId(parse_id(id)?)))
+    } else {
+      Err(LemmyErrorType::NotFound)
+    }
+  }
+}
+
+/// Posts and comments are both exposed as Mastodon statuses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StatusId {
+  Post(PostId),
+  Comment(CommentId),
+}
+
+impl Display for StatusId {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      StatusId::Post(id) => write!(f, "p{}", id.0),
+      StatusId::Comment(id) => write!(f, "c{}", id.0),
+    }
+  }
+}
+
+impl FromStr for StatusId {
+  type Err = LemmyErrorType;
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    if let Some(id) = s.strip_prefix('p') {
+      Ok(StatusId::Post(PostId(parse_id(id)?)))
+    } else if let Some(id) = s.strip_prefix('c') {
+      Ok(StatusId::Comment(CommentId(parse_id(id)?)))
+    } else {
+      Err(LemmyErrorType::NotFound)
+    }
+  }
+}
+
+fn parse_id(id: &str) -> Result<i32, LemmyErrorType> {
+  id.parse().map_err(|_e| LemmyErrorType::NotFound)
+}
+
+pub(crate) fn person_account(person: Person) -> Account {
+  let avatar = url_or_empty(person.avatar);
+  let header = url_or_empty(person.banner);
+  Account {
+    id: AccountId::Person(person.id).to_string(),
+    acct: acct(&person.name, person.local, &person.ap_id),
+    display_name: person.display_name.unwrap_or_else(|| person.name.clone()),
+    username: person.name,
+    locked: false,
+    bot: person.bot_account,
+    group: false,
+    created_at: person.published_at,
+    note: person
+      .bio
+      .as_deref()
+      .map(markdown_to_html)
+      .unwrap_or_default(),
+    url: person.ap_id.to_string(),
+    avatar_static: avatar.clone(),
+    avatar,
+    header_static: header.clone(),
+    header,
+    followers_count: 0,
+    following_count: 0,
+    statuses_count: person.post_count + person.comment_count,
+    emojis: vec![],
+    fields: vec![],
+  }
+}
+
+pub(crate) fn community_account(community: Community) -> Account {
+  let avatar = url_or_empty(community.icon);
+  let header = url_or_empty(community.banner);
+  Account {
+    id: AccountId::Community(community.id).to_string(),
+    acct: acct(&community.name, community.local, &community.ap_id),
+    display_name: community.title.unwrap_or_else(|| community.name.clone()),
+    username: community.name,
+    locked: false,
+    bot: false,
+    group: true,
+    created_at: community.published_at,
+    note: community
+      .sidebar
+      .as_deref()
+      .map(markdown_to_html)
+      .unwrap_or_default(),
+    url: community.ap_id.to_string(),
+    avatar_static: avatar.clone(),
+    avatar,
+    header_static: header.clone(),
+    header,
+    followers_count: community.subscribers,
+    following_count: 0,
+    statuses_count: community.posts,
+    emojis: vec![],
+    fields: vec![],
+  }
+}
+
+pub(crate) fn community_relationship(community_view: &CommunityView) -> Relationship {
+  let actions = community_view.community_actions.as_ref();
+  let follow_state = actions.and_then(|a| a.follow_state);
+  Relationship {
+    id: AccountId::Community(community_view.community.id).to_string(),
+    following: follow_state == Some(CommunityFollowerState::Accepted),
+    requested: matches!(
+      follow_state,
+      Some(CommunityFollowerState::Pending | CommunityFollowerState::ApprovalRequired)
+    ),
+    blocking: actions.and_then(|a| a.blocked_at).is_some(),
+    ..Default::default()
+  }
+}
+
+pub(crate) fn person_relationship(person_id: PersonId, blocking: bool) -> Relationship {
+  Relationship {
+    id: AccountId::Person(person_id).to_string(),
+    blocking,
+    ..Default::default()
+  }
+}
+
+pub(crate) fn post_status(post_view: PostView) -> Status {
+  let PostView {
+    post,
+    creator,
+    community,
+    post_actions,
+    ..
+  } = post_view;
+  let id = StatusId::Post(post.id).to_string();
+
+  // Mastodon has no titles, so the title, link and community are included in the content
+  let mut content = format!("<p><strong>{}</strong></p>", escape_html(&post.name));
+  if let Some(url) = &post.url {
+    let url = escape_html(url.as_str());
+    content.push_str(&format!("<p><a href=\"{url}\">{url}</a></p>"));
+  }
+  if let Some(body) = &post.body {
+    content.push_str(&markdown_to_html(body));
+  }
+  content.push_str(&format!(
+    "<p><a href=\"{}\">!{}</a></p>",
+    escape_html(community.ap_id.as_str()),
+    escape_html(&acct(&community.name, community.local, &community.ap_id))
+  ));
+
+  let is_image = post
+    .url_content_type
+    .as_deref()
+    .is_some_and(|t| t.starts_with("image/"));
+  let media_attachments = match &post.url {
+    Some(url) if is_image => vec![MediaAttachment {
+      id: id.clone(),
+      type_: "image",
+      url: url.to_string(),
+      preview_url: post.thumbnail_url.as_ref().unwrap_or(url).to_string(),
+      description: post.alt_text.clone(),
+    }],
+    _ => vec![],
+  };
+
+  Status {
+    id,
+    uri: post.ap_id.to_string(),
+    url: post.ap_id.to_string(),
+    created_at: post.published_at,
+    edited_at: post.updated_at,
+    account: person_account(creator),
+    content,
+    visibility: "public",
+    sensitive: post.nsfw || community.nsfw,
+    spoiler_text: String::new(),
+    media_attachments,
+    mentions: vec![],
+    tags: vec![],
+    emojis: vec![],
+    reblogs_count: 0,
+    favourites_count: post.upvotes,
+    replies_count: post.comments,
+    in_reply_to_id: None,
+    in_reply_to_account_id: None,
+    reblog: None,
+    card: None,
+    poll: None,
+    language: None,
+    favourited: post_actions.as_ref().and_then(|a| a.vote_is_upvote) == Some(true),
+    reblogged: false,
+    muted: false,
+    bookmarked: post_actions.and_then(|a| a.saved_at).is_some(),
+    pinned: post.featured_community,
+  }
+}
+
+pub(crate) fn comment_status(comment_view: CommentView) -> Status {
+  let CommentView {
+    comment,
+    creator,
+    post,
+    community,
+    comment_actions,
+    ..
+  } = comment_view;
+  let (in_reply_to_id, in_reply_to_account_id) = match comment.parent_comment_id() {
+    Some(parent_id) => (StatusId::Comment(parent_id), None),
+    None => (
+      StatusId::Post(post.id),
+      Some(AccountId::Person(post.creator_id).to_string()),
+    ),
+  };
+  Status {
+    id: StatusId::Comment(comment.id).to_string(),
+    uri: comment.ap_id.to_string(),
+    url: comment.ap_id.to_string(),
+    created_at: comment.published_at,
+    edited_at: comment.updated_at,
+    account: person_account(creator),
+    content: markdown_to_html(&comment.content),
+    visibility: "public",
+    sensitive: post.nsfw || community.nsfw,
+    spoiler_text: String::new(),
+    media_attachments: vec![],
+    mentions: vec![],
+    tags: vec![],
+    emojis: vec![],
+    reblogs_count: 0,
+    favourites_count: comment.upvotes,
+    replies_count: comment.child_count,
+    in_reply_to_id: Some(in_reply_to_id.to_string()),
+    in_reply_to_account_id,
+    reblog: None,
+    card: None,
+    poll: None,
+    language: None,
+    favourited: comment_actions.as_ref().and_then(|a| a.vote_is_upvote) == Some(true),
+    reblogged: false,
+    muted: false,
+    bookmarked: comment_actions.and_then(|a| a.saved_at).is_some(),
+    pinned: false,
+  }
+}
+
+pub(crate) fn combined_status(view: PostCommentCombinedView) -> Status {
+  match view {
+    PostCommentCombinedView::Post(p) => post_status(p),
+    PostCommentCombinedView::Comment(c) => comment_status(c),
+  }
+}
+
+/// Only replies, mentions and posts from subscribed communities have an equivalent in Mastodon,
+/// other notifications are skipped.
+pub(crate) fn notification(view: NotificationView) -> Option<Notification> {
+  let type_ = match view.notification.kind {
+    NotificationType::Reply | NotificationType::Mention => "mention",
+    NotificationType::Subscribed => "status",
+    NotificationType::PrivateMessage
+    | NotificationType::ModAction
+    | NotificationType::Modmail
+    | NotificationType::BanAppeal => return None,
+  };
+  let status = match view.data {
+    NotificationData::Comment(c) => comment_status(c),
+    NotificationData::Post(p) => post_status(p),
+    _ => return None,
+  };
+  Some(Notification {
+    id: view.notification.id.0.to_string(),
+    type_,
+    created_at: view.notification.published_at,
+    account: status.account.clone(),
+    status: Some(status),
+  })
+}
+
+fn acct(name: &str, local: bool, ap_id: &DbUrl) -> String {
+  match ap_id.domain() {
+    Some(domain) if !local => format!("{name}@{domain}"),
+    _ => name.to_string(),
+  }
+}
+
+fn url_or_empty(url: Option<DbUrl>) -> String {
+  url.map(|u| u.to_string()).unwrap_or_default()
+}
+
+fn escape_html(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_parse_ids() -> LemmyResult<()> {
+    let account = AccountId::Community(CommunityId(5));
+    assert_eq!("c5", account.to_string());
+    assert_eq!(account, "c5".parse()?);
+    assert_eq!(AccountId::Person(PersonId(12)), "u12".parse()?);
+
+    let status = StatusId::Comment(CommentId(7));
+    assert_eq!("c7", status.to_string());
+    assert_eq!(status, "c7".parse()?);
+    assert_eq!(StatusId::Post(PostId(3)), "p3".parse()?);
+
+    assert!("12".parse::<StatusId>().is_err());
+    assert!("px".parse::<StatusId>().is_err());
+    assert!("p".parse::<AccountId>().is_err());
+    Ok(())
+  }
+}
@@ -0,0 +1,118 @@
+//! The subset of Mastodon API entities which is returned by these routes, see
+//! <https://docs.joinmastodon.org/entities/>
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub(crate) struct Account {
+  pub id: String,
+  pub username: String,
+  /// Username for local accounts, and `username@domain` for remote ones
+  pub acct: String,
+  pub display_name: String,
+  pub locked: bool,
+  pub bot: bool,
+  /// True for communities
+  pub group: bool,
+  pub created_at: DateTime<Utc>,
+  /// Bio or sidebar, rendered as HTML
+  pub note: String,
+  pub url: String,
+  pub avatar: String,
+  pub avatar_static: String,
+  pub header: String,
+  pub header_static: String,
+  pub followers_count: i32,
+  pub following_count: i32,
+  pub statuses_count: i32,
+  pub emojis: Vec<()>,
+  pub fields: Vec<()>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub(crate) struct Status {
+  pub id: String,
+  pub uri: String,
+  pub url: String,
+  pub created_at: DateTime<Utc>,
+  pub edited_at: Option<DateTime<Utc>>,
+  pub account: Account,
+  pub content: String,
+  pub visibility: &'static str,
+  pub sensitive: bool,
+  pub spoiler_text: String,
+  pub media_attachments: Vec<MediaAttachment>,
+  pub mentions: Vec<()>,
+  pub tags: Vec<()>,
+  pub emojis: Vec<()>,
+  pub reblogs_count: i32,
+  pub favourites_count: i32,
+  pub replies_count: i32,
+  pub in_reply_to_id: Option<String>,
+  pub in_reply_to_account_id: Option<String>,
+  pub reblog: Option<()>,
+  pub card: Option<()>,
+  pub poll: Option<()>,
+  pub language: Option<String>,
+  pub favourited: bool,
+  pub reblogged: bool,
+  pub muted: bool,
+  pub bookmarked: bool,
+  pub pinned: bool,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub(crate) struct MediaAttachment {
+  pub id: String,
+  #[serde(rename = "type")]
+  pub type_: &'static str,
+  pub url: String,
+  pub preview_url: String,
+  pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub(crate) struct Context {
+  pub ancestors: Vec<Status>,
+  pub descendants: Vec<Status>,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub(crate) struct Notification {
+  pub id: String,
+  #[serde(rename = "type")]
+  pub type_: &'static str,
+  pub created_at: DateTime<Utc>,
+  pub account: Account,
+  pub status: Option<Status>,
+}
+
+#[derive(Debug, Serialize, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Relationship {
+  pub id: String,
+  pub following: bool,
+  pub showing_reblogs: bool,
+  pub notifying: bool,
+  pub followed_by: bool,
+  pub blocking: bool,
+  pub blocked_by: bool,
+  pub muting: bool,
+  pub muting_notifications: bool,
+  pub requested: bool,
+  pub domain_blocking: bool,
+  pub endorsed: bool,
+  pub note: String,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub(crate) struct Instance {
+  pub uri: String,
+  pub title: String,
+  pub short_description: String,
+  pub description: String,
+  pub email: String,
+  pub version: String,
+  pub registrations: bool,
+  pub languages: Vec<String>,
+}
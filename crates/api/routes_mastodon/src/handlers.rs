@@ -0,0 +1,653 @@
+use crate::{
+  convert::{
+    AccountId,
+    StatusId,
+    combined_status,
+    comment_status,
+    community_account,
+    community_relationship,
+    notification,
+    person_account,
+    person_relationship,
+    post_status,
+  },
+  entities::{Account, Context, Instance, Relationship, Status},
+};
+use activitypub_federation::config::Data as ApubData;
+use actix_web::{
+  Either,
+  HttpRequest,
+  HttpResponse,
+  http::header::LINK,
+  web::{Data, Form, Json, Path, Query},
+};
+use lemmy_api::{
+  comment::like::like_comment,
+  community::follow::follow_community,
+  federation::{
+    list_comments::list_comments,
+    list_person_content::list_person_content,
+    list_posts::list_posts,
+    read_community::get_community,
+    read_person::read_person,
+  },
+  local_user::{list_liked::list_person_liked, notifications::list::list_notifications},
+  post::like::like_post,
+};
+use lemmy_api_crud::{
+  comment::{create::create_comment, delete::delete_comment, read::get_comment},
+  post::{delete::delete_post, read::get_post},
+};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::{
+  LikeType,
+  newtypes::CommentId,
+  source::comment::Comment,
+  utils::FETCH_LIMIT_MAX,
+};
+use lemmy_db_schema_file::enums::{CommentSortType, ListingType, PostSortType, RegistrationMode};
+use lemmy_db_views_comment::api::{
+  CreateComment,
+  CreateCommentLike,
+  DeleteComment,
+  GetComment,
+  GetComments,
+};
+use lemmy_db_views_community::api::{FollowCommunity, GetCommunity};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_notification::ListNotifications;
+use lemmy_db_views_person::api::GetPersonDetails;
+use lemmy_db_views_person_content_combined::ListPersonContent;
+use lemmy_db_views_person_liked_combined::ListPersonLiked;
+use lemmy_db_views_post::api::{CreatePostLike, DeletePost, GetPost, GetPosts};
+use lemmy_db_views_site::SiteView;
+use lemmy_diesel_utils::{pagination::PaginationCursor, traits::Crud};
+use lemmy_utils::{
+  VERSION,
+  error::{LemmyErrorType, LemmyResult},
+};
+use serde::{Deserialize, Serialize};
+use url::{Url, form_urlencoded};
+
+#[derive(Deserialize)]
+pub(crate) struct PageParams {
+  /// Lemmy pagination cursor, which is passed to clients in the `Link` header
+  max_id: Option<String>,
+  limit: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct TimelineParams {
+  local: Option<bool>,
+  max_id: Option<String>,
+  limit: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct LookupParams {
+  acct: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct CreateStatus {
+  status: String,
+  in_reply_to_id: Option<String>,
+}
+
+pub(crate) async fn get_instance(context: Data<LemmyContext>) -> LemmyResult<Json<Instance>> {
+  let SiteView {
+    site, local_site, ..
+  } = SiteView::read_local(&mut context.pool()).await?;
+  Ok(Json(Instance {
+    uri: context.settings().hostname.clone(),
+    title: site.name,
+    short_description: site.summary.clone().unwrap_or_default(),
+    description: site.summary.unwrap_or_default(),
+    email: String::new(),
+    version: format!("4.0.0 (compatible; Lemmy {})", *VERSION),
+    registrations: local_site.registration_mode != RegistrationMode::Closed,
+    languages: vec![],
+  }))
+}
+
+pub(crate) async fn verify_credentials(local_user_view: LocalUserView) -> Json<Account> {
+  Json(person_account(local_user_view.person))
+}
+
+pub(crate) async fn get_account(
+  id: Path<String>,
+  context: ApubData<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<Account>> {
+  let account = match id.parse()? {
+    AccountId::Person(person_id) => {
+      let data = GetPersonDetails {
+        person_id: Some(person_id),
+        username: None,
+      };
+      let res = read_person(Query(data), context, local_user_view).await?;
+      person_account(res.0.person_view.person)
+    }
+    AccountId::Community(community_id) => {
+      let data = GetCommunity {
+        id: Some(community_id),
+        ..Default::default()
+      };
+      let res = get_community(Query(data), context, local_user_view).await?;
+      community_account(res.0.community_view.community)
+    }
+  };
+  Ok(Json(account))
+}
+
+/// Looks up a user, or a community if there is no user with the given name.
+pub(crate) async fn lookup_account(
+  Query(params): Query<LookupParams>,
+  context: ApubData<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<Account>> {
+  let data = GetPersonDetails {
+    person_id: None,
+    username: Some(params.acct.clone()),
+  };
+  if let Ok(res) = read_person(
+    Query(data),
+    context.reset_request_count(),
+    local_user_view.clone(),
+  )
+  .await
+  {
+    return Ok(Json(person_account(res.0.person_view.person)));
+  }
+  let data = GetCommunity {
+    name: Some(params.acct),
+    ..Default::default()
+  };
+  let res = get_community(Query(data), context, local_user_view).await?;
+  Ok(Json(community_account(res.0.community_view.community)))
+}
+
+pub(crate) async fn account_statuses(
+  id: Path<String>,
+  Query(params): Query<PageParams>,
+  req: HttpRequest,
+  context: ApubData<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<HttpResponse> {
+  let page_cursor = params.max_id.map(PaginationCursor);
+  let (statuses, next_page) = match id.parse()? {
+    AccountId::Person(person_id) => {
+      let data = ListPersonContent {
+        person_id: Some(person_id),
+        page_cursor,
+        limit: params.limit,
+        ..Default::default()
+      };
+      let res = list_person_content(Query(data), context.reset_request_count(), local_user_view)
+        .await?
+        .0;
+      let statuses: Vec<_> = res.items.into_iter().map(combined_status).collect();
+      (statuses, res.next_page)
+    }
+    AccountId::Community(community_id) => {
+      let data = GetPosts {
+        community_id: Some(community_id),
+        sort: Some(PostSortType::New),
+        page_cursor,
+        limit: params.limit,
+        ..Default::default()
+      };
+      let res = list_posts(Query(data), context.reset_request_count(), local_user_view)
+        .await?
+        .0;
+      let statuses: Vec<_> = res.items.into_iter().map(post_status).collect();
+      (statuses, res.next_page)
+    }
+  };
+  paged_response(&statuses, next_page, &req, &context)
+}
+
+/// Returns the relationship to each account in the `id[]` query parameter. Accounts which can't be
+/// read are skipped.
+pub(crate) async fn account_relationships(
+  req: HttpRequest,
+  context: ApubData<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<Vec<Relationship>>> {
+  let ids = form_urlencoded::parse(req.query_string().as_bytes())
+    .filter(|(key, _)| key == "id[]" || key == "id")
+    .filter_map(|(_, value)| value.parse::<AccountId>().ok());
+  let mut relationships = vec![];
+  for id in ids {
+    let relationship = match id {
+      AccountId::Person(person_id) => {
+        let data = GetPersonDetails {
+          person_id: Some(person_id),
+          username: None,
+        };
+        let Ok(res) = read_person(
+          Query(data),
+          context.reset_request_count(),
+          Some(local_user_view.clone()),
+        )
+        .await
+        else {
+          continue;
+        };
+        let person_view = res.0.person_view;
+        let blocking = person_view
+          .person_actions
+          .and_then(|a| a.blocked_at)
+          .is_some();
+        person_relationship(person_id, blocking)
+      }
+      AccountId::Community(community_id) => {
+        let data = GetCommunity {
+          id: Some(community_id),
+          ..Default::default()
+        };
+        let Ok(res) = get_community(
+          Query(data),
+          context.reset_request_count(),
+          Some(local_user_view.clone()),
+        )
+        .await
+        else {
+          continue;
+        };
+        community_relationship(&res.0.community_view)
+      }
+    };
+    relationships.push(relationship);
+  }
+  Ok(Json(relationships))
+}
+
+pub(crate) async fn follow_account(
+  id: Path<String>,
+  context: ApubData<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<Relationship>> {
+  set_follow(&id, true, context, local_user_view).await
+}
+
+pub(crate) async fn unfollow_account(
+  id: Path<String>,
+  context: ApubData<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<Relationship>> {
+  set_follow(&id, false, context, local_user_view).await
+}
+
+/// Only communities can be followed in Lemmy.
+async fn set_follow(
+  id: &str,
+  follow: bool,
+  context: ApubData<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<Relationship>> {
+  let AccountId::Community(community_id) = id.parse()? else {
+    return Err(LemmyErrorType::UnsupportedMastodonRequest.into());
+  };
+  let data = FollowCommunity {
+    community_id,
+    follow,
+  };
+  let res = follow_community(Json(data), context, local_user_view).await?;
+  Ok(Json(community_relationship(&res.0.community_view)))
+}
+
+/// Posts from subscribed communities
+pub(crate) async fn home_timeline(
+  Query(params): Query<TimelineParams>,
+  req: HttpRequest,
+  context: ApubData<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<HttpResponse> {
+  timeline(
+    ListingType::Subscribed,
+    params,
+    req,
+    context,
+    Some(local_user_view),
+  )
+  .await
+}
+
+/// Posts from local communities with `local=true`, otherwise from all communities
+pub(crate) async fn public_timeline(
+  Query(params): Query<TimelineParams>,
+  req: HttpRequest,
+  context: ApubData<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<HttpResponse> {
+  let listing_type = if params.local.unwrap_or_default() {
+    ListingType::Local
+  } else {
+    ListingType::All
+  };
+  timeline(listing_type, params, req, context, local_user_view).await
+}
+
+async fn timeline(
+  listing_type: ListingType,
+  params: TimelineParams,
+  req: HttpRequest,
+  context: ApubData<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<HttpResponse> {
+  let data = GetPosts {
+    type_: Some(listing_type),
+    sort: Some(PostSortType::New),
+    page_cursor: params.max_id.map(PaginationCursor),
+    limit: params.limit,
+    ..Default::default()
+  };
+  let res = list_posts(Query(data), context.reset_request_count(), local_user_view)
+    .await?
+    .0;
+  let statuses: Vec<_> = res.items.into_iter().map(post_status).collect();
+  paged_response(&statuses, res.next_page, &req, &context)
+}
+
+pub(crate) async fn get_status(
+  id: Path<String>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<Status>> {
+  Ok(Json(
+    read_status(id.parse()?, context, local_user_view).await?,
+  ))
+}
+
+async fn read_status(
+  id: StatusId,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Status> {
+  Ok(match id {
+    StatusId::Post(post_id) => {
+      let data = GetPost {
+        id: Some(post_id),
+        comment_id: None,
+      };
+      post_status(
+        get_post(Query(data), context, local_user_view)
+          .await?
+          .0
+          .post_view,
+      )
+    }
+    StatusId::Comment(comment_id) => {
+      let data = GetComment { id: comment_id };
+      comment_status(
+        get_comment(Query(data), context, local_user_view)
+          .await?
+          .0
+          .comment_view,
+      )
+    }
+  })
+}
+
+/// Maximum number of comment pages which are loaded for the descendants of a status.
+const CONTEXT_MAX_PAGES: usize = 10;
+
+/// For a post, the descendants are its comments. For a comment, the ancestors are the post and
+/// parent comments, and the descendants are the replies below it. At most
+/// [CONTEXT_MAX_PAGES] pages of descendants are returned.
+pub(crate) async fn status_context(
+  id: Path<String>,
+  context: Data<LemmyContext>,
+  apub_context: ApubData<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<Context>> {
+  let id = id.parse()?;
+  let (ancestors, mut comments) = match id {
+    StatusId::Post(post_id) => {
+      let data = GetComments {
+        post_id: Some(post_id),
+        sort: Some(CommentSortType::Old),
+        ..Default::default()
+      };
+      (vec![], data)
+    }
+    StatusId::Comment(comment_id) => {
+      let comment = Comment::read(&mut context.pool(), comment_id).await?;
+      let mut ancestors = vec![
+        read_status(
+          StatusId::Post(comment.post_id),
+          context.clone(),
+          local_user_view.clone(),
+        )
+        .await?,
+      ];
+      // The path starts with 0 and ends with the comment itself
+      let parent_ids = comment
+        .path
+        .0
+        .split('.')
+        .skip(1)
+        .filter_map(|p| p.parse().ok())
+        .filter(|p| *p != comment_id.0);
+      for parent_id in parent_ids {
+        let data = GetComment {
+          id: CommentId(parent_id),
+        };
+        let comment_view = get_comment(Query(data), context.clone(), local_user_view.clone())
+          .await?
+          .0
+          .comment_view;
+        ancestors.push(comment_status(comment_view));
+      }
+      let data = GetComments {
+        parent_id: Some(comment_id),
+        sort: Some(CommentSortType::Old),
+        ..Default::default()
+      };
+      (ancestors, data)
+    }
+  };
+  comments.limit = Some(FETCH_LIMIT_MAX.try_into()?);
+  let mut descendants = vec![];
+  for _ in 0..CONTEXT_MAX_PAGES {
+    let res = list_comments(
+      Query(comments.clone()),
+      apub_context.reset_request_count(),
+      local_user_view.clone(),
+    )
+    .await?
+    .0;
+    descendants.extend(
+      res
+        .items
+        .into_iter()
+        .filter(|c| id != StatusId::Comment(c.comment.id))
+        .map(comment_status),
+    );
+    match res.next_page {
+      Some(next_page) => comments.page_cursor = Some(next_page),
+      None => break,
+    }
+  }
+  Ok(Json(Context {
+    ancestors,
+    descendants,
+  }))
+}
+
+/// Statuses can only be created as replies, because a new post would need a community and a
+/// title.
+pub(crate) async fn create_status(
+  data: Either<Json<CreateStatus>, Form<CreateStatus>>,
+  context: ApubData<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<Status>> {
+  let data = data.into_inner();
+  let Some(in_reply_to_id) = data.in_reply_to_id else {
+    return Err(LemmyErrorType::UnsupportedMastodonRequest.into());
+  };
+  let (post_id, parent_id) = match in_reply_to_id.parse()? {
+    StatusId::Post(post_id) => (post_id, None),
+    StatusId::Comment(comment_id) => {
+      let parent = Comment::read(&mut context.pool(), comment_id).await?;
+      (parent.post_id, Some(parent.id))
+    }
+  };
+  let data = CreateComment {
+    content: data.status,
+    post_id,
+    parent_id,
+    language_id: None,
+  };
+  let res = Box::pin(create_comment(Json(data), context, local_user_view)).await?;
+  Ok(Json(comment_status(res.0.comment_view)))
+}
+
+pub(crate) async fn delete_status(
+  id: Path<String>,
+  context: ApubData<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<Status>> {
+  let status = match id.parse()? {
+    StatusId::Post(post_id) => {
+      let data = DeletePost {
+        post_id,
+        deleted: true,
+      };
+      post_status(
+        delete_post(Json(data), context, local_user_view)
+          .await?
+          .0
+          .post_view,
+      )
+    }
+    StatusId::Comment(comment_id) => {
+      let data = DeleteComment {
+        comment_id,
+        deleted: true,
+      };
+      comment_status(
+        delete_comment(Json(data), context, local_user_view)
+          .await?
+          .0
+          .comment_view,
+      )
+    }
+  };
+  Ok(Json(status))
+}
+
+/// Favourites are upvotes
+pub(crate) async fn favourite_status(
+  id: Path<String>,
+  context: ApubData<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<Status>> {
+  vote_status(&id, Some(true), context, local_user_view).await
+}
+
+pub(crate) async fn unfavourite_status(
+  id: Path<String>,
+  context: ApubData<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<Status>> {
+  vote_status(&id, None, context, local_user_view).await
+}
+
+async fn vote_status(
+  id: &str,
+  is_upvote: Option<bool>,
+  context: ApubData<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<Status>> {
+  let status = match id.parse()? {
+    StatusId::Post(post_id) => {
+      let data = CreatePostLike { post_id, is_upvote };
+      post_status(
+        like_post(Json(data), context, local_user_view)
+          .await?
+          .0
+          .post_view,
+      )
+    }
+    StatusId::Comment(comment_id) => {
+      let data = CreateCommentLike {
+        comment_id,
+        is_upvote,
+      };
+      comment_status(
+        like_comment(Json(data), context, local_user_view)
+          .await?
+          .0
+          .comment_view,
+      )
+    }
+  };
+  Ok(Json(status))
+}
+
+/// Posts and comments which the user upvoted
+pub(crate) async fn list_favourites(
+  Query(params): Query<PageParams>,
+  req: HttpRequest,
+  context: ApubData<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<HttpResponse> {
+  let data = ListPersonLiked {
+    type_: None,
+    like_type: Some(LikeType::LikedOnly),
+    page_cursor: params.max_id.map(PaginationCursor),
+    limit: params.limit,
+  };
+  let res = list_person_liked(Query(data), context.reset_request_count(), local_user_view)
+    .await?
+    .0;
+  let statuses: Vec<_> = res.items.into_iter().map(combined_status).collect();
+  paged_response(&statuses, res.next_page, &req, &context)
+}
+
+pub(crate) async fn list_mastodon_notifications(
+  Query(params): Query<PageParams>,
+  req: HttpRequest,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<HttpResponse> {
+  let data = ListNotifications {
+    page_cursor: params.max_id.map(PaginationCursor),
+    limit: params.limit,
+    ..Default::default()
+  };
+  let res = list_notifications(Query(data), context.clone(), local_user_view)
+    .await?
+    .0;
+  let notifications: Vec<_> = res.items.into_iter().filter_map(notification).collect();
+  paged_response(&notifications, res.next_page, &req, &context)
+}
+
+/// Mastodon clients paginate by following the `Link` header, so the cursor of the next page is
+/// passed to them as `max_id`.
+fn paged_response<T: Serialize>(
+  items: &[T],
+  next_page: Option<PaginationCursor>,
+  req: &HttpRequest,
+  context: &LemmyContext,
+) -> LemmyResult<HttpResponse> {
+  let mut res = HttpResponse::Ok();
+  if let Some(next_page) = next_page {
+    let mut url = Url::parse(&format!(
+      "{}{}",
+      context.settings().get_protocol_and_hostname(),
+      req.path()
+    ))?;
+    let params = form_urlencoded::parse(req.query_string().as_bytes())
+      .into_owned()
+      .filter(|(key, _)| key != "max_id");
+    url
+      .query_pairs_mut()
+      .extend_pairs(params)
+      .append_pair("max_id", &next_page.0);
+    res.insert_header((LINK, format!("<{url}>; rel=\"next\"")));
+  }
+  Ok(res.json(items))
+}
@@ -0,0 +1,185 @@
+//! A subset of the Mastodon client API, so that Mastodon apps can be used with Lemmy. Users and
+//! communities are exposed as accounts, posts and comments as statuses. Apps authenticate with a
+//! bearer token like for the Lemmy API.
+//!
+//! The Mastodon OAuth flow (`/api/v1/apps`, `/oauth/authorize` and `/oauth/token`) is not
+//! available, so only apps which allow entering an access token manually can be used. The user
+//! creates an API token with `POST /api/v4/account/api_token`, with the `read` scope and `post`
+//! and `vote` as needed, and enters it in the app.
+
+use crate::handlers::{
+  account_relationships,
+  account_statuses,
+  create_status,
+  delete_status,
+  favourite_status,
+  follow_account,
+  get_account,
+  get_instance,
+  get_status,
+  home_timeline,
+  list_favourites,
+  list_mastodon_notifications,
+  lookup_account,
+  public_timeline,
+  status_context,
+  unfavourite_status,
+  unfollow_account,
+  verify_credentials,
+};
+use actix_web::{guard, web::*};
+use lemmy_db_schema_file::enums::ApiTokenScope;
+use lemmy_routes::middleware::api_token_scope::ApiTokenScopeMiddleware;
+use lemmy_utils::rate_limit::RateLimit;
+
+mod convert;
+mod entities;
+mod handlers;
+
+pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
+  cfg.service(
+    scope("/api/v1")
+      .wrap(rate_limit.message())
+      .wrap(ApiTokenScopeMiddleware::read_only())
+      .route("/instance", get().to(get_instance))
+      .service(
+        scope("/accounts")
+          .route("/verify_credentials", get().to(verify_credentials))
+          .route("/relationships", get().to(account_relationships))
+          .route("/lookup", get().to(lookup_account))
+          .route("/{id}", get().to(get_account))
+          .route("/{id}/statuses", get().to(account_statuses))
+          .route(
+            "/{id}/follow",
+            post()
+              .to(follow_account)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Post)),
+          )
+          .route(
+            "/{id}/unfollow",
+            post()
+              .to(unfollow_account)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Post)),
+          ),
+      )
+      .service(
+        scope("/timelines")
+          .route("/home", get().to(home_timeline))
+          .route("/public", get().to(public_timeline)),
+      )
+      .service(
+        resource("/statuses")
+          .guard(guard::Post())
+          .wrap(rate_limit.comment())
+          .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Post))
+          .route(post().to(create_status)),
+      )
+      .service(
+        scope("/statuses")
+          .route("/{id}", get().to(get_status))
+          .route(
+            "/{id}",
+            delete()
+              .to(delete_status)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Post)),
+          )
+          .route("/{id}/context", get().to(status_context))
+          .route(
+            "/{id}/favourite",
+            post()
+              .to(favourite_status)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Vote)),
+          )
+          .route(
+            "/{id}/unfavourite",
+            post()
+              .to(unfavourite_status)
+              .wrap(ApiTokenScopeMiddleware::all(ApiTokenScope::Vote)),
+          ),
+      )
+      .route("/favourites", get().to(list_favourites))
+      .route("/notifications", get().to(list_mastodon_notifications)),
+  );
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use activitypub_federation::config::FederationMiddleware;
+  use actix_web::{
+    App,
+    http::header::AUTHORIZATION,
+    test::{TestRequest, call_service, init_service, read_body},
+  };
+  use lemmy_api_utils::{
+    context::LemmyContext,
+    utils::{API_TOKEN_PREFIX, hash_api_token},
+  };
+  use lemmy_db_schema::source::{
+    api_token::{ApiToken, ApiTokenInsertForm},
+    instance::Instance,
+    local_user::{LocalUser, LocalUserInsertForm},
+    person::{Person, PersonInsertForm},
+  };
+  use lemmy_diesel_utils::traits::Crud;
+  use lemmy_routes::middleware::session::SessionMiddleware;
+  use lemmy_utils::error::LemmyResult;
+  use serial_test::serial;
+  use std::ops::Deref;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_api_token_scopes() -> LemmyResult<()> {
+    let federation_config = LemmyContext::init_test_federation_config().await;
+    let context: LemmyContext = federation_config.deref().clone();
+    let pool = &mut context.pool();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "mastodon_app_user");
+    let person = Person::create(pool, &person_form).await?;
+    let local_user_form = LocalUserInsertForm::test_form(person.id);
+    let local_user = LocalUser::create(pool, &local_user_form, vec![]).await?;
+
+    let token = format!("{API_TOKEN_PREFIX}mastodon_app_token");
+    let form = ApiTokenInsertForm::new(
+      local_user.id,
+      "Mastodon app".to_string(),
+      hash_api_token(&token),
+      vec![Some(ApiTokenScope::Read)],
+    );
+    ApiToken::create(pool, &form).await?;
+
+    let rate_limit = federation_config.rate_limit_cell().clone();
+    let app = init_service(
+      App::new()
+        .app_data(Data::new(context.clone()))
+        .wrap(FederationMiddleware::new(federation_config.clone()))
+        .wrap(SessionMiddleware::new(context.clone()))
+        .configure(|cfg| config(cfg, &rate_limit)),
+    )
+    .await;
+    let bearer = (AUTHORIZATION, format!("Bearer {token}"));
+
+    // Reading works with the read scope
+    let req = TestRequest::get()
+      .uri("/api/v1/accounts/verify_credentials")
+      .insert_header(bearer.clone())
+      .to_request();
+    let res = call_service(&app, req).await;
+    assert!(res.status().is_success());
+
+    // Following needs the post scope
+    let req = TestRequest::post()
+      .uri("/api/v1/accounts/c1/follow")
+      .insert_header(bearer)
+      .to_request();
+    let res = call_service(&app, req).await;
+    assert!(res.status().is_client_error());
+    let body = String::from_utf8(read_body(res).await.to_vec())?;
+    assert!(body.contains("missing_api_token_scope"));
+
+    Person::delete(pool, person.id).await?;
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -372,6 +372,9 @@ pub(crate) async fn create_post_v3(
     custom_thumbnail,
     tags: None,
     scheduled_publish_time_at: None,
+    poll_choices: None,
+    poll_multiple_choice: None,
+    poll_end_at: None,
   };
   let res = Box::pin(create_post(Json(data), context, local_user_view)).await?;
   convert_post_response(res)
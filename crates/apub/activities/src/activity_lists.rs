@@ -18,6 +18,7 @@ use crate::protocol::{
     reject::RejectFollow,
     undo_follow::UndoFollow,
   },
+  moving::move_actor::Move,
   voting::{undo_vote::UndoVote, vote::Vote},
 };
 use activitypub_federation::{config::Data, traits::Activity};
@@ -47,6 +48,7 @@ pub enum SharedInboxActivities {
   Report(Report),
   ResolveReport(ResolveReport),
   AnnounceActivity(AnnounceActivity),
+  Move(Move),
   /// This is a catch-all and needs to be last
   RawAnnouncableActivities(RawAnnouncableActivities),
 }
@@ -127,6 +129,9 @@ mod tests {
     test_parse_lemmy_item::<SharedInboxActivities>(
       "../apub/assets/lemmy/activities/create_or_update/create_comment.json",
     )?;
+    test_parse_lemmy_item::<SharedInboxActivities>(
+      "../apub/assets/lemmy/activities/moving/move.json",
+    )?;
     test_json::<SharedInboxActivities>("../apub/assets/mastodon/activities/follow.json")?;
     Ok(())
   }
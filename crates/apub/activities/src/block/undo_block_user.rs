@@ -112,7 +112,7 @@ impl Activity for UndoBlockUser {
           ModlogInsertForm::admin_ban(&mod_person, blocked_person.id, false, expires_at, &reason);
         let action = Modlog::create(&mut context.pool(), &[form]).await?;
         let parent_id = action.first().ok_or(LemmyErrorType::NotFound)?.id;
-        notify_mod_action(action, context.app_data());
+        notify_mod_action(action, context);
 
         if self.restore_data.unwrap_or(false) {
           if blocked_person.instance_id == site.instance_id {
@@ -147,7 +147,7 @@ impl Activity for UndoBlockUser {
         );
         let action = Modlog::create(&mut context.pool(), &[form]).await?;
         let parent_id = action.first().ok_or(LemmyErrorType::NotFound)?.id;
-        notify_mod_action(action, context.app_data());
+        notify_mod_action(action, context);
 
         if self.restore_data.unwrap_or(false) {
           remove_or_restore_user_data_in_community(
@@ -198,7 +198,7 @@ impl TryFrom<AnnouncableActivities> for RawAnnouncableActivities {
 /// TODO: This means mentions dont work if the community has no local followers. Can be fixed
 ///       by checking if any local user is in to/cc fields of activity. Anyway this is a minor
 ///       problem compared to receiving unsolicited posts.
-async fn can_accept_activity_in_community(
+pub(crate) async fn can_accept_activity_in_community(
   community: &Option<ApubCommunity>,
   context: &Data<LemmyContext>,
 ) -> LemmyResult<()> {
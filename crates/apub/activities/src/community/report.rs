@@ -23,6 +23,7 @@ use lemmy_api_utils::{
     check_community_deleted_removed,
     check_post_deleted_or_removed,
   },
+  webhooks::{WebhookData, send_webhooks},
 };
 use lemmy_apub_objects::{
   objects::{
@@ -45,6 +46,7 @@ use lemmy_db_schema::{
   },
   traits::Reportable,
 };
+use lemmy_db_views_report_combined::{ReportCombinedView, ReportCombinedViewInternal};
 use lemmy_diesel_utils::traits::Crud;
 use lemmy_utils::error::{LemmyError, LemmyResult};
 use url::Url;
@@ -130,7 +132,8 @@ impl Activity for Report {
   async fn receive(self, context: &Data<Self::DataType>) -> LemmyResult<()> {
     let actor = self.actor.dereference(context).await?;
     let reason = self.reason()?;
-    match self.object.dereference(context).await? {
+    let pool = &mut context.pool();
+    let (report, community_id) = match self.object.dereference(context).await? {
       ReportableObjects::Left(PostOrComment::Left(post)) => {
         let report_form = PostReportForm {
           creator_id: actor.id,
@@ -141,7 +144,9 @@ impl Activity for Report {
           original_post_body: post.body.clone(),
           violates_instance_rules: false,
         };
-        PostReport::report(&mut context.pool(), &report_form).await?;
+        let report = PostReport::report(pool, &report_form).await?;
+        let view = ReportCombinedViewInternal::read_post_report(pool, report.id, &actor).await?;
+        (ReportCombinedView::Post(view), Some(post.community_id))
       }
       ReportableObjects::Left(PostOrComment::Right(comment)) => {
         let report_form = CommentReportForm {
@@ -151,7 +156,10 @@ impl Activity for Report {
           reason,
           violates_instance_rules: false,
         };
-        CommentReport::report(&mut context.pool(), &report_form).await?;
+        let report = CommentReport::report(pool, &report_form).await?;
+        let view = ReportCombinedViewInternal::read_comment_report(pool, report.id, &actor).await?;
+        let community_id = view.community.id;
+        (ReportCombinedView::Comment(view), Some(community_id))
       }
       ReportableObjects::Right(Either::Left(community)) => {
         let report_form = CommunityReportForm {
@@ -165,7 +173,10 @@ impl Activity for Report {
           original_community_summary: community.summary.clone(),
           original_community_sidebar: community.sidebar.clone(),
         };
-        CommunityReport::report(&mut context.pool(), &report_form).await?;
+        let report = CommunityReport::report(pool, &report_form).await?;
+        let view =
+          ReportCombinedViewInternal::read_community_report(pool, report.id, &actor).await?;
+        (ReportCombinedView::Community(view), Some(community.id))
       }
       ReportableObjects::Right(Either::Right(private_message)) => {
         let form = PrivateMessageReportForm {
@@ -174,9 +185,17 @@ impl Activity for Report {
           original_pm_text: private_message.content.clone(),
           reason,
         };
-        PrivateMessageReport::report(&mut context.pool(), &form).await?;
+        let report = PrivateMessageReport::report(pool, &form).await?;
+        let view =
+          ReportCombinedViewInternal::read_private_message_report(pool, report.id, &actor).await?;
+        (ReportCombinedView::PrivateMessage(view), None)
       }
     };
+    send_webhooks(
+      WebhookData::NewReport(Box::new(report)),
+      community_id,
+      context,
+    );
 
     let receiver = self.to[0].dereference(context).await?;
     if let Some(community) = local_community(&receiver) {
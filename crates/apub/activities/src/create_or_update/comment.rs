@@ -12,9 +12,11 @@ use activitypub_federation::{
   traits::{Activity, Object},
 };
 use lemmy_api_utils::{
+  automod::{AutomodContent, run_automod},
   context::LemmyContext,
   notify::NotifyData,
   utils::{check_is_mod_or_admin, check_post_deleted_or_removed},
+  webhooks::{WebhookData, send_webhooks},
 };
 use lemmy_apub_objects::{
   objects::{comment::ApubComment, community::ApubCommunity, person::ApubPerson},
@@ -132,6 +134,14 @@ impl Activity for CreateOrUpdateNote {
     // Calculate initial hot_rank
     Comment::update_hot_rank(&mut context.pool(), comment.id).await?;
 
+    if self.kind == CreateOrUpdateType::Create {
+      send_webhooks(
+        WebhookData::NewComment(comment.id),
+        Some(post.community_id),
+        context,
+      );
+      run_automod(AutomodContent::Comment(comment.0.clone()), context);
+    }
     let do_send_email =
       self.kind == CreateOrUpdateType::Create && !site_view.local_site.email_notifications_disabled;
     let actor = self.actor.dereference(context).await?;
@@ -5,7 +5,9 @@ use lemmy_db_schema::source::{activity::ActivitySendTargets, person::Person};
 use lemmy_utils::error::LemmyResult;
 
 pub mod comment;
+pub mod modmail;
 pub(crate) mod note_wrapper;
+pub(crate) mod poll_vote;
 pub mod post;
 pub mod private_message;
 
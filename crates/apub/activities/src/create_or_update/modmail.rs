@@ -0,0 +1,254 @@
+use crate::{
+  community::announce::can_accept_activity_in_community,
+  generate_activity_id,
+  protocol::create_or_update::modmail::{CreateModmailMessage, ModmailNote, ModmailThreadObject},
+  send_lemmy_activity,
+  verify_person,
+};
+use activitypub_federation::{
+  config::Data,
+  kinds::activity::CreateType,
+  protocol::{
+    values::MediaTypeHtml,
+    verification::{verify_domains_match, verify_urls_match},
+  },
+  traits::{Activity, Actor},
+};
+use lemmy_api_utils::{
+  context::LemmyContext,
+  notify::notify_modmail,
+  utils::{get_url_blocklist, process_markdown, slur_regex},
+};
+use lemmy_apub_objects::{
+  objects::{community::ApubCommunity, person::ApubPerson},
+  utils::{
+    functions::{read_from_string_or_source, verify_person_in_community},
+    markdown_links::markdown_rewrite_remote_links,
+    protocol::Source,
+  },
+};
+use lemmy_db_schema::source::{
+  activity::ActivitySendTargets,
+  comment::Comment,
+  community::Community,
+  modmail::{ModmailMessage, ModmailMessageInsertForm, ModmailThread, ModmailThreadInsertForm},
+  person::Person,
+  post::Post,
+};
+use lemmy_db_schema_file::InstanceId;
+use lemmy_db_views_community_moderator::CommunityModeratorView;
+use lemmy_db_views_site::SiteView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorType, LemmyResult},
+  utils::markdown::markdown_to_html,
+};
+use url::Url;
+
+/// Sends a modmail message to every remote instance which is involved in the thread: that of the
+/// community, of the user who opened the thread, and of each moderator.
+pub(crate) async fn send_modmail_message(
+  thread: ModmailThread,
+  message: ModmailMessage,
+  context: Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let community: ApubCommunity = Community::read(&mut context.pool(), thread.community_id)
+    .await?
+    .into();
+  let actor: ApubPerson = Person::read(&mut context.pool(), message.creator_id)
+    .await?
+    .into();
+  let thread_creator = Person::read(&mut context.pool(), thread.creator_id).await?;
+  let moderators = CommunityModeratorView::for_community(&mut context.pool(), community.id).await?;
+
+  let mut inbox = ActivitySendTargets::empty();
+  if !community.local {
+    inbox.add_inbox(community.shared_inbox_or_inbox());
+  }
+  let people = moderators
+    .into_iter()
+    .map(|m| m.moderator)
+    .chain([thread_creator]);
+  for person in people.filter(|p| !p.local) {
+    inbox.add_inbox(ApubPerson(person).shared_inbox_or_inbox());
+  }
+  if inbox.inboxes.is_empty() {
+    return Ok(());
+  }
+
+  let create = CreateModmailMessage {
+    id: generate_activity_id(CreateType::Create, &context)?,
+    actor: actor.ap_id.clone().into(),
+    to: [community.ap_id.clone().into()],
+    object: modmail_note(&thread, message, &context).await?,
+    kind: CreateType::Create,
+  };
+  send_lemmy_activity(&context, create, &actor, inbox, true).await
+}
+
+/// The ActivityPub representation of a modmail message, including its thread.
+pub async fn modmail_note(
+  thread: &ModmailThread,
+  message: ModmailMessage,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<ModmailNote> {
+  let community = Community::read(&mut context.pool(), thread.community_id).await?;
+  let creator = Person::read(&mut context.pool(), message.creator_id).await?;
+  Ok(ModmailNote {
+    kind: Default::default(),
+    id: message.ap_id.into(),
+    attributed_to: creator.ap_id.into(),
+    to: [community.ap_id.into()],
+    content: markdown_to_html(&message.content),
+    media_type: Some(MediaTypeHtml::Html),
+    source: Some(Source::new(message.content)),
+    published: Some(message.published_at),
+    from_mod_team: message.from_mod_team,
+    modmail_thread: modmail_thread_object(thread, context).await?,
+  })
+}
+
+/// The ActivityPub representation of a modmail thread.
+pub async fn modmail_thread_object(
+  thread: &ModmailThread,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<ModmailThreadObject> {
+  let thread_creator = Person::read(&mut context.pool(), thread.creator_id).await?;
+  let post = match thread.post_id {
+    Some(post_id) => Some(Post::read(&mut context.pool(), post_id).await?.ap_id.into()),
+    None => None,
+  };
+  let comment = match thread.comment_id {
+    Some(comment_id) => Some(
+      Comment::read(&mut context.pool(), comment_id)
+        .await?
+        .ap_id
+        .into(),
+    ),
+    None => None,
+  };
+  Ok(ModmailThreadObject {
+    id: thread.ap_id.clone().into(),
+    name: thread.subject.clone(),
+    attributed_to: thread_creator.ap_id.into(),
+    post,
+    comment,
+    published: Some(thread.published_at),
+  })
+}
+
+/// Instances which take part in the thread, and are allowed to fetch it: that of the community,
+/// of the user who opened the thread, and of each moderator.
+pub async fn modmail_thread_instances(
+  thread: &ModmailThread,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<Vec<InstanceId>> {
+  let community = Community::read(&mut context.pool(), thread.community_id).await?;
+  let thread_creator = Person::read(&mut context.pool(), thread.creator_id).await?;
+  let moderators = CommunityModeratorView::for_community(&mut context.pool(), community.id).await?;
+  Ok(
+    moderators
+      .into_iter()
+      .map(|m| m.moderator.instance_id)
+      .chain([community.instance_id, thread_creator.instance_id])
+      .collect(),
+  )
+}
+
+#[async_trait::async_trait]
+impl Activity for CreateModmailMessage {
+  type DataType = LemmyContext;
+  type Error = LemmyError;
+
+  fn id(&self) -> &Url {
+    &self.id
+  }
+
+  fn actor(&self) -> &Url {
+    self.actor.inner()
+  }
+
+  async fn verify(&self, context: &Data<Self::DataType>) -> LemmyResult<()> {
+    verify_person(&self.actor, context).await?;
+    verify_domains_match(self.actor.inner(), &self.object.id)?;
+    verify_urls_match(self.actor.inner(), self.object.attributed_to.inner())?;
+    verify_urls_match(self.to[0].inner(), self.object.to[0].inner())?;
+    let thread = &self.object.modmail_thread;
+    verify_domains_match(thread.attributed_to.inner(), &thread.id)?;
+    Ok(())
+  }
+
+  async fn receive(self, context: &Data<Self::DataType>) -> LemmyResult<()> {
+    let community = self.to[0].dereference(context).await?;
+    can_accept_activity_in_community(&Some(community.clone()), context).await?;
+    verify_person_in_community(&self.actor, &community, context).await?;
+    let actor = self.actor.dereference(context).await?;
+    let note = self.object;
+
+    let thread = match ModmailThread::read_from_apub_id(
+      &mut context.pool(),
+      note.modmail_thread.id.clone().into(),
+    )
+    .await?
+    {
+      Some(thread) => thread,
+      None => {
+        let info = note.modmail_thread;
+        // Only the user who opened the thread can create it, otherwise a remote actor could open
+        // threads in the name of someone else
+        verify_urls_match(info.attributed_to.inner(), self.actor.inner())?;
+        let creator = info.attributed_to.dereference(context).await?;
+        let post_id = match info.post {
+          Some(post) => post.dereference_local(context).await.ok().map(|p| p.id),
+          None => None,
+        };
+        let comment_id = match info.comment {
+          Some(comment) => comment.dereference_local(context).await.ok().map(|c| c.id),
+          None => None,
+        };
+        let form = ModmailThreadInsertForm {
+          post_id,
+          comment_id,
+          ap_id: Some(info.id.into()),
+          local: Some(false),
+          published_at: info.published,
+          ..ModmailThreadInsertForm::new(community.id, creator.id, info.name)
+        };
+        ModmailThread::create(&mut context.pool(), &form).await?
+      }
+    };
+    if thread.community_id != community.id {
+      return Err(LemmyErrorType::InvalidModmailThread.into());
+    }
+
+    // Only the user who opened the thread and the moderators can write in it
+    if note.from_mod_team {
+      CommunityModeratorView::check_is_community_moderator(
+        &mut context.pool(),
+        community.id,
+        actor.id,
+      )
+      .await?;
+    } else if thread.creator_id != actor.id {
+      return Err(LemmyErrorType::NotAModerator.into());
+    }
+
+    let slur_regex = slur_regex(context).await?;
+    let url_blocklist = get_url_blocklist(context).await?;
+    let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+    let content = read_from_string_or_source(&note.content, &None, &note.source);
+    let content =
+      process_markdown(&content, &slur_regex, &url_blocklist, &local_site, context).await?;
+    let content = markdown_rewrite_remote_links(content, context).await;
+
+    let form = ModmailMessageInsertForm {
+      ap_id: Some(note.id.into()),
+      local: Some(false),
+      published_at: note.published,
+      ..ModmailMessageInsertForm::new(thread.id, actor.id, note.from_mod_team, content)
+    };
+    let message = ModmailMessage::insert_apub(&mut context.pool(), &form).await?;
+    notify_modmail(&thread, &message, context);
+    Ok(())
+  }
+}
@@ -1,18 +1,21 @@
 use crate::protocol::create_or_update::{
+  modmail::CreateModmailMessage,
   note::CreateOrUpdateNote,
   note_wrapper::CreateOrUpdateNoteWrapper,
+  poll_vote::CreatePollVote,
   private_message::CreateOrUpdatePrivateMessage,
 };
 use activitypub_federation::{config::Data, traits::Activity};
 use lemmy_api_utils::context::LemmyContext;
 use lemmy_apub_objects::{objects::community::ApubCommunity, utils::protocol::InCommunity};
-use lemmy_utils::error::{LemmyError, LemmyResult};
+use lemmy_db_schema::source::poll::Poll;
+use lemmy_utils::error::{LemmyError, LemmyErrorType, LemmyResult};
 use serde_json::{from_value, to_value};
 use url::Url;
 
-/// In Activitypub, both private messages and comments are represented by `type: Note` which
-/// makes it difficult to distinguish them. This wrapper handles receiving of both types, and
-/// routes them to the correct handler.
+/// In Activitypub, private messages, comments, poll votes and modmail are all represented by
+/// `type: Note` which makes it difficult to distinguish them. This wrapper handles receiving of all
+/// these types, and routes them to the correct handler.
 #[async_trait::async_trait]
 impl Activity for CreateOrUpdateNoteWrapper {
   type DataType = LemmyContext;
@@ -37,6 +40,29 @@ impl Activity for CreateOrUpdateNoteWrapper {
     // additional data in field `other: Map<String, Value>`.
     let val = to_value(self)?;
 
+    // Poll votes have a name and reply to a local post with a poll.
+    if let Ok(poll_vote) = from_value::<CreatePollVote>(val.clone())
+      && let Ok(post) = poll_vote
+        .object
+        .in_reply_to
+        .dereference_local(context)
+        .await
+      && Poll::read(&mut context.pool(), post.id)
+        .await
+        .is_ok_and(|p| p.is_some())
+    {
+      CreatePollVote::verify(&poll_vote, context).await?;
+      CreatePollVote::receive(poll_vote, context).await?;
+      return Ok(());
+    }
+
+    // Modmail messages are the only notes with a `modmailThread`.
+    if let Ok(modmail) = from_value::<CreateModmailMessage>(val.clone()) {
+      CreateModmailMessage::verify(&modmail, context).await?;
+      CreateModmailMessage::receive(modmail, context).await?;
+      return Ok(());
+    }
+
     // Convert self to a comment and get the community. If the conversion is
     // successful and a community is returned, this is a comment.
     let comment = from_value::<CreateOrUpdateNote>(val.clone());
@@ -60,6 +86,10 @@ impl InCommunity for CreateOrUpdateNoteWrapper {
   async fn community(&self, context: &Data<LemmyContext>) -> LemmyResult<ApubCommunity> {
     // Same logic as in receive. In case this is a private message, an error is returned.
     let val = to_value(self)?;
+    // Modmail is private, so it must never be announced to the followers of the community.
+    if from_value::<CreateModmailMessage>(val.clone()).is_ok() {
+      return Err(LemmyErrorType::NotFound.into());
+    }
     let comment: CreateOrUpdateNote = from_value(val.clone())?;
     comment.community(context).await
   }
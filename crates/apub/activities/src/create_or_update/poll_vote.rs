@@ -0,0 +1,128 @@
+use crate::{
+  check_community_deleted_or_removed,
+  generate_activity_id,
+  protocol::create_or_update::poll_vote::{CreatePollVote, PollVoteNote},
+  send_lemmy_activity,
+  verify_person,
+};
+use activitypub_federation::{
+  config::Data,
+  kinds::activity::CreateType,
+  protocol::verification::{verify_domains_match, verify_urls_match},
+  traits::{Activity, Actor, Object},
+};
+use lemmy_api_utils::{context::LemmyContext, utils::check_post_deleted_or_removed};
+use lemmy_apub_objects::{
+  objects::{community::ApubCommunity, person::ApubPerson, post::ApubPost},
+  utils::functions::verify_person_in_community,
+};
+use lemmy_db_schema::source::{
+  activity::ActivitySendTargets,
+  community::Community,
+  person::Person,
+  poll::{Poll, PollChoice, PollVote, PollVoteForm},
+  post::Post,
+};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::{LemmyError, LemmyErrorType, LemmyResult};
+use url::Url;
+
+/// Sends poll votes to the instance of the poll. Votes in local polls only need to federate the
+/// updated vote counts.
+pub(crate) async fn send_poll_vote(
+  post: Post,
+  actor: Person,
+  choices: Vec<PollChoice>,
+  context: Data<LemmyContext>,
+) -> LemmyResult<()> {
+  if post.local {
+    return Poll::mark_pending_update(&mut context.pool(), post.id).await;
+  }
+  let actor: ApubPerson = actor.into();
+  let creator: ApubPerson = Person::read(&mut context.pool(), post.creator_id)
+    .await?
+    .into();
+  for choice in choices {
+    let id = generate_activity_id(CreateType::Create, &context)?;
+    let mut note_id = id.clone();
+    note_id.set_fragment(Some("vote"));
+    let create = CreatePollVote {
+      id,
+      actor: actor.id().clone().into(),
+      to: [creator.id().clone().into()],
+      object: PollVoteNote {
+        kind: Default::default(),
+        id: note_id,
+        attributed_to: actor.id().clone().into(),
+        to: [creator.id().clone().into()],
+        name: choice.name,
+        in_reply_to: post.ap_id.clone().into(),
+      },
+      kind: CreateType::Create,
+    };
+    let inbox = ActivitySendTargets::to_inbox(creator.shared_inbox_or_inbox());
+    send_lemmy_activity(&context, create, &actor, inbox, true).await?;
+  }
+  Ok(())
+}
+
+#[async_trait::async_trait]
+impl Activity for CreatePollVote {
+  type DataType = LemmyContext;
+  type Error = LemmyError;
+
+  fn id(&self) -> &Url {
+    &self.id
+  }
+
+  fn actor(&self) -> &Url {
+    self.actor.inner()
+  }
+
+  async fn verify(&self, context: &Data<Self::DataType>) -> LemmyResult<()> {
+    verify_person(&self.actor, context).await?;
+    verify_domains_match(self.actor.inner(), &self.object.id)?;
+    verify_urls_match(self.actor.inner(), self.object.attributed_to.inner())?;
+    Ok(())
+  }
+
+  async fn receive(self, context: &Data<Self::DataType>) -> LemmyResult<()> {
+    let post: ApubPost = self.object.in_reply_to.dereference_local(context).await?;
+    let community: ApubCommunity = Community::read(&mut context.pool(), post.community_id)
+      .await?
+      .into();
+    check_community_deleted_or_removed(&community)?;
+    check_post_deleted_or_removed(&post)?;
+    if post.locked {
+      return Err(LemmyErrorType::Locked.into());
+    }
+    verify_person_in_community(&self.actor, &community, context).await?;
+
+    let poll = Poll::read(&mut context.pool(), post.id)
+      .await?
+      .ok_or(LemmyErrorType::NotFound)?;
+    if poll.is_closed() {
+      return Err(LemmyErrorType::PollEnded.into());
+    }
+    let choice = PollChoice::read_for_post(&mut context.pool(), post.id)
+      .await?
+      .into_iter()
+      .find(|c| c.name == self.object.name)
+      .ok_or(LemmyErrorType::InvalidPollVote)?;
+    let voter = self.actor.dereference(context).await?;
+
+    // Like for local users, this rejects a second vote in single choice polls
+    let form = PollVoteForm {
+      poll_choice_id: choice.id,
+      person_id: voter.id,
+      post_id: post.id,
+    };
+    let inserted = PollVote::vote(&mut context.pool(), &[form]).await?;
+
+    // Let other instances know about the new vote counts
+    if !inserted.is_empty() {
+      Poll::mark_pending_update(&mut context.pool(), post.id).await?;
+    }
+    Ok(())
+  }
+}
@@ -12,7 +12,12 @@ use activitypub_federation::{
   traits::{Activity, Object},
 };
 use chrono::Utc;
-use lemmy_api_utils::{context::LemmyContext, notify::NotifyData};
+use lemmy_api_utils::{
+  automod::{AutomodContent, run_automod},
+  context::LemmyContext,
+  notify::NotifyData,
+  webhooks::{WebhookData, send_webhooks},
+};
 use lemmy_apub_objects::{
   objects::{
     community::ApubCommunity,
@@ -143,6 +148,10 @@ impl Activity for CreateOrUpdatePage {
     // Calculate initial hot_rank for post
     Post::update_ranks(&mut context.pool(), post.id).await?;
 
+    if self.kind == CreateOrUpdateType::Create {
+      send_webhooks(WebhookData::NewPost(post.id), Some(community.id), context);
+      run_automod(AutomodContent::Post(post.0.clone()), context);
+    }
     let do_send_email =
       self.kind == CreateOrUpdateType::Create && !site_view.local_site.email_notifications_disabled;
     let actor = self.actor.dereference(context).await?;
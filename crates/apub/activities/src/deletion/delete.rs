@@ -123,7 +123,7 @@ pub(crate) async fn receive_remove_action(
         &reason,
       );
       let action = Modlog::create(&mut context.pool(), &[form]).await?;
-      notify_mod_action(action.clone(), context.app_data());
+      notify_mod_action(action.clone(), context);
 
       Community::update(
         &mut context.pool(),
@@ -139,7 +139,7 @@ pub(crate) async fn receive_remove_action(
       PostReport::resolve_all_for_object(&mut context.pool(), post.id, actor.id).await?;
       let form = ModlogInsertForm::mod_remove_post(actor.id, &post, true, &reason, None);
       let action = Modlog::create(&mut context.pool(), &[form]).await?;
-      notify_mod_action(action, context.app_data());
+      notify_mod_action(action, context);
       let post = Post::update(
         &mut context.pool(),
         post.id,
@@ -222,7 +222,7 @@ pub(crate) async fn receive_remove_action(
           None,
         );
         let action = Modlog::create(&mut context.pool(), &[form]).await?;
-        notify_mod_action(action, context.app_data());
+        notify_mod_action(action, context);
         Comment::update(
           &mut context.pool(),
           comment.id,
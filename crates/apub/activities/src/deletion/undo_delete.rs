@@ -107,7 +107,7 @@ impl UndoDelete {
           &reason,
         );
         let action = Modlog::create(&mut context.pool(), &[form]).await?;
-        notify_mod_action(action.clone(), context.app_data());
+        notify_mod_action(action.clone(), context);
 
         Community::update(
           &mut context.pool(),
@@ -122,7 +122,7 @@ impl UndoDelete {
       DeletableObjects::Post(post) => {
         let form = ModlogInsertForm::mod_remove_post(actor.id, &post, false, &reason, None);
         let action = Modlog::create(&mut context.pool(), &[form]).await?;
-        notify_mod_action(action, context.app_data());
+        notify_mod_action(action, context);
         Post::update(
           &mut context.pool(),
           post.id,
@@ -200,7 +200,7 @@ impl UndoDelete {
             None,
           );
           let action = Modlog::create(&mut context.pool(), &[form]).await?;
-          notify_mod_action(action, context.app_data());
+          notify_mod_action(action, context);
           Comment::update(
             &mut context.pool(),
             comment.id,
@@ -109,12 +109,18 @@ impl Activity for Follow {
 
     match object {
       Left(u) => {
+        if u.moved_to.is_some() {
+          return Err(UntranslatedError::InvalidFollow("Account was moved".to_string()).into());
+        }
         let form = PersonFollowerForm::new(u.id, person.id, false);
         PersonActions::follow(&mut context.pool(), &form).await?;
         AcceptFollow::send(self, context).await?;
       }
       Right(Left(c)) => {
         check_community_deleted_or_removed(&c)?;
+        if c.moved_to.is_some() {
+          return Err(UntranslatedError::InvalidFollow("Community was moved".to_string()).into());
+        }
         CommunityPersonBanView::check(&mut context.pool(), person.id, c.id).await?;
         if c.visibility == CommunityVisibility::Private {
           let instance = Instance::read(&mut context.pool(), person.instance_id).await?;
@@ -5,7 +5,11 @@ use crate::{
     lock::send_lock,
     update::{send_update_community, send_update_multi_community},
   },
-  create_or_update::private_message::send_create_or_update_pm,
+  create_or_update::{
+    modmail::send_modmail_message,
+    poll_vote::send_poll_vote,
+    private_message::send_create_or_update_pm,
+  },
   deletion::{
     DeletableObjects,
     send_apub_delete_in_community,
@@ -13,6 +17,7 @@ use crate::{
     send_apub_delete_user,
   },
   following::send_follow,
+  moving::{send_move_community, send_move_user},
   protocol::{
     CreateOrUpdateType,
     community::{report::Report, resolve_report::ResolveReport, warn::Warn},
@@ -56,6 +61,7 @@ pub mod community;
 pub mod create_or_update;
 pub mod deletion;
 pub mod following;
+pub mod moving;
 pub mod protocol;
 pub mod voting;
 
@@ -255,6 +261,11 @@ pub async fn match_outgoing_activities(
         )
         .await
       }
+      VotePoll {
+        post,
+        actor,
+        choices,
+      } => send_poll_vote(post, actor, choices, context).await,
       LikePostOrComment {
         object_id,
         actor,
@@ -342,7 +353,10 @@ pub async fn match_outgoing_activities(
       DeletePrivateMessage(person, pm, deleted) => {
         send_apub_delete_private_message(&person.into(), pm, deleted, context).await
       }
+      CreateModmailMessage(thread, message) => send_modmail_message(thread, message, context).await,
       DeleteUser(person, remove_data) => send_apub_delete_user(person, remove_data, context).await,
+      MoveUser(person, target) => send_move_user(person, target, context).await,
+      MoveCommunity(community, target) => send_move_community(community, target, context).await,
       CreateReport {
         object_id,
         actor,
@@ -0,0 +1,60 @@
+use crate::{protocol::moving::move_actor::Move, send_lemmy_activity};
+use activitypub_federation::{config::Data, traits::Actor};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_apub_objects::objects::{community::ApubCommunity, person::ApubPerson};
+use lemmy_db_schema::source::{
+  activity::ActivitySendTargets,
+  community::{Community, CommunityActions},
+  person::{Person, PersonActions},
+};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+
+pub(crate) mod move_actor;
+
+/// Sends the account move to the followers, so that they can migrate their follows, and to the
+/// remote communities moderated by the user, so that they can transfer the roles.
+pub async fn send_move_user(
+  person: Person,
+  target: Person,
+  context: Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let actor: ApubPerson = person.into();
+  let target: ApubPerson = target.into();
+  let activity = Move::new(&actor, &target, &context)?;
+
+  // The followers and moderator roles were already transferred to the new account
+  let mut inboxes = ActivitySendTargets::empty();
+  inboxes.add_inboxes(PersonActions::follower_inboxes(&mut context.pool(), target.id).await?);
+  let moderated =
+    CommunityActions::get_person_moderated_communities(&mut context.pool(), target.id).await?;
+  for community_id in moderated {
+    let community: ApubCommunity = Community::read(&mut context.pool(), community_id)
+      .await?
+      .into();
+    if !community.local {
+      inboxes.add_inbox(community.shared_inbox_or_inbox());
+    }
+  }
+
+  send_lemmy_activity(&context, activity, &actor, inboxes, true).await
+}
+
+/// Sends the community move to the instances of remote followers, so that they can migrate their
+/// follows, and to the new community.
+pub async fn send_move_community(
+  community: Community,
+  target: Community,
+  context: Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let actor: ApubCommunity = community.into();
+  let target: ApubCommunity = target.into();
+  let activity = Move::new(&actor, &target, &context)?;
+
+  let mut inboxes = ActivitySendTargets::to_local_community_followers(actor.id);
+  if !target.local {
+    inboxes.add_inbox(target.shared_inbox_or_inbox());
+  }
+
+  send_lemmy_activity(&context, activity, &actor, inboxes, true).await
+}
@@ -0,0 +1,75 @@
+use crate::{generate_activity_id, protocol::moving::move_actor::Move};
+use activitypub_federation::{
+  config::Data,
+  kinds::{activity::MoveType, public},
+  protocol::verification::verify_urls_match,
+  traits::{Activity, Object},
+};
+use either::Either::{Left, Right};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_apub_objects::utils::{
+  functions::{apply_community_move, verify_community_move_target, verify_move_target},
+  protocol::Id,
+};
+use lemmy_db_schema::source::person::Person;
+use lemmy_utils::error::{LemmyError, LemmyResult};
+use url::Url;
+
+impl Move {
+  pub(in crate::moving) fn new<T: Object>(
+    actor: &T,
+    target: &T,
+    context: &Data<LemmyContext>,
+  ) -> LemmyResult<Move> {
+    Ok(Move {
+      actor: actor.id().clone().into(),
+      to: vec![public()],
+      object: actor.id().clone().into(),
+      target: target.id().clone().into(),
+      kind: MoveType::Move,
+      id: generate_activity_id(MoveType::Move, context)?,
+    })
+  }
+}
+
+#[async_trait::async_trait]
+impl Activity for Move {
+  type DataType = LemmyContext;
+  type Error = LemmyError;
+
+  fn id(&self) -> &Url {
+    &self.id
+  }
+
+  fn actor(&self) -> &Url {
+    self.actor.inner()
+  }
+
+  async fn verify(&self, _context: &Data<LemmyContext>) -> LemmyResult<()> {
+    // Accounts and communities can only move themselves
+    verify_urls_match(self.actor.inner(), self.object.inner())?;
+    Ok(())
+  }
+
+  async fn receive(self, context: &Data<LemmyContext>) -> LemmyResult<()> {
+    let target = self.target.inner().clone();
+    match self.object.dereference(context).await? {
+      Left(old_person) => {
+        let new_person = verify_move_target(&old_person, &target.into(), context).await?;
+        Person::apply_move(&mut context.pool(), old_person.id, &new_person).await?;
+      }
+      Right(old_community) => {
+        let new_community =
+          verify_community_move_target(&old_community, &target.into(), context).await?;
+        apply_community_move(&old_community, &new_community, context).await?;
+      }
+    }
+    Ok(())
+  }
+}
+
+impl Id for Move {
+  fn id(&self) -> &Url {
+    &self.id
+  }
+}
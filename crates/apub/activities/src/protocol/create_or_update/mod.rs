@@ -1,14 +1,18 @@
+pub mod modmail;
 pub mod note;
 pub(crate) mod note_wrapper;
 pub mod page;
+pub mod poll_vote;
 pub mod private_message;
 
 #[cfg(test)]
 mod tests {
   use super::note_wrapper::{CreateOrUpdateNoteWrapper, NoteWrapper};
   use crate::protocol::create_or_update::{
+    modmail::CreateModmailMessage,
     note::CreateOrUpdateNote,
     page::CreateOrUpdatePage,
+    poll_vote::CreatePollVote,
     private_message::CreateOrUpdatePrivateMessage,
   };
   use lemmy_apub_objects::utils::test::{test_json, test_parse_lemmy_item};
@@ -37,6 +41,18 @@ mod tests {
     test_json::<CreateOrUpdateNoteWrapper>(
       "../apub/assets/lemmy/activities/create_or_update/create_private_message_0.19.json",
     )?;
+    test_parse_lemmy_item::<CreatePollVote>(
+      "../apub/assets/lemmy/activities/create_or_update/create_poll_vote.json",
+    )?;
+    test_parse_lemmy_item::<CreateOrUpdateNoteWrapper>(
+      "../apub/assets/lemmy/activities/create_or_update/create_poll_vote.json",
+    )?;
+    test_parse_lemmy_item::<CreateModmailMessage>(
+      "../apub/assets/lemmy/activities/create_or_update/create_modmail_message.json",
+    )?;
+    test_parse_lemmy_item::<CreateOrUpdateNoteWrapper>(
+      "../apub/assets/lemmy/activities/create_or_update/create_modmail_message.json",
+    )?;
     test_parse_lemmy_item::<NoteWrapper>("../apub/assets/lemmy/objects/comment.json")?;
     test_parse_lemmy_item::<NoteWrapper>("../apub/assets/lemmy/objects/private_message.json")?;
     Ok(())
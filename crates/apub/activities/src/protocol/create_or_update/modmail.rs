@@ -0,0 +1,62 @@
+use activitypub_federation::{
+  fetch::object_id::ObjectId,
+  kinds::{activity::CreateType, object::NoteType},
+  protocol::{helpers::deserialize_one, values::MediaTypeHtml},
+};
+use chrono::{DateTime, Utc};
+use lemmy_apub_objects::{
+  objects::{comment::ApubComment, community::ApubCommunity, person::ApubPerson, post::ApubPost},
+  utils::protocol::Source,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use url::Url;
+
+/// A message in a modmail thread between a user and the mod team of a community. It is sent to the
+/// community, the user who opened the thread and all moderators, so that each of their instances
+/// has a copy of the thread. It is never announced to community followers.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateModmailMessage {
+  pub(crate) id: Url,
+  pub(crate) actor: ObjectId<ApubPerson>,
+  #[serde(deserialize_with = "deserialize_one")]
+  pub(crate) to: [ObjectId<ApubCommunity>; 1],
+  pub(crate) object: ModmailNote,
+  #[serde(rename = "type")]
+  pub(crate) kind: CreateType,
+}
+
+#[skip_serializing_none]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModmailNote {
+  #[serde(rename = "type")]
+  pub(crate) kind: NoteType,
+  pub(crate) id: Url,
+  pub(crate) attributed_to: ObjectId<ApubPerson>,
+  #[serde(deserialize_with = "deserialize_one")]
+  pub(crate) to: [ObjectId<ApubCommunity>; 1],
+  pub(crate) content: String,
+  pub(crate) media_type: Option<MediaTypeHtml>,
+  pub(crate) source: Option<Source>,
+  pub(crate) published: Option<DateTime<Utc>>,
+  /// Sent by one of the moderators on behalf of the mod team
+  pub(crate) from_mod_team: bool,
+  pub(crate) modmail_thread: ModmailThreadObject,
+}
+
+/// The thread which a modmail message belongs to. The thread is created by the instance of the user
+/// who opened it.
+#[skip_serializing_none]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModmailThreadObject {
+  pub(crate) id: Url,
+  /// The subject of the thread
+  pub(crate) name: String,
+  pub(crate) attributed_to: ObjectId<ApubPerson>,
+  pub(crate) post: Option<ObjectId<ApubPost>>,
+  pub(crate) comment: Option<ObjectId<ApubComment>>,
+  pub(crate) published: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,37 @@
+use activitypub_federation::{
+  fetch::object_id::ObjectId,
+  kinds::{activity::CreateType, object::NoteType},
+  protocol::helpers::deserialize_one,
+};
+use lemmy_apub_objects::objects::{person::ApubPerson, post::ApubPost};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// A vote in a poll, using the same format as Mastodon. It is sent directly to the poll creator,
+/// with one activity per selected choice.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePollVote {
+  pub(crate) id: Url,
+  pub(crate) actor: ObjectId<ApubPerson>,
+  #[serde(deserialize_with = "deserialize_one")]
+  pub(crate) to: [ObjectId<ApubPerson>; 1],
+  pub(crate) object: PollVoteNote,
+  #[serde(rename = "type")]
+  pub(crate) kind: CreateType,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PollVoteNote {
+  #[serde(rename = "type")]
+  pub(crate) kind: NoteType,
+  pub(crate) id: Url,
+  pub(crate) attributed_to: ObjectId<ApubPerson>,
+  #[serde(deserialize_with = "deserialize_one")]
+  pub(crate) to: [ObjectId<ApubPerson>; 1],
+  /// Name of the selected poll choice
+  pub(crate) name: String,
+  /// The poll being voted on
+  pub(crate) in_reply_to: ObjectId<ApubPost>,
+}
@@ -0,0 +1,16 @@
+pub mod move_actor;
+
+#[cfg(test)]
+mod tests {
+  use crate::protocol::moving::move_actor::Move;
+  use lemmy_apub_objects::utils::test::{test_json, test_parse_lemmy_item};
+  use lemmy_utils::error::LemmyResult;
+
+  #[test]
+  fn test_parse_lemmy_move() -> LemmyResult<()> {
+    test_parse_lemmy_item::<Move>("../apub/assets/lemmy/activities/moving/move.json")?;
+    test_parse_lemmy_item::<Move>("../apub/assets/lemmy/activities/moving/move_community.json")?;
+    test_json::<Move>("../apub/assets/mastodon/activities/move.json")?;
+    Ok(())
+  }
+}
@@ -0,0 +1,26 @@
+use activitypub_federation::{
+  fetch::object_id::ObjectId,
+  kinds::activity::MoveType,
+  protocol::helpers::deserialize_one_or_many,
+};
+use lemmy_apub_objects::objects::UserOrCommunity;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Moves an account or community to a new actor, which has to list the old one in `alsoKnownAs`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Move {
+  pub(crate) actor: ObjectId<UserOrCommunity>,
+  /// Optional, Mastodon doesn't include any recipients
+  #[serde(deserialize_with = "deserialize_one_or_many", default)]
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub(crate) to: Vec<Url>,
+  /// The old actor, same as actor
+  pub(crate) object: ObjectId<UserOrCommunity>,
+  /// The new actor, of the same type as the old one
+  pub(crate) target: ObjectId<UserOrCommunity>,
+  #[serde(rename = "type")]
+  pub(crate) kind: MoveType,
+  pub(crate) id: Url,
+}
@@ -1,15 +1,34 @@
-use crate::protocol::collections::group_followers::GroupFollowers;
+use crate::protocol::collections::{
+  group_followers::GroupFollowers,
+  group_followers_sync::GroupFollowersSync,
+};
 use activitypub_federation::{
   config::Data,
   kinds::collection::CollectionType,
-  protocol::verification::verify_domains_match,
+  protocol::verification::{verify_domains_match, verify_urls_match},
   traits::Collection,
 };
-use lemmy_api_utils::{context::LemmyContext, utils::generate_followers_url};
-use lemmy_apub_objects::objects::community::ApubCommunity;
-use lemmy_db_schema::source::community::Community;
+use actix_web::web::Bytes;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::generate_followers_url,
+};
+use lemmy_apub_objects::{
+  objects::community::ApubCommunity,
+  utils::collection_sync::{CollectionSynchronization, followers_digest},
+};
+use lemmy_db_schema::{
+  source::{
+    community::{Community, CommunityActions},
+    person::Person,
+  },
+  traits::{ApubActor, Followable},
+};
 use lemmy_db_views_community_follower::CommunityFollowerView;
-use lemmy_utils::error::LemmyError;
+use lemmy_db_views_site::SiteView;
+use lemmy_utils::error::{LemmyError, LemmyErrorType, LemmyResult};
+use std::collections::HashSet;
 use url::Url;
 
 #[derive(Clone, Debug)]
@@ -58,3 +77,74 @@ impl Collection for ApubCommunityFollower {
     Ok(ApubCommunityFollower(()))
   }
 }
+
+/// Compares the followers of a remote community from this instance with the digest sent by the
+/// community (FEP-8fcf). If they differ, fetches the partial followers collection and fixes the
+/// local follow state.
+pub(crate) async fn synchronize_followers(
+  sync: CollectionSynchronization,
+  community: ApubCommunity,
+  context: Data<LemmyContext>,
+) -> LemmyResult<()> {
+  // The header isn't signed, so only accept urls belonging to the community
+  let followers_url = community
+    .followers_url
+    .clone()
+    .ok_or(LemmyErrorType::NotFound)?;
+  verify_urls_match(followers_url.inner(), &sync.collection_id)?;
+  verify_domains_match(community.ap_id.inner(), &sync.url)?;
+
+  let pool = &mut context.pool();
+  let local_instance_id = SiteView::read_local(pool).await?.instance.id;
+  let local_followers =
+    CommunityFollowerView::list_follower_ids_from_instance(pool, community.id, local_instance_id)
+      .await?;
+  if followers_digest(local_followers.iter().map(|(_, ap_id)| ap_id)) == sync.digest {
+    return Ok(());
+  }
+
+  context.is_valid_ip(&sync.url).await?;
+  let req = context.client().get(sync.url.as_str());
+  let req = context.sign_request(req, Bytes::new()).await?;
+  let remote_followers: HashSet<Url> = context
+    .client()
+    .execute(req)
+    .await?
+    .error_for_status()?
+    .json::<GroupFollowersSync>()
+    .await?
+    .ordered_items
+    .into_iter()
+    .collect();
+
+  // The community doesn't know about these follows, so they are not in effect
+  for (person_id, ap_id) in &local_followers {
+    if !remote_followers.contains(ap_id.inner()) {
+      CommunityActions::unfollow(pool, *person_id, community.id).await?;
+    }
+  }
+
+  // These follows were removed here, so tell the community about it
+  let local_followers: HashSet<&Url> = local_followers.iter().map(|(_, a)| a.inner()).collect();
+  for ap_id in remote_followers
+    .iter()
+    .filter(|a| !local_followers.contains(a))
+  {
+    let Some(person) = Person::read_from_apub_id(pool, &ap_id.clone().into()).await? else {
+      continue;
+    };
+    // Skip follows which are still waiting for an accept
+    let pending = CommunityActions::read(pool, community.id, person.id)
+      .await
+      .ok()
+      .and_then(|a| a.followed_at)
+      .is_some();
+    if person.local && !pending {
+      ActivityChannel::submit_activity(
+        SendActivityData::FollowCommunity(community.0.clone(), person, false),
+        &context,
+      )?;
+    }
+  }
+  Ok(())
+}
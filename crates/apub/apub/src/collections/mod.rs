@@ -6,7 +6,7 @@ use crate::{
 use activitypub_federation::{
   actix_web::response::create_http_response,
   config::Data,
-  fetch::{collection_id::CollectionId, object_id::ObjectId},
+  fetch::{collection_id::CollectionId, fetch_object_http, object_id::ObjectId},
 };
 use actix_web::HttpResponse;
 use community_featured::ApubCommunityFeatured;
@@ -66,6 +66,22 @@ pub fn fetch_community_collections(
   });
 }
 
+pub fn import_community_history(community: ApubCommunity, context: Data<LemmyContext>) {
+  spawn_try_task(async move {
+    // The outbox url is not stored, so the group needs to be fetched again
+    let group: Group = fetch_object_http(community.ap_id.inner(), &context)
+      .await?
+      .object;
+    let outbox: CollectionId<ApubCommunityOutbox> = group.outbox.into();
+    outbox.dereference(&community, &context).await?;
+    if let Some(featured) = group.featured {
+      let featured: CollectionId<ApubCommunityFeatured> = featured.into();
+      featured.dereference(&community, &context).await?;
+    }
+    Ok(())
+  });
+}
+
 impl UrlCollection {
   pub(crate) async fn new_response(
     post: &Post,
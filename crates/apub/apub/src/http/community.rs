@@ -6,12 +6,14 @@ use crate::{
     community_moderators::ApubCommunityModerators,
     community_outbox::ApubCommunityOutbox,
   },
-  http::{check_community_fetchable, get_instance_id},
+  http::{actor_http_response, check_community_fetchable, check_fetch_authorized, get_instance_id},
+  protocol::collections::group_followers_sync::GroupFollowersSync,
 };
 use activitypub_federation::{
   actix_web::{response::create_http_response, signing_actor},
   config::Data,
   fetch::object_id::ObjectId,
+  kinds::collection::OrderedCollectionType,
   traits::{Collection, Object},
 };
 use actix_web::{
@@ -19,7 +21,7 @@ use actix_web::{
   HttpResponse,
   web::{Path, Query},
 };
-use lemmy_api_utils::context::LemmyContext;
+use lemmy_api_utils::{context::LemmyContext, utils::generate_followers_sync_url};
 use lemmy_apub_objects::{
   objects::{
     SiteOrMultiOrCommunityOrUser,
@@ -34,6 +36,7 @@ use lemmy_db_schema::{
   traits::ApubActor,
 };
 use lemmy_db_schema_file::enums::CommunityVisibility;
+use lemmy_db_views_community_follower::CommunityFollowerView;
 use lemmy_db_views_community_follower_approval::PendingFollowerView;
 use lemmy_utils::{
   FEDERATION_CONTEXT,
@@ -55,6 +58,7 @@ pub(crate) struct CommunityIsFollowerQuery {
 pub(crate) async fn get_apub_community_http(
   info: Path<CommunityPath>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> LemmyResult<HttpResponse> {
   let community: ApubCommunity =
     Community::read_from_name(&mut context.pool(), &info.community_name, None, true)
@@ -62,9 +66,11 @@ pub(crate) async fn get_apub_community_http(
       .ok_or(LemmyErrorType::NotFound)?
       .into();
 
-  check_community_fetchable(&community)?;
+  if !community.visibility.can_federate() {
+    return Err(LemmyErrorType::NotFound.into());
+  }
 
-  community.http_response(&FEDERATION_CONTEXT, &context).await
+  actor_http_response(community, &request, &context).await
 }
 
 /// Returns an empty followers collection, only populating the size (for privacy).
@@ -80,11 +86,46 @@ pub(crate) async fn get_apub_community_followers(
   if let Some(is_follower) = &query.is_follower {
     return check_is_follower(community, is_follower, context, request).await;
   }
-  check_community_fetchable(&community)?;
+  check_community_fetchable(&community, &request, &context).await?;
   let followers = ApubCommunityFollower::read_local(&community.into(), &context).await?;
   Ok(create_http_response(followers, &FEDERATION_CONTEXT)?)
 }
 
+/// Returns the followers from the instance of the signing actor, for followers collection
+/// synchronization (FEP-8fcf).
+pub(crate) async fn get_apub_community_followers_sync(
+  info: Path<CommunityPath>,
+  context: Data<LemmyContext>,
+  request: HttpRequest,
+) -> LemmyResult<HttpResponse> {
+  let community = Community::read_from_name(&mut context.pool(), &info.community_name, None, false)
+    .await?
+    .ok_or(LemmyErrorType::NotFound)?;
+  if !community.visibility.can_federate() {
+    return Err(LemmyErrorType::NotFound.into());
+  }
+  // Reuse the signature which was already checked for authorized fetch
+  let signing_actor = match check_fetch_authorized(&request, &context).await? {
+    Some(actor) => actor,
+    None => signing_actor::<SiteOrMultiOrCommunityOrUser>(&request, None, &context).await?,
+  };
+  let followers = CommunityFollowerView::list_follower_ids_from_instance(
+    &mut context.pool(),
+    community.id,
+    get_instance_id(&signing_actor),
+  )
+  .await?;
+  let collection = GroupFollowersSync {
+    id: generate_followers_sync_url(&community.ap_id)?.into(),
+    r#type: OrderedCollectionType::OrderedCollection,
+    ordered_items: followers
+      .into_iter()
+      .map(|(_, ap_id)| ap_id.into())
+      .collect(),
+  };
+  Ok(create_http_response(collection, &FEDERATION_CONTEXT)?)
+}
+
 /// Checks if a given actor follows the private community. Returns status 200 if true.
 async fn check_is_follower(
   community: Community,
@@ -139,13 +180,14 @@ pub(crate) async fn get_apub_community_outbox(
 pub(crate) async fn get_apub_community_moderators(
   info: Path<CommunityPath>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> LemmyResult<HttpResponse> {
   let community: ApubCommunity =
     Community::read_from_name(&mut context.pool(), &info.community_name, None, false)
       .await?
       .ok_or(LemmyErrorType::NotFound)?
       .into();
-  check_community_fetchable(&community)?;
+  check_community_fetchable(&community, &request, &context).await?;
   let moderators = ApubCommunityModerators::read_local(&community, &context).await?;
   Ok(create_http_response(moderators, &FEDERATION_CONTEXT)?)
 }
@@ -174,7 +216,9 @@ pub(crate) struct MultiCommunityQuery {
 pub(crate) async fn get_apub_person_multi_community(
   query: Path<MultiCommunityQuery>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> LemmyResult<HttpResponse> {
+  check_fetch_authorized(&request, &context).await?;
   let multi: ApubMultiCommunity =
     MultiCommunity::read_from_name(&mut context.pool(), &query.multi_name, None, false)
       .await?
@@ -187,7 +231,9 @@ pub(crate) async fn get_apub_person_multi_community(
 pub(crate) async fn get_apub_person_multi_community_follows(
   query: Path<MultiCommunityQuery>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> LemmyResult<HttpResponse> {
+  check_fetch_authorized(&request, &context).await?;
   let multi = MultiCommunity::read_from_name(&mut context.pool(), &query.multi_name, None, false)
     .await?
     .ok_or(LemmyErrorType::NotFound)?
@@ -207,6 +253,7 @@ pub(crate) struct CommunityTagPath {
 pub(crate) async fn get_apub_community_tag_http(
   info: Path<CommunityTagPath>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> LemmyResult<HttpResponse> {
   let community: ApubCommunity =
     Community::read_from_name(&mut context.pool(), &info.community_name, None, true)
@@ -214,7 +261,7 @@ pub(crate) async fn get_apub_community_tag_http(
       .ok_or(LemmyErrorType::NotFound)?
       .into();
 
-  check_community_fetchable(&community)?;
+  check_community_fetchable(&community, &request, &context).await?;
 
   let tag = CommunityTag::read_for_community(&mut context.pool(), community.id)
     .await?
@@ -236,6 +283,7 @@ pub(crate) mod tests {
   use lemmy_db_schema::{
     source::{
       community::CommunityInsertForm,
+      local_site::{LocalSite, LocalSiteUpdateForm},
       person::{Person, PersonInsertForm},
       post::{Post, PostInsertForm},
     },
@@ -288,11 +336,12 @@ pub(crate) mod tests {
     let query = CommunityPath {
       community_name: "asd".to_string(),
     };
-    let res = get_apub_community_http(query.into(), context.clone()).await;
+    let res = get_apub_community_http(query.into(), context.clone(), request.clone()).await;
     assert!(res.is_err());
 
     // fetch valid community
-    let res = get_apub_community_http(path.clone().into(), context.clone()).await?;
+    let res =
+      get_apub_community_http(path.clone().into(), context.clone(), request.clone()).await?;
     assert_eq!(200, res.status());
     let res_group: Group = decode_response(res).await?;
     let community: ApubCommunity = community.into();
@@ -307,7 +356,8 @@ pub(crate) mod tests {
       get_apub_community_followers(path.clone().into(), query, context.clone(), request.clone())
         .await?;
     assert_eq!(200, res.status());
-    let res = get_apub_community_moderators(path.clone().into(), context.clone()).await?;
+    let res =
+      get_apub_community_moderators(path.clone().into(), context.clone(), request.clone()).await?;
     assert_eq!(200, res.status());
     let res = get_apub_community_outbox(path, context.clone(), request).await?;
     assert_eq!(200, res.status());
@@ -324,7 +374,8 @@ pub(crate) mod tests {
     let request = TestRequest::default().to_http_request();
 
     // should return tombstone
-    let res = get_apub_community_http(path.clone().into(), context.clone()).await?;
+    let res =
+      get_apub_community_http(path.clone().into(), context.clone(), request.clone()).await?;
     assert_eq!(410, res.status());
     let res_tombstone = decode_response::<Tombstone>(res).await;
     assert!(res_tombstone.is_ok());
@@ -337,7 +388,8 @@ pub(crate) mod tests {
       get_apub_community_followers(path.clone().into(), query, context.clone(), request.clone())
         .await;
     assert!(res.is_err());
-    let res = get_apub_community_moderators(path.clone().into(), context.clone()).await;
+    let res =
+      get_apub_community_moderators(path.clone().into(), context.clone(), request.clone()).await;
     assert!(res.is_err());
     let res = get_apub_community_outbox(path, context.clone(), request).await;
     assert!(res.is_err());
@@ -353,7 +405,7 @@ pub(crate) mod tests {
     let (data, _, path) = init(false, CommunityVisibility::LocalOnlyPrivate, &context).await?;
     let request = TestRequest::default().to_http_request();
 
-    let res = get_apub_community_http(path.clone().into(), context.clone()).await;
+    let res = get_apub_community_http(path.clone().into(), context.clone(), request.clone()).await;
     assert!(res.is_err());
     let res =
       get_apub_community_featured(path.clone().into(), context.clone(), request.clone()).await;
@@ -363,8 +415,38 @@ pub(crate) mod tests {
       get_apub_community_followers(path.clone().into(), query, context.clone(), request.clone())
         .await;
     assert!(res.is_err());
-    let res = get_apub_community_moderators(path.clone().into(), context.clone()).await;
+    let res =
+      get_apub_community_moderators(path.clone().into(), context.clone(), request.clone()).await;
+    assert!(res.is_err());
+    let res = get_apub_community_outbox(path, context.clone(), request).await;
     assert!(res.is_err());
+
+    data.delete(&mut context.pool()).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_get_community_authorized_fetch() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let (data, _, path) = init(false, CommunityVisibility::Public, &context).await?;
+    let request = TestRequest::default().to_http_request();
+
+    let form = LocalSiteUpdateForm {
+      federation_authorized_fetch: Some(true),
+      ..Default::default()
+    };
+    LocalSite::update(&mut context.pool(), &form).await?;
+
+    // unsigned fetches only get the public key
+    let res =
+      get_apub_community_http(path.clone().into(), context.clone(), request.clone()).await?;
+    assert_eq!(200, res.status());
+    let json: serde_json::Value = decode_response(res).await?;
+    assert!(json.get("publicKey").is_some());
+    assert!(json.get("name").is_none());
+
+    // other unsigned fetches are rejected
     let res = get_apub_community_outbox(path, context.clone(), request).await;
     assert!(res.is_err());
 
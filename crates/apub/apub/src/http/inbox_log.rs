@@ -0,0 +1,89 @@
+use super::ReceiveState;
+use activitypub_federation::{
+  config::Data,
+  fetch::object_id::ObjectId,
+  protocol::verification::verify_domains_match,
+  traits::Activity,
+};
+use actix_web::HttpResponse;
+use futures::future::BoxFuture;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_apub_activities::activity_lists::SharedInboxActivities;
+use lemmy_apub_objects::objects::UserOrCommunity;
+use lemmy_db_schema::source::received_activity_log::{
+  ReceivedActivityLog,
+  ReceivedActivityLogInsertForm,
+};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+use serde_json::Value;
+use std::sync::atomic::Ordering;
+use tracing::warn;
+use url::Url;
+
+/// Stores an incoming activity together with the result of processing it, if the inbox log is
+/// enabled in the config.
+pub(super) async fn store_received_activity(
+  body: &[u8],
+  state: &ReceiveState,
+  result: &LemmyResult<HttpResponse>,
+  context: &Data<LemmyContext>,
+) {
+  let Some(config) = &context.settings().inbox_log else {
+    return;
+  };
+  let signature_verified = state.signature_verified.load(Ordering::Relaxed);
+  // Duplicate activities are rejected before processing, no need to log them again
+  if signature_verified && !state.is_new.load(Ordering::Relaxed) {
+    return;
+  }
+
+  // The activity may be invalid, so only read the fields which are needed for searching
+  let json: Option<Value> = serde_json::from_slice(body).ok();
+  let field = |name: &str| {
+    json
+      .as_ref()
+      .and_then(|j| j.get(name))
+      .and_then(|v| v.as_str().or_else(|| v.get("id").and_then(Value::as_str)))
+      .map(ToString::to_string)
+  };
+  let actor_id = field("actor");
+  let domain = actor_id
+    .as_ref()
+    .and_then(|a| Url::parse(a).ok())
+    .and_then(|a| a.domain().map(ToString::to_string));
+  let data =
+    (body.len() <= config.max_activity_size).then(|| String::from_utf8_lossy(body).into_owned());
+
+  let form = ReceivedActivityLogInsertForm {
+    ap_id: field("id"),
+    actor_id,
+    domain,
+    kind: field("type"),
+    data,
+    error: result.as_ref().err().map(ToString::to_string),
+    ..ReceivedActivityLogInsertForm::new(signature_verified, result.is_ok())
+  };
+  if let Err(e) = ReceivedActivityLog::create(&mut context.pool(), &form).await {
+    warn!("Failed to store received activity in inbox log: {e}");
+  }
+}
+
+/// Processes a logged activity again. The HTTP signature can't be checked a second time, so this
+/// must only be called for activities whose signature was verified when they were received.
+pub fn replay_received_activity(
+  data: String,
+  context: Data<LemmyContext>,
+) -> BoxFuture<'static, LemmyResult<()>> {
+  Box::pin(async move {
+    let activity: SharedInboxActivities = serde_json::from_str(&data)?;
+    verify_domains_match(activity.id(), activity.actor())?;
+    // Also rejects actors from blocked instances
+    ObjectId::<UserOrCommunity>::from(activity.actor().clone())
+      .dereference(&context)
+      .await?;
+    activity.verify(&context).await?;
+    activity.receive(&context).await?;
+    Ok(())
+  })
+}
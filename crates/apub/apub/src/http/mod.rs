@@ -1,3 +1,4 @@
+use crate::collections::community_follower::synchronize_followers;
 use activitypub_federation::{
   actix_web::{
     inbox::{ReceiveActivityHook, receive_activity_with_hook},
@@ -13,9 +14,17 @@ use actix_web::{
   web::{self, Bytes},
 };
 use either::Either;
+pub use inbox_log::replay_received_activity;
+use inbox_log::store_received_activity;
 use lemmy_api_utils::{context::LemmyContext, plugins::plugin_hook_after};
 use lemmy_apub_activities::activity_lists::SharedInboxActivities;
-use lemmy_apub_objects::objects::{SiteOrMultiOrCommunityOrUser, UserOrCommunity};
+use lemmy_apub_objects::{
+  objects::{SiteOrMultiOrCommunityOrUser, UserOrCommunity},
+  utils::{
+    collection_sync::{COLLECTION_SYNCHRONIZATION_HEADER, CollectionSynchronization},
+    functions::{check_apub_id_valid, local_site_data_cached},
+  },
+};
 use lemmy_db_schema::source::{
   activity::{ReceivedActivity, SentActivity},
   community::Community,
@@ -24,16 +33,22 @@ use lemmy_db_schema_file::{InstanceId, enums::CommunityVisibility};
 use lemmy_db_views_community_follower_approval::PendingFollowerView;
 use lemmy_utils::{
   FEDERATION_CONTEXT,
-  error::{LemmyErrorExt, LemmyErrorType, LemmyResult, UntranslatedError},
+  error::{LemmyError, LemmyErrorExt, LemmyErrorType, LemmyResult, UntranslatedError},
+  spawn_try_task,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+  sync::atomic::{AtomicBool, Ordering},
+  time::Duration,
 };
-use serde::Deserialize;
-use std::time::Duration;
 use tokio::time::timeout;
 use tracing::debug;
 use url::Url;
 
 mod comment;
 mod community;
+mod inbox_log;
+mod modmail;
 mod person;
 mod post;
 pub mod routes;
@@ -46,32 +61,73 @@ pub async fn shared_inbox(
   body: Bytes,
   data: Data<LemmyContext>,
 ) -> LemmyResult<HttpResponse> {
+  let state = ReceiveState::default();
+  let log_body = data.settings().inbox_log.is_some().then(|| body.clone());
+  let collection_sync = request
+    .headers()
+    .get(COLLECTION_SYNCHRONIZATION_HEADER)
+    .and_then(|h| h.to_str().ok())
+    .and_then(CollectionSynchronization::parse);
+  let hook = InboxHook {
+    state: &state,
+    collection_sync,
+  };
   let receive_fut =
     receive_activity_with_hook::<SharedInboxActivities, UserOrCommunity, LemmyContext>(
-      request, body, Dummy, &data,
+      request, body, hook, &data,
     );
   // Set a timeout shorter than `REQWEST_TIMEOUT` for processing incoming activities. This is to
   // avoid taking a long time to process an incoming activity when a required data fetch times out.
   // In this case our own instance would timeout and be marked as dead by the sender. Better to
   // consider the activity broken and move on.
-  timeout(INCOMING_ACTIVITY_TIMEOUT, receive_fut)
+  let res = timeout(INCOMING_ACTIVITY_TIMEOUT, receive_fut)
     .await
-    .with_lemmy_type(UntranslatedError::InboxTimeout.into())?
+    .with_lemmy_type(UntranslatedError::InboxTimeout.into())
+    .and_then(|r| r);
+  if let Some(body) = log_body {
+    store_received_activity(&body, &state, &res, &data).await;
+  }
+  res
 }
 
-struct Dummy;
+/// Tracks how far an incoming activity was processed, for the inbox log.
+#[derive(Default)]
+struct ReceiveState {
+  signature_verified: AtomicBool,
+  is_new: AtomicBool,
+}
+
+struct InboxHook<'a> {
+  state: &'a ReceiveState,
+  collection_sync: Option<CollectionSynchronization>,
+}
 
-impl ReceiveActivityHook<SharedInboxActivities, UserOrCommunity, LemmyContext> for Dummy {
+impl ReceiveActivityHook<SharedInboxActivities, UserOrCommunity, LemmyContext> for InboxHook<'_> {
   async fn hook(
     self,
     activity: &SharedInboxActivities,
-    _actor: &UserOrCommunity,
+    actor: &UserOrCommunity,
     context: &Data<LemmyContext>,
   ) -> LemmyResult<()> {
+    // The hook is only called after the HTTP signature was verified
+    self.state.signature_verified.store(true, Ordering::Relaxed);
+
     // Store received activities in the database. This ensures that the same activity doesn't get
     // received and processed more than once, which would be a waste of resources.
     debug!("Received activity {}", activity.id().to_string());
     ReceivedActivity::create(&mut context.pool(), &activity.id().clone().into()).await?;
+    self.state.is_new.store(true, Ordering::Relaxed);
+
+    // Check if our follows of the sending community are in sync (FEP-8fcf)
+    if let (Some(sync), Either::Right(community)) = (self.collection_sync, actor)
+      && !community.local
+    {
+      spawn_try_task(synchronize_followers(
+        sync,
+        community.clone(),
+        context.reset_request_count(),
+      ));
+    }
 
     // This could also take the actor as param, but lifetimes and serde derives are tricky.
     // It is really a before hook, but doesnt allow modifying the data. It could use a
@@ -94,7 +150,9 @@ struct ActivityQuery {
 async fn get_activity(
   info: web::Path<ActivityQuery>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> LemmyResult<HttpResponse> {
+  check_fetch_authorized(&request, &context).await?;
   let settings = context.settings();
   let activity_id = Url::parse(&format!(
     "{}/activities/{}/{}",
@@ -113,11 +171,67 @@ async fn get_activity(
   }
 }
 
+/// With authorized fetch enabled, objects are only served to requests with a valid HTTP signature
+/// from an instance which is allowed to federate with us. Returns the signing actor in that case.
+pub(in crate::http) async fn check_fetch_authorized(
+  request: &HttpRequest,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<Option<SiteOrMultiOrCommunityOrUser>> {
+  let local_site_data = local_site_data_cached(&mut context.pool()).await?;
+  if !local_site_data.authorized_fetch() {
+    return Ok(None);
+  }
+  let signing_actor = signing_actor::<SiteOrMultiOrCommunityOrUser>(request, None, context).await?;
+  check_apub_id_valid(signing_actor.id(), &local_site_data)?;
+  Ok(Some(signing_actor))
+}
+
+/// Fields of a local actor which are served to unsigned fetches with authorized fetch. Other
+/// instances fetch the public key without signature, and need it to verify our signatures.
+const KEY_ONLY_ACTOR_FIELDS: [&str; 7] = [
+  "type",
+  "id",
+  "preferredUsername",
+  "inbox",
+  "outbox",
+  "endpoints",
+  "publicKey",
+];
+
+/// Return the ActivityPub json representation of a local person or community, or only its
+/// public key if the fetch is unsigned and authorized fetch is enabled.
+async fn actor_http_response<T>(
+  actor: T,
+  request: &HttpRequest,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<HttpResponse>
+where
+  T: Object<DataType = LemmyContext, Error = LemmyError> + Send,
+  T::Kind: Serialize + Send,
+{
+  let local_site_data = local_site_data_cached(&mut context.pool()).await?;
+  let is_signed = request.headers().contains_key("signature");
+  if local_site_data.authorized_fetch() && !is_signed && !actor.is_deleted() {
+    let mut json = serde_json::to_value(actor.into_json(context).await?)?;
+    if let Some(fields) = json.as_object_mut() {
+      fields.retain(|k, _| KEY_ONLY_ACTOR_FIELDS.contains(&k.as_str()));
+    }
+    return Ok(create_http_response(json, &FEDERATION_CONTEXT)?);
+  }
+  check_fetch_authorized(request, context).await?;
+  actor.http_response(&FEDERATION_CONTEXT, context).await
+}
+
 /// Ensure that the community is public and not removed/deleted.
-fn check_community_fetchable(community: &Community) -> LemmyResult<()> {
+async fn check_community_fetchable(
+  community: &Community,
+  request: &HttpRequest,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
   if !community.visibility.can_federate() {
     return Err(LemmyErrorType::NotFound.into());
   }
+  check_fetch_authorized(request, context).await?;
   Ok(())
 }
 
@@ -128,11 +242,15 @@ async fn check_community_content_fetchable(
   context: &Data<LemmyContext>,
 ) -> LemmyResult<()> {
   use CommunityVisibility::*;
+  let authorized_actor = check_fetch_authorized(request, context).await?;
   match community.visibility {
     Public | Unlisted => Ok(()),
     Private => {
-      let signing_actor =
-        signing_actor::<SiteOrMultiOrCommunityOrUser>(request, None, context).await?;
+      // Reuse the signature which was already checked for authorized fetch
+      let signing_actor = match authorized_actor {
+        Some(actor) => actor,
+        None => signing_actor::<SiteOrMultiOrCommunityOrUser>(request, None, context).await?,
+      };
       if community.local {
         Ok(
           PendingFollowerView::check_has_followers_from_instance(
@@ -0,0 +1,87 @@
+use super::get_instance_id;
+use activitypub_federation::{
+  actix_web::{response::create_http_response, signing_actor},
+  config::Data,
+  traits::Object,
+};
+use actix_web::{HttpRequest, HttpResponse, web::Path};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_apub_activities::create_or_update::modmail::{
+  modmail_note,
+  modmail_thread_instances,
+  modmail_thread_object,
+};
+use lemmy_apub_objects::{
+  objects::SiteOrMultiOrCommunityOrUser,
+  utils::functions::{check_apub_id_valid, local_site_data_cached},
+};
+use lemmy_db_schema::{
+  newtypes::{ModmailMessageId, ModmailThreadId},
+  source::modmail::{ModmailMessage, ModmailThread},
+};
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{
+  FEDERATION_CONTEXT,
+  error::{LemmyErrorType, LemmyResult},
+};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub(crate) struct ModmailThreadQuery {
+  thread_id: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ModmailMessageQuery {
+  message_id: String,
+}
+
+/// Return the ActivityPub json representation of a local modmail thread over HTTP.
+pub(crate) async fn get_apub_modmail_thread(
+  info: Path<ModmailThreadQuery>,
+  context: Data<LemmyContext>,
+  request: HttpRequest,
+) -> LemmyResult<HttpResponse> {
+  let id = ModmailThreadId(info.thread_id.parse::<i32>()?);
+  let thread = ModmailThread::read(&mut context.pool(), id).await?;
+  check_modmail_fetchable(&thread, &request, &context).await?;
+  let object = modmail_thread_object(&thread, &context).await?;
+  Ok(create_http_response(object, &FEDERATION_CONTEXT)?)
+}
+
+/// Return the ActivityPub json representation of a local modmail message over HTTP.
+pub(crate) async fn get_apub_modmail_message(
+  info: Path<ModmailMessageQuery>,
+  context: Data<LemmyContext>,
+  request: HttpRequest,
+) -> LemmyResult<HttpResponse> {
+  let id = ModmailMessageId(info.message_id.parse::<i32>()?);
+  let message = ModmailMessage::read(&mut context.pool(), id).await?;
+  if !message.local {
+    return Err(LemmyErrorType::NotFound.into());
+  }
+  let thread = ModmailThread::read(&mut context.pool(), message.thread_id).await?;
+  check_modmail_fetchable(&thread, &request, &context).await?;
+  let object = modmail_note(&thread, message, &context).await?;
+  Ok(create_http_response(object, &FEDERATION_CONTEXT)?)
+}
+
+/// Modmail is private, so it can only be fetched with a signed request from an instance which takes
+/// part in the thread, regardless of the authorized fetch setting.
+async fn check_modmail_fetchable(
+  thread: &ModmailThread,
+  request: &HttpRequest,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  if !thread.local {
+    return Err(LemmyErrorType::NotFound.into());
+  }
+  let signing_actor = signing_actor::<SiteOrMultiOrCommunityOrUser>(request, None, context).await?;
+  let local_site_data = local_site_data_cached(&mut context.pool()).await?;
+  check_apub_id_valid(signing_actor.id(), &local_site_data)?;
+  let instances = modmail_thread_instances(thread, context).await?;
+  if !instances.contains(&get_instance_id(&signing_actor)) {
+    return Err(LemmyErrorType::NotFound.into());
+  }
+  Ok(())
+}
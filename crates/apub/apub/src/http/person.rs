@@ -1,13 +1,11 @@
+use super::{actor_http_response, check_fetch_authorized};
 use crate::protocol::collections::url_collection::UrlCollection;
-use activitypub_federation::{config::Data, traits::Object};
-use actix_web::{HttpResponse, web::Path};
+use activitypub_federation::config::Data;
+use actix_web::{HttpRequest, HttpResponse, web::Path};
 use lemmy_api_utils::{context::LemmyContext, utils::generate_outbox_url};
 use lemmy_apub_objects::objects::person::ApubPerson;
 use lemmy_db_schema::{source::person::Person, traits::ApubActor};
-use lemmy_utils::{
-  FEDERATION_CONTEXT,
-  error::{LemmyErrorType, LemmyResult},
-};
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
 use serde::Deserialize;
 
 #[derive(Deserialize)]
@@ -19,6 +17,7 @@ pub(crate) struct PersonQuery {
 pub(crate) async fn get_apub_person_http(
   info: Path<PersonQuery>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> LemmyResult<HttpResponse> {
   let user_name = info.into_inner().user_name;
   // This needs to be able to read deleted persons, so that it can send tombstones
@@ -27,13 +26,15 @@ pub(crate) async fn get_apub_person_http(
     .ok_or(LemmyErrorType::NotFound)?
     .into();
 
-  person.http_response(&FEDERATION_CONTEXT, &context).await
+  actor_http_response(person, &request, &context).await
 }
 
 pub(crate) async fn get_apub_person_outbox(
   info: Path<PersonQuery>,
   context: Data<LemmyContext>,
+  request: HttpRequest,
 ) -> LemmyResult<HttpResponse> {
+  check_fetch_authorized(&request, &context).await?;
   let person = Person::read_from_name(&mut context.pool(), &info.user_name, None, false)
     .await?
     .ok_or(LemmyErrorType::NotFound)?;
@@ -3,6 +3,7 @@ use crate::http::{
   community::{
     get_apub_community_featured,
     get_apub_community_followers,
+    get_apub_community_followers_sync,
     get_apub_community_http,
     get_apub_community_moderators,
     get_apub_community_outbox,
@@ -11,6 +12,7 @@ use crate::http::{
     get_apub_person_multi_community_follows,
   },
   get_activity,
+  modmail::{get_apub_modmail_message, get_apub_modmail_thread},
   person::{get_apub_person_http, get_apub_person_outbox},
   post::{get_apub_post, get_apub_post_context},
   shared_inbox,
@@ -34,6 +36,10 @@ pub fn config(cfg: &mut web::ServiceConfig) {
       "/c/{community_name}/followers",
       web::get().to(get_apub_community_followers),
     )
+    .route(
+      "/c/{community_name}/followers_synchronization",
+      web::get().to(get_apub_community_followers_sync),
+    )
     .route(
       "/c/{community_name}/outbox",
       web::get().to(get_apub_community_outbox),
@@ -73,6 +79,14 @@ pub fn config(cfg: &mut web::ServiceConfig) {
       "/comment/{comment_id}/context",
       web::get().to(get_apub_comment_context),
     )
+    .route(
+      "/modmail/{thread_id}",
+      web::get().to(get_apub_modmail_thread),
+    )
+    .route(
+      "/modmail_message/{message_id}",
+      web::get().to(get_apub_modmail_message),
+    )
     .route("/activities/{type_}/{id}", web::get().to(get_activity));
 
   cfg.service(
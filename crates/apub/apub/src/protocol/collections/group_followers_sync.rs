@@ -0,0 +1,13 @@
+use activitypub_federation::kinds::collection::OrderedCollectionType;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Partial followers collection for FEP-8fcf, which only contains the followers from a single
+/// instance.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GroupFollowersSync {
+  pub(crate) id: Url,
+  pub(crate) r#type: OrderedCollectionType,
+  pub(crate) ordered_items: Vec<Url>,
+}
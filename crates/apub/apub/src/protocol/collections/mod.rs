@@ -1,5 +1,6 @@
 pub(crate) mod group_featured;
 pub(crate) mod group_followers;
+pub(crate) mod group_followers_sync;
 pub(crate) mod group_moderators;
 pub(crate) mod group_outbox;
 pub mod url_collection;
@@ -10,6 +11,7 @@ mod tests {
   use crate::protocol::collections::{
     group_featured::GroupFeatured,
     group_followers::GroupFollowers,
+    group_followers_sync::GroupFollowersSync,
     group_moderators::GroupModerators,
     group_outbox::GroupOutbox,
     url_collection::UrlCollection,
@@ -21,6 +23,9 @@ mod tests {
   #[test]
   fn test_parse_lemmy_collections() -> LemmyResult<()> {
     test_parse_lemmy_item::<GroupFollowers>("assets/lemmy/collections/group_followers.json")?;
+    test_parse_lemmy_item::<GroupFollowersSync>(
+      "assets/lemmy/collections/group_followers_sync.json",
+    )?;
     let outbox =
       test_parse_lemmy_item::<GroupOutbox>("assets/lemmy/collections/group_outbox.json")?;
     assert_eq!(outbox.ordered_items.len(), outbox.total_items as usize);
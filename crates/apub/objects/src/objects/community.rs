@@ -59,6 +59,11 @@ pub static FETCH_COMMUNITY_COLLECTIONS: OnceLock<
   fn(ApubCommunity, Group, Data<LemmyContext>) -> (),
 > = OnceLock::new();
 
+/// Fetches the outbox and featured posts of a community which moved to a local community, so
+/// that its history is kept after the old instance goes offline.
+pub static IMPORT_COMMUNITY_HISTORY: OnceLock<fn(ApubCommunity, Data<LemmyContext>) -> ()> =
+  OnceLock::new();
+
 #[derive(Clone, Debug)]
 pub struct ApubCommunity(pub Community);
 
@@ -149,6 +154,13 @@ impl Object for ApubCommunity {
         .into_iter()
         .map(ApubCommunityTag::to_json)
         .collect(),
+      also_known_as: self
+        .also_known_as
+        .iter()
+        .flatten()
+        .map(|u| u.clone().into())
+        .collect(),
+      moved_to: self.moved_to.clone().map(Into::into),
     };
     Ok(group)
   }
@@ -233,6 +245,15 @@ impl Object for ApubCommunity {
       featured_url: group.featured.clone().clone().map(Into::into),
       title,
       visibility,
+      also_known_as: Some(
+        group
+          .also_known_as
+          .iter()
+          .map(|u| Some(u.clone().into()))
+          .collect(),
+      ),
+      // Only set when a Move activity is received, after checking that the new community agrees
+      moved_to: None,
       ..CommunityInsertForm::new(instance_id, name, group.public_key.public_key_pem.clone())
     };
     let languages =
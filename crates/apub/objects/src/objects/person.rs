@@ -122,6 +122,13 @@ impl Object for ApubPerson {
       public_key: self.public_key(),
       updated: self.updated_at,
       inbox: self.inbox_url.clone().into(),
+      also_known_as: self
+        .also_known_as
+        .iter()
+        .flatten()
+        .map(|u| u.clone().into())
+        .collect(),
+      moved_to: self.moved_to.clone().map(Into::into),
     };
     Ok(person)
   }
@@ -181,6 +188,15 @@ impl Object for ApubPerson {
       ),
       matrix_user_id: person.matrix_user_id,
       instance_id,
+      also_known_as: Some(
+        person
+          .also_known_as
+          .into_iter()
+          .map(|u| Some(u.into()))
+          .collect(),
+      ),
+      // Only set when a Move activity is received, after checking that the new account agrees
+      moved_to: None,
     };
     let person = DbPerson::upsert(&mut context.pool(), &person_form).await?;
 
@@ -1,6 +1,6 @@
 use crate::{
   protocol::{
-    page::{Attachment, Page, PageType},
+    page::{Attachment, Page, PageType, PollOption, PollOptionReplies},
     tags::{ApubCommunityTag, ApubTag, Hashtag, HashtagType},
   },
   utils::{
@@ -45,6 +45,7 @@ use lemmy_db_schema::source::{
   community_tag::CommunityTag,
   local_site::LocalSite,
   person::Person,
+  poll::{Poll, PollChoice, PollChoiceInsertForm, PollInsertForm},
   post::{Post, PostInsertForm, PostUpdateForm},
 };
 use lemmy_db_views_community_moderator::CommunityModeratorView;
@@ -157,8 +158,35 @@ impl Object for ApubPost {
     let maa = collect_non_local_mentions(self.body.as_deref(), None, &community, context).await?;
     tags.extend(maa.mentions);
 
+    let poll = Poll::read(&mut context.pool(), self.id).await?;
+    let kind = if poll.is_some() {
+      PageType::Question
+    } else {
+      PageType::Page
+    };
+    let (mut one_of, mut any_of) = (None, None);
+    if let Some(poll) = &poll {
+      let options = PollChoice::read_for_post(&mut context.pool(), self.id)
+        .await?
+        .into_iter()
+        .map(|c| PollOption {
+          kind: Default::default(),
+          name: c.name,
+          replies: PollOptionReplies {
+            kind: Default::default(),
+            total_items: c.vote_count,
+          },
+        })
+        .collect();
+      if poll.multiple_choice {
+        any_of = Some(options);
+      } else {
+        one_of = Some(options);
+      }
+    }
+
     let page = Page {
-      kind: PageType::Page,
+      kind,
       id: self.ap_id.clone().into(),
       attributed_to: AttributedTo::Lemmy(creator.ap_id.into()),
       to: generate_to(&community)?,
@@ -177,6 +205,14 @@ impl Object for ApubPost {
       in_reply_to: None,
       tag: tags,
       context: Some(context_url(&self.ap_id)),
+      one_of,
+      any_of,
+      end_time: poll.as_ref().and_then(|p| p.end_at),
+      closed: poll
+        .as_ref()
+        .filter(|p| p.is_closed())
+        .and_then(|p| p.end_at),
+      voters_count: poll.map(|p| p.voter_count),
     };
     Ok(page)
   }
@@ -310,6 +346,7 @@ impl Object for ApubPost {
     plugin_hook_after("federated_post_after_receive", &post);
 
     update_apub_post_tags(&page, &post, context).await?;
+    update_apub_post_poll(&page, &post, context).await?;
 
     let post_ = post.clone();
     let context_ = context.clone();
@@ -349,6 +386,42 @@ pub async fn update_apub_post_tags(
   Ok(())
 }
 
+/// Stores the poll of a remote post, including its current vote counts. Local polls are only
+/// changed through votes.
+async fn update_apub_post_poll(
+  page: &Page,
+  post: &Post,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let (options, multiple_choice) = match (&page.one_of, &page.any_of) {
+    (Some(one_of), _) => (one_of, false),
+    (None, Some(any_of)) => (any_of, true),
+    (None, None) => return Ok(()),
+  };
+  if post.local {
+    return Ok(());
+  }
+  // Choices are unique by name, which may collide after truncation. Only the first one is kept, so
+  // that the upsert doesn't affect the same row twice.
+  let mut names = HashSet::new();
+  let choices = options
+    .iter()
+    .map(|o| PollChoiceInsertForm {
+      vote_count: Some(o.replies.total_items),
+      ..PollChoiceInsertForm::new(post.id, truncate_for_db(&o.name, MAX_TITLE_LENGTH))
+    })
+    .filter(|c| names.insert(c.name.clone()))
+    .collect::<Vec<_>>();
+  let form = PollInsertForm {
+    multiple_choice: Some(multiple_choice),
+    end_at: page.end_time.or(page.closed),
+    voter_count: page.voters_count,
+    ..PollInsertForm::new(post.id)
+  };
+  Poll::upsert_apub(&mut context.pool(), &form, &choices).await?;
+  Ok(())
+}
+
 pub async fn append_attachments_to_body(
   content: &Option<String>,
   attachments: &[Attachment],
@@ -432,6 +505,66 @@ mod tests {
     Ok(())
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn test_parse_lemmy_question() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let test_data = TestData::create(&mut context.pool()).await?;
+    parse_lemmy_person(&context).await?;
+    parse_lemmy_community(&context).await?;
+
+    let json = file_to_json_object("../apub/assets/lemmy/objects/question.json")?;
+    let url = Url::parse("https://enterprise.lemmy.ml/post/55144")?;
+    ApubPost::verify(&json, &url, &context).await?;
+    let post = ApubPost::from_json(json, &context).await?;
+
+    let poll = Poll::read(&mut context.pool(), post.id).await?;
+    assert_eq!(Some(8), poll.as_ref().map(|p| p.voter_count));
+    assert!(poll.is_some_and(|p| !p.multiple_choice && p.is_closed()));
+    let choices = PollChoice::read_for_post(&mut context.pool(), post.id)
+      .await?
+      .into_iter()
+      .map(|c| (c.name, c.vote_count))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      vec![("Picard".to_string(), 5), ("Sisko".to_string(), 3)],
+      choices
+    );
+
+    test_data.delete(&mut context.pool()).await?;
+    Instance::delete_all(&mut context.pool()).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_parse_question_duplicate_truncated_choices() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let test_data = TestData::create(&mut context.pool()).await?;
+    parse_lemmy_person(&context).await?;
+    parse_lemmy_community(&context).await?;
+
+    let mut json: Page = file_to_json_object("../apub/assets/lemmy/objects/question.json")?;
+    let prefix = "a".repeat(MAX_TITLE_LENGTH);
+    for option in json.one_of.iter_mut().flatten() {
+      option.name = format!("{prefix}{}", option.name);
+    }
+    let url = Url::parse("https://enterprise.lemmy.ml/post/55144")?;
+    ApubPost::verify(&json, &url, &context).await?;
+    let post = ApubPost::from_json(json, &context).await?;
+
+    let choices = PollChoice::read_for_post(&mut context.pool(), post.id)
+      .await?
+      .into_iter()
+      .map(|c| (c.name, c.vote_count))
+      .collect::<Vec<_>>();
+    assert_eq!(vec![(prefix, 5)], choices);
+
+    test_data.delete(&mut context.pool()).await?;
+    Instance::delete_all(&mut context.pool()).await?;
+    Ok(())
+  }
+
   #[tokio::test]
   #[serial]
   async fn test_parse_post_with_image_and_link() -> LemmyResult<()> {
@@ -7,7 +7,7 @@ use activitypub_federation::{
   fetch::object_id::ObjectId,
   kinds::actor::GroupType,
   protocol::{
-    helpers::{deserialize_last, deserialize_skip_error},
+    helpers::{deserialize_last, deserialize_one_or_many, deserialize_skip_error},
     public_key::PublicKey,
     values::MediaTypeHtml,
   },
@@ -64,4 +64,11 @@ pub struct Group {
   pub(crate) discoverable: Option<bool>,
   #[serde(deserialize_with = "deserialize_skip_error", default)]
   pub(crate) tag: Vec<ApubCommunityTag>,
+  /// Previous identities of the community, which are allowed to move here
+  #[serde(deserialize_with = "deserialize_one_or_many", default)]
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub(crate) also_known_as: Vec<Url>,
+  /// New community after the community was moved
+  #[serde(deserialize_with = "deserialize_skip_error", default)]
+  pub(crate) moved_to: Option<Url>,
 }
@@ -27,6 +27,7 @@ mod tests {
     test_parse_lemmy_item::<Group>("../apub/assets/lemmy/objects/group.json")?;
     test_parse_lemmy_item::<Person>("../apub/assets/lemmy/objects/person.json")?;
     test_parse_lemmy_item::<Page>("../apub/assets/lemmy/objects/page.json")?;
+    test_parse_lemmy_item::<Page>("../apub/assets/lemmy/objects/question.json")?;
     test_parse_lemmy_item::<Note>("../apub/assets/lemmy/objects/comment.json")?;
     test_parse_lemmy_item::<PrivateMessage>("../apub/assets/lemmy/objects/private_message.json")?;
     test_parse_lemmy_item::<Tombstone>("../apub/assets/lemmy/objects/tombstone.json")?;
@@ -14,8 +14,9 @@ use activitypub_federation::{
   config::Data,
   fetch::object_id::ObjectId,
   kinds::{
+    collection::CollectionType,
     link::LinkType,
-    object::{DocumentType, ImageType},
+    object::{DocumentType, ImageType, NoteType},
   },
   protocol::{
     helpers::{deserialize_one_or_many, deserialize_skip_error},
@@ -40,6 +41,8 @@ pub enum PageType {
   Note,
   Video,
   Event,
+  /// A post with a poll
+  Question,
 }
 
 #[skip_serializing_none]
@@ -78,6 +81,31 @@ pub struct Page {
   #[serde(deserialize_with = "deserialize_skip_error", default)]
   pub tag: Vec<ApubTag>,
   pub(crate) context: Option<String>,
+  /// Choices of a poll where only one can be selected.
+  pub(crate) one_of: Option<Vec<PollOption>>,
+  /// Choices of a poll where multiple can be selected.
+  pub(crate) any_of: Option<Vec<PollOption>>,
+  pub(crate) end_time: Option<DateTime<Utc>>,
+  pub(crate) closed: Option<DateTime<Utc>>,
+  pub(crate) voters_count: Option<i32>,
+}
+
+/// A poll choice, in the format used by Mastodon.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PollOption {
+  #[serde(rename = "type")]
+  pub(crate) kind: NoteType,
+  pub(crate) name: String,
+  pub(crate) replies: PollOptionReplies,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PollOptionReplies {
+  #[serde(rename = "type")]
+  pub(crate) kind: CollectionType,
+  pub(crate) total_items: i32,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
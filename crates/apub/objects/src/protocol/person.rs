@@ -5,7 +5,7 @@ use crate::{
 use activitypub_federation::{
   fetch::object_id::ObjectId,
   protocol::{
-    helpers::{deserialize_last, deserialize_skip_error},
+    helpers::{deserialize_last, deserialize_one_or_many, deserialize_skip_error},
     public_key::PublicKey,
   },
 };
@@ -50,4 +50,11 @@ pub struct Person {
   pub(crate) endpoints: Option<Endpoints>,
   pub(crate) published: Option<DateTime<Utc>>,
   pub(crate) updated: Option<DateTime<Utc>>,
+  /// Other accounts of the same person, which are allowed to move to this account
+  #[serde(deserialize_with = "deserialize_one_or_many", default)]
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub(crate) also_known_as: Vec<Url>,
+  /// New account after the account was moved
+  #[serde(deserialize_with = "deserialize_skip_error", default)]
+  pub(crate) moved_to: Option<Url>,
 }
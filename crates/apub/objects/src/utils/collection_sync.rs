@@ -0,0 +1,101 @@
+//! Followers collection synchronization, as described in
+//! <https://codeberg.org/fediverse/fep/src/branch/main/fep/8fcf/fep-8fcf.md>
+
+use lemmy_diesel_utils::dburl::DbUrl;
+use sha2::{Digest, Sha256};
+use std::fmt::{Display, Formatter};
+use url::Url;
+
+pub const COLLECTION_SYNCHRONIZATION_HEADER: &str = "Collection-Synchronization";
+
+/// Value of the `Collection-Synchronization` header which is attached to activities sent by a
+/// community.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CollectionSynchronization {
+  /// Followers collection of the community
+  pub collection_id: Url,
+  /// Partial followers collection, which only contains followers from the receiving instance
+  pub url: Url,
+  /// Digest of the partial followers collection, see [followers_digest]
+  pub digest: String,
+}
+
+impl CollectionSynchronization {
+  /// Parses the header value, which uses the same syntax as HTTP signature parameters. Returns None
+  /// if the value is invalid.
+  pub fn parse(value: &str) -> Option<Self> {
+    let (mut collection_id, mut url, mut digest) = (None, None, None);
+    for param in value.split(',') {
+      let (key, val) = param.trim().split_once('=')?;
+      let val = val.trim_matches('"');
+      match key {
+        "collectionId" => collection_id = Url::parse(val).ok(),
+        "url" => url = Url::parse(val).ok(),
+        "digest" => digest = Some(val.to_string()),
+        _ => {}
+      }
+    }
+    Some(Self {
+      collection_id: collection_id?,
+      url: url?,
+      digest: digest?,
+    })
+  }
+}
+
+impl Display for CollectionSynchronization {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "collectionId=\"{}\", url=\"{}\", digest=\"{}\"",
+      self.collection_id, self.url, self.digest
+    )
+  }
+}
+
+/// Combines the SHA256 hashes of all follower ids with XOR, so that the order doesn't matter.
+pub fn followers_digest<'a>(followers: impl IntoIterator<Item = &'a DbUrl>) -> String {
+  let mut digest = [0u8; 32];
+  for follower in followers {
+    let hash = Sha256::digest(follower.as_str().as_bytes());
+    for (d, h) in digest.iter_mut().zip(hash) {
+      *d ^= h;
+    }
+  }
+  hex::encode(digest)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_parse_collection_synchronization() -> LemmyResult<()> {
+    let sync = CollectionSynchronization {
+      collection_id: Url::parse("https://lemmy.ml/c/asklemmy/followers")?,
+      url: Url::parse("https://lemmy.ml/c/asklemmy/followers_synchronization")?,
+      digest: followers_digest([]),
+    };
+    assert_eq!(
+      Some(sync.clone()),
+      CollectionSynchronization::parse(&sync.to_string())
+    );
+    assert_eq!(None, CollectionSynchronization::parse("digest=\"abc\""));
+    Ok(())
+  }
+
+  #[test]
+  fn test_followers_digest() -> LemmyResult<()> {
+    let alice: DbUrl = Url::parse("https://example.com/u/alice")?.into();
+    let bob: DbUrl = Url::parse("https://example.com/u/bob")?.into();
+    assert_eq!(
+      followers_digest([&alice, &bob]),
+      followers_digest([&bob, &alice])
+    );
+    assert_ne!(followers_digest([&alice]), followers_digest([&alice, &bob]));
+    assert_eq!("0".repeat(64), followers_digest([]));
+    Ok(())
+  }
+}
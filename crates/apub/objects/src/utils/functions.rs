@@ -10,8 +10,13 @@ use activitypub_federation::{
   protocol::values::MediaTypeMarkdownOrHtml,
 };
 use either::Either;
+use futures::future::BoxFuture;
 use html2md::parse_html;
-use lemmy_api_utils::{context::LemmyContext, utils::check_is_mod_or_admin};
+use lemmy_api_utils::{
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::check_is_mod_or_admin,
+};
 use lemmy_db_schema::source::{
   community::Community,
   instance::{Instance, InstanceActions},
@@ -24,12 +29,19 @@ use lemmy_diesel_utils::connection::DbPool;
 use lemmy_utils::{
   CACHE_DURATION_FEDERATION,
   CacheLock,
-  error::{LemmyError, LemmyResult, UntranslatedError},
+  error::{LemmyError, LemmyErrorType, LemmyResult, UntranslatedError},
 };
 use moka::future::Cache;
-use std::sync::{Arc, LazyLock};
+use std::sync::{Arc, LazyLock, OnceLock};
 use url::Url;
 
+type ReplayReceivedActivityFn =
+  fn(String, Data<LemmyContext>) -> BoxFuture<'static, LemmyResult<()>>;
+
+/// Processes the json of a logged incoming activity again. The activity types are not available
+/// in this crate, so the function is set on startup.
+pub static REPLAY_RECEIVED_ACTIVITY: OnceLock<ReplayReceivedActivityFn> = OnceLock::new();
+
 pub fn read_from_string_or_source(
   content: &str,
   media_type: &Option<MediaTypeMarkdownOrHtml>,
@@ -64,6 +76,15 @@ pub struct LocalSiteData {
   blocked_instances: Vec<Instance>,
 }
 
+impl LocalSiteData {
+  pub fn authorized_fetch(&self) -> bool {
+    self
+      .local_site
+      .as_ref()
+      .is_some_and(|l| l.federation_authorized_fetch)
+  }
+}
+
 pub async fn local_site_data_cached(pool: &mut DbPool<'_>) -> LemmyResult<Arc<LocalSiteData>> {
   // All incoming and outgoing federation actions read the blocklist/allowlist and slur filters
   // multiple times. This causes a huge number of database reads if we hit the db directly. So we
@@ -231,6 +252,76 @@ pub async fn verify_person_in_site_or_community(
   Ok(())
 }
 
+/// Fetches the new account of an account move from its instance, and checks that it lists the old
+/// account in `alsoKnownAs`. Neither account may have moved already.
+pub async fn verify_move_target(
+  old_person: &ApubPerson,
+  target: &ObjectId<ApubPerson>,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<ApubPerson> {
+  if old_person.moved_to.is_some() || target.inner() == old_person.ap_id.inner() {
+    return Err(LemmyErrorType::InvalidAccountMove.into());
+  }
+  // Always fetch the new account, because the alias may have been added just before the move
+  let new_person = target.dereference_forced(context).await?;
+  let has_alias = new_person
+    .also_known_as
+    .iter()
+    .flatten()
+    .any(|a| a == &old_person.ap_id);
+  if !has_alias || new_person.moved_to.is_some() || new_person.deleted {
+    return Err(LemmyErrorType::InvalidAccountMove.into());
+  }
+  Ok(new_person)
+}
+
+/// Fetches the new community of a community move from its instance, and checks that it lists the
+/// old community in `alsoKnownAs`. Neither community may have moved already.
+pub async fn verify_community_move_target(
+  old_community: &ApubCommunity,
+  target: &ObjectId<ApubCommunity>,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<ApubCommunity> {
+  if old_community.moved_to.is_some() || target.inner() == old_community.ap_id.inner() {
+    return Err(LemmyErrorType::InvalidCommunityMove.into());
+  }
+  // Always fetch the new community, because the alias may have been added just before the move
+  let new_community = target.dereference_forced(context).await?;
+  let has_alias = new_community
+    .also_known_as
+    .iter()
+    .flatten()
+    .any(|a| a == &old_community.ap_id);
+  if !has_alias
+    || new_community.moved_to.is_some()
+    || new_community.deleted
+    || new_community.removed
+  {
+    return Err(LemmyErrorType::InvalidCommunityMove.into());
+  }
+  Ok(new_community)
+}
+
+/// Marks the old community as moved, and lets its local followers follow the new community
+/// instead. If the new community is remote, follow activities are sent for them.
+pub async fn apply_community_move(
+  old_community: &ApubCommunity,
+  new_community: &ApubCommunity,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let followers =
+    Community::apply_move(&mut context.pool(), old_community.id, new_community).await?;
+  if !new_community.local {
+    for follower in followers {
+      ActivityChannel::submit_activity(
+        SendActivityData::FollowCommunity(new_community.0.clone(), follower, true),
+        context,
+      )?;
+    }
+  }
+  Ok(())
+}
+
 pub fn verify_is_public(to: &[Url], cc: &[Url]) -> LemmyResult<()> {
   if ![to, cc].iter().any(|set| set.contains(&public())) {
     Err(UntranslatedError::ObjectIsNotPublic.into())
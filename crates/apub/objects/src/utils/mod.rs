@@ -1,3 +1,4 @@
+pub mod collection_sync;
 pub mod functions;
 pub mod markdown_links;
 pub mod mentions;
@@ -25,7 +25,7 @@ pub struct Source {
 }
 
 impl Source {
-  pub(crate) fn new(content: String) -> Self {
+  pub fn new(content: String) -> Self {
     Source {
       content,
       media_type: MediaTypeMarkdown::Markdown,
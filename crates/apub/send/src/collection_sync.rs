@@ -0,0 +1,141 @@
+use activitypub_federation::{
+  FEDERATION_CONTENT_TYPE,
+  config::Data,
+  traits::{Actor, Object},
+};
+use anyhow::{Context, Result, anyhow};
+use base64::{Engine, engine::general_purpose::STANDARD as Base64};
+use chrono::Utc;
+use either::Either::Right;
+use http_signature_normalization_reqwest::prelude::{Config, SignExt};
+use lemmy_api_utils::{context::LemmyContext, utils::generate_followers_sync_url};
+use lemmy_apub_objects::{
+  objects::SiteOrMultiOrCommunityOrUser,
+  utils::collection_sync::{
+    COLLECTION_SYNCHRONIZATION_HEADER,
+    CollectionSynchronization,
+    followers_digest,
+  },
+};
+use lemmy_db_schema_file::InstanceId;
+use lemmy_db_views_community_follower::CommunityFollowerView;
+use lemmy_utils::error::LemmyResult;
+use moka::future::Cache;
+use reqwest::{
+  StatusCode,
+  Url,
+  header::{CONTENT_TYPE, DATE, HOST, HeaderValue},
+};
+use rsa::{Pkcs1v15Sign, RsaPrivateKey, pkcs8::DecodePrivateKey};
+use sha2::{Digest, Sha256};
+use std::{
+  sync::{Arc, LazyLock},
+  time::Duration,
+};
+
+/// Same as in the activitypub library, which doesn't specify a reason for this value.
+const SIGNATURE_EXPIRES_AFTER: Duration = Duration::from_secs(60 * 60);
+
+/// Builds the header for activities of a local community, which lets the receiving instance check
+/// if its follow state is in sync with ours.
+pub(crate) async fn collection_sync_header(
+  actor: &SiteOrMultiOrCommunityOrUser,
+  instance_id: InstanceId,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<Option<HeaderValue>> {
+  let Right(Right(community)) = actor else {
+    return Ok(None);
+  };
+  let Some(collection_id) = community.followers_url.clone() else {
+    return Ok(None);
+  };
+  let followers = CommunityFollowerView::list_follower_ids_from_instance(
+    &mut context.pool(),
+    community.id,
+    instance_id,
+  )
+  .await?;
+  let sync = CollectionSynchronization {
+    collection_id: collection_id.into(),
+    url: generate_followers_sync_url(&community.ap_id)?.into(),
+    digest: followers_digest(followers.iter().map(|(_, ap_id)| ap_id)),
+  };
+  Ok(Some(HeaderValue::from_str(&sync.to_string())?))
+}
+
+/// Signs and sends an activity with the `Collection-Synchronization` header (FEP-8fcf). Receivers
+/// ignore the header unless it is covered by the HTTP signature, and the activitypub library
+/// doesn't allow adding headers before signing. So this does the same as
+/// `SendActivityTask::sign_and_send`, with the additional header.
+pub(crate) async fn sign_and_send_with_sync_header(
+  activity: Vec<u8>,
+  actor: &SiteOrMultiOrCommunityOrUser,
+  inbox: &Url,
+  sync_header: HeaderValue,
+  context: &Data<LemmyContext>,
+) -> Result<()> {
+  static SIGNATURE_CONFIG: LazyLock<Config> = LazyLock::new(|| {
+    Config::new()
+      .mastodon_compat()
+      .set_expiration(SIGNATURE_EXPIRES_AFTER)
+  });
+
+  let private_key = private_key_cached(actor).await?;
+  let mut host = inbox.domain().context("inbox without domain")?.to_string();
+  if let Some(port) = inbox.port() {
+    host = format!("{host}:{port}");
+  }
+  let date = Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string();
+  // All headers which are present when signing are included in the signature
+  let request = context
+    .client()
+    .post(inbox.as_str())
+    .header(CONTENT_TYPE, FEDERATION_CONTENT_TYPE)
+    .header(HOST, host)
+    .header(DATE, date)
+    .header(COLLECTION_SYNCHRONIZATION_HEADER, sync_header)
+    .signature_with_digest(
+      SIGNATURE_CONFIG.clone(),
+      format!("{}#main-key", actor.id()),
+      Sha256::new(),
+      activity,
+      move |signing_string| {
+        let signature = private_key.sign(
+          Pkcs1v15Sign::new::<Sha256>(),
+          &Sha256::digest(signing_string.as_bytes()),
+        )?;
+        Ok::<_, anyhow::Error>(Base64.encode(signature))
+      },
+    )
+    .await?;
+
+  // Same as the activitypub library, rejected activities are not retried
+  let response = context.client().execute(request).await?;
+  let status = response.status();
+  if status.is_success()
+    || (status.is_client_error()
+      && status != StatusCode::REQUEST_TIMEOUT
+      && status != StatusCode::TOO_MANY_REQUESTS)
+  {
+    Ok(())
+  } else {
+    Err(anyhow!("Activity to {inbox} failed with status {status}"))
+  }
+}
+
+/// Parsing the private key is expensive, so it is cached like in the activitypub library.
+async fn private_key_cached(actor: &SiteOrMultiOrCommunityOrUser) -> Result<RsaPrivateKey> {
+  static CACHE: LazyLock<Cache<Url, RsaPrivateKey>> =
+    LazyLock::new(|| Cache::builder().max_capacity(10000).build());
+  let private_key_pem = actor
+    .private_key_pem()
+    .context("actor without private key")?;
+  CACHE
+    .try_get_with(actor.id().clone(), async {
+      let key =
+        tokio::task::spawn_blocking(move || RsaPrivateKey::from_pkcs8_pem(&private_key_pem));
+      Ok::<_, anyhow::Error>(key.await??)
+    })
+    .await
+    .map_err(|e: Arc<anyhow::Error>| anyhow!("failed to read private key: {e}"))
+}
@@ -1,11 +1,17 @@
 use crate::{util::CancellableTask, worker::InstanceWorker};
 use activitypub_federation::config::FederationConfig;
+use chrono::Utc;
 use lemmy_api_utils::context::LemmyContext;
-use lemmy_db_schema::source::instance::Instance;
+use lemmy_db_schema::source::{
+  federation_queue_control::FederationQueueControl,
+  federation_queue_state::FederationQueueState,
+  instance::{Instance, InstanceForm},
+};
 use lemmy_db_schema_file::InstanceId;
+use lemmy_diesel_utils::connection::DbPool;
 use lemmy_utils::{error::LemmyResult, settings::structs::FederationWorkerConfig};
 use stats::receive_print_stats;
-use std::{collections::HashMap, time::Duration};
+use std::{cmp::max, collections::HashMap, time::Duration};
 use tokio::{
   sync::mpsc::{UnboundedSender, unbounded_channel},
   task::JoinHandle,
@@ -15,6 +21,7 @@ use tokio_util::sync::CancellationToken;
 use tracing::info;
 use util::FederationQueueStateWithDomain;
 
+mod collection_sync;
 mod inboxes;
 mod send;
 mod stats;
@@ -109,7 +116,13 @@ impl SendManager {
       let mut total_count = 0;
       let mut dead_count = 0;
       let mut disallowed_count = 0;
-      for (instance, allowed, is_dead) in
+      let mut paused_count = 0;
+      let controls: HashMap<_, _> = FederationQueueControl::read_all(&mut pool)
+        .await?
+        .into_iter()
+        .map(|c| (c.instance_id, c))
+        .collect();
+      for (instance, allowed, mut is_dead) in
         Instance::read_federated_with_blocked_and_dead(&mut pool).await?
       {
         if instance.domain == local_domain {
@@ -118,6 +131,22 @@ impl SendManager {
         if instance.id.inner() % self.opts.process_count != process_index {
           continue;
         }
+        let control = controls.get(&instance.id);
+        if let Some(control) = control
+          && (control.retry_requested_at.is_some() || control.skip_to_id.is_some())
+        {
+          // stop the worker first so that it saves its state, it is restarted below
+          if let Some(worker) = self.workers.remove(&instance.id)
+            && let Err(e) = worker.cancel().await
+          {
+            tracing::error!("error stopping worker: {e}");
+          }
+          apply_control_requests(&mut pool, &instance, control).await?;
+          if control.retry_requested_at.is_some() {
+            is_dead = false;
+          }
+        }
+        let paused = control.is_some_and(|c| c.paused);
         total_count += 1;
         if !allowed {
           disallowed_count += 1;
@@ -125,7 +154,10 @@ impl SendManager {
         if is_dead {
           dead_count += 1;
         }
-        let should_federate = allowed && !is_dead;
+        if paused {
+          paused_count += 1;
+        }
+        let should_federate = allowed && !is_dead && !paused;
         if should_federate {
           if self.workers.contains_key(&instance.id) {
             // worker already running
@@ -160,7 +192,7 @@ impl SendManager {
       }
       let worker_count = self.workers.len();
       tracing::info!(
-        "Federating to {worker_count}/{total_count} instances ({dead_count} dead, {disallowed_count} disallowed)"
+        "Federating to {worker_count}/{total_count} instances ({dead_count} dead, {disallowed_count} disallowed, {paused_count} paused)"
       );
       tokio::select! {
         () = sleep(INSTANCES_RECHECK_DELAY) => {},
@@ -190,6 +222,40 @@ impl SendManager {
   }
 }
 
+/// Applies retry and skip requests of admins to the saved queue state. The instance worker must
+/// not be running, otherwise it would overwrite the state.
+async fn apply_control_requests(
+  pool: &mut DbPool<'_>,
+  instance: &Instance,
+  control: &FederationQueueControl,
+) -> LemmyResult<()> {
+  let mut state = FederationQueueState::load(pool, instance.id).await?;
+  if control.retry_requested_at.is_some() {
+    info!("{}: retrying as requested by admin", instance.domain);
+    state.fail_count = 0;
+    state.last_retry_at = None;
+    // Dead instances are not federated with, so mark it as alive
+    let form = InstanceForm {
+      updated_at: Some(Utc::now()),
+      ..InstanceForm::new(instance.domain.clone())
+    };
+    Instance::update(pool, instance.id, form).await?;
+  }
+  if let Some(skip_to_id) = control.skip_to_id {
+    info!(
+      "{}: skipping to activity {} as requested by admin",
+      instance.domain, skip_to_id.0
+    );
+    // The activity which failed is skipped, so the fail count doesn't apply anymore
+    state.last_successful_id = max(state.last_successful_id, Some(skip_to_id));
+    state.fail_count = 0;
+    state.last_retry_at = None;
+  }
+  FederationQueueState::upsert(pool, &state).await?;
+  control.clear_requests(pool).await?;
+  Ok(())
+}
+
 #[cfg(test)]
 #[expect(clippy::unwrap_used)]
 #[expect(clippy::indexing_slicing)]
@@ -198,11 +264,14 @@ mod test {
   use super::*;
   use activitypub_federation::config::Data;
   use chrono::DateTime;
-  use lemmy_db_schema::source::{
-    federation_allowlist::{FederationAllowList, FederationAllowListForm},
-    federation_blocklist::{FederationBlockList, FederationBlockListForm},
-    instance::InstanceForm,
-    person::{Person, PersonInsertForm},
+  use lemmy_db_schema::{
+    newtypes::ActivityId,
+    source::{
+      federation_allowlist::{FederationAllowList, FederationAllowListForm},
+      federation_blocklist::{FederationBlockList, FederationBlockListForm},
+      instance::InstanceForm,
+      person::{Person, PersonInsertForm},
+    },
   };
   use lemmy_diesel_utils::traits::Crud;
   use lemmy_utils::error::LemmyError;
@@ -378,4 +447,39 @@ mod test {
     data.cleanup().await?;
     Ok(())
   }
+
+  /// Paused instance should not have a worker, and the skip request should be applied
+  #[tokio::test]
+  #[serial]
+  async fn test_send_manager_paused_and_skip() -> LemmyResult<()> {
+    let mut data = TestData::init(1, 1).await?;
+
+    let pool = &mut data.context.pool();
+    FederationQueueControl::set_paused(pool, data.instances[0].id, true).await?;
+    let skipped = data.instances[1].id;
+    let state = FederationQueueState {
+      last_successful_id: Some(ActivityId(5)),
+      fail_count: 3,
+      last_retry_at: Some(Utc::now()),
+      ..FederationQueueState::load(pool, skipped).await?
+    };
+    FederationQueueState::upsert(pool, &state).await?;
+    FederationQueueControl::request_skip(pool, skipped, ActivityId(6)).await?;
+
+    data.run().await?;
+    let workers = &data.send_manager.workers;
+    assert_eq!(2, workers.len());
+    assert!(workers.contains_key(&data.instances[1].id));
+    assert!(workers.contains_key(&data.instances[2].id));
+
+    let pool = &mut data.context.pool();
+    let state = FederationQueueState::load(pool, skipped).await?;
+    assert_eq!(Some(ActivityId(6)), state.last_successful_id);
+    assert_eq!(0, state.fail_count);
+    let controls = FederationQueueControl::read_all(pool).await?;
+    assert!(controls.iter().all(|c| c.skip_to_id.is_none()));
+
+    data.cleanup().await?;
+    Ok(())
+  }
 }
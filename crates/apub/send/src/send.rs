@@ -1,4 +1,7 @@
-use crate::util::get_actor_cached;
+use crate::{
+  collection_sync::{collection_sync_header, sign_and_send_with_sync_header},
+  util::get_actor_cached,
+};
 use activitypub_federation::{
   activity_sending::SendActivityTask,
   config::Data,
@@ -7,8 +10,16 @@ use activitypub_federation::{
 };
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
+use either::Either::{Left, Right};
 use lemmy_api_utils::context::LemmyContext;
-use lemmy_db_schema::{newtypes::ActivityId, source::activity::SentActivity};
+use lemmy_db_schema::{
+  newtypes::ActivityId,
+  source::{
+    activity::SentActivity,
+    federation_send_error::{FederationSendError, FederationSendErrorForm},
+  },
+};
+use lemmy_db_schema_file::InstanceId;
 use lemmy_utils::{
   FEDERATION_CONTEXT,
   error::{LemmyError, LemmyResult},
@@ -87,6 +98,8 @@ pub(crate) struct SendRetryTask<'a> {
   /// thread to each send task. It allows the task to determine how long to sleep initially
   /// if the request fails.
   pub initial_fail_count: i32,
+  /// Send errors are stored for this instance, so that admins can view them
+  pub instance_id: InstanceId,
   /// For logging purposes
   pub domain: String,
   pub context: Data<LemmyContext>,
@@ -103,6 +116,7 @@ impl SendRetryTask<'_> {
       inbox_urls,
       report,
       initial_fail_count,
+      instance_id,
       domain,
       context,
       stop,
@@ -119,12 +133,44 @@ impl SendRetryTask<'_> {
 
     let object: DummyActivity = serde_json::from_value(object.clone())?;
     let object = WithContext::new(object, FEDERATION_CONTEXT.deref().clone());
-    let requests = SendActivityTask::prepare(&object, actor.as_ref(), inbox_urls, &context).await?;
-    for task in requests {
+    // Missing header only means that the receiver can't check its follows, so don't fail here
+    let sync_header = collection_sync_header(&actor, instance_id, &context)
+      .await
+      .inspect_err(|e| tracing::warn!("{domain}: failed to build collection sync header: {e}"))
+      .ok()
+      .flatten();
+    // Community activities are signed and sent here, so that the signature includes the header
+    let requests = if let Some(sync_header) = sync_header {
+      let mut inbox_urls = inbox_urls;
+      inbox_urls.sort();
+      inbox_urls.dedup();
+      inbox_urls
+        .into_iter()
+        .map(|inbox| Right((inbox, sync_header.clone())))
+        .collect()
+    } else {
+      SendActivityTask::prepare(&object, actor.as_ref(), inbox_urls, &context)
+        .await?
+        .into_iter()
+        .map(Left)
+        .collect::<Vec<_>>()
+    };
+    let body = serde_json::to_vec(&object)?;
+    for request in requests {
       // usually only one due to shared inbox
-      tracing::debug!("sending out {}", task);
+      let send = async || match &request {
+        Left(task) => {
+          tracing::debug!("sending out {}", task);
+          Ok(task.sign_and_send(&context).await?)
+        }
+        Right((inbox, sync_header)) => {
+          tracing::debug!("sending out {} to {}", activity.ap_id, inbox);
+          sign_and_send_with_sync_header(body.clone(), &actor, inbox, sync_header.clone(), &context)
+            .await
+        }
+      };
       let mut fail_count = initial_fail_count;
-      while let Err(e) = task.sign_and_send(&context).await {
+      while let Err(e) = send().await {
         fail_count += 1;
         report.send(SendActivityResult::Failure {
           fail_count,
@@ -137,6 +183,10 @@ impl SendRetryTask<'_> {
           activity.id,
           fail_count
         );
+        let form = FederationSendErrorForm::new(instance_id, activity.id, e.to_string());
+        if let Err(e) = FederationSendError::create(pool, &form).await {
+          tracing::warn!("{domain}: failed to store send error: {e}");
+        }
         tokio::select! {
           () = sleep(retry_delay) => {},
           () = stop.cancelled() => {
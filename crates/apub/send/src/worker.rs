@@ -390,6 +390,7 @@ impl InstanceWorker {
     let initial_fail_count = self.state.fail_count;
     let data = self.federation_lib_config.to_request_data();
     let stop = self.stop.clone();
+    let instance_id = self.instance.id;
     let domain = self.instance.domain.clone();
     let mut report = self.report_send_result.clone();
     tokio::spawn(async move {
@@ -399,6 +400,7 @@ impl InstanceWorker {
         inbox_urls,
         report: &mut report,
         initial_fail_count,
+        instance_id,
         domain,
         context: data,
         stop,
@@ -40,6 +40,17 @@ impl SentActivity {
       .await
       .with_lemmy_type(LemmyErrorType::NotFound)
   }
+
+  /// Id of the newest activity, or none if the table is empty.
+  pub async fn read_latest_id(pool: &mut DbPool<'_>) -> LemmyResult<Option<ActivityId>> {
+    use lemmy_db_schema_file::schema::sent_activity::dsl::{id, sent_activity};
+    let conn = &mut get_conn(pool).await?;
+    sent_activity
+      .select(diesel::dsl::max(id))
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
 }
 
 impl ReceivedActivity {
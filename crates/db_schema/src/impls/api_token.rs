@@ -0,0 +1,195 @@
+use crate::{
+  newtypes::{ApiTokenId, LocalUserId, OAuthClientId},
+  source::api_token::{ApiToken, ApiTokenInsertForm, ApiTokenRefreshForm},
+};
+use diesel::{
+  BoolExpressionMethods,
+  ExpressionMethods,
+  NullableExpressionMethods,
+  QueryDsl,
+  delete,
+  insert_into,
+  update,
+};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::api_token;
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  utils::now,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl ApiToken {
+  pub async fn create(pool: &mut DbPool<'_>, form: &ApiTokenInsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(api_token::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  /// Returns the token with the given hash if it hasn't expired yet, and marks it as used.
+  pub async fn validate(pool: &mut DbPool<'_>, token_hash: &str) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    let not_expired = api_token::expires_at
+      .is_null()
+      .or(api_token::expires_at.gt(now().nullable()));
+    update(
+      api_token::table
+        .filter(api_token::token_hash.eq(token_hash))
+        .filter(not_expired),
+    )
+    .set(api_token::last_used_at.eq(now().nullable()))
+    .get_result::<Self>(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::NotLoggedIn)
+  }
+
+  pub async fn list(pool: &mut DbPool<'_>, local_user_id: LocalUserId) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    api_token::table
+      .filter(api_token::local_user_id.eq(local_user_id))
+      .order_by(api_token::id)
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Replaces the access and refresh token of an OAuth grant, so that each refresh token can only
+  /// be used once.
+  pub async fn refresh(
+    pool: &mut DbPool<'_>,
+    refresh_token_hash: &str,
+    oauth_client_id: OAuthClientId,
+    form: &ApiTokenRefreshForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    update(
+      api_token::table
+        .filter(api_token::refresh_token_hash.eq(refresh_token_hash))
+        .filter(api_token::oauth_client_id.eq(oauth_client_id)),
+    )
+    .set(form)
+    .get_result::<Self>(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::OauthInvalidGrant)
+  }
+
+  /// Revokes the grant of an OAuth client, given either its access or its refresh token.
+  pub async fn revoke_oauth(
+    pool: &mut DbPool<'_>,
+    token_hash: &str,
+    oauth_client_id: OAuthClientId,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(
+      api_token::table
+        .filter(
+          api_token::token_hash
+            .eq(token_hash)
+            .or(api_token::refresh_token_hash.eq(token_hash)),
+        )
+        .filter(api_token::oauth_client_id.eq(oauth_client_id)),
+    )
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+
+  /// Revokes a token. Users can only revoke their own tokens.
+  pub async fn delete(
+    pool: &mut DbPool<'_>,
+    id: ApiTokenId,
+    local_user_id: LocalUserId,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(
+      api_token::table
+        .find(id)
+        .filter(api_token::local_user_id.eq(local_user_id)),
+    )
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::{
+    api_token::{ApiToken, ApiTokenInsertForm},
+    instance::Instance,
+    local_user::{LocalUser, LocalUserInsertForm},
+    person::{Person, PersonInsertForm},
+  };
+  use chrono::{Days, Utc};
+  use lemmy_db_schema_file::enums::ApiTokenScope;
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_api_token() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person =
+      Person::create(pool, &PersonInsertForm::test_form(instance.id, "token_bot")).await?;
+    let local_user =
+      LocalUser::create(pool, &LocalUserInsertForm::test_form(person.id), vec![]).await?;
+    let other_person = Person::create(
+      pool,
+      &PersonInsertForm::test_form(instance.id, "token_thief"),
+    )
+    .await?;
+    let other_local_user = LocalUser::create(
+      pool,
+      &LocalUserInsertForm::test_form(other_person.id),
+      vec![],
+    )
+    .await?;
+
+    let form = ApiTokenInsertForm::new(
+      local_user.id,
+      "feed reader".to_string(),
+      "hash1".to_string(),
+      vec![Some(ApiTokenScope::Read)],
+    );
+    let token = ApiToken::create(pool, &form).await?;
+    assert_eq!(None, token.last_used_at);
+
+    let validated = ApiToken::validate(pool, "hash1").await?;
+    assert_eq!(token.id, validated.id);
+    assert!(validated.last_used_at.is_some());
+    assert!(ApiToken::validate(pool, "hash2").await.is_err());
+
+    // Expired tokens can't be used anymore
+    let form = ApiTokenInsertForm {
+      expires_at: Some(Utc::now() - Days::new(1)),
+      ..ApiTokenInsertForm::new(
+        local_user.id,
+        "old bot".to_string(),
+        "hash2".to_string(),
+        vec![Some(ApiTokenScope::Post), Some(ApiTokenScope::Vote)],
+      )
+    };
+    ApiToken::create(pool, &form).await?;
+    assert!(ApiToken::validate(pool, "hash2").await.is_err());
+    assert_eq!(2, ApiToken::list(pool, local_user.id).await?.len());
+
+    // Only the owner can revoke a token
+    assert_eq!(
+      0,
+      ApiToken::delete(pool, token.id, other_local_user.id).await?
+    );
+    assert_eq!(1, ApiToken::delete(pool, token.id, local_user.id).await?);
+    assert!(ApiToken::validate(pool, "hash1").await.is_err());
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
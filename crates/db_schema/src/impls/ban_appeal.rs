@@ -0,0 +1,147 @@
+use crate::{
+  diesel::OptionalExtension,
+  newtypes::{BanAppealId, ModlogId},
+  source::ban_appeal::{BanAppeal, BanAppealInsertForm, BanAppealUpdateForm},
+};
+use diesel::{ExpressionMethods, QueryDsl, dsl::insert_into};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::ban_appeal;
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  traits::Crud,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl Crud for BanAppeal {
+  type InsertForm = BanAppealInsertForm;
+  type UpdateForm = BanAppealUpdateForm;
+  type IdType = BanAppealId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(ban_appeal::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    appeal_id: BanAppealId,
+    form: &Self::UpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(ban_appeal::table.find(appeal_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+impl BanAppeal {
+  pub async fn read_by_modlog(
+    pool: &mut DbPool<'_>,
+    modlog_id: ModlogId,
+  ) -> LemmyResult<Option<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    ban_appeal::table
+      .filter(ban_appeal::modlog_id.eq(modlog_id))
+      .first(conn)
+      .await
+      .optional()
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Resolves the appeal. Fails if it was already resolved, eg by another mod at the same time.
+  pub async fn resolve(
+    pool: &mut DbPool<'_>,
+    appeal_id: BanAppealId,
+    form: &BanAppealUpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(
+      ban_appeal::table
+        .find(appeal_id)
+        .filter(Self::is_unresolved()),
+    )
+    .set(form)
+    .get_result::<Self>(conn)
+    .await
+    .optional()
+    .with_lemmy_type(LemmyErrorType::CouldntUpdate)?
+    .ok_or(LemmyErrorType::InvalidBanAppeal.into())
+  }
+
+  /// A missing resolve time means the appeal still needs to be handled
+  #[diesel::dsl::auto_type(no_type_alias)]
+  pub fn is_unresolved() -> _ {
+    ban_appeal::resolved_at.is_null()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::{
+    ban_appeal::{BanAppeal, BanAppealInsertForm, BanAppealUpdateForm},
+    instance::Instance,
+    modlog::{Modlog, ModlogInsertForm},
+    person::{Person, PersonInsertForm},
+  };
+  use chrono::Utc;
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_ban_appeal() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let admin = Person::create(
+      pool,
+      &PersonInsertForm::test_form(instance.id, "appeal_admin"),
+    )
+    .await?;
+    let banned = Person::create(
+      pool,
+      &PersonInsertForm::test_form(instance.id, "appeal_banned"),
+    )
+    .await?;
+
+    let form = ModlogInsertForm::admin_ban(&admin, banned.id, true, None, "spam");
+    let ban = Modlog::create(pool, &[form]).await?;
+    let ban_id = ban.first().ok_or(LemmyErrorType::NotFound)?.id;
+
+    let form = BanAppealInsertForm::new(ban_id, banned.id, None, "not spam".to_string());
+    let appeal = BanAppeal::create(pool, &form).await?;
+    assert!(appeal.resolved_at.is_none());
+    assert_eq!(
+      Some(appeal.clone()),
+      BanAppeal::read_by_modlog(pool, ban_id).await?
+    );
+
+    // The same ban can only be appealed once
+    assert!(BanAppeal::create(pool, &form).await.is_err());
+
+    let form = BanAppealUpdateForm {
+      resolver_id: Some(Some(admin.id)),
+      approved: Some(false),
+      deny_reason: Some(Some("it was spam".to_string())),
+      resolved_at: Some(Some(Utc::now())),
+    };
+    let resolved = BanAppeal::resolve(pool, appeal.id, &form).await?;
+    assert_eq!(Some(admin.id), resolved.resolver_id);
+    assert!(!resolved.approved);
+
+    // An appeal can only be resolved once
+    assert!(BanAppeal::resolve(pool, appeal.id, &form).await.is_err());
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,22 @@
+use crate::{newtypes::CommentId, source::comment_history::CommentHistory};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::comment_history;
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl CommentHistory {
+  /// Lists the previous versions of a comment, oldest first.
+  pub async fn list_for_comment(
+    pool: &mut DbPool<'_>,
+    comment_id: CommentId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    comment_history::table
+      .filter(comment_history::comment_id.eq(comment_id))
+      .order_by(comment_history::id)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
@@ -13,6 +13,7 @@ use crate::{
       CommunityPersonBanForm,
       CommunityUpdateForm,
     },
+    person::Person,
     post::Post,
   },
   traits::{ApubActor, Bannable, Blockable, Followable},
@@ -28,13 +29,14 @@ use diesel::{
   expression::SelectableHelper,
   select,
   update,
+  upsert::excluded,
 };
-use diesel_async::RunQueryDsl;
+use diesel_async::{RunQueryDsl, scoped_futures::ScopedFutureExt};
 use diesel_uplete::{UpleteCount, uplete};
 use lemmy_db_schema_file::{
   PersonId,
   enums::{CommunityFollowerState, CommunityNotificationsMode, CommunityVisibility, ListingType},
-  schema::{comment, community, community_actions, instance, local_user, post},
+  schema::{comment, community, community_actions, instance, local_user, person, post},
 };
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
@@ -278,6 +280,105 @@ impl Community {
       .await
       .with_lemmy_type(LemmyErrorType::CouldntUpdate)
   }
+
+  /// Communities which were moved to the given community.
+  pub async fn list_moved_to(pool: &mut DbPool<'_>, ap_id: &DbUrl) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    community::table
+      .filter(community::moved_to.eq(ap_id))
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Marks the community as moved to the new community, and changes local followers of the old
+  /// community to follow the new one. Remote followers are migrated by their own instance.
+  ///
+  /// Returns the followers which were migrated, so that follow activities can be sent if the new
+  /// community is remote.
+  pub async fn apply_move(
+    pool: &mut DbPool<'_>,
+    old_community_id: CommunityId,
+    new_community: &Community,
+  ) -> LemmyResult<Vec<Person>> {
+    let conn = &mut get_conn(pool).await?;
+    let new_community_id = new_community.id;
+    let new_ap_id = new_community.ap_id.clone();
+    let follow_state = if !new_community.local {
+      CommunityFollowerState::Pending
+    } else if new_community.visibility == CommunityVisibility::Private {
+      CommunityFollowerState::ApprovalRequired
+    } else {
+      CommunityFollowerState::Accepted
+    };
+
+    conn
+      .run_transaction(|conn| {
+        async move {
+          let already_following = community_actions::table
+            .filter(community_actions::community_id.eq(new_community_id))
+            .filter(community_actions::followed_at.is_not_null())
+            .select(community_actions::person_id)
+            .load::<PersonId>(conn)
+            .await?;
+          let followers = community_actions::table
+            .inner_join(person::table.on(community_actions::person_id.eq(person::id)))
+            .filter(community_actions::community_id.eq(old_community_id))
+            .filter(community_actions::followed_at.is_not_null())
+            .filter(person::local)
+            .filter(not(person::id.eq_any(already_following)))
+            .select((
+              person::all_columns,
+              community_actions::followed_at.assume_not_null(),
+            ))
+            .load::<(Person, DateTime<Utc>)>(conn)
+            .await?;
+
+          let forms = followers
+            .iter()
+            .map(|(follower, followed_at)| CommunityFollowerForm {
+              followed_at: *followed_at,
+              ..CommunityFollowerForm::new(new_community_id, follower.id, follow_state)
+            })
+            .collect::<Vec<_>>();
+          insert_into(community_actions::table)
+            .values(forms)
+            .on_conflict((
+              community_actions::person_id,
+              community_actions::community_id,
+            ))
+            .do_update()
+            .set((
+              community_actions::followed_at.eq(excluded(community_actions::followed_at)),
+              community_actions::follow_state.eq(excluded(community_actions::follow_state)),
+            ))
+            .execute(conn)
+            .await?;
+          uplete(
+            community_actions::table
+              .filter(community_actions::community_id.eq(old_community_id))
+              .filter(community_actions::person_id.eq_any(followers.iter().map(|(p, _)| p.id))),
+          )
+          .set_null(community_actions::followed_at)
+          .set_null(community_actions::follow_state)
+          .set_null(community_actions::follow_approver_id)
+          .get_result::<UpleteCount>(conn)
+          .await?;
+
+          diesel::update(community::table.find(old_community_id))
+            .set((
+              community::moved_to.eq(new_ap_id),
+              community::updated_at.eq(Utc::now()),
+            ))
+            .execute(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+          Ok(followers.into_iter().map(|(p, _)| p).collect())
+        }
+        .scope_boxed()
+      })
+      .await
+  }
 }
 
 impl CommunityActions {
@@ -748,6 +849,8 @@ mod tests {
       unresolved_report_count: 0,
       interactions_month: 0,
       local_removed: false,
+      also_known_as: vec![],
+      moved_to: None,
     };
 
     let community_follower_form = CommunityFollowerForm::new(
@@ -980,4 +1083,64 @@ mod tests {
 
     Ok(())
   }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_apply_move() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person_form = PersonInsertForm::test_form(inserted_instance.id, "move_follower");
+    let follower = Person::create(pool, &person_form).await?;
+    let old_form = CommunityInsertForm::new(
+      inserted_instance.id,
+      "move_old".into(),
+      "pubkey".to_string(),
+    );
+    let old_community = Community::create(pool, &old_form).await?;
+    let new_form = CommunityInsertForm::new(
+      inserted_instance.id,
+      "move_new".into(),
+      "pubkey".to_string(),
+    );
+    let new_community = Community::create(pool, &new_form).await?;
+
+    let follow_form = CommunityFollowerForm::new(
+      old_community.id,
+      follower.id,
+      CommunityFollowerState::Accepted,
+    );
+    let old_follow = CommunityActions::follow(pool, &follow_form).await?;
+
+    let moved_followers = Community::apply_move(pool, old_community.id, &new_community).await?;
+    assert_eq!(
+      vec![follower.id],
+      moved_followers.iter().map(|p| p.id).collect::<Vec<_>>()
+    );
+
+    let read_old = Community::read(pool, old_community.id).await?;
+    assert_eq!(Some(new_community.ap_id.clone()), read_old.moved_to);
+    let moved_to_new = Community::list_moved_to(pool, &new_community.ap_id).await?;
+    assert_eq!(
+      vec![old_community.id],
+      moved_to_new.iter().map(|c| c.id).collect::<Vec<_>>()
+    );
+    let new_follow = CommunityActions::read(pool, new_community.id, follower.id).await?;
+    assert_eq!(old_follow.followed_at, new_follow.followed_at);
+    assert_eq!(
+      Some(CommunityFollowerState::Accepted),
+      new_follow.follow_state
+    );
+
+    // Moving again doesn't find any followers
+    let moved_again = Community::apply_move(pool, old_community.id, &new_community).await?;
+    assert!(moved_again.is_empty());
+
+    Community::delete(pool, old_community.id).await?;
+    Community::delete(pool, new_community.id).await?;
+    Person::delete(pool, follower.id).await?;
+    Instance::delete(pool, inserted_instance.id).await?;
+    Ok(())
+  }
 }
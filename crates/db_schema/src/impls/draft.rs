@@ -0,0 +1,152 @@
+use crate::{
+  newtypes::DraftId,
+  source::draft::{Draft, DraftInsertForm, DraftUpdateForm},
+};
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, dsl::insert_into};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::{PersonId, schema::draft};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  traits::Crud,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl Crud for Draft {
+  type InsertForm = DraftInsertForm;
+  type UpdateForm = DraftUpdateForm;
+  type IdType = DraftId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(draft::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    draft_id: DraftId,
+    form: &Self::UpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(draft::table.find(draft_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+impl Draft {
+  /// Deletes the draft of the given creator and returns it, so that it can only be published once
+  /// even with concurrent requests. Drafts of other users are treated as nonexistent.
+  pub async fn claim(
+    pool: &mut DbPool<'_>,
+    draft_id: DraftId,
+    creator_id: PersonId,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(
+      draft::table
+        .find(draft_id)
+        .filter(draft::creator_id.eq(creator_id)),
+    )
+    .get_result::<Self>(conn)
+    .await
+    .optional()
+    .with_lemmy_type(LemmyErrorType::NotFound)?
+    .ok_or(LemmyErrorType::NotFound.into())
+  }
+
+  /// Puts back a draft which was claimed with [Draft::claim], keeping its id.
+  pub async fn restore(&self, pool: &mut DbPool<'_>) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(draft::table)
+      .values(self)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::{
+    community::{Community, CommunityInsertForm},
+    draft::{Draft, DraftInsertForm, DraftUpdateForm},
+    instance::Instance,
+    person::{Person, PersonInsertForm},
+  };
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_draft() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person = Person::create(pool, &PersonInsertForm::test_form(instance.id, "drafter")).await?;
+    let community = Community::create(
+      pool,
+      &CommunityInsertForm::new(instance.id, "drafts".into(), "pubkey".into()),
+    )
+    .await?;
+
+    let form = DraftInsertForm {
+      community_id: Some(community.id),
+      name: Some("Half a title".to_string()),
+      ..DraftInsertForm::new(person.id)
+    };
+    let draft = Draft::create(pool, &form).await?;
+    assert_eq!(Some(community.id), draft.community_id);
+    assert!(draft.body.is_none());
+
+    // A draft needs exactly one target
+    assert!(
+      Draft::create(pool, &DraftInsertForm::new(person.id))
+        .await
+        .is_err()
+    );
+    let form = DraftInsertForm {
+      community_id: Some(community.id),
+      recipient_id: Some(person.id),
+      ..DraftInsertForm::new(person.id)
+    };
+    assert!(Draft::create(pool, &form).await.is_err());
+
+    let form = DraftUpdateForm {
+      name: Some(Some("A full title".to_string())),
+      body: Some(Some("Body".to_string())),
+      ..Default::default()
+    };
+    let updated = Draft::update(pool, draft.id, &form).await?;
+    assert_eq!(Some("A full title".to_string()), updated.name);
+    assert_eq!(Some("Body".to_string()), updated.body);
+
+    // Only the creator can claim a draft, and only once
+    let other = Person::create(pool, &PersonInsertForm::test_form(instance.id, "other")).await?;
+    assert!(Draft::claim(pool, draft.id, other.id).await.is_err());
+    let claimed = Draft::claim(pool, draft.id, person.id).await?;
+    assert_eq!(updated, claimed);
+    let second = Draft::claim(pool, draft.id, person.id).await;
+    assert_eq!(
+      Some(LemmyErrorType::NotFound),
+      second.err().map(|e| e.error_type)
+    );
+
+    // A restored draft can be claimed again
+    let restored = claimed.restore(pool).await?;
+    assert_eq!(claimed, restored);
+    Draft::claim(pool, draft.id, person.id).await?;
+    assert!(Draft::read(pool, draft.id).await.is_err());
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,170 @@
+use crate::{
+  newtypes::EmailTemplateId,
+  source::email_template::{EmailTemplate, EmailTemplateInsertForm, EmailTemplateUpdateForm},
+};
+use chrono::Utc;
+use diesel::{
+  BoolExpressionMethods,
+  ExpressionMethods,
+  OptionalExtension,
+  PgSortExpressionMethods,
+  QueryDsl,
+  insert_into,
+};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::{enums::EmailTemplateType, schema::email_template};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  traits::Crud,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl Crud for EmailTemplate {
+  type InsertForm = EmailTemplateInsertForm;
+  type UpdateForm = EmailTemplateUpdateForm;
+  type IdType = EmailTemplateId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(email_template::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    template_id: EmailTemplateId,
+    form: &Self::UpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(email_template::table.find(template_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+impl EmailTemplate {
+  pub async fn list(pool: &mut DbPool<'_>) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    email_template::table
+      .order_by(email_template::template_type)
+      .then_order_by(email_template::language.asc().nulls_first())
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// The template for the given interface language, or otherwise the one for all languages.
+  pub async fn read_for_language(
+    pool: &mut DbPool<'_>,
+    template_type: EmailTemplateType,
+    language: &str,
+  ) -> LemmyResult<Option<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    email_template::table
+      .filter(email_template::template_type.eq(template_type))
+      .filter(
+        email_template::language
+          .eq(language)
+          .or(email_template::language.is_null()),
+      )
+      .order_by(email_template::language.asc().nulls_last())
+      .first::<Self>(conn)
+      .await
+      .optional()
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Creates the template, or replaces the existing one with the same type and language.
+  pub async fn upsert(pool: &mut DbPool<'_>, form: &EmailTemplateInsertForm) -> LemmyResult<Self> {
+    let existing = {
+      let conn = &mut get_conn(pool).await?;
+      let query = email_template::table
+        .filter(email_template::template_type.eq(form.template_type))
+        .into_boxed();
+      let query = match &form.language {
+        Some(language) => query.filter(email_template::language.eq(language)),
+        None => query.filter(email_template::language.is_null()),
+      };
+      query
+        .select(email_template::id)
+        .first::<EmailTemplateId>(conn)
+        .await
+        .optional()?
+    };
+
+    match existing {
+      Some(template_id) => {
+        let form = EmailTemplateUpdateForm {
+          subject: Some(form.subject.clone()),
+          body: Some(form.body.clone()),
+          updated_at: Some(Some(Utc::now())),
+        };
+        Self::update(pool, template_id, &form).await
+      }
+      None => Self::create(pool, form).await,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::email_template::{EmailTemplate, EmailTemplateInsertForm};
+  use lemmy_db_schema_file::enums::EmailTemplateType;
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_email_template_language() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let template_type = EmailTemplateType::PasswordReset;
+    assert_eq!(
+      None,
+      EmailTemplate::read_for_language(pool, template_type, "de").await?
+    );
+
+    let form = EmailTemplateInsertForm::new(
+      template_type,
+      None,
+      "Reset".to_string(),
+      "{{ reset_link }}".to_string(),
+    );
+    let default = EmailTemplate::upsert(pool, &form).await?;
+    let form = EmailTemplateInsertForm::new(
+      template_type,
+      Some("de".to_string()),
+      "Zurücksetzen".to_string(),
+      "{{ reset_link }}".to_string(),
+    );
+    let german = EmailTemplate::upsert(pool, &form).await?;
+
+    // Languages without their own template use the default one
+    let read_de = EmailTemplate::read_for_language(pool, template_type, "de").await?;
+    assert_eq!(Some(german.clone()), read_de);
+    let read_fr = EmailTemplate::read_for_language(pool, template_type, "fr").await?;
+    assert_eq!(Some(default.clone()), read_fr);
+
+    // Saving again replaces the template
+    let form = EmailTemplateInsertForm {
+      subject: "Passwort zurücksetzen".to_string(),
+      ..form
+    };
+    let updated = EmailTemplate::upsert(pool, &form).await?;
+    assert_eq!(german.id, updated.id);
+    assert_eq!("Passwort zurücksetzen", updated.subject);
+    assert_eq!(2, EmailTemplate::list(pool).await?.len());
+
+    EmailTemplate::delete(pool, default.id).await?;
+    EmailTemplate::delete(pool, german.id).await?;
+    Ok(())
+  }
+}
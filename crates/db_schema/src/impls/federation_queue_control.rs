@@ -0,0 +1,148 @@
+use crate::{newtypes::ActivityId, source::federation_queue_control::FederationQueueControl};
+use chrono::{DateTime, Utc};
+use diesel::{ExpressionMethods, QueryDsl, SelectableHelper, dsl::insert_into, update};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::{InstanceId, schema::federation_queue_control};
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl FederationQueueControl {
+  pub async fn read_all(pool: &mut DbPool<'_>) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    federation_queue_control::table
+      .select(Self::as_select())
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  pub async fn set_paused(
+    pool: &mut DbPool<'_>,
+    instance_id: InstanceId,
+    paused: bool,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(federation_queue_control::table)
+      .values((
+        federation_queue_control::instance_id.eq(instance_id),
+        federation_queue_control::paused.eq(paused),
+      ))
+      .on_conflict(federation_queue_control::instance_id)
+      .do_update()
+      .set((
+        federation_queue_control::paused.eq(paused),
+        federation_queue_control::updated_at.eq(Utc::now()),
+      ))
+      .returning(Self::as_returning())
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
+  pub async fn request_retry(pool: &mut DbPool<'_>, instance_id: InstanceId) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    let now = Utc::now();
+    insert_into(federation_queue_control::table)
+      .values((
+        federation_queue_control::instance_id.eq(instance_id),
+        federation_queue_control::retry_requested_at.eq(now),
+      ))
+      .on_conflict(federation_queue_control::instance_id)
+      .do_update()
+      .set((
+        federation_queue_control::retry_requested_at.eq(now),
+        federation_queue_control::updated_at.eq(now),
+      ))
+      .returning(Self::as_returning())
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
+  pub async fn request_skip(
+    pool: &mut DbPool<'_>,
+    instance_id: InstanceId,
+    skip_to_id: ActivityId,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(federation_queue_control::table)
+      .values((
+        federation_queue_control::instance_id.eq(instance_id),
+        federation_queue_control::skip_to_id.eq(skip_to_id),
+      ))
+      .on_conflict(federation_queue_control::instance_id)
+      .do_update()
+      .set((
+        federation_queue_control::skip_to_id.eq(skip_to_id),
+        federation_queue_control::updated_at.eq(Utc::now()),
+      ))
+      .returning(Self::as_returning())
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
+  /// Marks the retry and skip requests as applied. Nothing is changed if there was another
+  /// request in the meantime, so that it gets applied as well.
+  pub async fn clear_requests(&self, pool: &mut DbPool<'_>) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    update(
+      federation_queue_control::table
+        .filter(federation_queue_control::instance_id.eq(self.instance_id))
+        .filter(federation_queue_control::updated_at.eq(self.updated_at)),
+    )
+    .set((
+      federation_queue_control::retry_requested_at.eq(None::<DateTime<Utc>>),
+      federation_queue_control::skip_to_id.eq(None::<ActivityId>),
+    ))
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::source::instance::Instance;
+  use lemmy_diesel_utils::connection::build_db_pool_for_tests;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_federation_queue_control() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let instance = Instance::read_or_create(pool, "queue-control.com").await?;
+
+    let control = FederationQueueControl::set_paused(pool, instance.id, true).await?;
+    assert!(control.paused);
+    assert_eq!(None, control.retry_requested_at);
+
+    FederationQueueControl::request_retry(pool, instance.id).await?;
+    let control = FederationQueueControl::request_skip(pool, instance.id, ActivityId(5)).await?;
+    assert!(control.paused);
+    assert!(control.retry_requested_at.is_some());
+    assert_eq!(Some(ActivityId(5)), control.skip_to_id);
+
+    // Requests are only cleared if there was no newer one
+    let stale = FederationQueueControl {
+      updated_at: control.updated_at - chrono::Duration::seconds(1),
+      ..control.clone()
+    };
+    assert_eq!(0, stale.clear_requests(pool).await?);
+    assert_eq!(1, control.clear_requests(pool).await?);
+
+    let all = FederationQueueControl::read_all(pool).await?;
+    let control = all
+      .iter()
+      .find(|c| c.instance_id == instance.id)
+      .ok_or(LemmyErrorType::NotFound)?;
+    assert!(control.paused);
+    assert_eq!(None, control.retry_requested_at);
+    assert_eq!(None, control.skip_to_id);
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
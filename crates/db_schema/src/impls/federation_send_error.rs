@@ -0,0 +1,116 @@
+use crate::{
+  newtypes::FederationSendErrorId,
+  source::federation_send_error::{FederationSendError, FederationSendErrorForm},
+};
+use diesel::{
+  ExpressionMethods,
+  OptionalExtension,
+  QueryDsl,
+  SelectableHelper,
+  delete,
+  dsl::insert_into,
+};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::{InstanceId, schema::federation_send_error};
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+/// How many send errors are kept for each instance.
+pub const FEDERATION_SEND_ERROR_LIMIT: i64 = 50;
+
+impl FederationSendError {
+  /// Stores a new error, and removes old errors for the same instance so that only the most recent
+  /// ones are kept.
+  pub async fn create(pool: &mut DbPool<'_>, form: &FederationSendErrorForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    let error = insert_into(federation_send_error::table)
+      .values(form)
+      .returning(Self::as_returning())
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)?;
+
+    let oldest_kept: Option<FederationSendErrorId> = federation_send_error::table
+      .filter(federation_send_error::instance_id.eq(form.instance_id))
+      .select(federation_send_error::id)
+      .order_by(federation_send_error::id.desc())
+      .offset(FEDERATION_SEND_ERROR_LIMIT - 1)
+      .first(conn)
+      .await
+      .optional()?;
+    if let Some(oldest_kept) = oldest_kept {
+      delete(
+        federation_send_error::table
+          .filter(federation_send_error::instance_id.eq(form.instance_id))
+          .filter(federation_send_error::id.lt(oldest_kept)),
+      )
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)?;
+    }
+    Ok(error)
+  }
+
+  /// Lists the most recent errors for the instance, newest first.
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    instance_id: InstanceId,
+    limit: Option<i64>,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    let limit = limit
+      .unwrap_or(FEDERATION_SEND_ERROR_LIMIT)
+      .clamp(1, FEDERATION_SEND_ERROR_LIMIT);
+    federation_send_error::table
+      .filter(federation_send_error::instance_id.eq(instance_id))
+      .select(Self::as_select())
+      .order_by(federation_send_error::id.desc())
+      .limit(limit)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::FEDERATION_SEND_ERROR_LIMIT;
+  use crate::{
+    newtypes::ActivityId,
+    source::{
+      federation_send_error::{FederationSendError, FederationSendErrorForm},
+      instance::Instance,
+    },
+  };
+  use lemmy_diesel_utils::connection::build_db_pool_for_tests;
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_federation_send_error_limit() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let instance = Instance::read_or_create(pool, "send-error.com").await?;
+
+    for i in 0..FEDERATION_SEND_ERROR_LIMIT + 5 {
+      let form = FederationSendErrorForm::new(instance.id, ActivityId(i), format!("error {i}"));
+      FederationSendError::create(pool, &form).await?;
+    }
+
+    let errors = FederationSendError::list(pool, instance.id, Some(100)).await?;
+    assert_eq!(usize::try_from(FEDERATION_SEND_ERROR_LIMIT)?, errors.len());
+    assert_eq!(
+      Some(ActivityId(FEDERATION_SEND_ERROR_LIMIT + 4)),
+      errors.first().map(|e| e.activity_id)
+    );
+    assert_eq!(Some(ActivityId(5)), errors.last().map(|e| e.activity_id));
+
+    let errors = FederationSendError::list(pool, instance.id, Some(3)).await?;
+    assert_eq!(3, errors.len());
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -1,16 +1,24 @@
 pub mod activity;
 pub mod actor_language;
+pub mod api_token;
+pub mod automod_rule;
+pub mod ban_appeal;
 pub mod comment;
+pub mod comment_history;
 pub mod comment_report;
 pub mod community;
 pub mod community_community_follow;
 pub mod community_report;
 pub mod community_tag;
 pub mod custom_emoji;
+pub mod draft;
+pub mod email_template;
 pub mod email_verification;
 pub mod federation_allowlist;
 pub mod federation_blocklist;
+pub mod federation_queue_control;
 pub mod federation_queue_state;
+pub mod federation_send_error;
 pub mod images;
 pub mod instance;
 pub mod keyword_block;
@@ -22,17 +30,27 @@ pub mod local_user;
 pub mod local_user_invite;
 pub mod login_token;
 pub mod modlog;
+pub mod modmail;
 pub mod multi_community;
 pub mod notification;
 pub mod oauth_account;
+pub mod oauth_client;
 pub mod oauth_provider;
+pub mod passkey;
 pub mod password_reset_request;
 pub mod person;
+pub mod poll;
 pub mod post;
+pub mod post_history;
 pub mod post_report;
 pub mod private_message;
 pub mod private_message_report;
+pub mod push_subscription;
+pub mod queued_email;
+pub mod received_activity_log;
+pub mod recurring_post;
 pub mod registration_application;
 pub mod secret;
 pub mod site;
 pub mod tagline;
+pub mod webhook;
@@ -150,6 +150,30 @@ impl<'a> ModlogInsertForm<'a> {
       ..ModlogInsertForm::new(ModlogKind::ModWarnPost, false, mod_person_id)
     }
   }
+  pub fn mod_automod_post(mod_person_id: PersonId, post: &Post, reason: &'a str) -> Self {
+    Self {
+      reason: Some(reason),
+      target_post_id: Some(post.id),
+      target_community_id: Some(post.community_id),
+      target_person_id: Some(post.creator_id),
+      ..ModlogInsertForm::new(ModlogKind::ModAutomodAction, false, mod_person_id)
+    }
+  }
+  pub fn mod_automod_comment(
+    mod_person_id: PersonId,
+    comment: &Comment,
+    community_id: CommunityId,
+    reason: &'a str,
+  ) -> Self {
+    Self {
+      reason: Some(reason),
+      target_comment_id: Some(comment.id),
+      target_post_id: Some(comment.post_id),
+      target_community_id: Some(community_id),
+      target_person_id: Some(comment.creator_id),
+      ..ModlogInsertForm::new(ModlogKind::ModAutomodAction, false, mod_person_id)
+    }
+  }
   pub fn admin_remove_community(
     mod_person: &Person,
     community_id: CommunityId,
@@ -0,0 +1,177 @@
+use crate::{
+  diesel::OptionalExtension,
+  newtypes::{ModmailMessageId, ModmailThreadId},
+  source::modmail::{
+    ModmailMessage,
+    ModmailMessageInsertForm,
+    ModmailThread,
+    ModmailThreadInsertForm,
+    ModmailThreadUpdateForm,
+  },
+};
+use diesel::{ExpressionMethods, QueryDsl, dsl::insert_into};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::{modmail_message, modmail_thread};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  dburl::DbUrl,
+  traits::Crud,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl Crud for ModmailThread {
+  type InsertForm = ModmailThreadInsertForm;
+  type UpdateForm = ModmailThreadUpdateForm;
+  type IdType = ModmailThreadId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(modmail_thread::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    thread_id: ModmailThreadId,
+    form: &Self::UpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(modmail_thread::table.find(thread_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+impl ModmailThread {
+  pub async fn read_from_apub_id(
+    pool: &mut DbPool<'_>,
+    object_id: DbUrl,
+  ) -> LemmyResult<Option<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    modmail_thread::table
+      .filter(modmail_thread::ap_id.eq(object_id))
+      .first(conn)
+      .await
+      .optional()
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+impl ModmailMessage {
+  pub async fn read(pool: &mut DbPool<'_>, message_id: ModmailMessageId) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    modmail_message::table
+      .find(message_id)
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  pub async fn create(pool: &mut DbPool<'_>, form: &ModmailMessageInsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(modmail_message::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  /// Messages can't be edited, so receiving the same message again leaves it unchanged.
+  pub async fn insert_apub(
+    pool: &mut DbPool<'_>,
+    form: &ModmailMessageInsertForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(modmail_message::table)
+      .values(form)
+      .on_conflict(modmail_message::ap_id)
+      .do_update()
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  pub async fn list_for_thread(
+    pool: &mut DbPool<'_>,
+    thread_id: ModmailThreadId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    modmail_message::table
+      .filter(modmail_message::thread_id.eq(thread_id))
+      .order_by(modmail_message::published_at)
+      .then_order_by(modmail_message::id)
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::{
+    community::{Community, CommunityInsertForm},
+    instance::Instance,
+    modmail::{
+      ModmailMessage,
+      ModmailMessageInsertForm,
+      ModmailThread,
+      ModmailThreadInsertForm,
+      ModmailThreadUpdateForm,
+    },
+    person::{Person, PersonInsertForm},
+  };
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_modmail_thread() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person = Person::create(pool, &PersonInsertForm::test_form(instance.id, "mailer")).await?;
+    let community = Community::create(
+      pool,
+      &CommunityInsertForm::new(instance.id, "modmail".into(), "pubkey".into()),
+    )
+    .await?;
+
+    let form = ModmailThreadInsertForm::new(community.id, person.id, "Appeal".to_string());
+    let thread = ModmailThread::create(pool, &form).await?;
+    assert!(!thread.archived);
+    assert!(thread.local);
+    assert_eq!(
+      Some(thread.clone()),
+      ModmailThread::read_from_apub_id(pool, thread.ap_id.clone()).await?
+    );
+
+    let form = ModmailMessageInsertForm::new(thread.id, person.id, false, "first".to_string());
+    let first = ModmailMessage::create(pool, &form).await?;
+    let form = ModmailMessageInsertForm::new(thread.id, person.id, true, "second".to_string());
+    let second = ModmailMessage::create(pool, &form).await?;
+    assert_ne!(first.ap_id, second.ap_id);
+    assert_eq!(
+      vec![first, second],
+      ModmailMessage::list_for_thread(pool, thread.id).await?
+    );
+
+    let form = ModmailThreadUpdateForm {
+      archived: Some(true),
+      ..Default::default()
+    };
+    let thread = ModmailThread::update(pool, thread.id, &form).await?;
+    assert!(thread.archived);
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -1,5 +1,5 @@
 use crate::{
-  newtypes::{CommentId, NotificationId, PostId},
+  newtypes::{CommentId, ModmailThreadId, NotificationId, PostId},
   source::notification::{Notification, NotificationInsertForm},
 };
 use diesel::{
@@ -63,6 +63,23 @@ impl Notification {
     .with_lemmy_type(LemmyErrorType::NotFound)
   }
 
+  pub async fn mark_read_by_modmail_thread_and_recipient(
+    pool: &mut DbPool<'_>,
+    modmail_thread_id: ModmailThreadId,
+    recipient_id: PersonId,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    update(
+      notification::table
+        .filter(notification::modmail_thread_id.eq(modmail_thread_id))
+        .filter(notification::recipient_id.eq(recipient_id)),
+    )
+    .set(notification::read.eq(true))
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
   pub async fn mark_all_as_read(
     pool: &mut DbPool<'_>,
     for_recipient_id: PersonId,
@@ -0,0 +1,192 @@
+use crate::source::oauth_client::{
+  OAuthAuthorizationCode,
+  OAuthAuthorizationCodeInsertForm,
+  OAuthClient,
+  OAuthClientInsertForm,
+};
+use diesel::{ExpressionMethods, QueryDsl, delete, insert_into};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::{oauth_authorization_code, oauth_client};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  utils::now,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl OAuthClient {
+  pub async fn create(pool: &mut DbPool<'_>, form: &OAuthClientInsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(oauth_client::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  pub async fn read_by_client_id(pool: &mut DbPool<'_>, client_id: &str) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    oauth_client::table
+      .filter(oauth_client::client_id.eq(client_id))
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::OauthInvalidClient)
+  }
+}
+
+impl OAuthAuthorizationCode {
+  pub async fn create(
+    pool: &mut DbPool<'_>,
+    form: &OAuthAuthorizationCodeInsertForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(oauth_authorization_code::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  /// Deletes and returns the code with the given hash, so that it can only be used once. Fails if
+  /// the code has expired.
+  pub async fn consume(pool: &mut DbPool<'_>, code_hash: &str) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    delete(
+      oauth_authorization_code::table
+        .find(code_hash)
+        .filter(oauth_authorization_code::expires_at.gt(now())),
+    )
+    .get_result::<Self>(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::OauthInvalidGrant)
+  }
+
+  /// Removes codes which expired without being used.
+  pub async fn delete_expired(pool: &mut DbPool<'_>) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(oauth_authorization_code::table.filter(oauth_authorization_code::expires_at.le(now())))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::{
+    api_token::{ApiToken, ApiTokenInsertForm, ApiTokenRefreshForm},
+    instance::Instance,
+    local_user::{LocalUser, LocalUserInsertForm},
+    oauth_client::{
+      OAuthAuthorizationCode,
+      OAuthAuthorizationCodeInsertForm,
+      OAuthClient,
+      OAuthClientInsertForm,
+    },
+    person::{Person, PersonInsertForm},
+  };
+  use chrono::{Duration, Utc};
+  use lemmy_db_schema_file::enums::ApiTokenScope;
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_oauth_grant() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person = Person::create(
+      pool,
+      &PersonInsertForm::test_form(instance.id, "oauth_user"),
+    )
+    .await?;
+    let local_user =
+      LocalUser::create(pool, &LocalUserInsertForm::test_form(person.id), vec![]).await?;
+
+    let scopes = vec![Some(ApiTokenScope::Read)];
+    let form = OAuthClientInsertForm::new(
+      "client1".to_string(),
+      None,
+      "Test app".to_string(),
+      vec![Some("https://app.example/callback".to_string())],
+      scopes.clone(),
+    );
+    let client = OAuthClient::create(pool, &form).await?;
+    assert_eq!(
+      client,
+      OAuthClient::read_by_client_id(pool, "client1").await?
+    );
+
+    // Codes can only be used once, and not after they expire
+    let code_form = OAuthAuthorizationCodeInsertForm {
+      code_hash: "code1".to_string(),
+      oauth_client_id: client.id,
+      local_user_id: local_user.id,
+      redirect_uri: "https://app.example/callback".to_string(),
+      scopes: scopes.clone(),
+      code_challenge: "challenge".to_string(),
+      expires_at: Utc::now() + Duration::minutes(10),
+    };
+    OAuthAuthorizationCode::create(pool, &code_form).await?;
+    let code = OAuthAuthorizationCode::consume(pool, "code1").await?;
+    assert_eq!(local_user.id, code.local_user_id);
+    assert!(
+      OAuthAuthorizationCode::consume(pool, "code1")
+        .await
+        .is_err()
+    );
+
+    let expired_form = OAuthAuthorizationCodeInsertForm {
+      code_hash: "code2".to_string(),
+      expires_at: Utc::now() - Duration::minutes(1),
+      ..code_form
+    };
+    OAuthAuthorizationCode::create(pool, &expired_form).await?;
+    assert!(
+      OAuthAuthorizationCode::consume(pool, "code2")
+        .await
+        .is_err()
+    );
+    assert_eq!(1, OAuthAuthorizationCode::delete_expired(pool).await?);
+
+    // Refresh tokens are replaced on each use
+    let token_form = ApiTokenInsertForm {
+      expires_at: Some(Utc::now() + Duration::hours(1)),
+      oauth_client_id: Some(client.id),
+      refresh_token_hash: Some("refresh1".to_string()),
+      ..ApiTokenInsertForm::new(
+        local_user.id,
+        client.name.clone(),
+        "access1".to_string(),
+        scopes,
+      )
+    };
+    ApiToken::create(pool, &token_form).await?;
+    let refresh_form = ApiTokenRefreshForm {
+      token_hash: "access2".to_string(),
+      refresh_token_hash: "refresh2".to_string(),
+      expires_at: Utc::now() + Duration::hours(1),
+    };
+    ApiToken::refresh(pool, "refresh1", client.id, &refresh_form).await?;
+    assert!(
+      ApiToken::refresh(pool, "refresh1", client.id, &refresh_form)
+        .await
+        .is_err()
+    );
+    assert!(ApiToken::validate(pool, "access1").await.is_err());
+    assert!(ApiToken::validate(pool, "access2").await.is_ok());
+
+    // Revoking the refresh token also revokes the access token
+    assert_eq!(
+      1,
+      ApiToken::revoke_oauth(pool, "refresh2", client.id).await?
+    );
+    assert!(ApiToken::validate(pool, "access2").await.is_err());
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
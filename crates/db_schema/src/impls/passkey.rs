@@ -0,0 +1,263 @@
+use crate::{
+  newtypes::{LocalUserId, PasskeyId},
+  source::passkey::{Passkey, PasskeyChallenge, PasskeyChallengeInsertForm, PasskeyInsertForm},
+};
+use diesel::{
+  ExpressionMethods,
+  NullableExpressionMethods,
+  QueryDsl,
+  delete,
+  dsl::exists,
+  insert_into,
+  select,
+  update,
+};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::{passkey, passkey_challenge};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  utils::now,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl Passkey {
+  pub async fn create(pool: &mut DbPool<'_>, form: &PasskeyInsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(passkey::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  pub async fn read_by_credential_id(
+    pool: &mut DbPool<'_>,
+    credential_id: &str,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    passkey::table
+      .filter(passkey::credential_id.eq(credential_id))
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::InvalidPasskey)
+  }
+
+  pub async fn list(pool: &mut DbPool<'_>, local_user_id: LocalUserId) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    passkey::table
+      .filter(passkey::local_user_id.eq(local_user_id))
+      .order_by(passkey::id)
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  pub async fn exists_for_user(
+    pool: &mut DbPool<'_>,
+    local_user_id: LocalUserId,
+  ) -> LemmyResult<bool> {
+    let conn = &mut get_conn(pool).await?;
+    select(exists(
+      passkey::table.filter(passkey::local_user_id.eq(local_user_id)),
+    ))
+    .get_result(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Renames a passkey. Users can only rename their own passkeys.
+  pub async fn update_name(
+    pool: &mut DbPool<'_>,
+    id: PasskeyId,
+    local_user_id: LocalUserId,
+    name: &str,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    update(
+      passkey::table
+        .find(id)
+        .filter(passkey::local_user_id.eq(local_user_id)),
+    )
+    .set(passkey::name.eq(name))
+    .get_result::<Self>(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Stores the signature counter after a successful login.
+  pub async fn mark_as_used(
+    pool: &mut DbPool<'_>,
+    id: PasskeyId,
+    sign_count: i64,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    update(passkey::table.find(id))
+      .set((
+        passkey::sign_count.eq(sign_count),
+        passkey::last_used_at.eq(now().nullable()),
+      ))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    Ok(())
+  }
+
+  /// Deletes a passkey. Users can only delete their own passkeys.
+  pub async fn delete(
+    pool: &mut DbPool<'_>,
+    id: PasskeyId,
+    local_user_id: LocalUserId,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(
+      passkey::table
+        .find(id)
+        .filter(passkey::local_user_id.eq(local_user_id)),
+    )
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
+
+impl PasskeyChallenge {
+  pub async fn create(
+    pool: &mut DbPool<'_>,
+    form: &PasskeyChallengeInsertForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(passkey_challenge::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  /// Deletes and returns the given challenge, so that it can only be used once. Fails if the
+  /// challenge has expired.
+  pub async fn consume(pool: &mut DbPool<'_>, challenge: &str) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    delete(
+      passkey_challenge::table
+        .find(challenge)
+        .filter(passkey_challenge::expires_at.gt(now())),
+    )
+    .get_result::<Self>(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::InvalidPasskey)
+  }
+
+  /// Removes challenges which expired without being used.
+  pub async fn delete_expired(pool: &mut DbPool<'_>) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(passkey_challenge::table.filter(passkey_challenge::expires_at.le(now())))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::{
+    instance::Instance,
+    local_user::{LocalUser, LocalUserInsertForm},
+    passkey::{Passkey, PasskeyChallenge, PasskeyChallengeInsertForm, PasskeyInsertForm},
+    person::{Person, PersonInsertForm},
+  };
+  use chrono::{Duration, Utc};
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_passkey() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person = Person::create(
+      pool,
+      &PersonInsertForm::test_form(instance.id, "passkey_user"),
+    )
+    .await?;
+    let local_user =
+      LocalUser::create(pool, &LocalUserInsertForm::test_form(person.id), vec![]).await?;
+    let other_person = Person::create(
+      pool,
+      &PersonInsertForm::test_form(instance.id, "passkey_thief"),
+    )
+    .await?;
+    let other_local_user = LocalUser::create(
+      pool,
+      &LocalUserInsertForm::test_form(other_person.id),
+      vec![],
+    )
+    .await?;
+
+    assert!(!Passkey::exists_for_user(pool, local_user.id).await?);
+    let form = PasskeyInsertForm::new(
+      local_user.id,
+      "Laptop".to_string(),
+      "credential1".to_string(),
+      vec![1, 2, 3],
+      0,
+    );
+    let passkey = Passkey::create(pool, &form).await?;
+    assert!(Passkey::exists_for_user(pool, local_user.id).await?);
+    assert_eq!(
+      passkey,
+      Passkey::read_by_credential_id(pool, "credential1").await?
+    );
+
+    // Only the owner can rename or delete a passkey
+    assert!(
+      Passkey::update_name(pool, passkey.id, other_local_user.id, "Mine")
+        .await
+        .is_err()
+    );
+    let renamed = Passkey::update_name(pool, passkey.id, local_user.id, "Phone").await?;
+    assert_eq!("Phone", renamed.name);
+
+    Passkey::mark_as_used(pool, passkey.id, 5).await?;
+    let used = Passkey::read_by_credential_id(pool, "credential1").await?;
+    assert_eq!(5, used.sign_count);
+    assert!(used.last_used_at.is_some());
+
+    assert_eq!(
+      0,
+      Passkey::delete(pool, passkey.id, other_local_user.id).await?
+    );
+    assert_eq!(1, Passkey::delete(pool, passkey.id, local_user.id).await?);
+    assert_eq!(0, Passkey::list(pool, local_user.id).await?.len());
+
+    // Challenges can only be used once, and not after they expire
+    let form = PasskeyChallengeInsertForm::new(
+      "challenge1".to_string(),
+      None,
+      Utc::now() + Duration::minutes(5),
+    );
+    PasskeyChallenge::create(pool, &form).await?;
+    assert_eq!(
+      None,
+      PasskeyChallenge::consume(pool, "challenge1")
+        .await?
+        .local_user_id
+    );
+    assert!(PasskeyChallenge::consume(pool, "challenge1").await.is_err());
+
+    let form = PasskeyChallengeInsertForm::new(
+      "challenge2".to_string(),
+      Some(local_user.id),
+      Utc::now() - Duration::minutes(1),
+    );
+    PasskeyChallenge::create(pool, &form).await?;
+    assert!(PasskeyChallenge::consume(pool, "challenge2").await.is_err());
+    assert_eq!(1, PasskeyChallenge::delete_expired(pool).await?);
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -1,32 +1,44 @@
 use crate::{
   diesel::{BoolExpressionMethods, NullableExpressionMethods, OptionalExtension},
   newtypes::{CommunityId, LocalUserId},
-  source::person::{
-    Person,
-    PersonActions,
-    PersonBlockForm,
-    PersonFollowerForm,
-    PersonInsertForm,
-    PersonNoteForm,
-    PersonUpdateForm,
+  source::{
+    community::CommunityModeratorForm,
+    person::{
+      Person,
+      PersonActions,
+      PersonBlockForm,
+      PersonFollowerForm,
+      PersonInsertForm,
+      PersonNoteForm,
+      PersonUpdateForm,
+    },
   },
   traits::{ApubActor, Blockable, Followable},
   utils::format_actor_url,
 };
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use diesel::{
   ExpressionMethods,
   JoinOnDsl,
   QueryDsl,
   dsl::{exists, insert_into, not, select},
   expression::SelectableHelper,
+  upsert::excluded,
 };
-use diesel_async::RunQueryDsl;
+use diesel_async::{RunQueryDsl, scoped_futures::ScopedFutureExt};
 use diesel_uplete::{UpleteCount, uplete};
 use lemmy_db_schema_file::{
   InstanceId,
   PersonId,
-  schema::{instance, instance_actions, local_user, person, person_actions},
+  schema::{
+    community,
+    community_actions,
+    instance,
+    instance_actions,
+    local_user,
+    person,
+    person_actions,
+  },
 };
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
@@ -152,6 +164,120 @@ impl Person {
     .then_some(())
     .ok_or(LemmyErrorType::UsernameAlreadyTaken.into())
   }
+
+  /// Marks the person as moved to the new account. Followers of the old account are changed to
+  /// follow the new one, and the new account takes over moderator roles in local communities.
+  pub async fn apply_move(
+    pool: &mut DbPool<'_>,
+    old_person_id: PersonId,
+    new_person: &Person,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    let new_person_id = new_person.id;
+    let new_ap_id = new_person.ap_id.clone();
+
+    conn
+      .run_transaction(|conn| {
+        async move {
+          let followers = person_actions::table
+            .filter(person_actions::target_id.eq(old_person_id))
+            .filter(person_actions::person_id.ne(new_person_id))
+            .filter(person_actions::followed_at.is_not_null())
+            .select((
+              person_actions::person_id,
+              person_actions::followed_at.assume_not_null(),
+              person_actions::follow_pending.assume_not_null(),
+            ))
+            .load::<(PersonId, DateTime<Utc>, bool)>(conn)
+            .await?
+            .into_iter()
+            .map(
+              |(person_id, followed_at, follow_pending)| PersonFollowerForm {
+                target_id: new_person_id,
+                person_id,
+                follow_pending,
+                followed_at,
+              },
+            )
+            .collect::<Vec<_>>();
+          insert_into(person_actions::table)
+            .values(followers)
+            .on_conflict((person_actions::person_id, person_actions::target_id))
+            .do_update()
+            .set((
+              person_actions::followed_at.eq(excluded(person_actions::followed_at)),
+              person_actions::follow_pending.eq(excluded(person_actions::follow_pending)),
+            ))
+            .execute(conn)
+            .await?;
+          uplete(person_actions::table.filter(person_actions::target_id.eq(old_person_id)))
+            .set_null(person_actions::followed_at)
+            .set_null(person_actions::follow_pending)
+            .get_result::<UpleteCount>(conn)
+            .await?;
+
+          // Moderators of remote communities are managed by the community's instance
+          let local_moderated = community_actions::table
+            .inner_join(community::table)
+            .filter(community_actions::person_id.eq(old_person_id))
+            .filter(community_actions::became_moderator_at.is_not_null())
+            .filter(community::local.eq(true))
+            .select((
+              community_actions::community_id,
+              community_actions::became_moderator_at.assume_not_null(),
+            ))
+            .load::<(CommunityId, DateTime<Utc>)>(conn)
+            .await?;
+          let community_ids = local_moderated
+            .iter()
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+          let moderators = local_moderated
+            .into_iter()
+            .map(
+              |(community_id, became_moderator_at)| CommunityModeratorForm {
+                community_id,
+                person_id: new_person_id,
+                // Keep the position in the list of moderators
+                became_moderator_at,
+              },
+            )
+            .collect::<Vec<_>>();
+          insert_into(community_actions::table)
+            .values(moderators)
+            .on_conflict((
+              community_actions::person_id,
+              community_actions::community_id,
+            ))
+            .do_update()
+            .set(
+              community_actions::became_moderator_at
+                .eq(excluded(community_actions::became_moderator_at)),
+            )
+            .execute(conn)
+            .await?;
+          uplete(
+            community_actions::table
+              .filter(community_actions::person_id.eq(old_person_id))
+              .filter(community_actions::community_id.eq_any(community_ids)),
+          )
+          .set_null(community_actions::became_moderator_at)
+          .get_result::<UpleteCount>(conn)
+          .await?;
+
+          diesel::update(person::table.find(old_person_id))
+            .set((
+              person::moved_to.eq(new_ap_id),
+              person::updated_at.eq(Utc::now()),
+            ))
+            .get_result::<Self>(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+        }
+        .scope_boxed()
+      })
+      .await
+  }
 }
 
 impl PersonInsertForm {
@@ -416,7 +542,7 @@ mod tests {
   use crate::{
     source::{
       comment::{Comment, CommentActions, CommentInsertForm, CommentLikeForm, CommentUpdateForm},
-      community::{Community, CommunityInsertForm},
+      community::{Community, CommunityActions, CommunityInsertForm, CommunityModeratorForm},
       person::{Person, PersonActions, PersonFollowerForm, PersonInsertForm, PersonUpdateForm},
       post::{Post, PostActions, PostInsertForm, PostLikeForm},
     },
@@ -459,6 +585,8 @@ mod tests {
       post_score: 0,
       comment_count: 0,
       comment_score: 0,
+      also_known_as: vec![],
+      moved_to: None,
     };
 
     let read_person = Person::read(pool, data.person.id).await?;
@@ -508,6 +636,48 @@ mod tests {
     Ok(())
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn apply_move() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = TestData::create(pool).await?;
+
+    let follower_form = PersonInsertForm::test_form(data.instance.id, "move_follower");
+    let follower = Person::create(pool, &follower_form).await?;
+    let new_person_form = PersonInsertForm::test_form(data.instance.id, "move_target");
+    let new_person = Person::create(pool, &new_person_form).await?;
+
+    let follow_form = PersonFollowerForm::new(data.person.id, follower.id, false);
+    PersonActions::follow(pool, &follow_form).await?;
+    let community_form = CommunityInsertForm {
+      local: Some(true),
+      ..CommunityInsertForm::new(data.instance.id, "move_community".into(), "pubkey".into())
+    };
+    let community = Community::create(pool, &community_form).await?;
+    let moderator_form = CommunityModeratorForm::new(community.id, data.person.id);
+    CommunityActions::join(pool, &moderator_form).await?;
+
+    let moved = Person::apply_move(pool, data.person.id, &new_person).await?;
+    assert_eq!(Some(new_person.ap_id.clone()), moved.moved_to);
+
+    // Followers and moderator roles were transferred
+    let old_followers = PersonActions::follower_inboxes(pool, data.person.id).await?;
+    assert!(old_followers.is_empty());
+    let new_followers = PersonActions::follower_inboxes(pool, new_person.id).await?;
+    assert_eq!(vec![follower.inbox_url.clone()], new_followers);
+    let old_moderated = CommunityActions::get_person_moderated_communities(pool, data.person.id);
+    assert!(old_moderated.await?.is_empty());
+    let new_moderated = CommunityActions::get_person_moderated_communities(pool, new_person.id);
+    assert_eq!(vec![community.id], new_moderated.await?);
+
+    Community::delete(pool, community.id).await?;
+    Person::delete(pool, follower.id).await?;
+    Person::delete(pool, new_person.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+
   #[tokio::test]
   #[serial]
   async fn test_aggregates() -> LemmyResult<()> {
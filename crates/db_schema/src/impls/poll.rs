@@ -0,0 +1,437 @@
+use crate::{
+  newtypes::PostId,
+  source::{
+    poll::{
+      Poll,
+      PollChoice,
+      PollChoiceInsertForm,
+      PollInsertForm,
+      PollView,
+      PollVote,
+      PollVoteForm,
+    },
+    post::{Post, PostInsertForm},
+  },
+};
+use chrono::{DateTime, TimeDelta, Utc};
+use diesel::{
+  ExpressionMethods,
+  OptionalExtension,
+  QueryDsl,
+  SelectableHelper,
+  delete,
+  deserialize::FromSql,
+  dsl::{exists, insert_into, select, update},
+  pg::{Pg, PgValue},
+  serialize::ToSql,
+  sql_types::Json,
+  upsert::excluded,
+};
+use diesel_async::{RunQueryDsl, scoped_futures::ScopedFutureExt};
+use lemmy_db_schema_file::{
+  PersonId,
+  schema::{poll, poll_choice, poll_vote, post, post_actions},
+};
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl Poll {
+  /// Creates a local post together with its poll and choices, in a single transaction.
+  pub async fn create(
+    pool: &mut DbPool<'_>,
+    post_form: &PostInsertForm,
+    multiple_choice: Option<bool>,
+    end_at: Option<DateTime<Utc>>,
+    choices: &[String],
+  ) -> LemmyResult<(Post, Self)> {
+    let conn = &mut get_conn(pool).await?;
+
+    conn
+      .run_transaction(|conn| {
+        async move {
+          let post = insert_into(post::table)
+            .values(post_form)
+            .get_result::<Post>(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntCreate)?;
+          let form = PollInsertForm {
+            multiple_choice,
+            end_at,
+            ..PollInsertForm::new(post.id)
+          };
+          let poll = insert_into(poll::table)
+            .values(form)
+            .get_result::<Self>(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntCreate)?;
+          let choice_forms = choices
+            .iter()
+            .map(|name| PollChoiceInsertForm::new(post.id, name.clone()))
+            .collect::<Vec<_>>();
+          insert_into(poll_choice::table)
+            .values(choice_forms)
+            .execute(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntCreate)?;
+          Ok((post, poll))
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+
+  /// Inserts or updates a poll received over federation. Vote counts are taken from the origin
+  /// instance, and choices which are missing from the new list are removed.
+  pub async fn upsert_apub(
+    pool: &mut DbPool<'_>,
+    form: &PollInsertForm,
+    choices: &[PollChoiceInsertForm],
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    let names = choices.iter().map(|c| c.name.clone()).collect::<Vec<_>>();
+
+    conn
+      .run_transaction(|conn| {
+        async move {
+          let poll = insert_into(poll::table)
+            .values(form)
+            .on_conflict(poll::post_id)
+            .do_update()
+            .set(form)
+            .get_result::<Self>(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+          delete(
+            poll_choice::table
+              .filter(poll_choice::post_id.eq(form.post_id))
+              .filter(poll_choice::name.ne_all(names)),
+          )
+          .execute(conn)
+          .await
+          .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+          insert_into(poll_choice::table)
+            .values(choices)
+            .on_conflict((poll_choice::post_id, poll_choice::name))
+            .do_update()
+            .set(poll_choice::vote_count.eq(excluded(poll_choice::vote_count)))
+            .execute(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+          Ok(poll)
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+
+  pub async fn read(pool: &mut DbPool<'_>, post_id: PostId) -> LemmyResult<Option<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    poll::table
+      .find(post_id)
+      .first(conn)
+      .await
+      .optional()
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Marks the vote counts of a local poll as changed, so that they get federated by
+  /// [Poll::take_pending_updates]. Further changes until then are federated together.
+  pub async fn mark_pending_update(pool: &mut DbPool<'_>, post_id: PostId) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    update(
+      poll::table
+        .find(post_id)
+        .filter(poll::pending_update_at.is_null()),
+    )
+    .set(poll::pending_update_at.eq(Utc::now()))
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    Ok(())
+  }
+
+  /// Returns the polls whose vote counts changed at least `delay` ago, and clears their pending
+  /// update. This is done in a single statement, so each update is only returned once even with
+  /// multiple processes.
+  pub async fn take_pending_updates(
+    pool: &mut DbPool<'_>,
+    delay: TimeDelta,
+  ) -> LemmyResult<Vec<PostId>> {
+    let conn = &mut get_conn(pool).await?;
+    update(poll::table.filter(poll::pending_update_at.le(Utc::now() - delay)))
+      .set(poll::pending_update_at.eq(None::<DateTime<Utc>>))
+      .returning(poll::post_id)
+      .get_results(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
+  /// Polls which reached their end time can't be voted on anymore.
+  pub fn is_closed(&self) -> bool {
+    self.end_at.is_some_and(|e| e < Utc::now())
+  }
+}
+
+impl PollChoice {
+  pub async fn read_for_post(pool: &mut DbPool<'_>, post_id: PostId) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    poll_choice::table
+      .filter(poll_choice::post_id.eq(post_id))
+      .order_by(poll_choice::id)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+impl PollVote {
+  /// Adds the votes of a single person in a single poll, and updates the vote counts. Votes which
+  /// already exist are ignored, only the newly inserted ones are returned. In single choice polls
+  /// only one vote per person is allowed, the poll row is locked so that concurrent votes can't
+  /// bypass this.
+  pub async fn vote(pool: &mut DbPool<'_>, forms: &[PollVoteForm]) -> LemmyResult<Vec<Self>> {
+    let Some(first) = forms.first() else {
+      return Ok(vec![]);
+    };
+    let (post_id, person_id) = (first.post_id, first.person_id);
+    let conn = &mut get_conn(pool).await?;
+
+    conn
+      .run_transaction(|conn| {
+        async move {
+          let multiple_choice = poll::table
+            .find(post_id)
+            .select(poll::multiple_choice)
+            .for_update()
+            .first::<bool>(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::NotFound)?;
+          let voted_before = select(exists(
+            poll_vote::table
+              .filter(poll_vote::post_id.eq(post_id))
+              .filter(poll_vote::person_id.eq(person_id)),
+          ))
+          .get_result::<bool>(conn)
+          .await?;
+          if !multiple_choice && (voted_before || forms.len() > 1) {
+            return Err(LemmyErrorType::AlreadyVotedInPoll.into());
+          }
+
+          let inserted = insert_into(poll_vote::table)
+            .values(forms)
+            .on_conflict_do_nothing()
+            .returning(Self::as_select())
+            .get_results::<Self>(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntCreate)?;
+          if inserted.is_empty() {
+            return Ok(inserted);
+          }
+
+          let choice_ids = inserted
+            .iter()
+            .map(|v| v.poll_choice_id)
+            .collect::<Vec<_>>();
+          update(poll_choice::table.filter(poll_choice::id.eq_any(choice_ids)))
+            .set(poll_choice::vote_count.eq(poll_choice::vote_count + 1))
+            .execute(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+          if !voted_before {
+            update(poll::table.find(post_id))
+              .set(poll::voter_count.eq(poll::voter_count + 1))
+              .execute(conn)
+              .await
+              .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+          }
+
+          insert_into(post_actions::table)
+            .values((
+              post_actions::post_id.eq(post_id),
+              post_actions::person_id.eq(person_id),
+              post_actions::poll_voted_at.eq(Utc::now()),
+            ))
+            .on_conflict((post_actions::person_id, post_actions::post_id))
+            .do_update()
+            .set(post_actions::poll_voted_at.eq(Utc::now()))
+            .execute(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+          Ok(inserted)
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+
+  pub async fn read_for_person(
+    pool: &mut DbPool<'_>,
+    post_id: PostId,
+    person_id: PersonId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    poll_vote::table
+      .filter(poll_vote::post_id.eq(post_id))
+      .filter(poll_vote::person_id.eq(person_id))
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+impl FromSql<Json, Pg> for PollView {
+  fn from_sql(bytes: PgValue) -> diesel::deserialize::Result<Self> {
+    let value = <serde_json::Value as FromSql<Json, Pg>>::from_sql(bytes)?;
+    Ok(serde_json::from_value::<PollView>(value)?)
+  }
+}
+
+impl ToSql<Json, Pg> for PollView {
+  fn to_sql(&self, out: &mut diesel::serialize::Output<Pg>) -> diesel::serialize::Result {
+    let value = serde_json::to_value(self)?;
+    <serde_json::Value as ToSql<Json, Pg>>::to_sql(&value, &mut out.reborrow())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    newtypes::PostId,
+    source::{
+      community::{Community, CommunityInsertForm},
+      instance::Instance,
+      person::{Person, PersonInsertForm},
+      poll::{Poll, PollChoice, PollChoiceInsertForm, PollInsertForm, PollVote, PollVoteForm},
+      post::PostInsertForm,
+    },
+  };
+  use chrono::TimeDelta;
+  use lemmy_db_schema_file::PersonId;
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  fn vote_forms(
+    choices: &[&PollChoice],
+    person_id: PersonId,
+    post_id: PostId,
+  ) -> Vec<PollVoteForm> {
+    choices
+      .iter()
+      .map(|c| PollVoteForm {
+        poll_choice_id: c.id,
+        person_id,
+        post_id,
+      })
+      .collect()
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_poll_votes() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let alice = Person::create(pool, &PersonInsertForm::test_form(instance.id, "alice")).await?;
+    let bob = Person::create(pool, &PersonInsertForm::test_form(instance.id, "bob")).await?;
+    let community = Community::create(
+      pool,
+      &CommunityInsertForm::new(instance.id, "poll_community".into(), "pubkey".into()),
+    )
+    .await?;
+    let post_form = PostInsertForm::new("Favourite fruit?".into(), alice.id, community.id);
+    let (post, poll) = Poll::create(
+      pool,
+      &post_form,
+      Some(true),
+      None,
+      &["apple".into(), "pear".into()],
+    )
+    .await?;
+    assert!(poll.multiple_choice);
+    assert!(!poll.is_closed());
+    let choices = PollChoice::read_for_post(pool, post.id).await?;
+    let [apple, pear] = choices.as_slice() else {
+      panic!("wrong number of poll choices");
+    };
+
+    // Voting twice for the same choice only counts once
+    PollVote::vote(pool, &vote_forms(&[apple, pear], alice.id, post.id)).await?;
+    let repeated = PollVote::vote(pool, &vote_forms(&[apple], alice.id, post.id)).await?;
+    assert!(repeated.is_empty());
+    PollVote::vote(pool, &vote_forms(&[pear], bob.id, post.id)).await?;
+
+    assert_eq!(
+      Some(2),
+      Poll::read(pool, post.id).await?.map(|p| p.voter_count)
+    );
+    let counts = PollChoice::read_for_post(pool, post.id)
+      .await?
+      .into_iter()
+      .map(|c| (c.name, c.vote_count))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      vec![("apple".to_string(), 1), ("pear".to_string(), 2)],
+      counts
+    );
+    assert_eq!(
+      2,
+      PollVote::read_for_person(pool, post.id, alice.id)
+        .await?
+        .len()
+    );
+
+    // Federated updates overwrite the counts and remove missing choices
+    let apub_choices = [
+      PollChoiceInsertForm {
+        vote_count: Some(5),
+        ..PollChoiceInsertForm::new(post.id, "pear".into())
+      },
+      PollChoiceInsertForm::new(post.id, "plum".into()),
+    ];
+    let form = PollInsertForm {
+      voter_count: Some(5),
+      ..PollInsertForm::new(post.id)
+    };
+    let poll = Poll::upsert_apub(pool, &form, &apub_choices).await?;
+    assert_eq!(5, poll.voter_count);
+    let counts = PollChoice::read_for_post(pool, post.id)
+      .await?
+      .into_iter()
+      .map(|c| (c.name, c.vote_count))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      vec![("pear".to_string(), 5), ("plum".to_string(), 0)],
+      counts
+    );
+
+    // Single choice polls only allow one vote per person
+    let post_form = PostInsertForm::new("Best colour?".into(), alice.id, community.id);
+    let (single_post, single_poll) =
+      Poll::create(pool, &post_form, None, None, &["red".into(), "blue".into()]).await?;
+    assert!(!single_poll.multiple_choice);
+    let choices = PollChoice::read_for_post(pool, single_post.id).await?;
+    let [red, blue] = choices.as_slice() else {
+      panic!("wrong number of poll choices");
+    };
+    let both = PollVote::vote(pool, &vote_forms(&[red, blue], alice.id, single_post.id)).await;
+    assert!(both.is_err());
+    PollVote::vote(pool, &vote_forms(&[red], alice.id, single_post.id)).await?;
+    let second = PollVote::vote(pool, &vote_forms(&[blue], alice.id, single_post.id)).await;
+    assert!(second.is_err());
+
+    // Pending updates are only returned after the delay, and only once
+    Poll::mark_pending_update(pool, single_post.id).await?;
+    let delayed = Poll::take_pending_updates(pool, TimeDelta::minutes(1)).await?;
+    assert!(delayed.is_empty());
+    let due = Poll::take_pending_updates(pool, TimeDelta::zero()).await?;
+    assert_eq!(vec![single_post.id], due);
+    let again = Poll::take_pending_updates(pool, TimeDelta::zero()).await?;
+    assert!(again.is_empty());
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,97 @@
+use crate::{newtypes::PostId, source::post_history::PostHistory};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::post_history;
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl PostHistory {
+  /// Lists the previous versions of a post, oldest first.
+  pub async fn list_for_post(pool: &mut DbPool<'_>, post_id: PostId) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    post_history::table
+      .filter(post_history::post_id.eq(post_id))
+      .order_by(post_history::id)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::{
+    community::{Community, CommunityInsertForm},
+    instance::Instance,
+    person::{Person, PersonInsertForm},
+    post::{Post, PostInsertForm, PostUpdateForm},
+    post_history::PostHistory,
+  };
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_post_history() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person = Person::create(pool, &PersonInsertForm::test_form(instance.id, "jim")).await?;
+    let community = Community::create(
+      pool,
+      &CommunityInsertForm::new(instance.id, "history".into(), "pubkey".into()),
+    )
+    .await?;
+    let post = Post::create(
+      pool,
+      &PostInsertForm {
+        body: Some("first body".into()),
+        ..PostInsertForm::new("first".into(), person.id, community.id)
+      },
+    )
+    .await?;
+    assert!(PostHistory::list_for_post(pool, post.id).await?.is_empty());
+
+    // Changes which don't touch the text are not recorded
+    let form = PostUpdateForm {
+      nsfw: Some(true),
+      ..Default::default()
+    };
+    Post::update(pool, post.id, &form).await?;
+    assert!(PostHistory::list_for_post(pool, post.id).await?.is_empty());
+
+    let form = PostUpdateForm {
+      name: Some("second".into()),
+      ..Default::default()
+    };
+    Post::update(pool, post.id, &form).await?;
+    let form = PostUpdateForm {
+      body: Some(Some("third body".into())),
+      ..Default::default()
+    };
+    Post::update(pool, post.id, &form).await?;
+
+    let history = PostHistory::list_for_post(pool, post.id)
+      .await?
+      .into_iter()
+      .map(|h| (h.name, h.body))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      vec![
+        ("first".to_string(), Some("first body".to_string())),
+        ("second".to_string(), Some("first body".to_string()))
+      ],
+      history
+    );
+
+    // Permanently deleting the post also removes its history
+    Post::permadelete_for_creator(pool, person.id).await?;
+    assert!(PostHistory::list_for_post(pool, post.id).await?.is_empty());
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,145 @@
+use crate::{
+  newtypes::LocalUserId,
+  source::push_subscription::{PushSubscription, PushSubscriptionInsertForm},
+};
+use diesel::{ExpressionMethods, QueryDsl, SelectableHelper, delete, insert_into};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::{
+  PersonId,
+  schema::{local_user, push_subscription},
+};
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl PushSubscription {
+  /// Stores a subscription. The same endpoint can only belong to a single user, so subscribing
+  /// again replaces the keys and the owner.
+  pub async fn upsert(
+    pool: &mut DbPool<'_>,
+    form: &PushSubscriptionInsertForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(push_subscription::table)
+      .values(form)
+      .on_conflict(push_subscription::endpoint)
+      .do_update()
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  /// All subscriptions of the local user with the given person id.
+  pub async fn list_for_person(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    push_subscription::table
+      .inner_join(local_user::table)
+      .filter(local_user::person_id.eq(person_id))
+      .select(Self::as_select())
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Unsubscribes. Users can only delete their own subscriptions.
+  pub async fn delete(
+    pool: &mut DbPool<'_>,
+    endpoint: &str,
+    local_user_id: LocalUserId,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(
+      push_subscription::table
+        .filter(push_subscription::endpoint.eq(endpoint))
+        .filter(push_subscription::local_user_id.eq(local_user_id)),
+    )
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+
+  /// Removes a subscription which the push service reported as expired.
+  pub async fn delete_expired(pool: &mut DbPool<'_>, endpoint: &str) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(push_subscription::table.filter(push_subscription::endpoint.eq(endpoint)))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::{
+    instance::Instance,
+    local_user::{LocalUser, LocalUserInsertForm},
+    person::{Person, PersonInsertForm},
+    push_subscription::{PushSubscription, PushSubscriptionInsertForm},
+  };
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_push_subscription() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person =
+      Person::create(pool, &PersonInsertForm::test_form(instance.id, "push_user")).await?;
+    let local_user =
+      LocalUser::create(pool, &LocalUserInsertForm::test_form(person.id), vec![]).await?;
+    let other_person = Person::create(
+      pool,
+      &PersonInsertForm::test_form(instance.id, "push_other"),
+    )
+    .await?;
+    let other_local_user = LocalUser::create(
+      pool,
+      &LocalUserInsertForm::test_form(other_person.id),
+      vec![],
+    )
+    .await?;
+
+    let endpoint = "https://push.example.com/abc";
+    let form = PushSubscriptionInsertForm::new(
+      local_user.id,
+      endpoint.to_string(),
+      "key1".to_string(),
+      "auth1".to_string(),
+    );
+    PushSubscription::upsert(pool, &form).await?;
+
+    // Subscribing again with the same endpoint replaces the keys
+    let form = PushSubscriptionInsertForm {
+      p256dh: "key2".to_string(),
+      ..form
+    };
+    let subscription = PushSubscription::upsert(pool, &form).await?;
+    assert_eq!("key2", subscription.p256dh);
+    assert_eq!(
+      vec![subscription],
+      PushSubscription::list_for_person(pool, person.id).await?
+    );
+
+    // Only the owner can unsubscribe
+    assert_eq!(
+      0,
+      PushSubscription::delete(pool, endpoint, other_local_user.id).await?
+    );
+    assert_eq!(
+      1,
+      PushSubscription::delete(pool, endpoint, local_user.id).await?
+    );
+    assert_eq!(0, PushSubscription::delete_expired(pool, endpoint).await?);
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
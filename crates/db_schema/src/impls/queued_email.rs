@@ -0,0 +1,262 @@
+use crate::{
+  QueuedEmailStatus,
+  newtypes::QueuedEmailId,
+  source::queued_email::{
+    QueuedEmail,
+    QueuedEmailInsertForm,
+    QueuedEmailUpdateForm,
+    queued_email_keys as key,
+  },
+  utils::limit_fetch,
+};
+use chrono::{DateTime, Utc};
+use diesel::{
+  ExpressionMethods,
+  NullableExpressionMethods,
+  QueryDsl,
+  delete,
+  dsl::{IntervalDsl, count_star},
+  insert_into,
+};
+use diesel_async::{RunQueryDsl, scoped_futures::ScopedFutureExt};
+use i_love_jesus::SortDirection;
+use lemmy_db_schema_file::schema::queued_email;
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  pagination::{
+    CursorData,
+    PagedResponse,
+    PaginationCursor,
+    PaginationCursorConversion,
+    paginate_response,
+  },
+  traits::Crud,
+  utils::now,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+/// Maximum number of due emails which are sent in one batch.
+const PENDING_EMAILS_BATCH: i64 = 100;
+
+impl Crud for QueuedEmail {
+  type InsertForm = QueuedEmailInsertForm;
+  type UpdateForm = QueuedEmailUpdateForm;
+  type IdType = QueuedEmailId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(queued_email::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    email_id: QueuedEmailId,
+    form: &Self::UpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(queued_email::table.find(email_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+impl PaginationCursorConversion for QueuedEmail {
+  type PaginatedType = QueuedEmail;
+
+  fn to_cursor(&self) -> CursorData {
+    CursorData::new_id(self.id.0)
+  }
+
+  async fn from_cursor(
+    cursor: CursorData,
+    pool: &mut DbPool<'_>,
+  ) -> LemmyResult<Self::PaginatedType> {
+    QueuedEmail::read(pool, QueuedEmailId(cursor.id()?)).await
+  }
+}
+
+impl QueuedEmail {
+  /// The email queue for admins, newest first.
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    status: QueuedEmailStatus,
+    page_cursor: Option<PaginationCursor>,
+    limit: Option<i64>,
+  ) -> LemmyResult<PagedResponse<Self>> {
+    let limit = limit_fetch(limit, None)?;
+    let mut query = queued_email::table.limit(limit).into_boxed();
+    query = match status {
+      QueuedEmailStatus::All => query,
+      QueuedEmailStatus::Pending => query.filter(queued_email::next_attempt_at.is_not_null()),
+      QueuedEmailStatus::Sent => query.filter(queued_email::sent_at.is_not_null()),
+      QueuedEmailStatus::Failed => query
+        .filter(queued_email::next_attempt_at.is_null())
+        .filter(queued_email::sent_at.is_null()),
+    };
+    let paginated_query = Self::paginate(query, &page_cursor, SortDirection::Desc, pool)
+      .await?
+      .then_order_by(key::id);
+
+    let conn = &mut get_conn(pool).await?;
+    let res = paginated_query
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)?;
+    paginate_response(res, limit, page_cursor)
+  }
+
+  /// Claims the emails whose next attempt is due. Their next attempt is moved to `lease_until`, and
+  /// rows which are locked by a concurrent claim are skipped, so that each email is only sent once
+  /// at a time.
+  pub async fn claim_pending(
+    pool: &mut DbPool<'_>,
+    lease_until: DateTime<Utc>,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+
+    conn
+      .run_transaction(|conn| {
+        async move {
+          let due = queued_email::table
+            .filter(queued_email::next_attempt_at.le(now().nullable()))
+            .order_by(queued_email::next_attempt_at)
+            .limit(PENDING_EMAILS_BATCH)
+            .select(queued_email::id)
+            .for_update()
+            .skip_locked()
+            .load::<QueuedEmailId>(conn)
+            .await?;
+          diesel::update(queued_email::table.filter(queued_email::id.eq_any(due)))
+            .set(queued_email::next_attempt_at.eq(lease_until))
+            .get_results::<Self>(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+
+  /// Number of emails which are waiting to be sent, and which failed permanently.
+  pub async fn count_pending_and_failed(pool: &mut DbPool<'_>) -> LemmyResult<(i64, i64)> {
+    let conn = &mut get_conn(pool).await?;
+    let pending = queued_email::table
+      .filter(queued_email::next_attempt_at.is_not_null())
+      .select(count_star())
+      .first::<i64>(conn)
+      .await?;
+    let failed = queued_email::table
+      .filter(queued_email::next_attempt_at.is_null())
+      .filter(queued_email::sent_at.is_null())
+      .select(count_star())
+      .first::<i64>(conn)
+      .await?;
+    Ok((pending, failed))
+  }
+
+  /// Emails contain tokens for password reset and similar, so they are only kept for a short
+  /// time.
+  pub async fn delete_old(pool: &mut DbPool<'_>) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(
+      queued_email::table
+        .filter(queued_email::next_attempt_at.is_null())
+        .filter(queued_email::published_at.lt(now() - 7.days())),
+    )
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{
+    QueuedEmailStatus,
+    source::queued_email::{QueuedEmail, QueuedEmailInsertForm, QueuedEmailUpdateForm},
+  };
+  use chrono::{TimeDelta, Utc};
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_queued_email() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let form = QueuedEmailInsertForm::new(
+      "user@example.com".to_string().into(),
+      "user".to_string(),
+      "subject".to_string(),
+      "<p>body</p>".to_string().into(),
+    );
+    let sent = QueuedEmail::create(pool, &form).await?;
+    let failed = QueuedEmail::create(pool, &form).await?;
+    let pending = QueuedEmail::create(pool, &form).await?;
+    let lease_until = Utc::now() + TimeDelta::minutes(5);
+    assert_eq!(
+      3,
+      QueuedEmail::claim_pending(pool, lease_until).await?.len()
+    );
+    // Claimed emails aren't returned again until the lease expires
+    assert!(
+      QueuedEmail::claim_pending(pool, lease_until)
+        .await?
+        .is_empty()
+    );
+    let form = QueuedEmailUpdateForm {
+      next_attempt_at: Some(Some(Utc::now())),
+      ..Default::default()
+    };
+    QueuedEmail::update(pool, pending.id, &form).await?;
+
+    let form = QueuedEmailUpdateForm {
+      attempts: Some(1),
+      next_attempt_at: Some(None),
+      sent_at: Some(Some(Utc::now())),
+      ..Default::default()
+    };
+    QueuedEmail::update(pool, sent.id, &form).await?;
+    let form = QueuedEmailUpdateForm {
+      attempts: Some(10),
+      next_attempt_at: Some(None),
+      last_error: Some(Some("connection refused".to_string())),
+      ..Default::default()
+    };
+    QueuedEmail::update(pool, failed.id, &form).await?;
+
+    assert_eq!(
+      vec![pending.id],
+      QueuedEmail::claim_pending(pool, lease_until)
+        .await?
+        .iter()
+        .map(|e| e.id)
+        .collect::<Vec<_>>()
+    );
+    assert_eq!((1, 1), QueuedEmail::count_pending_and_failed(pool).await?);
+    let all_list = QueuedEmail::list(pool, QueuedEmailStatus::All, None, None).await?;
+    assert_eq!(3, all_list.items.len());
+    let failed_list = QueuedEmail::list(pool, QueuedEmailStatus::Failed, None, None).await?;
+    assert_eq!(
+      vec![failed.id],
+      failed_list.items.iter().map(|e| e.id).collect::<Vec<_>>()
+    );
+
+    // Recent emails are kept
+    assert_eq!(0, QueuedEmail::delete_old(pool).await?);
+
+    for id in [sent.id, failed.id, pending.id] {
+      QueuedEmail::delete(pool, id).await?;
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,180 @@
+use crate::{
+  newtypes::ReceivedActivityLogId,
+  source::received_activity_log::{
+    ReceivedActivityLog,
+    ReceivedActivityLogInsertForm,
+    ReceivedActivityLogUpdateForm,
+    received_activity_log_keys as key,
+  },
+  utils::limit_fetch,
+};
+use diesel::{ExpressionMethods, QueryDsl, delete, dsl::max, insert_into};
+use diesel_async::RunQueryDsl;
+use i_love_jesus::SortDirection;
+use lemmy_db_schema_file::schema::received_activity_log;
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  pagination::{
+    CursorData,
+    PagedResponse,
+    PaginationCursor,
+    PaginationCursorConversion,
+    paginate_response,
+  },
+  traits::Crud,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl Crud for ReceivedActivityLog {
+  type InsertForm = ReceivedActivityLogInsertForm;
+  type UpdateForm = ReceivedActivityLogUpdateForm;
+  type IdType = ReceivedActivityLogId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(received_activity_log::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    id: ReceivedActivityLogId,
+    form: &Self::UpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(received_activity_log::table.find(id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+impl PaginationCursorConversion for ReceivedActivityLog {
+  type PaginatedType = ReceivedActivityLog;
+
+  fn to_cursor(&self) -> CursorData {
+    CursorData::new_id(self.id.0)
+  }
+
+  async fn from_cursor(
+    cursor: CursorData,
+    pool: &mut DbPool<'_>,
+  ) -> LemmyResult<Self::PaginatedType> {
+    ReceivedActivityLog::read(pool, ReceivedActivityLogId(cursor.id()?)).await
+  }
+}
+
+impl ReceivedActivityLog {
+  /// Searches the received activities for admins, newest first.
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    actor_id: Option<String>,
+    domain: Option<String>,
+    kind: Option<String>,
+    failed_only: bool,
+    page_cursor: Option<PaginationCursor>,
+    limit: Option<i64>,
+  ) -> LemmyResult<PagedResponse<Self>> {
+    let limit = limit_fetch(limit, None)?;
+    let mut query = received_activity_log::table.limit(limit).into_boxed();
+    if let Some(actor_id) = actor_id {
+      query = query.filter(received_activity_log::actor_id.eq(actor_id));
+    }
+    if let Some(domain) = domain {
+      query = query.filter(received_activity_log::domain.eq(domain));
+    }
+    if let Some(kind) = kind {
+      query = query.filter(received_activity_log::kind.eq(kind));
+    }
+    if failed_only {
+      query = query.filter(received_activity_log::success.eq(false));
+    }
+    let paginated_query = Self::paginate(query, &page_cursor, SortDirection::Desc, pool)
+      .await?
+      .then_order_by(key::id);
+
+    let conn = &mut get_conn(pool).await?;
+    let res = paginated_query
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)?;
+    paginate_response(res, limit, page_cursor)
+  }
+
+  /// Deletes the oldest activities, so that at most `max_activities` are kept.
+  pub async fn delete_old(pool: &mut DbPool<'_>, max_activities: i64) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    let max_id: Option<ReceivedActivityLogId> = received_activity_log::table
+      .select(max(received_activity_log::id))
+      .get_result(conn)
+      .await?;
+    let Some(max_id) = max_id else {
+      return Ok(0);
+    };
+    let oldest_kept = i64::from(max_id.0) - max_activities;
+    delete(
+      received_activity_log::table
+        .filter(received_activity_log::id.le(i32::try_from(oldest_kept.max(0))?)),
+    )
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::received_activity_log::{ReceivedActivityLog, ReceivedActivityLogInsertForm};
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_received_activity_log() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let form = |domain: &str, success: bool| ReceivedActivityLogInsertForm {
+      ap_id: Some(format!("https://{domain}/activities/1")),
+      actor_id: Some(format!("https://{domain}/u/alice")),
+      domain: Some(domain.to_string()),
+      kind: Some("Create".to_string()),
+      data: Some("{}".to_string()),
+      error: (!success).then(|| "error".to_string()),
+      ..ReceivedActivityLogInsertForm::new(true, success)
+    };
+    let first = ReceivedActivityLog::create(pool, &form("alpha.com", true)).await?;
+    let second = ReceivedActivityLog::create(pool, &form("beta.com", false)).await?;
+    let third = ReceivedActivityLog::create(pool, &form("beta.com", true)).await?;
+
+    let list = ReceivedActivityLog::list(
+      pool,
+      None,
+      Some("beta.com".to_string()),
+      None,
+      false,
+      None,
+      None,
+    )
+    .await?;
+    assert_eq!(vec![third.clone(), second.clone()], list.items);
+
+    let list = ReceivedActivityLog::list(pool, None, None, None, true, None, None).await?;
+    assert_eq!(vec![second.clone()], list.items);
+
+    // Only the newest two are kept
+    assert_eq!(1, ReceivedActivityLog::delete_old(pool, 2).await?);
+    assert!(ReceivedActivityLog::read(pool, first.id).await.is_err());
+    let list = ReceivedActivityLog::list(pool, None, None, None, false, None, None).await?;
+    assert_eq!(vec![third, second], list.items);
+
+    ReceivedActivityLog::delete_old(pool, 0).await?;
+    Ok(())
+  }
+}
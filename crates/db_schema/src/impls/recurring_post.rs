@@ -0,0 +1,144 @@
+use crate::{
+  newtypes::{CommunityId, RecurringPostId},
+  source::recurring_post::{RecurringPost, RecurringPostInsertForm, RecurringPostUpdateForm},
+};
+use diesel::{ExpressionMethods, QueryDsl, insert_into};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::recurring_post;
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  traits::Crud,
+  utils::now,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl Crud for RecurringPost {
+  type InsertForm = RecurringPostInsertForm;
+  type UpdateForm = RecurringPostUpdateForm;
+  type IdType = RecurringPostId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(recurring_post::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    recurring_post_id: RecurringPostId,
+    form: &Self::UpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(recurring_post::table.find(recurring_post_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+impl RecurringPost {
+  pub async fn list(pool: &mut DbPool<'_>, community_id: CommunityId) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    recurring_post::table
+      .filter(recurring_post::community_id.eq(community_id))
+      .order_by(recurring_post::id)
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Enabled templates whose next post should have been published already.
+  pub async fn list_due(pool: &mut DbPool<'_>) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    recurring_post::table
+      .filter(recurring_post::enabled)
+      .filter(recurring_post::next_publish_at.le(now()))
+      .order_by(recurring_post::next_publish_at)
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::{
+    community::{Community, CommunityInsertForm},
+    instance::Instance,
+    person::{Person, PersonInsertForm},
+    recurring_post::{RecurringPost, RecurringPostInsertForm, RecurringPostUpdateForm},
+  };
+  use chrono::{Days, Utc};
+  use lemmy_db_schema_file::enums::RecurringPostFrequency;
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_recurring_post_list_due() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person =
+      Person::create(pool, &PersonInsertForm::test_form(instance.id, "scheduler")).await?;
+    let community = Community::create(
+      pool,
+      &CommunityInsertForm::new(instance.id, "recurring".into(), "pubkey".into()),
+    )
+    .await?;
+
+    let yesterday = Utc::now() - Days::new(1);
+    let tomorrow = Utc::now() + Days::new(1);
+    let form = RecurringPostInsertForm::new(
+      community.id,
+      person.id,
+      "Weekly discussion {week}".to_string(),
+      RecurringPostFrequency::Weekly,
+      yesterday,
+    );
+    let due = RecurringPost::create(pool, &form).await?;
+    assert!(due.enabled);
+    assert!(!due.feature);
+
+    let form = RecurringPostInsertForm::new(
+      community.id,
+      person.id,
+      "Daily question".to_string(),
+      RecurringPostFrequency::Daily,
+      tomorrow,
+    );
+    RecurringPost::create(pool, &form).await?;
+
+    let form = RecurringPostInsertForm {
+      enabled: Some(false),
+      ..RecurringPostInsertForm::new(
+        community.id,
+        person.id,
+        "Monthly meta".to_string(),
+        RecurringPostFrequency::Monthly,
+        yesterday,
+      )
+    };
+    RecurringPost::create(pool, &form).await?;
+
+    assert_eq!(3, RecurringPost::list(pool, community.id).await?.len());
+    assert_eq!(vec![due.clone()], RecurringPost::list_due(pool).await?);
+
+    let form = RecurringPostUpdateForm {
+      next_publish_at: Some(tomorrow),
+      ..Default::default()
+    };
+    RecurringPost::update(pool, due.id, &form).await?;
+    assert!(RecurringPost::list_due(pool).await?.is_empty());
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
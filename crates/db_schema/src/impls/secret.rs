@@ -1,6 +1,7 @@
 use crate::source::secret::Secret;
+use diesel::{ExpressionMethods, QueryDsl, update};
 use diesel_async::RunQueryDsl;
-use lemmy_db_schema_file::schema::secret::dsl::secret;
+use lemmy_db_schema_file::schema::secret::dsl::{secret, vapid_private_key};
 use lemmy_diesel_utils::connection::{DbPool, get_conn};
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
 
@@ -11,6 +12,18 @@ impl Secret {
     Self::read_secrets(pool).await
   }
 
+  /// Stores the VAPID key unless another server process was faster, and returns the key which
+  /// is actually used.
+  pub async fn set_vapid_private_key(pool: &mut DbPool<'_>, key: &str) -> LemmyResult<Secret> {
+    let conn = &mut get_conn(pool).await?;
+    update(secret.filter(vapid_private_key.is_null()))
+      .set(vapid_private_key.eq(key))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    Self::read_secrets(&mut conn.into()).await
+  }
+
   async fn read_secrets(pool: &mut DbPool<'_>) -> LemmyResult<Self> {
     let conn = &mut get_conn(pool).await?;
     secret
@@ -0,0 +1,339 @@
+use crate::{
+  newtypes::{CommunityId, WebhookDeliveryId, WebhookId},
+  source::webhook::{
+    Webhook,
+    WebhookDelivery,
+    WebhookDeliveryInsertForm,
+    WebhookDeliveryUpdateForm,
+    WebhookInsertForm,
+    WebhookUpdateForm,
+    webhook_delivery_keys as key,
+  },
+  utils::limit_fetch,
+};
+use chrono::{DateTime, Utc};
+use diesel::{
+  BoolExpressionMethods,
+  ExpressionMethods,
+  NullableExpressionMethods,
+  PgArrayExpressionMethods,
+  QueryDsl,
+  insert_into,
+};
+use diesel_async::{RunQueryDsl, scoped_futures::ScopedFutureExt};
+use i_love_jesus::SortDirection;
+use lemmy_db_schema_file::{
+  PersonId,
+  enums::WebhookEventType,
+  schema::{webhook, webhook_delivery},
+};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  pagination::{
+    CursorData,
+    PagedResponse,
+    PaginationCursor,
+    PaginationCursorConversion,
+    paginate_response,
+  },
+  traits::Crud,
+  utils::now,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+/// Maximum number of due deliveries which are retried in one batch.
+const PENDING_DELIVERIES_BATCH: i64 = 100;
+
+impl Crud for Webhook {
+  type InsertForm = WebhookInsertForm;
+  type UpdateForm = WebhookUpdateForm;
+  type IdType = WebhookId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(webhook::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    webhook_id: WebhookId,
+    form: &Self::UpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(webhook::table.find(webhook_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+impl Webhook {
+  /// Lists the webhooks of a community, or the webhooks created by the given person if no
+  /// community is given.
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    community_id: Option<CommunityId>,
+    creator_id: PersonId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    let query = webhook::table.into_boxed();
+    let query = match community_id {
+      Some(community_id) => query.filter(webhook::community_id.eq(community_id)),
+      None => query.filter(webhook::creator_id.eq(creator_id)),
+    };
+    query
+      .order_by(webhook::id)
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Enabled webhooks which are subscribed to the given event. These are all site-wide webhooks,
+  /// plus the webhooks of the community where the event happened.
+  pub async fn list_for_event(
+    pool: &mut DbPool<'_>,
+    event: WebhookEventType,
+    community_id: Option<CommunityId>,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    let query = webhook::table
+      .filter(webhook::enabled)
+      .filter(webhook::events.contains(vec![Some(event)]))
+      .into_boxed();
+    let query = match community_id {
+      Some(community_id) => query.filter(
+        webhook::community_id
+          .is_null()
+          .or(webhook::community_id.eq(community_id)),
+      ),
+      None => query.filter(webhook::community_id.is_null()),
+    };
+    query
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+impl Crud for WebhookDelivery {
+  type InsertForm = WebhookDeliveryInsertForm;
+  type UpdateForm = WebhookDeliveryUpdateForm;
+  type IdType = WebhookDeliveryId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(webhook_delivery::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    delivery_id: WebhookDeliveryId,
+    form: &Self::UpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(webhook_delivery::table.find(delivery_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+impl PaginationCursorConversion for WebhookDelivery {
+  type PaginatedType = WebhookDelivery;
+
+  fn to_cursor(&self) -> CursorData {
+    CursorData::new_id(self.id.0)
+  }
+
+  async fn from_cursor(
+    cursor: CursorData,
+    pool: &mut DbPool<'_>,
+  ) -> LemmyResult<Self::PaginatedType> {
+    WebhookDelivery::read(pool, WebhookDeliveryId(cursor.id()?)).await
+  }
+}
+
+impl WebhookDelivery {
+  /// The delivery log of a webhook, newest first.
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    webhook_id: WebhookId,
+    page_cursor: Option<PaginationCursor>,
+    limit: Option<i64>,
+  ) -> LemmyResult<PagedResponse<Self>> {
+    let limit = limit_fetch(limit, None)?;
+    let query = webhook_delivery::table
+      .filter(webhook_delivery::webhook_id.eq(webhook_id))
+      .limit(limit)
+      .into_boxed();
+    let paginated_query = Self::paginate(query, &page_cursor, SortDirection::Desc, pool)
+      .await?
+      .then_order_by(key::id);
+
+    let conn = &mut get_conn(pool).await?;
+    let res = paginated_query
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)?;
+    paginate_response(res, limit, page_cursor)
+  }
+
+  /// Claims the deliveries whose next attempt is due, together with their (enabled) webhook. The
+  /// next attempt of the claimed deliveries is moved to `lease_until`, and rows which are locked by
+  /// a concurrent claim are skipped, so that each delivery is only attempted once at a time.
+  pub async fn claim_pending(
+    pool: &mut DbPool<'_>,
+    lease_until: DateTime<Utc>,
+  ) -> LemmyResult<Vec<(Self, Webhook)>> {
+    let conn = &mut get_conn(pool).await?;
+
+    conn
+      .run_transaction(|conn| {
+        async move {
+          let due = webhook_delivery::table
+            .filter(webhook_delivery::next_attempt_at.le(now().nullable()))
+            .filter(
+              webhook_delivery::webhook_id
+                .eq_any(webhook::table.filter(webhook::enabled).select(webhook::id)),
+            )
+            .order_by(webhook_delivery::next_attempt_at)
+            .limit(PENDING_DELIVERIES_BATCH)
+            .select(webhook_delivery::id)
+            .for_update()
+            .skip_locked()
+            .load::<WebhookDeliveryId>(conn)
+            .await?;
+          let claimed =
+            diesel::update(webhook_delivery::table.filter(webhook_delivery::id.eq_any(due)))
+              .set(webhook_delivery::next_attempt_at.eq(lease_until))
+              .get_results::<Self>(conn)
+              .await
+              .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+
+          let webhook_ids = claimed.iter().map(|d| d.webhook_id).collect::<Vec<_>>();
+          let webhooks = webhook::table
+            .filter(webhook::id.eq_any(webhook_ids))
+            .load::<Webhook>(conn)
+            .await?;
+          Ok(
+            claimed
+              .into_iter()
+              .filter_map(|d| {
+                let webhook = webhooks.iter().find(|w| w.id == d.webhook_id)?.clone();
+                Some((d, webhook))
+              })
+              .collect(),
+          )
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::source::{
+    community::{Community, CommunityInsertForm},
+    instance::Instance,
+    person::{Person, PersonInsertForm},
+    webhook::{Webhook, WebhookDelivery, WebhookDeliveryInsertForm, WebhookInsertForm},
+  };
+  use chrono::{TimeDelta, Utc};
+  use lemmy_db_schema_file::enums::WebhookEventType;
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+  use url::Url;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_webhook_list_for_event() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person = Person::create(pool, &PersonInsertForm::test_form(instance.id, "jim")).await?;
+    let community = Community::create(
+      pool,
+      &CommunityInsertForm::new(instance.id, "webhooks".into(), "pubkey".into()),
+    )
+    .await?;
+    let other_community = Community::create(
+      pool,
+      &CommunityInsertForm::new(instance.id, "webhooks_2".into(), "pubkey".into()),
+    )
+    .await?;
+
+    let url = Url::parse("https://example.com/hook")?;
+    let site_form = WebhookInsertForm::new(
+      None,
+      person.id,
+      url.clone().into(),
+      "secret".to_string().into(),
+      vec![Some(WebhookEventType::NewPost)],
+    );
+    let site_webhook = Webhook::create(pool, &site_form).await?;
+    let community_form = WebhookInsertForm::new(
+      Some(community.id),
+      person.id,
+      url.into(),
+      "secret".to_string().into(),
+      vec![
+        Some(WebhookEventType::NewPost),
+        Some(WebhookEventType::NewComment),
+      ],
+    );
+    let community_webhook = Webhook::create(pool, &community_form).await?;
+
+    let ids = |hooks: Vec<Webhook>| hooks.into_iter().map(|h| h.id).collect::<Vec<_>>();
+    let new_post =
+      Webhook::list_for_event(pool, WebhookEventType::NewPost, Some(community.id)).await?;
+    assert_eq!(vec![site_webhook.id, community_webhook.id], ids(new_post));
+    let other_post =
+      Webhook::list_for_event(pool, WebhookEventType::NewPost, Some(other_community.id)).await?;
+    assert_eq!(vec![site_webhook.id], ids(other_post));
+    let new_comment =
+      Webhook::list_for_event(pool, WebhookEventType::NewComment, Some(community.id)).await?;
+    assert_eq!(vec![community_webhook.id], ids(new_comment));
+    let report = Webhook::list_for_event(pool, WebhookEventType::NewReport, None).await?;
+    assert!(report.is_empty());
+
+    // New deliveries are due immediately
+    let delivery_form =
+      WebhookDeliveryInsertForm::new(site_webhook.id, WebhookEventType::NewPost, "{}".to_string());
+    let delivery = WebhookDelivery::create(pool, &delivery_form).await?;
+    let lease_until = Utc::now() + TimeDelta::minutes(5);
+    let pending = WebhookDelivery::claim_pending(pool, lease_until).await?;
+    assert_eq!(
+      vec![delivery.id],
+      pending.iter().map(|p| p.0.id).collect::<Vec<_>>()
+    );
+    // Claimed deliveries aren't returned again until the lease expires
+    let claimed_again = WebhookDelivery::claim_pending(pool, lease_until).await?;
+    assert!(claimed_again.is_empty());
+
+    // Without a community, only the webhooks of the caller are listed
+    let own = Webhook::list(pool, None, person.id).await?;
+    assert_eq!(vec![site_webhook.id, community_webhook.id], ids(own));
+    let other_person =
+      Person::create(pool, &PersonInsertForm::test_form(instance.id, "jill")).await?;
+    assert!(Webhook::list(pool, None, other_person.id).await?.is_empty());
+    let log = WebhookDelivery::list(pool, site_webhook.id, None, None).await?;
+    assert_eq!(1, log.items.len());
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -135,6 +135,19 @@ pub enum SearchType {
   MultiCommunities,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+/// The sort order for search results.
+pub enum SearchSortType {
+  /// Newest first. This is used when no sort is given.
+  New,
+  /// Order posts and comments by how well they match the search term. Only available when
+  /// full text search is enabled for the site, otherwise the same as `New`.
+  Relevance,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
@@ -229,6 +242,21 @@ pub enum LikeType {
   DislikedOnly,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+/// Filter for the outgoing email queue.
+pub enum QueuedEmailStatus {
+  #[default]
+  All,
+  /// Not sent yet, but will be retried.
+  Pending,
+  Sent,
+  /// Sending failed too often, so it was given up.
+  Failed,
+}
+
 /// Wrapper for assert_eq! macro. Checks that vec matches the given length, and prints the
 /// vec on failure.
 #[macro_export]
@@ -261,6 +289,8 @@ pub type Person1AliasAllColumnsTuple = (
   AliasedField<aliases::Person1, person::post_score>,
   AliasedField<aliases::Person1, person::comment_count>,
   AliasedField<aliases::Person1, person::comment_score>,
+  AliasedField<aliases::Person1, person::also_known_as>,
+  AliasedField<aliases::Person1, person::moved_to>,
 );
 
 #[cfg(feature = "full")]
@@ -288,6 +318,8 @@ pub type Person2AliasAllColumnsTuple = (
   AliasedField<aliases::Person2, person::post_score>,
   AliasedField<aliases::Person2, person::comment_count>,
   AliasedField<aliases::Person2, person::comment_score>,
+  AliasedField<aliases::Person2, person::also_known_as>,
+  AliasedField<aliases::Person2, person::moved_to>,
 );
 
 #[cfg(feature = "full")]
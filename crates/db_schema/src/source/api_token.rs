@@ -0,0 +1,60 @@
+use crate::newtypes::{ApiTokenId, LocalUserId, OAuthClientId};
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::enums::ApiTokenScope;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::api_token;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// A personal access token for bots and third-party apps, which is limited to the given scopes.
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = api_token))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct ApiToken {
+  pub id: ApiTokenId,
+  #[serde(skip)]
+  pub local_user_id: LocalUserId,
+  pub name: String,
+  /// SHA-256 hash of the token, which itself is only shown once after creating it.
+  #[serde(skip)]
+  pub token_hash: String,
+  pub scopes: Vec<Option<ApiTokenScope>>,
+  pub expires_at: Option<DateTime<Utc>>,
+  pub last_used_at: Option<DateTime<Utc>>,
+  pub published_at: DateTime<Utc>,
+  /// The OAuth client which this token was issued to. These tokens are renewed with a refresh
+  /// token.
+  pub oauth_client_id: Option<OAuthClientId>,
+  #[serde(skip)]
+  pub refresh_token_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = api_token))]
+pub struct ApiTokenInsertForm {
+  pub local_user_id: LocalUserId,
+  pub name: String,
+  pub token_hash: String,
+  pub scopes: Vec<Option<ApiTokenScope>>,
+  #[new(default)]
+  pub expires_at: Option<DateTime<Utc>>,
+  #[new(default)]
+  pub oauth_client_id: Option<OAuthClientId>,
+  #[new(default)]
+  pub refresh_token_hash: Option<String>,
+}
+
+/// Replaces the access and refresh token of an OAuth grant.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = api_token))]
+pub struct ApiTokenRefreshForm {
+  pub token_hash: String,
+  pub refresh_token_hash: String,
+  pub expires_at: DateTime<Utc>,
+}
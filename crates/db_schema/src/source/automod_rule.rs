@@ -0,0 +1,97 @@
+use crate::newtypes::{AutomodRuleId, CommunityId, CommunityTagId, LanguageId};
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::enums::AutomodAction;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::automod_rule;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// A rule which is evaluated against new posts and comments in a community. The rule matches if
+/// all of its conditions match, and then its action is taken by the site's system account.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = automod_rule))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct AutomodRule {
+  pub id: AutomodRuleId,
+  pub community_id: CommunityId,
+  pub name: String,
+  /// Case-insensitive regex which is matched against the post title.
+  pub title_regex: Option<String>,
+  /// Case-insensitive regex which is matched against the post body or comment text.
+  pub body_regex: Option<String>,
+  /// Case-insensitive regex which is matched against the post url.
+  pub url_regex: Option<String>,
+  /// Matches if the creator's account is at most this many days old.
+  pub max_account_age_days: Option<i32>,
+  /// Matches if the creator's total post score is at most this value.
+  pub max_post_score: Option<i32>,
+  /// Matches if the creator's total comment score is at most this value.
+  pub max_comment_score: Option<i32>,
+  /// Matches if the post url is on one of these domains, or their subdomains.
+  pub domains: Vec<Option<String>>,
+  /// Matches if the content is in one of these languages.
+  pub language_ids: Vec<Option<LanguageId>>,
+  pub action: AutomodAction,
+  /// The tag which is applied for [AutomodAction::ApplyTag].
+  pub community_tag_id: Option<CommunityTagId>,
+  /// The comment text for [AutomodAction::Reply].
+  pub reply_text: Option<String>,
+  pub enabled: bool,
+  pub published_at: DateTime<Utc>,
+  pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = automod_rule))]
+pub struct AutomodRuleInsertForm {
+  pub community_id: CommunityId,
+  pub name: String,
+  pub action: AutomodAction,
+  #[new(default)]
+  pub title_regex: Option<String>,
+  #[new(default)]
+  pub body_regex: Option<String>,
+  #[new(default)]
+  pub url_regex: Option<String>,
+  #[new(default)]
+  pub max_account_age_days: Option<i32>,
+  #[new(default)]
+  pub max_post_score: Option<i32>,
+  #[new(default)]
+  pub max_comment_score: Option<i32>,
+  #[new(default)]
+  pub domains: Option<Vec<Option<String>>>,
+  #[new(default)]
+  pub language_ids: Option<Vec<Option<LanguageId>>>,
+  #[new(default)]
+  pub community_tag_id: Option<CommunityTagId>,
+  #[new(default)]
+  pub reply_text: Option<String>,
+  #[new(default)]
+  pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = automod_rule))]
+pub struct AutomodRuleUpdateForm {
+  pub name: Option<String>,
+  pub title_regex: Option<Option<String>>,
+  pub body_regex: Option<Option<String>>,
+  pub url_regex: Option<Option<String>>,
+  pub max_account_age_days: Option<Option<i32>>,
+  pub max_post_score: Option<Option<i32>>,
+  pub max_comment_score: Option<Option<i32>>,
+  pub domains: Option<Vec<Option<String>>>,
+  pub language_ids: Option<Vec<Option<LanguageId>>>,
+  pub action: Option<AutomodAction>,
+  pub community_tag_id: Option<Option<CommunityTagId>>,
+  pub reply_text: Option<Option<String>>,
+  pub enabled: Option<bool>,
+  pub updated_at: Option<Option<DateTime<Utc>>>,
+}
@@ -0,0 +1,55 @@
+use crate::newtypes::{BanAppealId, CommunityId, ModlogId};
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::PersonId;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use {i_love_jesus::CursorKeysModule, lemmy_db_schema_file::schema::ban_appeal};
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(
+  feature = "full",
+  derive(Queryable, Selectable, Identifiable, CursorKeysModule)
+)]
+#[cfg_attr(feature = "full", diesel(table_name = ban_appeal))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", cursor_keys_module(name = ban_appeal_keys))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// An appeal by a banned user against a community or site ban.
+pub struct BanAppeal {
+  pub id: BanAppealId,
+  /// The modlog entry of the ban which is appealed.
+  pub modlog_id: ModlogId,
+  pub creator_id: PersonId,
+  /// The community which the user is banned from, or none for a site ban.
+  pub community_id: Option<CommunityId>,
+  pub reason: String,
+  /// The mod or admin who approved or denied the appeal.
+  pub resolver_id: Option<PersonId>,
+  pub approved: bool,
+  pub deny_reason: Option<String>,
+  pub resolved_at: Option<DateTime<Utc>>,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = ban_appeal))]
+pub struct BanAppealInsertForm {
+  pub modlog_id: ModlogId,
+  pub creator_id: PersonId,
+  pub community_id: Option<CommunityId>,
+  pub reason: String,
+}
+
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = ban_appeal))]
+pub struct BanAppealUpdateForm {
+  pub resolver_id: Option<Option<PersonId>>,
+  pub approved: Option<bool>,
+  pub deny_reason: Option<Option<String>>,
+  pub resolved_at: Option<Option<DateTime<Utc>>>,
+}
@@ -0,0 +1,20 @@
+use crate::newtypes::CommentId;
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::comment_history;
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Identifiable, Queryable, Selectable))]
+#[cfg_attr(feature = "full", diesel(table_name = comment_history))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// A previous version of a comment, saved when the comment was edited.
+pub struct CommentHistory {
+  pub id: i32,
+  pub comment_id: CommentId,
+  pub content: String,
+  /// When this version was published, or the time of the edit which created it.
+  pub published_at: DateTime<Utc>,
+}
@@ -94,6 +94,10 @@ pub struct Community {
   pub report_count: i16,
   pub unresolved_report_count: i16,
   pub local_removed: bool,
+  /// Previous identities of this community, which are allowed to move here.
+  pub also_known_as: Vec<Option<DbUrl>>,
+  /// If set, the community was moved to this new community.
+  pub moved_to: Option<DbUrl>,
 }
 
 #[derive(Debug, Clone, derive_new::new)]
@@ -145,6 +149,10 @@ pub struct CommunityInsertForm {
   pub summary: Option<String>,
   #[new(default)]
   pub local_removed: Option<bool>,
+  #[new(default)]
+  pub also_known_as: Option<Vec<Option<DbUrl>>>,
+  #[new(default)]
+  pub moved_to: Option<DbUrl>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -173,6 +181,8 @@ pub struct CommunityUpdateForm {
   pub visibility: Option<CommunityVisibility>,
   pub summary: Option<Option<String>>,
   pub local_removed: Option<bool>,
+  pub also_known_as: Option<Vec<Option<DbUrl>>>,
+  pub moved_to: Option<Option<DbUrl>>,
 }
 
 #[skip_serializing_none]
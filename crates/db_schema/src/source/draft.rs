@@ -0,0 +1,87 @@
+use crate::newtypes::{CommentId, CommunityId, DraftId, LanguageId, PostId};
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::PersonId;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use {i_love_jesus::CursorKeysModule, lemmy_db_schema_file::schema::draft};
+
+/// An unpublished post, comment or private message. Exactly one of `community_id`, `post_id` and
+/// `recipient_id` is set, depending on what the draft becomes once it is published.
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(
+  feature = "full",
+  derive(Queryable, Selectable, Identifiable, Insertable, CursorKeysModule)
+)]
+#[cfg_attr(feature = "full", diesel(table_name = draft))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", cursor_keys_module(name = draft_keys))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct Draft {
+  pub id: DraftId,
+  pub creator_id: PersonId,
+  /// The community for a post draft.
+  pub community_id: Option<CommunityId>,
+  /// The post for a comment draft.
+  pub post_id: Option<PostId>,
+  /// The parent comment for a comment draft.
+  pub parent_id: Option<CommentId>,
+  /// The recipient for a private message draft.
+  pub recipient_id: Option<PersonId>,
+  /// The post title.
+  pub name: Option<String>,
+  pub url: Option<String>,
+  /// The post body, or the content of a comment or private message.
+  pub body: Option<String>,
+  pub alt_text: Option<String>,
+  pub nsfw: Option<bool>,
+  pub language_id: Option<LanguageId>,
+  pub custom_thumbnail: Option<String>,
+  pub published_at: DateTime<Utc>,
+  pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = draft))]
+pub struct DraftInsertForm {
+  pub creator_id: PersonId,
+  #[new(default)]
+  pub community_id: Option<CommunityId>,
+  #[new(default)]
+  pub post_id: Option<PostId>,
+  #[new(default)]
+  pub parent_id: Option<CommentId>,
+  #[new(default)]
+  pub recipient_id: Option<PersonId>,
+  #[new(default)]
+  pub name: Option<String>,
+  #[new(default)]
+  pub url: Option<String>,
+  #[new(default)]
+  pub body: Option<String>,
+  #[new(default)]
+  pub alt_text: Option<String>,
+  #[new(default)]
+  pub nsfw: Option<bool>,
+  #[new(default)]
+  pub language_id: Option<LanguageId>,
+  #[new(default)]
+  pub custom_thumbnail: Option<String>,
+}
+
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = draft))]
+pub struct DraftUpdateForm {
+  pub name: Option<Option<String>>,
+  pub url: Option<Option<String>>,
+  pub body: Option<Option<String>>,
+  pub alt_text: Option<Option<String>>,
+  pub nsfw: Option<Option<bool>>,
+  pub language_id: Option<Option<LanguageId>>,
+  pub custom_thumbnail: Option<Option<String>>,
+  pub updated_at: Option<Option<DateTime<Utc>>>,
+}
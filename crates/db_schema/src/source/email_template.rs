@@ -0,0 +1,47 @@
+use crate::newtypes::EmailTemplateId;
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::enums::EmailTemplateType;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::email_template;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = email_template))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// An admin defined replacement for the subject and body of an email.
+pub struct EmailTemplate {
+  pub id: EmailTemplateId,
+  pub template_type: EmailTemplateType,
+  /// Interface language which the template is used for. If empty, it is used for all languages
+  /// which don't have their own template.
+  pub language: Option<String>,
+  pub subject: String,
+  /// HTML body of the email.
+  pub body: String,
+  pub published_at: DateTime<Utc>,
+  pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = email_template))]
+pub struct EmailTemplateInsertForm {
+  pub template_type: EmailTemplateType,
+  pub language: Option<String>,
+  pub subject: String,
+  pub body: String,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = email_template))]
+pub struct EmailTemplateUpdateForm {
+  pub subject: Option<String>,
+  pub body: Option<String>,
+  pub updated_at: Option<Option<DateTime<Utc>>>,
+}
@@ -0,0 +1,28 @@
+use crate::newtypes::ActivityId;
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use diesel::prelude::*;
+use lemmy_db_schema_file::InstanceId;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// Admin controls for the outgoing federation queue of an instance. Retry and skip requests are
+/// applied by the federation send manager, and cleared afterwards.
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = lemmy_db_schema_file::schema::federation_queue_control))]
+#[cfg_attr(feature = "full", diesel(primary_key(instance_id)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct FederationQueueControl {
+  pub instance_id: InstanceId,
+  /// No activities are sent to the instance while it is paused.
+  pub paused: bool,
+  /// An admin requested to retry sending immediately, instead of waiting for the retry delay.
+  pub retry_requested_at: Option<DateTime<Utc>>,
+  /// An admin requested to skip activities. Sending continues after this activity.
+  pub skip_to_id: Option<ActivityId>,
+  pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,33 @@
+use crate::newtypes::{ActivityId, FederationSendErrorId};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use diesel::prelude::*;
+use lemmy_db_schema_file::InstanceId;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::federation_send_error;
+use serde::{Deserialize, Serialize};
+
+/// An error which happened while sending an activity to an instance. Only the most recent errors
+/// are kept for each instance.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = federation_send_error))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct FederationSendError {
+  pub id: FederationSendErrorId,
+  pub instance_id: InstanceId,
+  pub activity_id: ActivityId,
+  pub error: String,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = federation_send_error))]
+pub struct FederationSendErrorForm {
+  pub instance_id: InstanceId,
+  pub activity_id: ActivityId,
+  pub error: String,
+}
@@ -115,6 +115,18 @@ pub struct LocalSite {
   pub image_upload_disabled: bool,
   /// How many active invite links a user can have
   pub max_invites_per_user_allowed: i32,
+  /// Use Postgres full text search for posts and comments instead of fuzzy matching. This also
+  /// allows sorting search results by relevance.
+  pub full_text_search: bool,
+  /// Show the edit history of posts and comments to everyone, instead of only to mods and admins.
+  pub public_edit_history: bool,
+  /// Moderators and admins need to set up TOTP or a passkey before they can log in.
+  pub require_2fa_for_mods_and_admins: bool,
+  /// Only serve Activitypub objects to fetches with a valid HTTP signature from an instance which
+  /// is allowed to federate with us. Other instances need to sign their fetches to federate with
+  /// this instance. Actors are still served without signature, but only with their public key.
+  /// Enabling this also signs outgoing fetches, which only takes effect after a restart.
+  pub federation_authorized_fetch: bool,
 }
 
 #[derive(Clone, derive_new::new)]
@@ -195,6 +207,14 @@ pub struct LocalSiteInsertForm {
   pub image_upload_disabled: Option<bool>,
   #[new(default)]
   pub max_invites_per_user_allowed: Option<i32>,
+  #[new(default)]
+  pub full_text_search: Option<bool>,
+  #[new(default)]
+  pub public_edit_history: Option<bool>,
+  #[new(default)]
+  pub require_2fa_for_mods_and_admins: Option<bool>,
+  #[new(default)]
+  pub federation_authorized_fetch: Option<bool>,
 }
 
 #[derive(Clone, Default)]
@@ -239,4 +259,8 @@ pub struct LocalSiteUpdateForm {
   pub image_allow_video_uploads: Option<bool>,
   pub image_upload_disabled: Option<bool>,
   pub max_invites_per_user_allowed: Option<i32>,
+  pub full_text_search: Option<bool>,
+  pub public_edit_history: Option<bool>,
+  pub require_2fa_for_mods_and_admins: Option<bool>,
+  pub federation_authorized_fetch: Option<bool>,
 }
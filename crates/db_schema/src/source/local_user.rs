@@ -4,7 +4,14 @@ use chrono::{DateTime, Utc};
 use lemmy_db_schema_file::schema::local_user;
 use lemmy_db_schema_file::{
   PersonId,
-  enums::{CommentSortType, ListingType, PostListingMode, PostSortType, VoteShow},
+  enums::{
+    CommentSortType,
+    EmailDigestFrequency,
+    ListingType,
+    PostListingMode,
+    PostSortType,
+    VoteShow,
+  },
 };
 use lemmy_diesel_utils::sensitive::SensitiveString;
 use serde::{Deserialize, Serialize};
@@ -81,6 +88,13 @@ pub struct LocalUser {
   pub invited_by_local_user_id: Option<LocalUserId>,
   /// Whether to show media in the UI.
   pub show_media: bool,
+  /// How often notification emails are sent. Anything other than immediate collects unread
+  /// notifications into a single digest email.
+  pub email_digest_frequency: EmailDigestFrequency,
+  /// Whether the digest email also lists top posts from subscribed communities.
+  pub email_digest_top_posts: bool,
+  /// The last time a digest email was sent. Only notifications after this are included.
+  pub last_email_digest_at: DateTime<Utc>,
 }
 
 #[derive(Clone, derive_new::new)]
@@ -157,6 +171,10 @@ pub struct LocalUserInsertForm {
   pub invited_by_local_user_id: Option<LocalUserId>,
   #[new(default)]
   pub show_media: Option<bool>,
+  #[new(default)]
+  pub email_digest_frequency: Option<EmailDigestFrequency>,
+  #[new(default)]
+  pub email_digest_top_posts: Option<bool>,
 }
 
 #[derive(Clone, Default)]
@@ -198,4 +216,7 @@ pub struct LocalUserUpdateForm {
   pub show_person_votes: Option<bool>,
   pub default_items_per_page: Option<i32>,
   pub show_media: Option<bool>,
+  pub email_digest_frequency: Option<EmailDigestFrequency>,
+  pub email_digest_top_posts: Option<bool>,
+  pub last_email_digest_at: Option<DateTime<Utc>>,
 }
@@ -0,0 +1,115 @@
+use crate::newtypes::{
+  CommentId,
+  CommunityId,
+  ModlogId,
+  ModmailMessageId,
+  ModmailThreadId,
+  PostId,
+};
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::PersonId;
+use lemmy_diesel_utils::dburl::DbUrl;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use {
+  i_love_jesus::CursorKeysModule,
+  lemmy_db_schema_file::schema::{modmail_message, modmail_thread},
+};
+
+/// A conversation between a user and the mod team of a community.
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(
+  feature = "full",
+  derive(Queryable, Selectable, Identifiable, CursorKeysModule)
+)]
+#[cfg_attr(feature = "full", diesel(table_name = modmail_thread))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", cursor_keys_module(name = modmail_thread_keys))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct ModmailThread {
+  pub id: ModmailThreadId,
+  pub community_id: CommunityId,
+  /// The user who opened the thread.
+  pub creator_id: PersonId,
+  pub subject: String,
+  /// The post which this thread is about, if any.
+  pub post_id: Option<PostId>,
+  /// The comment which this thread is about, if any.
+  pub comment_id: Option<CommentId>,
+  /// The mod action which this thread is about, if any.
+  pub modlog_id: Option<ModlogId>,
+  /// Archived threads are hidden from the default list for mods.
+  pub archived: bool,
+  pub ap_id: DbUrl,
+  pub local: bool,
+  pub published_at: DateTime<Utc>,
+  pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = modmail_thread))]
+pub struct ModmailThreadInsertForm {
+  pub community_id: CommunityId,
+  pub creator_id: PersonId,
+  pub subject: String,
+  #[new(default)]
+  pub post_id: Option<PostId>,
+  #[new(default)]
+  pub comment_id: Option<CommentId>,
+  #[new(default)]
+  pub modlog_id: Option<ModlogId>,
+  #[new(default)]
+  pub ap_id: Option<DbUrl>,
+  #[new(default)]
+  pub local: Option<bool>,
+  #[new(default)]
+  pub published_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = modmail_thread))]
+pub struct ModmailThreadUpdateForm {
+  pub archived: Option<bool>,
+  pub updated_at: Option<Option<DateTime<Utc>>>,
+}
+
+/// A single message in a modmail thread.
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = modmail_message))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct ModmailMessage {
+  pub id: ModmailMessageId,
+  pub thread_id: ModmailThreadId,
+  pub creator_id: PersonId,
+  /// Sent by one of the community moderators on behalf of the mod team.
+  pub from_mod_team: bool,
+  pub content: String,
+  pub ap_id: DbUrl,
+  pub local: bool,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = modmail_message))]
+pub struct ModmailMessageInsertForm {
+  pub thread_id: ModmailThreadId,
+  pub creator_id: PersonId,
+  pub from_mod_team: bool,
+  pub content: String,
+  #[new(default)]
+  pub ap_id: Option<DbUrl>,
+  #[new(default)]
+  pub local: Option<bool>,
+  #[new(default)]
+  pub published_at: Option<DateTime<Utc>>,
+}
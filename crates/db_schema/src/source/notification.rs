@@ -1,6 +1,22 @@
 use crate::{
-  newtypes::{CommentId, CommunityId, ModlogId, NotificationId, PostId, PrivateMessageId},
-  source::{comment::Comment, modlog::Modlog, post::Post, private_message::PrivateMessage},
+  newtypes::{
+    BanAppealId,
+    CommentId,
+    CommunityId,
+    ModlogId,
+    ModmailThreadId,
+    NotificationId,
+    PostId,
+    PrivateMessageId,
+  },
+  source::{
+    ban_appeal::BanAppeal,
+    comment::Comment,
+    modlog::Modlog,
+    modmail::ModmailThread,
+    post::Post,
+    private_message::PrivateMessage,
+  },
 };
 use chrono::{DateTime, Utc};
 #[cfg(feature = "full")]
@@ -37,6 +53,8 @@ pub struct Notification {
   pub instance_id: Option<InstanceId>,
   #[serde(skip)]
   pub community_id: Option<CommunityId>,
+  pub modmail_thread_id: Option<ModmailThreadId>,
+  pub ban_appeal_id: Option<BanAppealId>,
 }
 
 #[derive(derive_new::new)]
@@ -58,6 +76,10 @@ pub struct NotificationInsertForm {
   pub instance_id: Option<InstanceId>,
   #[new(default)]
   pub community_id: Option<CommunityId>,
+  #[new(default)]
+  pub modmail_thread_id: Option<ModmailThreadId>,
+  #[new(default)]
+  pub ban_appeal_id: Option<BanAppealId>,
 }
 
 impl NotificationInsertForm {
@@ -97,4 +119,27 @@ impl NotificationInsertForm {
       ..Self::new(recipient_id, action.mod_id, NotificationType::ModAction)
     }
   }
+
+  /// The creator is always the user who opened the thread, so that the name of the moderator who
+  /// replied is not revealed.
+  pub fn new_modmail(thread: &ModmailThread, recipient_id: PersonId) -> Self {
+    Self {
+      modmail_thread_id: Some(thread.id),
+      community_id: Some(thread.community_id),
+      ..Self::new(recipient_id, thread.creator_id, NotificationType::Modmail)
+    }
+  }
+
+  /// Sent to the user who appealed. The creator is the user themselves, so that the name of the
+  /// moderator who resolved the appeal is not revealed.
+  pub fn new_ban_appeal(appeal: &BanAppeal) -> Self {
+    Self {
+      ban_appeal_id: Some(appeal.id),
+      ..Self::new(
+        appeal.creator_id,
+        appeal.creator_id,
+        NotificationType::BanAppeal,
+      )
+    }
+  }
 }
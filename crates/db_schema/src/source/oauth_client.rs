@@ -0,0 +1,79 @@
+use crate::newtypes::{LocalUserId, OAuthClientId};
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::enums::ApiTokenScope;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::{oauth_authorization_code, oauth_client};
+use lemmy_diesel_utils::dburl::DbUrl;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// A third-party app which uses Lemmy as OAuth2 authorization server.
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = oauth_client))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct OAuthClient {
+  #[serde(skip)]
+  pub id: OAuthClientId,
+  /// The public identifier of the client.
+  pub client_id: String,
+  /// Confidential clients authenticate with a secret, public clients only with PKCE.
+  #[serde(skip)]
+  pub client_secret_hash: Option<String>,
+  pub name: String,
+  #[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+  pub client_uri: Option<DbUrl>,
+  pub redirect_uris: Vec<Option<String>>,
+  pub scopes: Vec<Option<ApiTokenScope>>,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = oauth_client))]
+pub struct OAuthClientInsertForm {
+  pub client_id: String,
+  pub client_secret_hash: Option<String>,
+  pub name: String,
+  pub redirect_uris: Vec<Option<String>>,
+  pub scopes: Vec<Option<ApiTokenScope>>,
+  #[new(default)]
+  pub client_uri: Option<DbUrl>,
+}
+
+/// A short-lived code which the client exchanges for tokens, after the user has given consent.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(
+  feature = "full",
+  derive(Queryable, Selectable, Identifiable, Insertable)
+)]
+#[cfg_attr(feature = "full", diesel(table_name = oauth_authorization_code))]
+#[cfg_attr(feature = "full", diesel(primary_key(code_hash)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct OAuthAuthorizationCode {
+  pub code_hash: String,
+  pub oauth_client_id: OAuthClientId,
+  pub local_user_id: LocalUserId,
+  pub redirect_uri: String,
+  pub scopes: Vec<Option<ApiTokenScope>>,
+  /// The PKCE code challenge, which is checked against the verifier of the token request.
+  pub code_challenge: String,
+  pub expires_at: DateTime<Utc>,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = oauth_authorization_code))]
+pub struct OAuthAuthorizationCodeInsertForm {
+  pub code_hash: String,
+  pub oauth_client_id: OAuthClientId,
+  pub local_user_id: LocalUserId,
+  pub redirect_uri: String,
+  pub scopes: Vec<Option<ApiTokenScope>>,
+  pub code_challenge: String,
+  pub expires_at: DateTime<Utc>,
+}
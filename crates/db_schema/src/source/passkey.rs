@@ -0,0 +1,65 @@
+use crate::newtypes::{LocalUserId, PasskeyId};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::{passkey, passkey_challenge};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// A WebAuthn credential, which can be used as second factor or for passwordless login.
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = passkey))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct Passkey {
+  pub id: PasskeyId,
+  #[serde(skip)]
+  pub local_user_id: LocalUserId,
+  pub name: String,
+  /// Base64url encoded id of the credential, chosen by the authenticator.
+  pub credential_id: String,
+  /// COSE encoded public key of the credential.
+  #[serde(skip)]
+  pub public_key: Vec<u8>,
+  /// Signature counter of the authenticator, used to detect cloned authenticators.
+  #[serde(skip)]
+  pub sign_count: i64,
+  pub last_used_at: Option<DateTime<Utc>>,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = passkey))]
+pub struct PasskeyInsertForm {
+  pub local_user_id: LocalUserId,
+  pub name: String,
+  pub credential_id: String,
+  pub public_key: Vec<u8>,
+  pub sign_count: i64,
+}
+
+/// A random challenge which the authenticator has to sign during passkey registration or login.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = passkey_challenge))]
+#[cfg_attr(feature = "full", diesel(primary_key(challenge)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct PasskeyChallenge {
+  pub challenge: String,
+  /// Empty for passwordless login, where the user is only known after verifying the passkey.
+  pub local_user_id: Option<LocalUserId>,
+  pub expires_at: DateTime<Utc>,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = passkey_challenge))]
+pub struct PasskeyChallengeInsertForm {
+  pub challenge: String,
+  pub local_user_id: Option<LocalUserId>,
+  pub expires_at: DateTime<Utc>,
+}
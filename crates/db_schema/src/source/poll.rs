@@ -0,0 +1,105 @@
+use crate::newtypes::{PollChoiceId, PostId};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use diesel::{AsExpression, FromSqlRow};
+use lemmy_db_schema_file::PersonId;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::{poll, poll_choice, poll_vote};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// A poll which is attached to a post.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = poll))]
+#[cfg_attr(feature = "full", diesel(primary_key(post_id)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct Poll {
+  pub post_id: PostId,
+  /// Whether voters can select more than one choice.
+  pub multiple_choice: bool,
+  /// After this time no more votes are accepted. None means the poll never closes.
+  pub end_at: Option<DateTime<Utc>>,
+  /// The number of people who voted in the poll.
+  pub voter_count: i32,
+  pub published_at: DateTime<Utc>,
+  /// For local polls, when the vote counts changed without being federated yet.
+  #[serde(skip)]
+  pub pending_update_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = poll))]
+pub struct PollInsertForm {
+  pub post_id: PostId,
+  #[new(default)]
+  pub multiple_choice: Option<bool>,
+  #[new(default)]
+  pub end_at: Option<DateTime<Utc>>,
+  #[new(default)]
+  pub voter_count: Option<i32>,
+}
+
+/// One of the choices which can be voted on in a poll.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = poll_choice))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct PollChoice {
+  pub id: PollChoiceId,
+  pub post_id: PostId,
+  pub name: String,
+  pub vote_count: i32,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = poll_choice))]
+pub struct PollChoiceInsertForm {
+  pub post_id: PostId,
+  pub name: String,
+  #[new(default)]
+  pub vote_count: Option<i32>,
+}
+
+/// The vote of a person for a single poll choice.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = poll_vote))]
+#[cfg_attr(feature = "full", diesel(primary_key(person_id, poll_choice_id)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct PollVote {
+  pub poll_choice_id: PollChoiceId,
+  pub person_id: PersonId,
+  pub post_id: PostId,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = poll_vote))]
+pub struct PollVoteForm {
+  pub poll_choice_id: PollChoiceId,
+  pub person_id: PersonId,
+  pub post_id: PostId,
+}
+
+/// A poll with its choices, as shown in a post view. This is selected as json so that it can be
+/// included in post listings without additional queries.
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(FromSqlRow, AsExpression))]
+#[cfg_attr(feature = "full", diesel(sql_type = diesel::sql_types::Json))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct PollView {
+  pub poll: Poll,
+  pub choices: Vec<PollChoice>,
+  /// The choices you voted for.
+  pub my_votes: Vec<PollChoiceId>,
+}
@@ -210,6 +210,8 @@ pub struct PostActions {
   /// True if upvoted, false if downvoted. Upvote is greater than downvote.
   pub vote_is_upvote: Option<bool>,
   pub notifications: Option<PostNotificationsMode>,
+  /// When you voted in the poll of this post.
+  pub poll_voted_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -0,0 +1,25 @@
+use crate::newtypes::PostId;
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::post_history;
+use lemmy_diesel_utils::dburl::DbUrl;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "full", derive(Identifiable, Queryable, Selectable))]
+#[cfg_attr(feature = "full", diesel(table_name = post_history))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// A previous version of a post, saved when the post was edited.
+pub struct PostHistory {
+  pub id: i32,
+  pub post_id: PostId,
+  pub name: String,
+  pub body: Option<String>,
+  pub url: Option<DbUrl>,
+  /// When this version was published, or the time of the edit which created it.
+  pub published_at: DateTime<Utc>,
+}
@@ -0,0 +1,31 @@
+use crate::newtypes::{LocalUserId, PushSubscriptionId};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::push_subscription;
+
+/// A web push subscription of a browser or app, which receives encrypted notifications.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = push_subscription))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+pub struct PushSubscription {
+  pub id: PushSubscriptionId,
+  pub local_user_id: LocalUserId,
+  /// Url of the push service, which is unique for each subscription.
+  pub endpoint: String,
+  /// Base64url encoded public key of the subscription, used to encrypt messages.
+  pub p256dh: String,
+  /// Base64url encoded authentication secret of the subscription.
+  pub auth: String,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = push_subscription))]
+pub struct PushSubscriptionInsertForm {
+  pub local_user_id: LocalUserId,
+  pub endpoint: String,
+  pub p256dh: String,
+  pub auth: String,
+}
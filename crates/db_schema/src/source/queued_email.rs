@@ -0,0 +1,58 @@
+use crate::newtypes::QueuedEmailId;
+use chrono::{DateTime, Utc};
+use lemmy_diesel_utils::sensitive::SensitiveString;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use {i_love_jesus::CursorKeysModule, lemmy_db_schema_file::schema::queued_email};
+
+/// An outgoing email. It is stored before sending, so that it can be retried if the mail server
+/// is unavailable.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(
+  feature = "full",
+  derive(Queryable, Selectable, Identifiable, CursorKeysModule)
+)]
+#[cfg_attr(feature = "full", diesel(table_name = queued_email))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", cursor_keys_module(name = queued_email_keys))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct QueuedEmail {
+  pub id: QueuedEmailId,
+  pub to_email: SensitiveString,
+  /// Name of the recipient, usually their username.
+  pub to_name: String,
+  pub subject: String,
+  #[serde(skip)]
+  pub html: SensitiveString,
+  pub attempts: i32,
+  /// When the next attempt is due. Empty once the email was sent, or after giving up.
+  pub next_attempt_at: Option<DateTime<Utc>>,
+  pub last_error: Option<String>,
+  pub sent_at: Option<DateTime<Utc>>,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = queued_email))]
+pub struct QueuedEmailInsertForm {
+  pub to_email: SensitiveString,
+  pub to_name: String,
+  pub subject: String,
+  pub html: SensitiveString,
+  #[new(default)]
+  pub next_attempt_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = queued_email))]
+pub struct QueuedEmailUpdateForm {
+  pub attempts: Option<i32>,
+  pub next_attempt_at: Option<Option<DateTime<Utc>>>,
+  pub last_error: Option<Option<String>>,
+  pub sent_at: Option<Option<DateTime<Utc>>>,
+}
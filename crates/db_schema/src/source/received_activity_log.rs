@@ -0,0 +1,66 @@
+use crate::newtypes::ReceivedActivityLogId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use {i_love_jesus::CursorKeysModule, lemmy_db_schema_file::schema::received_activity_log};
+
+/// A raw incoming activity, with the result of processing it. Only stored if enabled in the
+/// config, so that admins can debug federation issues.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(
+  feature = "full",
+  derive(Queryable, Selectable, Identifiable, CursorKeysModule)
+)]
+#[cfg_attr(feature = "full", diesel(table_name = received_activity_log))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", cursor_keys_module(name = received_activity_log_keys))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct ReceivedActivityLog {
+  pub id: ReceivedActivityLogId,
+  pub ap_id: Option<String>,
+  pub actor_id: Option<String>,
+  /// Domain of the actor.
+  pub domain: Option<String>,
+  /// The activity type, for example `Create` or `Follow`.
+  pub kind: Option<String>,
+  /// The activity json. Empty if it was too large to store.
+  pub data: Option<String>,
+  /// Whether the HTTP signature was valid. Only these activities can be replayed.
+  pub signature_verified: bool,
+  pub success: bool,
+  pub error: Option<String>,
+  pub published_at: DateTime<Utc>,
+  pub replayed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = received_activity_log))]
+pub struct ReceivedActivityLogInsertForm {
+  #[new(default)]
+  pub ap_id: Option<String>,
+  #[new(default)]
+  pub actor_id: Option<String>,
+  #[new(default)]
+  pub domain: Option<String>,
+  #[new(default)]
+  pub kind: Option<String>,
+  #[new(default)]
+  pub data: Option<String>,
+  pub signature_verified: bool,
+  pub success: bool,
+  #[new(default)]
+  pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = received_activity_log))]
+pub struct ReceivedActivityLogUpdateForm {
+  pub success: Option<bool>,
+  pub error: Option<Option<String>>,
+  pub replayed_at: Option<Option<DateTime<Utc>>>,
+}
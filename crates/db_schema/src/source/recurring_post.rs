@@ -0,0 +1,74 @@
+use crate::newtypes::{CommunityId, LanguageId, PostId, RecurringPostId};
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::recurring_post;
+use lemmy_db_schema_file::{PersonId, enums::RecurringPostFrequency};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// A template for a post which is published in a community on a fixed schedule. The title and
+/// body may contain the placeholders `{date}`, `{week}`, `{week_year}`, `{month}` and `{year}`.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = recurring_post))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct RecurringPost {
+  pub id: RecurringPostId,
+  pub community_id: CommunityId,
+  /// The moderator who created the template. Published posts are created by this account.
+  pub creator_id: PersonId,
+  /// The post title.
+  pub name: String,
+  pub body: Option<String>,
+  pub nsfw: bool,
+  pub language_id: Option<LanguageId>,
+  pub frequency: RecurringPostFrequency,
+  pub next_publish_at: DateTime<Utc>,
+  /// Feature each published post in the community, and unfeature the previous one.
+  pub feature: bool,
+  /// The post which was published most recently.
+  pub last_post_id: Option<PostId>,
+  pub enabled: bool,
+  pub published_at: DateTime<Utc>,
+  pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = recurring_post))]
+pub struct RecurringPostInsertForm {
+  pub community_id: CommunityId,
+  pub creator_id: PersonId,
+  pub name: String,
+  pub frequency: RecurringPostFrequency,
+  pub next_publish_at: DateTime<Utc>,
+  #[new(default)]
+  pub body: Option<String>,
+  #[new(default)]
+  pub nsfw: Option<bool>,
+  #[new(default)]
+  pub language_id: Option<LanguageId>,
+  #[new(default)]
+  pub feature: Option<bool>,
+  #[new(default)]
+  pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = recurring_post))]
+pub struct RecurringPostUpdateForm {
+  pub name: Option<String>,
+  pub body: Option<Option<String>>,
+  pub nsfw: Option<bool>,
+  pub language_id: Option<Option<LanguageId>>,
+  pub frequency: Option<RecurringPostFrequency>,
+  pub next_publish_at: Option<DateTime<Utc>>,
+  pub feature: Option<bool>,
+  pub last_post_id: Option<Option<PostId>>,
+  pub enabled: Option<bool>,
+  pub updated_at: Option<Option<DateTime<Utc>>>,
+}
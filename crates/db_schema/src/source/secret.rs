@@ -9,4 +9,6 @@ use lemmy_diesel_utils::sensitive::SensitiveString;
 pub struct Secret {
   pub id: i32,
   pub jwt_secret: SensitiveString,
+  /// Base64 encoded PKCS#8 key for signing web push requests. Generated on startup if empty.
+  pub vapid_private_key: Option<SensitiveString>,
 }
@@ -0,0 +1,111 @@
+use crate::newtypes::{CommunityId, WebhookDeliveryId, WebhookId};
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::{PersonId, enums::WebhookEventType};
+use lemmy_diesel_utils::{dburl::DbUrl, sensitive::SensitiveString};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use {
+  i_love_jesus::CursorKeysModule,
+  lemmy_db_schema_file::schema::{webhook, webhook_delivery},
+};
+
+/// An HTTPS endpoint which receives signed JSON payloads for site or community events.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = webhook))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct Webhook {
+  pub id: WebhookId,
+  /// Only events from this community are sent. If empty, this is a site-wide webhook which is
+  /// managed by admins.
+  pub community_id: Option<CommunityId>,
+  pub creator_id: PersonId,
+  #[cfg_attr(feature = "ts-rs", ts(type = "string"))]
+  pub url: DbUrl,
+  /// Used to sign payloads with HMAC-SHA256. The signature is sent in the `X-Lemmy-Signature`
+  /// header. It is only returned once, when the webhook is created.
+  #[serde(skip)]
+  pub secret: SensitiveString,
+  pub events: Vec<Option<WebhookEventType>>,
+  pub enabled: bool,
+  pub published_at: DateTime<Utc>,
+  pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = webhook))]
+pub struct WebhookInsertForm {
+  pub community_id: Option<CommunityId>,
+  pub creator_id: PersonId,
+  pub url: DbUrl,
+  pub secret: SensitiveString,
+  pub events: Vec<Option<WebhookEventType>>,
+  #[new(default)]
+  pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = webhook))]
+pub struct WebhookUpdateForm {
+  pub url: Option<DbUrl>,
+  pub events: Option<Vec<Option<WebhookEventType>>>,
+  pub enabled: Option<bool>,
+  pub updated_at: Option<Option<DateTime<Utc>>>,
+}
+
+/// A single event which was sent, or is waiting to be sent, to a webhook.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(
+  feature = "full",
+  derive(Queryable, Selectable, Identifiable, CursorKeysModule)
+)]
+#[cfg_attr(feature = "full", diesel(table_name = webhook_delivery))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "full", cursor_keys_module(name = webhook_delivery_keys))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct WebhookDelivery {
+  pub id: WebhookDeliveryId,
+  pub webhook_id: WebhookId,
+  pub event: WebhookEventType,
+  /// The JSON body which is posted to the webhook url.
+  pub payload: String,
+  pub attempts: i32,
+  /// When the next delivery attempt is due. Empty once the payload was delivered, or after
+  /// giving up.
+  pub next_attempt_at: Option<DateTime<Utc>>,
+  /// HTTP status code returned by the last attempt.
+  pub last_status: Option<i32>,
+  pub last_error: Option<String>,
+  pub delivered_at: Option<DateTime<Utc>>,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = webhook_delivery))]
+pub struct WebhookDeliveryInsertForm {
+  pub webhook_id: WebhookId,
+  pub event: WebhookEventType,
+  pub payload: String,
+  #[new(default)]
+  pub next_attempt_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = webhook_delivery))]
+pub struct WebhookDeliveryUpdateForm {
+  pub attempts: Option<i32>,
+  pub next_attempt_at: Option<Option<DateTime<Utc>>>,
+  pub last_status: Option<Option<i32>>,
+  pub last_error: Option<Option<String>>,
+  pub delivered_at: Option<Option<DateTime<Utc>>>,
+}
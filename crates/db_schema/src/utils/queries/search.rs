@@ -0,0 +1,71 @@
+//! Helpers for Postgres full text search on the `post_search` and `comment_search` tables. The
+//! search vectors are computed by triggers in the replaceable schema, using the search config for
+//! the language of each row.
+use diesel::{
+  Expression,
+  ExpressionMethods,
+  NullableExpressionMethods,
+  QueryDsl,
+  define_sql_function,
+  expression::AsExpression,
+  pg::Pg,
+  sql_types::{Integer, Text},
+};
+use lemmy_db_schema_file::schema::{
+  comment,
+  comment_search,
+  post,
+  post_search,
+  sql_types::Tsvector,
+};
+
+#[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+#[diesel(postgres_type(name = "tsquery"))]
+pub struct Tsquery;
+
+define_sql_function! {
+  /// Builds a tsquery from a query in `to_tsquery` syntax, see
+  /// [lemmy_diesel_utils::utils::full_text_search_query].
+  #[sql_name = "r.search_query"]
+  fn search_query(query: Text, language_id: Integer) -> Tsquery;
+}
+
+define_sql_function! {
+  /// Same as [search_query], combined for all languages. Used to filter on the search index before
+  /// each row is checked against the query for its own language.
+  #[sql_name = "r.search_query_any_language"]
+  fn search_query_any_language(query: Text) -> Tsquery;
+}
+
+define_sql_function!(fn ts_rank(vector: Tsvector, query: Tsquery) -> Float);
+
+diesel::infix_operator!(Matches, " @@ ", backend: Pg);
+
+pub trait TsvectorExpressionMethods: Expression<SqlType = Tsvector> + Sized {
+  /// Checks if the search vector matches the tsquery.
+  fn matches<T: AsExpression<Tsquery>>(self, query: T) -> Matches<Self, T::Expression> {
+    Matches::new(self, query.as_expression())
+  }
+}
+
+impl<T: Expression<SqlType = Tsvector>> TsvectorExpressionMethods for T {}
+
+/// The search vector of the current post, for checks which depend on the post language.
+#[diesel::dsl::auto_type]
+pub fn post_search_vector() -> _ {
+  post_search::table
+    .filter(post_search::post_id.eq(post::id))
+    .select(post_search::search_vector)
+    .single_value()
+    .assume_not_null()
+}
+
+/// The search vector of the current comment, for checks which depend on the comment language.
+#[diesel::dsl::auto_type]
+pub fn comment_search_vector() -> _ {
+  comment_search::table
+    .filter(comment_search::comment_id.eq(comment::id))
+    .select(comment_search::search_vector)
+    .single_value()
+    .assume_not_null()
+}
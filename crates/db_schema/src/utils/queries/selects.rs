@@ -33,6 +33,7 @@ use lemmy_db_schema_file::{
     local_user,
     person,
     post,
+    post_actions,
     post_community_tag,
   },
 };
@@ -354,6 +355,18 @@ pub fn post_community_tags_fragment() -> _ {
     .single_value()
 }
 
+diesel::define_sql_function! {
+  #[sql_name = "r.post_poll"]
+  fn post_poll(post_id: diesel::sql_types::Nullable<diesel::sql_types::Int4>, person_id: diesel::sql_types::Nullable<diesel::sql_types::Int4>) -> diesel::sql_types::Nullable<Json>;
+}
+
+#[diesel::dsl::auto_type]
+/// Gets the poll of a post, including the choices voted for by the person of the joined
+/// post_actions.
+pub fn post_poll_fragment() -> _ {
+  post_poll(post::id.nullable(), post_actions::person_id.nullable())
+}
+
 #[diesel::dsl::auto_type]
 /// Gets the tags available within a specific community
 pub fn community_tags_fragment() -> _ {
@@ -1,7 +1,7 @@
 #[cfg(feature = "full")]
 use diesel_derive_enum::DbEnum;
 use serde::{Deserialize, Serialize};
-use strum::Display;
+use strum::{Display, EnumString};
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Hash)]
 #[serde(rename_all = "snake_case")]
@@ -334,6 +334,8 @@ pub enum NotificationType {
   Subscribed,
   PrivateMessage,
   ModAction,
+  Modmail,
+  BanAppeal,
 }
 
 #[derive(Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Hash)]
@@ -371,4 +373,142 @@ pub enum ModlogKind {
   ModLockComment,
   ModWarnComment,
   ModWarnPost,
+  /// Report, tag or reply by an automod rule. Removals and locks by automod use the regular kinds.
+  ModAutomodAction,
+}
+
+#[derive(Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "full", derive(DbEnum))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::WebhookEventTypeEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+/// The events which can be delivered to a webhook.
+pub enum WebhookEventType {
+  #[default]
+  NewPost,
+  NewComment,
+  NewReport,
+  ModAction,
+  NewRegistrationApplication,
+}
+
+#[derive(Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "full", derive(DbEnum))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::AutomodActionEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+/// The action which is taken when an automod rule matches.
+pub enum AutomodAction {
+  #[default]
+  Remove,
+  Report,
+  Lock,
+  /// Only applies to posts.
+  ApplyTag,
+  /// Reply with the rule's text from the site's system account.
+  Reply,
+}
+
+#[derive(Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "full", derive(DbEnum))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::RecurringPostFrequencyEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+/// How often a recurring post is published.
+pub enum RecurringPostFrequency {
+  Daily,
+  #[default]
+  Weekly,
+  /// On the same day of every month.
+  Monthly,
+}
+
+#[derive(
+  Display, EnumString, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Hash,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[cfg_attr(feature = "full", derive(DbEnum))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::ApiTokenScopeEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+/// The actions which an API token is allowed to take. These are also the OAuth scopes.
+pub enum ApiTokenScope {
+  /// View content, and manage what the account sees, like follows, blocks, saved and read posts.
+  #[default]
+  Read,
+  /// Create and edit posts, comments and reports.
+  Post,
+  Vote,
+  /// Mod actions in the communities which the user moderates.
+  Moderate,
+  /// Admin actions, if the user is an admin.
+  Admin,
+  PrivateMessage,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "full", derive(DbEnum))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::EmailDigestFrequencyEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+/// How often notification emails are sent to a user.
+pub enum EmailDigestFrequency {
+  /// A separate email for each notification.
+  #[default]
+  Immediate,
+  Hourly,
+  Daily,
+  Weekly,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "full", derive(DbEnum))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::EmailTemplateTypeEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+/// The types of email which admins can customize. Templates can use the listed variables with
+/// `{{ name }}`. Every template can also use `username` and `hostname`.
+pub enum EmailTemplateType {
+  /// Variables: `verify_link`
+  VerifyEmail,
+  /// Variables: `reset_link`
+  PasswordReset,
+  /// Variables: none
+  ApplicationApproved,
+  /// Variables: `deny_reason`
+  ApplicationDenied,
+  /// Sent to admins. Variables: `reporter`, `reported`, `reports_link`
+  NewReport,
+  /// Variables: `summary` (the default subject), `content`, `link`, `inbox_link`
+  Notification,
 }
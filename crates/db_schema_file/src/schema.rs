@@ -5,6 +5,14 @@ pub mod sql_types {
   #[diesel(postgres_type(name = "actor_type_enum"))]
   pub struct ActorTypeEnum;
 
+  #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(name = "api_token_scope_enum"))]
+  pub struct ApiTokenScopeEnum;
+
+  #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(name = "automod_action_enum"))]
+  pub struct AutomodActionEnum;
+
   #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
   #[diesel(postgres_type(name = "comment_sort_type_enum"))]
   pub struct CommentSortTypeEnum;
@@ -21,6 +29,14 @@ pub mod sql_types {
   #[diesel(postgres_type(name = "community_visibility"))]
   pub struct CommunityVisibility;
 
+  #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(name = "email_digest_frequency_enum"))]
+  pub struct EmailDigestFrequencyEnum;
+
+  #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(name = "email_template_type_enum"))]
+  pub struct EmailTemplateTypeEnum;
+
   #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
   #[diesel(postgres_type(name = "federation_mode_enum"))]
   pub struct FederationModeEnum;
@@ -57,6 +73,10 @@ pub mod sql_types {
   #[diesel(postgres_type(name = "post_sort_type_enum"))]
   pub struct PostSortTypeEnum;
 
+  #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(name = "recurring_post_frequency_enum"))]
+  pub struct RecurringPostFrequencyEnum;
+
   #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
   #[diesel(postgres_type(name = "registration_mode_enum"))]
   pub struct RegistrationModeEnum;
@@ -65,9 +85,75 @@ pub mod sql_types {
   #[diesel(postgres_type(name = "tag_color_enum"))]
   pub struct TagColorEnum;
 
+  #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(name = "tsvector"))]
+  pub struct Tsvector;
+
   #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
   #[diesel(postgres_type(name = "vote_show_enum"))]
   pub struct VoteShowEnum;
+
+  #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(name = "webhook_event_type_enum"))]
+  pub struct WebhookEventTypeEnum;
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::ApiTokenScopeEnum;
+
+    api_token (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        name -> Text,
+        token_hash -> Text,
+        scopes -> Array<Nullable<ApiTokenScopeEnum>>,
+        expires_at -> Nullable<Timestamptz>,
+        last_used_at -> Nullable<Timestamptz>,
+        published_at -> Timestamptz,
+        oauth_client_id -> Nullable<Int4>,
+        refresh_token_hash -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::AutomodActionEnum;
+
+    automod_rule (id) {
+        id -> Int4,
+        community_id -> Int4,
+        name -> Text,
+        title_regex -> Nullable<Text>,
+        body_regex -> Nullable<Text>,
+        url_regex -> Nullable<Text>,
+        max_account_age_days -> Nullable<Int4>,
+        max_post_score -> Nullable<Int4>,
+        max_comment_score -> Nullable<Int4>,
+        domains -> Array<Nullable<Text>>,
+        language_ids -> Array<Nullable<Int4>>,
+        action -> AutomodActionEnum,
+        community_tag_id -> Nullable<Int4>,
+        reply_text -> Nullable<Text>,
+        enabled -> Bool,
+        published_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    ban_appeal (id) {
+        id -> Int4,
+        modlog_id -> Int4,
+        creator_id -> Int4,
+        community_id -> Nullable<Int4>,
+        reason -> Text,
+        resolver_id -> Nullable<Int4>,
+        approved -> Bool,
+        deny_reason -> Nullable<Text>,
+        resolved_at -> Nullable<Timestamptz>,
+        published_at -> Timestamptz,
+    }
 }
 
 diesel::table! {
@@ -113,6 +199,15 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    comment_history (id) {
+        id -> Int4,
+        comment_id -> Int4,
+        content -> Text,
+        published_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     comment_report (id) {
         id -> Int4,
@@ -128,6 +223,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::Tsvector;
+
+    comment_search (comment_id) {
+        comment_id -> Int4,
+        search_vector -> Tsvector,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::CommunityVisibility;
@@ -179,6 +284,9 @@ diesel::table! {
         report_count -> Int2,
         unresolved_report_count -> Int2,
         local_removed -> Bool,
+        also_known_as -> Array<Nullable<Text>>,
+        #[max_length = 255]
+        moved_to -> Nullable<Varchar>,
     }
 }
 
@@ -278,6 +386,41 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    draft (id) {
+        id -> Int4,
+        creator_id -> Int4,
+        community_id -> Nullable<Int4>,
+        post_id -> Nullable<Int4>,
+        parent_id -> Nullable<Int4>,
+        recipient_id -> Nullable<Int4>,
+        name -> Nullable<Text>,
+        url -> Nullable<Text>,
+        body -> Nullable<Text>,
+        alt_text -> Nullable<Text>,
+        nsfw -> Nullable<Bool>,
+        language_id -> Nullable<Int4>,
+        custom_thumbnail -> Nullable<Text>,
+        published_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::EmailTemplateTypeEnum;
+
+    email_template (id) {
+        id -> Int4,
+        template_type -> EmailTemplateTypeEnum,
+        language -> Nullable<Text>,
+        subject -> Text,
+        body -> Text,
+        published_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     email_verification (id) {
         id -> Int4,
@@ -305,6 +448,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    federation_queue_control (instance_id) {
+        instance_id -> Int4,
+        paused -> Bool,
+        retry_requested_at -> Nullable<Timestamptz>,
+        skip_to_id -> Nullable<Int8>,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     federation_queue_state (instance_id) {
         instance_id -> Int4,
@@ -315,6 +468,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    federation_send_error (id) {
+        id -> Int4,
+        instance_id -> Int4,
+        activity_id -> Int8,
+        error -> Text,
+        published_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     image_details (link) {
         link -> Text,
@@ -430,6 +593,10 @@ diesel::table! {
         image_allow_video_uploads -> Bool,
         image_upload_disabled -> Bool,
         max_invites_per_user_allowed -> Int4,
+        full_text_search -> Bool,
+        public_edit_history -> Bool,
+        require_2fa_for_mods_and_admins -> Bool,
+        federation_authorized_fetch -> Bool,
     }
 }
 
@@ -471,6 +638,7 @@ diesel::table! {
     use super::sql_types::PostListingModeEnum;
     use super::sql_types::CommentSortTypeEnum;
     use super::sql_types::VoteShowEnum;
+    use super::sql_types::EmailDigestFrequencyEnum;
 
     local_user (id) {
         id -> Int4,
@@ -512,6 +680,9 @@ diesel::table! {
         default_items_per_page -> Int4,
         invited_by_local_user_id -> Nullable<Int4>,
         show_media -> Bool,
+        email_digest_frequency -> EmailDigestFrequencyEnum,
+        email_digest_top_posts -> Bool,
+        last_email_digest_at -> Timestamptz,
     }
 }
 
@@ -574,6 +745,36 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    modmail_message (id) {
+        id -> Int4,
+        thread_id -> Int4,
+        creator_id -> Int4,
+        from_mod_team -> Bool,
+        content -> Text,
+        ap_id -> Text,
+        local -> Bool,
+        published_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    modmail_thread (id) {
+        id -> Int4,
+        community_id -> Int4,
+        creator_id -> Int4,
+        subject -> Text,
+        post_id -> Nullable<Int4>,
+        comment_id -> Nullable<Int4>,
+        modlog_id -> Nullable<Int4>,
+        archived -> Bool,
+        ap_id -> Text,
+        local -> Bool,
+        published_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     multi_community (id) {
         id -> Int4,
@@ -637,6 +838,8 @@ diesel::table! {
         creator_id -> Int4,
         instance_id -> Nullable<Int4>,
         community_id -> Nullable<Int4>,
+        modmail_thread_id -> Nullable<Int4>,
+        ban_appeal_id -> Nullable<Int4>,
     }
 }
 
@@ -650,6 +853,38 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::ApiTokenScopeEnum;
+
+    oauth_authorization_code (code_hash) {
+        code_hash -> Text,
+        oauth_client_id -> Int4,
+        local_user_id -> Int4,
+        redirect_uri -> Text,
+        scopes -> Array<Nullable<ApiTokenScopeEnum>>,
+        code_challenge -> Text,
+        expires_at -> Timestamptz,
+        published_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::ApiTokenScopeEnum;
+
+    oauth_client (id) {
+        id -> Int4,
+        client_id -> Text,
+        client_secret_hash -> Nullable<Text>,
+        name -> Text,
+        client_uri -> Nullable<Text>,
+        redirect_uris -> Array<Nullable<Text>>,
+        scopes -> Array<Nullable<ApiTokenScopeEnum>>,
+        published_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     oauth_provider (id) {
         id -> Int4,
@@ -671,6 +906,28 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    passkey (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        name -> Text,
+        credential_id -> Text,
+        public_key -> Bytea,
+        sign_count -> Int8,
+        last_used_at -> Nullable<Timestamptz>,
+        published_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    passkey_challenge (challenge) {
+        challenge -> Text,
+        local_user_id -> Nullable<Int4>,
+        expires_at -> Timestamptz,
+        published_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     password_reset_request (id) {
         id -> Int4,
@@ -708,6 +965,9 @@ diesel::table! {
         post_score -> Int4,
         comment_count -> Int4,
         comment_score -> Int4,
+        also_known_as -> Array<Nullable<Text>>,
+        #[max_length = 255]
+        moved_to -> Nullable<Varchar>,
     }
 }
 
@@ -762,6 +1022,35 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    poll (post_id) {
+        post_id -> Int4,
+        multiple_choice -> Bool,
+        end_at -> Nullable<Timestamptz>,
+        voter_count -> Int4,
+        published_at -> Timestamptz,
+        pending_update_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    poll_choice (id) {
+        id -> Int4,
+        post_id -> Int4,
+        name -> Text,
+        vote_count -> Int4,
+    }
+}
+
+diesel::table! {
+    poll_vote (person_id, poll_choice_id) {
+        poll_choice_id -> Int4,
+        person_id -> Int4,
+        post_id -> Int4,
+        published_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     post (id) {
         id -> Int4,
@@ -824,6 +1113,7 @@ diesel::table! {
         read_comments_amount -> Nullable<Int4>,
         vote_is_upvote -> Nullable<Bool>,
         notifications -> Nullable<PostNotificationsModeEnum>,
+        poll_voted_at -> Nullable<Timestamptz>,
     }
 }
 
@@ -835,6 +1125,17 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    post_history (id) {
+        id -> Int4,
+        post_id -> Int4,
+        name -> Text,
+        body -> Nullable<Text>,
+        url -> Nullable<Text>,
+        published_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     post_report (id) {
         id -> Int4,
@@ -853,6 +1154,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::Tsvector;
+
+    post_search (post_id) {
+        post_id -> Int4,
+        search_vector -> Tsvector,
+    }
+}
+
 diesel::table! {
     private_message (id) {
         id -> Int4,
@@ -884,6 +1195,32 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    push_subscription (id) {
+        id -> Int4,
+        local_user_id -> Int4,
+        endpoint -> Text,
+        p256dh -> Text,
+        auth -> Text,
+        published_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    queued_email (id) {
+        id -> Int4,
+        to_email -> Text,
+        to_name -> Text,
+        subject -> Text,
+        html -> Text,
+        attempts -> Int4,
+        next_attempt_at -> Nullable<Timestamptz>,
+        last_error -> Nullable<Text>,
+        sent_at -> Nullable<Timestamptz>,
+        published_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     received_activity (ap_id) {
         ap_id -> Text,
@@ -891,6 +1228,44 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    received_activity_log (id) {
+        id -> Int4,
+        ap_id -> Nullable<Text>,
+        actor_id -> Nullable<Text>,
+        domain -> Nullable<Text>,
+        kind -> Nullable<Text>,
+        data -> Nullable<Text>,
+        signature_verified -> Bool,
+        success -> Bool,
+        error -> Nullable<Text>,
+        published_at -> Timestamptz,
+        replayed_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::RecurringPostFrequencyEnum;
+
+    recurring_post (id) {
+        id -> Int4,
+        community_id -> Int4,
+        creator_id -> Int4,
+        name -> Text,
+        body -> Nullable<Text>,
+        nsfw -> Bool,
+        language_id -> Nullable<Int4>,
+        frequency -> RecurringPostFrequencyEnum,
+        next_publish_at -> Timestamptz,
+        feature -> Bool,
+        last_post_id -> Nullable<Int4>,
+        enabled -> Bool,
+        published_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
 diesel::table! {
     registration_application (id) {
         id -> Int4,
@@ -933,6 +1308,7 @@ diesel::table! {
     secret (id) {
         id -> Int4,
         jwt_secret -> Varchar,
+        vapid_private_key -> Nullable<Text>,
     }
 }
 
@@ -994,13 +1370,57 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::WebhookEventTypeEnum;
+
+    webhook (id) {
+        id -> Int4,
+        community_id -> Nullable<Int4>,
+        creator_id -> Int4,
+        url -> Text,
+        secret -> Text,
+        events -> Array<Nullable<WebhookEventTypeEnum>>,
+        enabled -> Bool,
+        published_at -> Timestamptz,
+        updated_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::WebhookEventTypeEnum;
+
+    webhook_delivery (id) {
+        id -> Int4,
+        webhook_id -> Int4,
+        event -> WebhookEventTypeEnum,
+        payload -> Text,
+        attempts -> Int4,
+        next_attempt_at -> Nullable<Timestamptz>,
+        last_status -> Nullable<Int4>,
+        last_error -> Nullable<Text>,
+        delivered_at -> Nullable<Timestamptz>,
+        published_at -> Timestamptz,
+    }
+}
+
+diesel::joinable!(api_token -> local_user (local_user_id));
+diesel::joinable!(api_token -> oauth_client (oauth_client_id));
+diesel::joinable!(automod_rule -> community (community_id));
+diesel::joinable!(automod_rule -> community_tag (community_tag_id));
+diesel::joinable!(ban_appeal -> community (community_id));
+diesel::joinable!(ban_appeal -> modlog (modlog_id));
+diesel::joinable!(ban_appeal -> person (creator_id));
 diesel::joinable!(comment -> community (community_id));
 diesel::joinable!(comment -> language (language_id));
 diesel::joinable!(comment -> person (creator_id));
 diesel::joinable!(comment -> post (post_id));
 diesel::joinable!(comment_actions -> comment (comment_id));
 diesel::joinable!(comment_actions -> person (person_id));
+diesel::joinable!(comment_history -> comment (comment_id));
 diesel::joinable!(comment_report -> comment (comment_id));
+diesel::joinable!(comment_search -> comment (comment_id));
 diesel::joinable!(community -> instance (instance_id));
 diesel::joinable!(community_actions -> community (community_id));
 diesel::joinable!(community_language -> community (community_id));
@@ -1008,10 +1428,15 @@ diesel::joinable!(community_language -> language (language_id));
 diesel::joinable!(community_report -> community (community_id));
 diesel::joinable!(community_tag -> community (community_id));
 diesel::joinable!(custom_emoji_keyword -> custom_emoji (custom_emoji_id));
+diesel::joinable!(draft -> community (community_id));
+diesel::joinable!(draft -> language (language_id));
+diesel::joinable!(draft -> post (post_id));
 diesel::joinable!(email_verification -> local_user (local_user_id));
 diesel::joinable!(federation_allowlist -> instance (instance_id));
 diesel::joinable!(federation_blocklist -> instance (instance_id));
+diesel::joinable!(federation_queue_control -> instance (instance_id));
 diesel::joinable!(federation_queue_state -> instance (instance_id));
+diesel::joinable!(federation_send_error -> instance (instance_id));
 diesel::joinable!(instance_actions -> instance (instance_id));
 diesel::joinable!(instance_actions -> person (person_id));
 diesel::joinable!(local_image -> person (person_id));
@@ -1030,20 +1455,33 @@ diesel::joinable!(modlog -> comment (target_comment_id));
 diesel::joinable!(modlog -> community (target_community_id));
 diesel::joinable!(modlog -> instance (target_instance_id));
 diesel::joinable!(modlog -> post (target_post_id));
+diesel::joinable!(modmail_message -> modmail_thread (thread_id));
+diesel::joinable!(modmail_message -> person (creator_id));
+diesel::joinable!(modmail_thread -> comment (comment_id));
+diesel::joinable!(modmail_thread -> community (community_id));
+diesel::joinable!(modmail_thread -> modlog (modlog_id));
+diesel::joinable!(modmail_thread -> person (creator_id));
+diesel::joinable!(modmail_thread -> post (post_id));
 diesel::joinable!(multi_community -> instance (instance_id));
 diesel::joinable!(multi_community -> person (creator_id));
 diesel::joinable!(multi_community_entry -> community (community_id));
 diesel::joinable!(multi_community_entry -> multi_community (multi_community_id));
 diesel::joinable!(multi_community_follow -> multi_community (multi_community_id));
 diesel::joinable!(multi_community_follow -> person (person_id));
+diesel::joinable!(notification -> ban_appeal (ban_appeal_id));
 diesel::joinable!(notification -> comment (comment_id));
 diesel::joinable!(notification -> community (community_id));
 diesel::joinable!(notification -> instance (instance_id));
 diesel::joinable!(notification -> modlog (modlog_id));
+diesel::joinable!(notification -> modmail_thread (modmail_thread_id));
 diesel::joinable!(notification -> post (post_id));
 diesel::joinable!(notification -> private_message (private_message_id));
 diesel::joinable!(oauth_account -> local_user (local_user_id));
 diesel::joinable!(oauth_account -> oauth_provider (oauth_provider_id));
+diesel::joinable!(oauth_authorization_code -> local_user (local_user_id));
+diesel::joinable!(oauth_authorization_code -> oauth_client (oauth_client_id));
+diesel::joinable!(passkey -> local_user (local_user_id));
+diesel::joinable!(passkey_challenge -> local_user (local_user_id));
 diesel::joinable!(password_reset_request -> local_user (local_user_id));
 diesel::joinable!(person -> instance (instance_id));
 diesel::joinable!(person_content_combined -> comment (comment_id));
@@ -1056,6 +1494,11 @@ diesel::joinable!(person_liked_combined -> post (post_id));
 diesel::joinable!(person_saved_combined -> comment (comment_id));
 diesel::joinable!(person_saved_combined -> community (community_id));
 diesel::joinable!(person_saved_combined -> post (post_id));
+diesel::joinable!(poll -> post (post_id));
+diesel::joinable!(poll_choice -> poll (post_id));
+diesel::joinable!(poll_vote -> person (person_id));
+diesel::joinable!(poll_vote -> poll (post_id));
+diesel::joinable!(poll_vote -> poll_choice (poll_choice_id));
 diesel::joinable!(post -> community (community_id));
 diesel::joinable!(post -> language (language_id));
 diesel::joinable!(post -> person (creator_id));
@@ -1063,8 +1506,15 @@ diesel::joinable!(post_actions -> person (person_id));
 diesel::joinable!(post_actions -> post (post_id));
 diesel::joinable!(post_community_tag -> community_tag (community_tag_id));
 diesel::joinable!(post_community_tag -> post (post_id));
+diesel::joinable!(post_history -> post (post_id));
 diesel::joinable!(post_report -> post (post_id));
+diesel::joinable!(post_search -> post (post_id));
 diesel::joinable!(private_message_report -> private_message (private_message_id));
+diesel::joinable!(push_subscription -> local_user (local_user_id));
+diesel::joinable!(recurring_post -> community (community_id));
+diesel::joinable!(recurring_post -> language (language_id));
+diesel::joinable!(recurring_post -> person (creator_id));
+diesel::joinable!(recurring_post -> post (last_post_id));
 diesel::joinable!(registration_application -> local_user (local_user_id));
 diesel::joinable!(registration_application -> person (admin_id));
 diesel::joinable!(report_combined -> comment (comment_id));
@@ -1078,20 +1528,32 @@ diesel::joinable!(report_combined -> private_message_report (private_message_rep
 diesel::joinable!(site -> instance (instance_id));
 diesel::joinable!(site_language -> language (language_id));
 diesel::joinable!(site_language -> site (site_id));
+diesel::joinable!(webhook -> community (community_id));
+diesel::joinable!(webhook -> person (creator_id));
+diesel::joinable!(webhook_delivery -> webhook (webhook_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+  api_token,
+  automod_rule,
+  ban_appeal,
   comment,
   comment_actions,
+  comment_history,
   comment_report,
+  comment_search,
   community,
   community_actions,
   community_language,
   community_report,
   community_tag,
+  draft,
+  email_template,
   email_verification,
   federation_allowlist,
   federation_blocklist,
+  federation_queue_control,
   federation_queue_state,
+  federation_send_error,
   instance,
   instance_actions,
   language,
@@ -1104,27 +1566,43 @@ diesel::allow_tables_to_appear_in_same_query!(
   local_user_language,
   login_token,
   modlog,
+  modmail_message,
+  modmail_thread,
   multi_community,
   multi_community_entry,
   multi_community_follow,
   notification,
   oauth_account,
+  oauth_authorization_code,
+  oauth_client,
   oauth_provider,
+  passkey,
+  passkey_challenge,
   password_reset_request,
   person,
   person_content_combined,
   person_liked_combined,
   person_saved_combined,
+  poll,
+  poll_choice,
+  poll_vote,
   post,
   post_actions,
   post_community_tag,
+  post_history,
   post_report,
+  post_search,
   private_message,
   private_message_report,
+  push_subscription,
+  queued_email,
+  recurring_post,
   registration_application,
   report_combined,
   site,
   site_language,
+  webhook,
+  webhook_delivery,
   person_actions,
   image_details,
 );
@@ -0,0 +1,57 @@
+use crate::BanAppealView;
+use lemmy_db_schema::newtypes::{BanAppealId, CommunityId, ModlogId};
+use lemmy_diesel_utils::pagination::PaginationCursor;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Appeal against a ban from a community or from the site. Each ban can only be appealed once.
+pub struct CreateBanAppeal {
+  /// The modlog entry of the ban.
+  pub modlog_id: ModlogId,
+  pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Gets the appeal against a ban.
+pub struct GetBanAppeal {
+  pub modlog_id: ModlogId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Approves or denies a ban appeal. Approving it reverts the ban.
+pub struct ResolveBanAppeal {
+  pub id: BanAppealId,
+  pub approve: bool,
+  pub deny_reason: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Fetches a list of ban appeals.
+pub struct ListBanAppeals {
+  /// Appeals against bans from this community. If empty, lists appeals against site bans, which
+  /// are only visible to admins.
+  pub community_id: Option<CommunityId>,
+  /// Only shows the appeals which weren't approved or denied yet
+  pub unresolved_only: Option<bool>,
+  pub page_cursor: Option<PaginationCursor>,
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// The response of an action done to a ban appeal.
+pub struct BanAppealResponse {
+  pub ban_appeal: BanAppealView,
+}
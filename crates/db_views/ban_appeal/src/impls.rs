@@ -0,0 +1,234 @@
+use crate::BanAppealView;
+use diesel::{ExpressionMethods, JoinOnDsl, NullableExpressionMethods, QueryDsl, SelectableHelper};
+use diesel_async::RunQueryDsl;
+use i_love_jesus::SortDirection;
+use lemmy_db_schema::{
+  newtypes::{BanAppealId, CommunityId, ModlogId},
+  source::ban_appeal::{BanAppeal, ban_appeal_keys as key},
+  utils::limit_fetch,
+};
+use lemmy_db_schema_file::{
+  aliases,
+  schema::{ban_appeal, community, modlog, person},
+};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  pagination::{
+    CursorData,
+    PagedResponse,
+    PaginationCursor,
+    PaginationCursorConversion,
+    paginate_response,
+  },
+  traits::Crud,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl PaginationCursorConversion for BanAppealView {
+  type PaginatedType = BanAppeal;
+  fn to_cursor(&self) -> CursorData {
+    CursorData::new_id(self.ban_appeal.id.0)
+  }
+
+  async fn from_cursor(
+    cursor: CursorData,
+    pool: &mut DbPool<'_>,
+  ) -> LemmyResult<Self::PaginatedType> {
+    BanAppeal::read(pool, BanAppealId(cursor.id()?)).await
+  }
+}
+
+impl BanAppealView {
+  #[diesel::dsl::auto_type(no_type_alias)]
+  fn joins() -> _ {
+    let modlog_join = modlog::table.on(ban_appeal::modlog_id.eq(modlog::id));
+    let creator_join = person::table.on(ban_appeal::creator_id.eq(person::id));
+    let community_join = community::table.on(ban_appeal::community_id.eq(community::id.nullable()));
+    let resolver_join = aliases::person1
+      .on(ban_appeal::resolver_id.eq(aliases::person1.field(person::id).nullable()));
+
+    ban_appeal::table
+      .inner_join(modlog_join)
+      .inner_join(creator_join)
+      .left_join(community_join)
+      .left_join(resolver_join)
+  }
+
+  pub async fn read(pool: &mut DbPool<'_>, id: BanAppealId) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    Self::joins()
+      .filter(ban_appeal::id.eq(id))
+      .select(Self::as_select())
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  pub async fn read_by_modlog(pool: &mut DbPool<'_>, modlog_id: ModlogId) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    Self::joins()
+      .filter(ban_appeal::modlog_id.eq(modlog_id))
+      .select(Self::as_select())
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+#[derive(Default)]
+pub struct BanAppealQuery {
+  /// Appeals against bans from this community. Otherwise only appeals against site bans.
+  pub community_id: Option<CommunityId>,
+  pub unresolved_only: Option<bool>,
+  pub page_cursor: Option<PaginationCursor>,
+  pub limit: Option<i64>,
+}
+
+impl BanAppealQuery {
+  pub async fn list(self, pool: &mut DbPool<'_>) -> LemmyResult<PagedResponse<BanAppealView>> {
+    let limit = limit_fetch(self.limit, None)?;
+
+    let mut query = BanAppealView::joins()
+      .select(BanAppealView::as_select())
+      .limit(limit)
+      .into_boxed();
+
+    if let Some(community_id) = self.community_id {
+      query = query.filter(ban_appeal::community_id.eq(community_id));
+    } else {
+      query = query.filter(ban_appeal::community_id.is_null());
+    }
+
+    if self.unresolved_only.unwrap_or_default() {
+      query = query.filter(BanAppeal::is_unresolved());
+    }
+
+    let paginated_query =
+      BanAppealView::paginate(query, &self.page_cursor, SortDirection::Desc, pool)
+        .await?
+        .then_order_by(key::published_at)
+        .then_order_by(key::id);
+
+    let conn = &mut get_conn(pool).await?;
+    let res = paginated_query
+      .load::<BanAppealView>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)?;
+    paginate_response(res, limit, self.page_cursor)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{BanAppealView, impls::BanAppealQuery};
+  use chrono::Utc;
+  use lemmy_db_schema::source::{
+    ban_appeal::{BanAppeal, BanAppealInsertForm, BanAppealUpdateForm},
+    community::{Community, CommunityInsertForm},
+    instance::Instance,
+    modlog::{Modlog, ModlogInsertForm},
+    person::{Person, PersonInsertForm},
+  };
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_ban_appeal_views() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let admin = Person::create(
+      pool,
+      &PersonInsertForm::test_form(instance.id, "appeal_view_admin"),
+    )
+    .await?;
+    let user = Person::create(
+      pool,
+      &PersonInsertForm::test_form(instance.id, "appeal_view_user"),
+    )
+    .await?;
+    let community = Community::create(
+      pool,
+      &CommunityInsertForm::new(instance.id, "ban_appeal_views".into(), "pubkey".into()),
+    )
+    .await?;
+
+    let form = ModlogInsertForm::admin_ban(&admin, user.id, true, None, "spam");
+    let site_ban = Modlog::create(pool, &[form]).await?;
+    let site_ban_id = site_ban.first().ok_or(LemmyErrorType::NotFound)?.id;
+    let form = ModlogInsertForm::mod_ban_from_community(
+      admin.id,
+      community.id,
+      user.id,
+      true,
+      None,
+      "off topic",
+    );
+    let community_ban = Modlog::create(pool, &[form]).await?;
+    let community_ban_id = community_ban.first().ok_or(LemmyErrorType::NotFound)?.id;
+
+    let form = BanAppealInsertForm::new(site_ban_id, user.id, None, "sorry".to_string());
+    let site_appeal = BanAppeal::create(pool, &form).await?;
+    let form = BanAppealInsertForm::new(
+      community_ban_id,
+      user.id,
+      Some(community.id),
+      "it was on topic".to_string(),
+    );
+    let community_appeal = BanAppeal::create(pool, &form).await?;
+
+    let view = BanAppealView::read_by_modlog(pool, community_ban_id).await?;
+    assert_eq!(community_appeal, view.ban_appeal);
+    assert_eq!(Some(community.clone()), view.community);
+    assert_eq!(user, view.creator);
+    assert!(view.resolver.is_none());
+
+    // Site bans and community bans are in separate queues
+    let site_appeals = BanAppealQuery::default().list(pool).await?;
+    assert_eq!(
+      vec![site_appeal.id],
+      site_appeals
+        .iter()
+        .map(|a| a.ban_appeal.id)
+        .collect::<Vec<_>>()
+    );
+    let community_appeals = BanAppealQuery {
+      community_id: Some(community.id),
+      ..Default::default()
+    }
+    .list(pool)
+    .await?;
+    assert_eq!(
+      vec![community_appeal.id],
+      community_appeals
+        .iter()
+        .map(|a| a.ban_appeal.id)
+        .collect::<Vec<_>>()
+    );
+
+    let form = BanAppealUpdateForm {
+      resolver_id: Some(Some(admin.id)),
+      approved: Some(true),
+      resolved_at: Some(Some(Utc::now())),
+      ..Default::default()
+    };
+    BanAppeal::update(pool, site_appeal.id, &form).await?;
+    let view = BanAppealView::read(pool, site_appeal.id).await?;
+    assert_eq!(Some(admin), view.resolver);
+
+    let unresolved = BanAppealQuery {
+      unresolved_only: Some(true),
+      ..Default::default()
+    }
+    .list(pool)
+    .await?;
+    assert!(unresolved.is_empty());
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
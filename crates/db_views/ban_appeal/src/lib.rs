@@ -0,0 +1,43 @@
+use lemmy_db_schema::source::{
+  ban_appeal::BanAppeal,
+  community::Community,
+  modlog::Modlog,
+  person::Person,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+#[cfg(feature = "full")]
+use {
+  diesel::{NullableExpressionMethods, Queryable, Selectable, helper_types::Nullable},
+  lemmy_db_schema::{Person1AliasAllColumnsTuple, utils::queries::selects::person1_select},
+};
+
+pub mod api;
+#[cfg(feature = "full")]
+pub mod impls;
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// A ban appeal view.
+pub struct BanAppealView {
+  #[cfg_attr(feature = "full", diesel(embed))]
+  pub ban_appeal: BanAppeal,
+  /// The modlog entry of the appealed ban.
+  #[cfg_attr(feature = "full", diesel(embed))]
+  pub ban: Modlog,
+  #[cfg_attr(feature = "full", diesel(embed))]
+  pub creator: Person,
+  #[cfg_attr(feature = "full", diesel(embed))]
+  pub community: Option<Community>,
+  #[cfg_attr(feature = "full",
+    diesel(
+      select_expression_type = Nullable<Person1AliasAllColumnsTuple>,
+      select_expression = person1_select().nullable()
+    )
+  )]
+  pub resolver: Option<Person>,
+}
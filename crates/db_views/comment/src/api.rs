@@ -1,4 +1,5 @@
 use crate::CommentView;
+use chrono::{DateTime, Utc};
 use lemmy_db_schema::newtypes::{CommentId, CommunityId, LanguageId, PostId};
 use lemmy_db_schema_file::{
   PersonId,
@@ -102,6 +103,37 @@ pub struct ListCommentLikes {
   pub limit: Option<i64>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Get the edit history of a comment. Only mods and admins can see it, unless the site makes edit
+/// history public.
+pub struct GetCommentHistory {
+  pub comment_id: CommentId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct GetCommentHistoryResponse {
+  /// All versions of the comment, oldest first. The last one is the current version. Empty if the
+  /// comment was removed or deleted, unless you are a mod or the creator.
+  pub revisions: Vec<CommentRevision>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// A version of a comment.
+pub struct CommentRevision {
+  pub content: String,
+  /// When this version was published, or the time of the edit which created it.
+  pub published_at: DateTime<Utc>,
+  /// Unified diff against the previous version, if the content changed.
+  pub content_diff: Option<String>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
@@ -24,11 +24,20 @@ use lemmy_db_schema::{
   },
   utils::{
     limit_fetch,
-    queries::filters::{
-      filter_blocked,
-      filter_is_subscribed,
-      filter_private_or_followed,
-      filter_unlisted_or_followed,
+    queries::{
+      filters::{
+        filter_blocked,
+        filter_is_subscribed,
+        filter_private_or_followed,
+        filter_unlisted_or_followed,
+      },
+      search::{
+        TsvectorExpressionMethods,
+        comment_search_vector,
+        search_query,
+        search_query_any_language,
+        ts_rank,
+      },
     },
   },
 };
@@ -52,7 +61,7 @@ use lemmy_db_schema_file::{
     my_local_user_admin_join,
     my_person_actions_join,
   },
-  schema::{comment, community, person, post},
+  schema::{comment, comment_search, community, person, post},
 };
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
@@ -61,10 +70,12 @@ use lemmy_diesel_utils::{
     PagedResponse,
     PaginationCursor,
     PaginationCursorConversion,
+    offset_from_cursor,
     paginate_response,
+    paginate_response_by_offset,
   },
   traits::Crud,
-  utils::{Subpath, fuzzy_search, now, seconds_to_pg_interval},
+  utils::{Subpath, full_text_search_query, fuzzy_search, now, seconds_to_pg_interval},
 };
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
 
@@ -170,6 +181,8 @@ pub struct CommentQuery<'a> {
   pub local_user: Option<&'a LocalUser>,
   pub max_depth: Option<i32>,
   pub search_term: Option<String>,
+  /// Order search results by relevance, only used with [LocalSite.full_text_search].
+  pub search_sort_by_relevance: Option<bool>,
   pub page_cursor: Option<PaginationCursor>,
   pub limit: Option<i64>,
 }
@@ -275,7 +288,30 @@ impl CommentQuery<'_> {
     }
 
     // The search term
-    if let Some(search_term) = self.search_term {
+    let full_text_query = self
+      .search_term
+      .as_deref()
+      .filter(|_| local_site.full_text_search)
+      .and_then(|t| full_text_search_query(t, false));
+    if let Some(full_text_query) = &full_text_query {
+      // The first filter can use the search index, the second one checks each comment against
+      // the query for its own language.
+      query = query
+        .filter(
+          comment::id.eq_any(
+            comment_search::table
+              .select(comment_search::comment_id)
+              .filter(
+                comment_search::search_vector
+                  .matches(search_query_any_language(full_text_query.clone())),
+              ),
+          ),
+        )
+        .filter(
+          comment_search_vector()
+            .matches(search_query(full_text_query.clone(), comment::language_id)),
+        );
+    } else if let Some(search_term) = self.search_term {
       let searcher = fuzzy_search(&search_term);
       query = query.filter(comment::content.ilike(searcher));
     }
@@ -326,6 +362,25 @@ impl CommentQuery<'_> {
     };
     query = query.limit(limit);
 
+    // Relevance can't be used as a cursor key, so these results are paginated with an offset
+    if self.search_sort_by_relevance.unwrap_or_default()
+      && let Some(full_text_query) = full_text_query
+    {
+      let offset = offset_from_cursor(&self.page_cursor)?;
+      let rank = ts_rank(
+        comment_search_vector(),
+        search_query(full_text_query, comment::language_id),
+      );
+      let query = query
+        .order_by(rank.desc())
+        .then_order_by(comment::id.desc())
+        .offset(offset);
+
+      let conn = &mut get_conn(pool).await?;
+      let res = query.load::<CommentView>(conn).await?;
+      return paginate_response_by_offset(res, limit, offset);
+    }
+
     // Only sort by ascending for Old
     let sort = self.sort.unwrap_or(Hot);
     let sort_direction = asc_if(sort == Old);
@@ -385,7 +440,7 @@ mod tests {
       },
       instance::Instance,
       language::Language,
-      local_site::LocalSite,
+      local_site::{LocalSite, LocalSiteUpdateForm},
       local_user::{LocalUser, LocalUserInsertForm, LocalUserUpdateForm},
       person::{Person, PersonActions, PersonBlockForm, PersonInsertForm},
       post::{Post, PostInsertForm, PostUpdateForm},
@@ -1143,4 +1198,61 @@ mod tests {
 
     cleanup(data, pool).await
   }
+
+  #[tokio::test]
+  #[serial]
+  async fn full_text_search() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+    let form = LocalSiteUpdateForm {
+      full_text_search: Some(true),
+      ..Default::default()
+    };
+    let local_site = LocalSite::update(pool, &form).await?;
+
+    let english_id = Language::read_id_from_code(pool, "en").await?;
+    let english_form = CommentInsertForm {
+      language_id: Some(english_id),
+      ..CommentInsertForm::new(
+        data.timmy.person.id,
+        data.post.id,
+        data.community.id,
+        "The cats are sleeping".into(),
+      )
+    };
+    let english_comment = Comment::create(pool, &english_form, None).await?;
+    let undetermined_form = CommentInsertForm {
+      language_id: Some(UNDETERMINED_ID),
+      ..CommentInsertForm::new(
+        data.timmy.person.id,
+        data.post.id,
+        data.community.id,
+        "More cats".into(),
+      )
+    };
+    Comment::create(pool, &undetermined_form, None).await?;
+
+    // Stemming depends on the language of the comment, not on the language of the user
+    let search_stemmed = CommentQuery {
+      search_term: Some("cat".into()),
+      ..Default::default()
+    }
+    .list(pool, &data.site, &local_site)
+    .await?;
+    assert_length!(1, search_stemmed);
+    assert_eq!(english_comment.id, search_stemmed[0].comment.id);
+
+    // Comments in every language match the exact word
+    let search_exact = CommentQuery {
+      search_term: Some("comment".into()),
+      search_sort_by_relevance: Some(true),
+      ..Default::default()
+    }
+    .list(pool, &data.site, &local_site)
+    .await?;
+    assert_length!(6, search_exact);
+
+    cleanup(data, pool).await
+  }
 }
@@ -3,12 +3,26 @@ use lemmy_db_schema::{
   CommunitySortType,
   MultiCommunityListingType,
   MultiCommunitySortType,
-  newtypes::{CommunityId, CommunityTagId, LanguageId, MultiCommunityId},
-  source::site::Site,
+  newtypes::{
+    AutomodRuleId,
+    CommunityId,
+    CommunityTagId,
+    LanguageId,
+    MultiCommunityId,
+    RecurringPostId,
+  },
+  source::{automod_rule::AutomodRule, recurring_post::RecurringPost, site::Site},
 };
 use lemmy_db_schema_file::{
   PersonId,
-  enums::{CommunityNotificationsMode, CommunityVisibility, ListingType, TagColor},
+  enums::{
+    AutomodAction,
+    CommunityNotificationsMode,
+    CommunityVisibility,
+    ListingType,
+    RecurringPostFrequency,
+    TagColor,
+  },
 };
 use lemmy_db_views_community_moderator::CommunityModeratorView;
 use lemmy_diesel_utils::pagination::PaginationCursor;
@@ -139,6 +153,9 @@ pub struct EditCommunity {
   pub posting_restricted_to_mods: Option<bool>,
   pub discussion_languages: Option<Vec<LanguageId>>,
   pub visibility: Option<CommunityVisibility>,
+  /// Previous identities of this community, as ActivityPub ids like
+  /// `https://example.com/c/name`. These communities are allowed to move here. At most 10.
+  pub also_known_as: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
@@ -244,6 +261,26 @@ pub struct TransferCommunity {
   pub person_id: PersonId,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Move a local community to another community, which needs to list this community in
+/// `also_known_as`. Followers are asked to follow the new community instead.
+pub struct MoveCommunity {
+  pub community_id: CommunityId,
+  /// The new community, for example `name@example.com`.
+  pub target: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Import the posts of communities which moved to this community. The import runs in the
+/// background.
+pub struct ImportCommunityHistory {
+  pub community_id: CommunityId,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
@@ -363,3 +400,147 @@ pub struct DeleteCommunityTag {
   pub tag_id: CommunityTagId,
   pub delete: bool,
 }
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Create an automod rule for a community. The rule matches new posts and comments for which all
+/// of the given conditions match.
+pub struct CreateAutomodRule {
+  pub community_id: CommunityId,
+  pub name: String,
+  pub title_regex: Option<String>,
+  pub body_regex: Option<String>,
+  pub url_regex: Option<String>,
+  pub max_account_age_days: Option<i32>,
+  pub max_post_score: Option<i32>,
+  pub max_comment_score: Option<i32>,
+  pub domains: Option<Vec<String>>,
+  pub language_ids: Option<Vec<LanguageId>>,
+  pub action: AutomodAction,
+  pub community_tag_id: Option<CommunityTagId>,
+  pub reply_text: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Edit an automod rule. The conditions and action of the rule are replaced with the given ones.
+pub struct EditAutomodRule {
+  pub id: AutomodRuleId,
+  pub name: String,
+  pub title_regex: Option<String>,
+  pub body_regex: Option<String>,
+  pub url_regex: Option<String>,
+  pub max_account_age_days: Option<i32>,
+  pub max_post_score: Option<i32>,
+  pub max_comment_score: Option<i32>,
+  pub domains: Option<Vec<String>>,
+  pub language_ids: Option<Vec<LanguageId>>,
+  pub action: AutomodAction,
+  pub community_tag_id: Option<CommunityTagId>,
+  pub reply_text: Option<String>,
+  pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Delete an automod rule.
+pub struct DeleteAutomodRule {
+  pub id: AutomodRuleId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// List the automod rules of a community.
+pub struct ListAutomodRules {
+  pub community_id: CommunityId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct ListAutomodRulesResponse {
+  pub rules: Vec<AutomodRule>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct AutomodRuleResponse {
+  pub rule: AutomodRule,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Create a recurring post for a community. The title and body may contain the placeholders
+/// `{date}`, `{week}`, `{week_year}`, `{month}` and `{year}`, which are filled in when a post is
+/// published. `{week_year}` is the year which the ISO week belongs to, and should be used together
+/// with `{week}`.
+pub struct CreateRecurringPost {
+  pub community_id: CommunityId,
+  pub name: String,
+  pub body: Option<String>,
+  pub nsfw: Option<bool>,
+  pub language_id: Option<LanguageId>,
+  pub frequency: RecurringPostFrequency,
+  /// Time when the first post is published, as unix timestamp. Later posts are published at the
+  /// same time of day.
+  pub first_publish_at: i64,
+  /// Feature each post in the community, and unfeature the previous one.
+  pub feature: Option<bool>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Edit a recurring post.
+pub struct EditRecurringPost {
+  pub id: RecurringPostId,
+  pub name: Option<String>,
+  pub body: Option<String>,
+  pub nsfw: Option<bool>,
+  pub language_id: Option<LanguageId>,
+  pub frequency: Option<RecurringPostFrequency>,
+  /// Move the schedule, so that the next post is published at this time.
+  pub next_publish_at: Option<i64>,
+  pub feature: Option<bool>,
+  pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Delete a recurring post. Posts which were already published are kept.
+pub struct DeleteRecurringPost {
+  pub id: RecurringPostId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// List the recurring posts of a community.
+pub struct ListRecurringPosts {
+  pub community_id: CommunityId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct ListRecurringPostsResponse {
+  pub recurring_posts: Vec<RecurringPost>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct RecurringPostResponse {
+  pub recurring_post: RecurringPost,
+}
@@ -73,6 +73,24 @@ impl CommunityFollowerView {
       .with_lemmy_type(LemmyErrorType::NotFound)
   }
 
+  /// Ids of the accepted followers from the given instance, for followers collection
+  /// synchronization.
+  pub async fn list_follower_ids_from_instance(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+    instance_id: InstanceId,
+  ) -> LemmyResult<Vec<(PersonId, DbUrl)>> {
+    let conn = &mut get_conn(pool).await?;
+    Self::joins()
+      .filter(community_actions::community_id.eq(community_id))
+      .filter(person::instance_id.eq(instance_id))
+      .filter(community_actions::follow_state.eq(CommunityFollowerState::Accepted))
+      .select((person::id, person::ap_id))
+      .load::<(PersonId, DbUrl)>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
   pub async fn for_person(pool: &mut DbPool<'_>, person_id: PersonId) -> LemmyResult<Vec<Self>> {
     let conn = &mut get_conn(pool).await?;
     Self::joins()
@@ -0,0 +1,100 @@
+use crate::DraftView;
+use lemmy_db_schema::newtypes::{CommentId, CommunityId, DraftId, LanguageId, PostId};
+use lemmy_db_schema_file::PersonId;
+use lemmy_db_views_comment::CommentView;
+use lemmy_db_views_post::PostView;
+use lemmy_db_views_private_message::PrivateMessageView;
+use lemmy_diesel_utils::pagination::PaginationCursor;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Save a draft. Set `community_id` for a post, `post_id` for a comment, or `recipient_id` for a
+/// private message.
+pub struct CreateDraft {
+  pub community_id: Option<CommunityId>,
+  pub post_id: Option<PostId>,
+  /// The parent comment when drafting a reply to a comment.
+  pub parent_id: Option<CommentId>,
+  pub recipient_id: Option<PersonId>,
+  /// The post title.
+  pub name: Option<String>,
+  pub url: Option<String>,
+  /// The post body, or the content of a comment or private message.
+  pub body: Option<String>,
+  pub alt_text: Option<String>,
+  pub nsfw: Option<bool>,
+  pub language_id: Option<LanguageId>,
+  pub custom_thumbnail: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Edit a draft. The target of a draft can't be changed.
+pub struct EditDraft {
+  pub draft_id: DraftId,
+  pub name: Option<String>,
+  pub url: Option<String>,
+  pub body: Option<String>,
+  pub alt_text: Option<String>,
+  pub nsfw: Option<bool>,
+  pub language_id: Option<LanguageId>,
+  pub custom_thumbnail: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Delete a draft.
+pub struct DeleteDraft {
+  pub draft_id: DraftId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Get a draft.
+pub struct GetDraft {
+  pub draft_id: DraftId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// List your drafts, newest first.
+pub struct ListDrafts {
+  pub page_cursor: Option<PaginationCursor>,
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Publish a draft as a post, comment or private message. The draft is deleted afterwards.
+pub struct PublishDraft {
+  pub draft_id: DraftId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct DraftResponse {
+  pub draft_view: DraftView,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// The published content, depending on the type of the draft.
+pub struct PublishDraftResponse {
+  pub post_view: Option<PostView>,
+  pub comment_view: Option<CommentView>,
+  pub private_message_view: Option<PrivateMessageView>,
+}
@@ -0,0 +1,177 @@
+use crate::DraftView;
+use diesel::{ExpressionMethods, JoinOnDsl, NullableExpressionMethods, QueryDsl, SelectableHelper};
+use diesel_async::RunQueryDsl;
+use i_love_jesus::SortDirection;
+use lemmy_db_schema::{
+  newtypes::DraftId,
+  source::draft::{Draft, draft_keys as key},
+  utils::limit_fetch,
+};
+use lemmy_db_schema_file::{
+  PersonId,
+  schema::{community, draft, person, post},
+};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  pagination::{
+    CursorData,
+    PagedResponse,
+    PaginationCursor,
+    PaginationCursorConversion,
+    paginate_response,
+  },
+  traits::Crud,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl PaginationCursorConversion for DraftView {
+  type PaginatedType = Draft;
+  fn to_cursor(&self) -> CursorData {
+    CursorData::new_id(self.draft.id.0)
+  }
+
+  async fn from_cursor(
+    cursor: CursorData,
+    pool: &mut DbPool<'_>,
+  ) -> LemmyResult<Self::PaginatedType> {
+    Draft::read(pool, DraftId(cursor.id()?)).await
+  }
+}
+
+impl DraftView {
+  #[diesel::dsl::auto_type(no_type_alias)]
+  fn joins() -> _ {
+    let community_join = community::table.on(draft::community_id.eq(community::id.nullable()));
+    let post_join = post::table.on(draft::post_id.eq(post::id.nullable()));
+    let recipient_join = person::table.on(draft::recipient_id.eq(person::id.nullable()));
+
+    draft::table
+      .left_join(community_join)
+      .left_join(post_join)
+      .left_join(recipient_join)
+  }
+
+  pub async fn read(pool: &mut DbPool<'_>, draft_id: DraftId) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    Self::joins()
+      .filter(draft::id.eq(draft_id))
+      .select(Self::as_select())
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+pub struct DraftQuery {
+  pub creator_id: PersonId,
+  pub page_cursor: Option<PaginationCursor>,
+  pub limit: Option<i64>,
+}
+
+impl DraftQuery {
+  pub async fn list(self, pool: &mut DbPool<'_>) -> LemmyResult<PagedResponse<DraftView>> {
+    let limit = limit_fetch(self.limit, None)?;
+
+    let query = DraftView::joins()
+      .filter(draft::creator_id.eq(self.creator_id))
+      .select(DraftView::as_select())
+      .limit(limit)
+      .into_boxed();
+
+    let paginated_query = DraftView::paginate(query, &self.page_cursor, SortDirection::Desc, pool)
+      .await?
+      .then_order_by(key::published_at)
+      .then_order_by(key::id);
+
+    let conn = &mut get_conn(pool).await?;
+    let res = paginated_query
+      .load::<DraftView>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)?;
+    paginate_response(res, limit, self.page_cursor)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{DraftView, impls::DraftQuery};
+  use lemmy_db_schema::source::{
+    community::{Community, CommunityInsertForm},
+    draft::{Draft, DraftInsertForm},
+    instance::Instance,
+    person::{Person, PersonInsertForm},
+    post::{Post, PostInsertForm},
+  };
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_draft_views() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person = Person::create(
+      pool,
+      &PersonInsertForm::test_form(instance.id, "draft_view_person"),
+    )
+    .await?;
+    let other = Person::create(
+      pool,
+      &PersonInsertForm::test_form(instance.id, "draft_view_other"),
+    )
+    .await?;
+    let community = Community::create(
+      pool,
+      &CommunityInsertForm::new(instance.id, "draft_views".into(), "pubkey".into()),
+    )
+    .await?;
+    let post = Post::create(
+      pool,
+      &PostInsertForm::new("Draft target".into(), other.id, community.id),
+    )
+    .await?;
+
+    let form = DraftInsertForm {
+      community_id: Some(community.id),
+      ..DraftInsertForm::new(person.id)
+    };
+    let post_draft = Draft::create(pool, &form).await?;
+    let form = DraftInsertForm {
+      post_id: Some(post.id),
+      body: Some("A comment".to_string()),
+      ..DraftInsertForm::new(person.id)
+    };
+    let comment_draft = Draft::create(pool, &form).await?;
+    let form = DraftInsertForm {
+      recipient_id: Some(person.id),
+      ..DraftInsertForm::new(other.id)
+    };
+    let message_draft = Draft::create(pool, &form).await?;
+
+    let view = DraftView::read(pool, comment_draft.id).await?;
+    assert_eq!(Some(post), view.post);
+    assert!(view.community.is_none());
+    let view = DraftView::read(pool, message_draft.id).await?;
+    assert_eq!(Some(person.clone()), view.recipient);
+
+    // Only the own drafts are listed, newest first
+    let drafts = DraftQuery {
+      creator_id: person.id,
+      page_cursor: None,
+      limit: None,
+    }
+    .list(pool)
+    .await?;
+    assert_eq!(
+      vec![comment_draft.id, post_draft.id],
+      drafts.iter().map(|d| d.draft.id).collect::<Vec<_>>()
+    );
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
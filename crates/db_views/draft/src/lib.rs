@@ -0,0 +1,27 @@
+#[cfg(feature = "full")]
+use diesel::{Queryable, Selectable};
+use lemmy_db_schema::source::{community::Community, draft::Draft, person::Person, post::Post};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+pub mod api;
+#[cfg(feature = "full")]
+pub mod impls;
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// A draft view, including the target of the draft.
+pub struct DraftView {
+  #[cfg_attr(feature = "full", diesel(embed))]
+  pub draft: Draft,
+  #[cfg_attr(feature = "full", diesel(embed))]
+  pub community: Option<Community>,
+  #[cfg_attr(feature = "full", diesel(embed))]
+  pub post: Option<Post>,
+  #[cfg_attr(feature = "full", diesel(embed))]
+  pub recipient: Option<Person>,
+}
@@ -1,11 +1,17 @@
-use crate::LocalUserView;
-use actix_web::{FromRequest, HttpMessage, HttpRequest, dev::Payload};
+use crate::{ApiTokenScopes, LocalUserView, RequiredApiTokenScope};
+use actix_web::{
+  FromRequest,
+  HttpMessage,
+  HttpRequest,
+  dev::{Extensions, Payload},
+};
 use diesel::{
   BoolExpressionMethods,
   ExpressionMethods,
   NullableExpressionMethods,
   QueryDsl,
   SelectableHelper,
+  dsl::IntervalDsl,
 };
 use diesel_async::RunQueryDsl;
 use i_love_jesus::asc_if;
@@ -21,6 +27,7 @@ use lemmy_db_schema::{
 use lemmy_db_schema_file::{
   PersonId,
   aliases::creator_home_instance_actions,
+  enums::EmailDigestFrequency,
   joins::creator_home_instance_actions_join,
   schema::{instance_actions, local_user, oauth_account, person},
 };
@@ -134,6 +141,37 @@ impl LocalUserView {
       .with_lemmy_type(LemmyErrorType::NotFound)
   }
 
+  /// Users who receive notification emails as digest, and whose last digest was sent at least one
+  /// period ago.
+  pub async fn list_due_for_email_digest(pool: &mut DbPool<'_>) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    let frequency = local_user::email_digest_frequency;
+    let last_sent = local_user::last_email_digest_at;
+    Self::joins()
+      .filter(local_user::send_notifications_to_email)
+      .filter(local_user::email.is_not_null())
+      .filter(person::deleted.eq(false))
+      .filter(
+        frequency
+          .eq(EmailDigestFrequency::Hourly)
+          .and(last_sent.le(now() - 1.hours()))
+          .or(
+            frequency
+              .eq(EmailDigestFrequency::Daily)
+              .and(last_sent.le(now() - 1.days())),
+          )
+          .or(
+            frequency
+              .eq(EmailDigestFrequency::Weekly)
+              .and(last_sent.le(now() - 7.days())),
+          ),
+      )
+      .select(Self::as_select())
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
   pub async fn create_test_user(
     pool: &mut DbPool<'_>,
     name: &str,
@@ -217,13 +255,26 @@ impl FromRequest for LocalUserView {
   type Future = Ready<Result<Self, Self::Error>>;
 
   fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
-    ready(match req.extensions().get::<LocalUserView>() {
-      Some(c) => Ok(c.clone()),
+    let extensions = req.extensions();
+    ready(match extensions.get::<LocalUserView>() {
+      Some(c) => check_api_token_scope(&extensions).map(|()| c.clone()),
       None => Err(LemmyErrorType::IncorrectLogin.into()),
     })
   }
 }
 
+/// Requests which are authenticated with an API token need the scope which is required by the
+/// route. Login tokens can be used for everything.
+pub fn check_api_token_scope(extensions: &Extensions) -> LemmyResult<()> {
+  let Some(ApiTokenScopes(scopes)) = extensions.get() else {
+    return Ok(());
+  };
+  match extensions.get() {
+    Some(RequiredApiTokenScope(Some(scope))) if scopes.contains(scope) => Ok(()),
+    _ => Err(LemmyErrorType::MissingApiTokenScope.into()),
+  }
+}
+
 impl PaginationCursorConversion for LocalUserView {
   type PaginatedType = Person;
 
@@ -253,6 +304,7 @@ mod tests {
     },
     traits::Bannable,
   };
+  use lemmy_db_schema_file::enums::ApiTokenScope;
   use lemmy_diesel_utils::{
     connection::{DbPool, build_db_pool_for_tests},
     traits::Crud,
@@ -308,4 +360,24 @@ mod tests {
 
     cleanup(data, pool).await
   }
+
+  #[test]
+  fn test_check_api_token_scope() {
+    // Login tokens can be used everywhere
+    let mut extensions = Extensions::new();
+    assert!(check_api_token_scope(&extensions).is_ok());
+
+    // API tokens only for routes which require one of their scopes
+    extensions.insert(ApiTokenScopes(vec![
+      ApiTokenScope::Read,
+      ApiTokenScope::Vote,
+    ]));
+    assert!(check_api_token_scope(&extensions).is_err());
+    extensions.insert(RequiredApiTokenScope(Some(ApiTokenScope::Vote)));
+    assert!(check_api_token_scope(&extensions).is_ok());
+    extensions.insert(RequiredApiTokenScope(Some(ApiTokenScope::Admin)));
+    assert!(check_api_token_scope(&extensions).is_err());
+    extensions.insert(RequiredApiTokenScope(None));
+    assert!(check_api_token_scope(&extensions).is_err());
+  }
 }
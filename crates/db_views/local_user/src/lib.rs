@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use lemmy_db_schema::source::{local_user::LocalUser, person::Person};
+use lemmy_db_schema_file::enums::ApiTokenScope;
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "full")]
 use {
@@ -35,3 +36,14 @@ pub struct LocalUserView {
   )]
   pub ban_expires_at: Option<DateTime<Utc>>,
 }
+
+/// Request extension for requests which are authenticated with an API token instead of a login
+/// token. Contains the scopes of the token.
+#[derive(Debug, Clone)]
+pub struct ApiTokenScopes(pub Vec<ApiTokenScope>);
+
+/// Request extension with the scope which an API token needs for the current route. It is set by
+/// the route configuration, where the innermost setting takes precedence. If this is `None`, the
+/// route can't be used with API tokens.
+#[derive(Debug, Clone, Copy)]
+pub struct RequiredApiTokenScope(pub Option<ApiTokenScope>);
@@ -37,7 +37,7 @@ use lemmy_diesel_utils::{
     paginate_response,
   },
 };
-use lemmy_utils::error::LemmyResult;
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
 
 impl ModlogView {
   #[diesel::dsl::auto_type(no_type_alias)]
@@ -209,6 +209,16 @@ impl ModlogQuery<'_> {
 }
 
 impl ModlogView {
+  pub async fn read(pool: &mut DbPool<'_>, modlog_id: ModlogId) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    ModlogView::joins(None)
+      .filter(modlog::id.eq(modlog_id))
+      .select(ModlogView::as_select())
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
   /// Hides modlog names by setting the moderator to None.
   pub fn hide_mod_name(self, hide_modlog_names: bool) -> Self {
     if hide_modlog_names {
@@ -0,0 +1,86 @@
+use crate::{ModmailMessageView, ModmailThreadView};
+use lemmy_db_schema::newtypes::{CommentId, CommunityId, ModlogId, ModmailThreadId, PostId};
+use lemmy_diesel_utils::pagination::PaginationCursor;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Open a modmail thread with the mod team of a community.
+pub struct CreateModmailThread {
+  pub community_id: CommunityId,
+  pub subject: String,
+  pub content: String,
+  /// The post which this thread is about.
+  pub post_id: Option<PostId>,
+  /// The comment which this thread is about.
+  pub comment_id: Option<CommentId>,
+  /// The mod action which this thread is about.
+  pub modlog_id: Option<ModlogId>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Reply to a modmail thread. Moderators of the community reply as the mod team.
+pub struct CreateModmailMessage {
+  pub thread_id: ModmailThreadId,
+  pub content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Archive or unarchive a modmail thread. Only for moderators.
+pub struct ArchiveModmailThread {
+  pub thread_id: ModmailThreadId,
+  pub archived: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Get a modmail thread with all of its messages.
+pub struct GetModmailThread {
+  pub thread_id: ModmailThreadId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// List modmail threads. With a community id, lists the threads of a community you moderate.
+/// Otherwise lists the threads which you opened.
+pub struct ListModmailThreads {
+  pub community_id: Option<CommunityId>,
+  pub archived: Option<bool>,
+  pub page_cursor: Option<PaginationCursor>,
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// A modmail thread response.
+pub struct ModmailThreadResponse {
+  pub thread_view: ModmailThreadView,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// A modmail thread with all of its messages, oldest first.
+pub struct GetModmailThreadResponse {
+  pub thread_view: ModmailThreadView,
+  pub messages: Vec<ModmailMessageView>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// A single modmail message response.
+pub struct ModmailMessageResponse {
+  pub message_view: ModmailMessageView,
+}
@@ -0,0 +1,261 @@
+use crate::{ModmailMessageView, ModmailThreadView};
+use diesel::{ExpressionMethods, QueryDsl, SelectableHelper};
+use diesel_async::RunQueryDsl;
+use i_love_jesus::SortDirection;
+use lemmy_db_schema::{
+  newtypes::{CommunityId, ModmailThreadId},
+  source::{
+    modmail::{ModmailMessage, ModmailThread, modmail_thread_keys as key},
+    person::Person,
+  },
+  utils::limit_fetch,
+};
+use lemmy_db_schema_file::{
+  PersonId,
+  schema::{community, modmail_message, modmail_thread, person},
+};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  pagination::{
+    CursorData,
+    PagedResponse,
+    PaginationCursor,
+    PaginationCursorConversion,
+    paginate_response,
+  },
+  traits::Crud,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl PaginationCursorConversion for ModmailThreadView {
+  type PaginatedType = ModmailThread;
+  fn to_cursor(&self) -> CursorData {
+    CursorData::new_id(self.thread.id.0)
+  }
+
+  async fn from_cursor(
+    cursor: CursorData,
+    pool: &mut DbPool<'_>,
+  ) -> LemmyResult<Self::PaginatedType> {
+    ModmailThread::read(pool, ModmailThreadId(cursor.id()?)).await
+  }
+}
+
+impl ModmailThreadView {
+  #[diesel::dsl::auto_type(no_type_alias)]
+  fn joins() -> _ {
+    modmail_thread::table
+      .inner_join(community::table)
+      .inner_join(person::table)
+  }
+
+  pub async fn read(pool: &mut DbPool<'_>, thread_id: ModmailThreadId) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    Self::joins()
+      .filter(modmail_thread::id.eq(thread_id))
+      .select(Self::as_select())
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+impl ModmailMessageView {
+  /// All messages of a thread, oldest first. If `hide_mod_names` is set, the individual moderator
+  /// who sent a message from the mod team is not included.
+  pub async fn list_for_thread(
+    pool: &mut DbPool<'_>,
+    thread_id: ModmailThreadId,
+    hide_mod_names: bool,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    let res = modmail_message::table
+      .inner_join(person::table)
+      .filter(modmail_message::thread_id.eq(thread_id))
+      .order_by(modmail_message::published_at)
+      .then_order_by(modmail_message::id)
+      .select((ModmailMessage::as_select(), Person::as_select()))
+      .load::<(ModmailMessage, Person)>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)?;
+    Ok(
+      res
+        .into_iter()
+        .map(|(message, creator)| Self::new(message, creator, hide_mod_names))
+        .collect(),
+    )
+  }
+
+  pub fn new(message: ModmailMessage, creator: Person, hide_mod_names: bool) -> Self {
+    if hide_mod_names && message.from_mod_team {
+      Self {
+        message: ModmailMessage {
+          creator_id: PersonId(0),
+          ..message
+        },
+        creator: None,
+      }
+    } else {
+      Self {
+        message,
+        creator: Some(creator),
+      }
+    }
+  }
+}
+
+#[derive(Default)]
+pub struct ModmailThreadQuery {
+  /// List the threads of this community. Otherwise, only the threads opened by `creator_id` are
+  /// listed.
+  pub community_id: Option<CommunityId>,
+  pub creator_id: Option<PersonId>,
+  pub archived: Option<bool>,
+  pub page_cursor: Option<PaginationCursor>,
+  pub limit: Option<i64>,
+}
+
+impl ModmailThreadQuery {
+  pub async fn list(self, pool: &mut DbPool<'_>) -> LemmyResult<PagedResponse<ModmailThreadView>> {
+    let limit = limit_fetch(self.limit, None)?;
+
+    let mut query = ModmailThreadView::joins()
+      .select(ModmailThreadView::as_select())
+      .limit(limit)
+      .into_boxed();
+
+    if let Some(community_id) = self.community_id {
+      query = query.filter(modmail_thread::community_id.eq(community_id));
+    }
+    if let Some(creator_id) = self.creator_id {
+      query = query.filter(modmail_thread::creator_id.eq(creator_id));
+    }
+    if let Some(archived) = self.archived {
+      query = query.filter(modmail_thread::archived.eq(archived));
+    }
+
+    let paginated_query =
+      ModmailThreadView::paginate(query, &self.page_cursor, SortDirection::Desc, pool)
+        .await?
+        .then_order_by(key::published_at)
+        .then_order_by(key::id);
+
+    let conn = &mut get_conn(pool).await?;
+    let res = paginated_query
+      .load::<ModmailThreadView>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)?;
+    paginate_response(res, limit, self.page_cursor)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{ModmailMessageView, impls::ModmailThreadQuery};
+  use lemmy_db_schema::source::{
+    community::{Community, CommunityInsertForm},
+    instance::Instance,
+    modmail::{
+      ModmailMessage,
+      ModmailMessageInsertForm,
+      ModmailThread,
+      ModmailThreadInsertForm,
+      ModmailThreadUpdateForm,
+    },
+    person::{Person, PersonInsertForm},
+  };
+  use lemmy_db_schema_file::PersonId;
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::error::LemmyResult;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_modmail_views() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let user = Person::create(
+      pool,
+      &PersonInsertForm::test_form(instance.id, "modmail_user"),
+    )
+    .await?;
+    let moderator = Person::create(
+      pool,
+      &PersonInsertForm::test_form(instance.id, "modmail_mod"),
+    )
+    .await?;
+    let community = Community::create(
+      pool,
+      &CommunityInsertForm::new(instance.id, "modmail_views".into(), "pubkey".into()),
+    )
+    .await?;
+
+    let form = ModmailThreadInsertForm::new(community.id, user.id, "Removed post".to_string());
+    let thread = ModmailThread::create(pool, &form).await?;
+    let form = ModmailThreadInsertForm::new(community.id, user.id, "Old thread".to_string());
+    let archived_thread = ModmailThread::create(pool, &form).await?;
+    let form = ModmailThreadUpdateForm {
+      archived: Some(true),
+      ..Default::default()
+    };
+    ModmailThread::update(pool, archived_thread.id, &form).await?;
+
+    let form = ModmailMessageInsertForm::new(thread.id, user.id, false, "Why?".to_string());
+    ModmailMessage::create(pool, &form).await?;
+    let form = ModmailMessageInsertForm::new(thread.id, moderator.id, true, "Spam".to_string());
+    ModmailMessage::create(pool, &form).await?;
+
+    // The user only sees the mod team
+    let messages = ModmailMessageView::list_for_thread(pool, thread.id, true).await?;
+    assert_eq!(2, messages.len());
+    assert_eq!(
+      Some(&user),
+      messages.first().and_then(|m| m.creator.as_ref())
+    );
+    let reply = messages
+      .get(1)
+      .map(|m| (m.message.creator_id, m.creator.is_none()));
+    assert_eq!(Some((PersonId(0), true)), reply);
+
+    // Mods see who replied
+    let messages = ModmailMessageView::list_for_thread(pool, thread.id, false).await?;
+    assert_eq!(
+      Some(&moderator),
+      messages.get(1).and_then(|m| m.creator.as_ref())
+    );
+
+    let open = ModmailThreadQuery {
+      community_id: Some(community.id),
+      archived: Some(false),
+      ..Default::default()
+    }
+    .list(pool)
+    .await?;
+    assert_eq!(
+      vec![thread.id],
+      open.iter().map(|t| t.thread.id).collect::<Vec<_>>()
+    );
+
+    let all = ModmailThreadQuery {
+      creator_id: Some(user.id),
+      ..Default::default()
+    }
+    .list(pool)
+    .await?;
+    assert_eq!(2, all.len());
+
+    let none = ModmailThreadQuery {
+      creator_id: Some(moderator.id),
+      ..Default::default()
+    }
+    .list(pool)
+    .await?;
+    assert!(none.is_empty());
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,39 @@
+#[cfg(feature = "full")]
+use diesel::{Queryable, Selectable};
+use lemmy_db_schema::source::{
+  community::Community,
+  modmail::{ModmailMessage, ModmailThread},
+  person::Person,
+};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+pub mod api;
+#[cfg(feature = "full")]
+pub mod impls;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// A modmail thread view.
+pub struct ModmailThreadView {
+  #[cfg_attr(feature = "full", diesel(embed))]
+  pub thread: ModmailThread,
+  #[cfg_attr(feature = "full", diesel(embed))]
+  pub community: Community,
+  #[cfg_attr(feature = "full", diesel(embed))]
+  pub creator: Person,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// A modmail message view.
+pub struct ModmailMessageView {
+  pub message: ModmailMessage,
+  /// Empty for messages from the mod team, unless the viewer is one of the moderators.
+  pub creator: Option<Person>,
+}
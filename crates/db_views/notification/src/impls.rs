@@ -1,4 +1,11 @@
-use crate::{CommentView, NotificationData, NotificationView, NotificationViewInternal};
+use crate::{
+  CommentView,
+  ModmailThreadView,
+  NotificationData,
+  NotificationView,
+  NotificationViewInternal,
+};
+use chrono::{DateTime, Utc};
 use diesel::{
   BoolExpressionMethods,
   ExpressionMethods,
@@ -119,6 +126,8 @@ pub struct NotificationQuery {
   pub show_bot_accounts: Option<bool>,
   pub hide_modlog_names: Option<bool>,
   pub creator_id: Option<PersonId>,
+  /// Only notifications which were created after this time.
+  pub published_after: Option<DateTime<Utc>>,
   pub page_cursor: Option<PaginationCursor>,
   pub limit: Option<i64>,
   pub no_limit: Option<bool>,
@@ -198,6 +207,10 @@ impl NotificationQuery {
         }
       }
 
+      if let Some(published_after) = self.published_after {
+        query = query.filter(notification::published_at.gt(published_after));
+      }
+
       if !self.show_bot_accounts.unwrap_or_default() {
         query = query.filter(person::bot_account.is_distinct_from(true));
       };
@@ -247,6 +260,14 @@ fn map_to_enum(
     };
     let m = m.hide_mod_name(hide_modlog_name);
     NotificationData::ModAction(m)
+  } else if let Some(ban_appeal) = v.ban_appeal {
+    NotificationData::BanAppeal(ban_appeal)
+  } else if let (Some(thread), Some(community)) = (v.modmail_thread, &v.community) {
+    NotificationData::Modmail(ModmailThreadView {
+      thread,
+      community: community.clone(),
+      creator: v.creator,
+    })
   } else if let (Some(comment), Some(post), Some(community)) = (v.comment, &v.post, &v.community) {
     NotificationData::Comment(CommentView {
       comment,
@@ -275,6 +296,7 @@ fn map_to_enum(
       post_actions: v.post_actions,
       person_actions: v.person_actions,
       tags: v.tags,
+      poll: v.poll,
       creator_banned_from_community: v.creator_banned_from_community,
       creator_community_ban_expires_at: v.creator_community_ban_expires_at,
       creator_is_admin: v.creator_is_admin,
@@ -7,14 +7,20 @@ use lemmy_db_schema::source::{
   images::ImageDetails,
   instance::Instance,
   modlog::Modlog,
+  modmail::ModmailThread,
   person::{Person, PersonActions},
+  poll::PollView,
   post::{Post, PostActions},
   private_message::PrivateMessage,
 };
-use lemmy_db_schema::{NotificationTypeFilter, source::notification::Notification};
+use lemmy_db_schema::{
+  NotificationTypeFilter,
+  source::{ban_appeal::BanAppeal, notification::Notification},
+};
 use lemmy_db_schema_file::PersonId;
 use lemmy_db_views_comment::CommentView;
 use lemmy_db_views_modlog::ModlogView;
+use lemmy_db_views_modmail::ModmailThreadView;
 use lemmy_db_views_post::PostView;
 use lemmy_db_views_private_message::PrivateMessageView;
 use lemmy_diesel_utils::pagination::PaginationCursor;
@@ -38,6 +44,7 @@ use {
       creator_banned_from_community,
       person1_select,
       post_community_tags_fragment,
+      post_poll_fragment,
     },
   },
 };
@@ -84,8 +91,14 @@ struct NotificationViewInternal {
   comment_actions: Option<CommentActions>,
   #[diesel(embed)]
   modlog: Option<Modlog>,
+  #[diesel(embed)]
+  modmail_thread: Option<ModmailThread>,
+  #[diesel(embed)]
+  ban_appeal: Option<BanAppeal>,
   #[diesel(select_expression = post_community_tags_fragment())]
   tags: CommunityTagsView,
+  #[diesel(select_expression = post_poll_fragment())]
+  poll: Option<PollView>,
   #[diesel(select_expression = creator_is_admin())]
   creator_is_admin: bool,
   #[diesel(select_expression = local_user_can_mod())]
@@ -122,6 +135,8 @@ pub enum NotificationData {
   Post(PostView),
   PrivateMessage(PrivateMessageView),
   ModAction(ModlogView),
+  Modmail(ModmailThreadView),
+  BanAppeal(BanAppeal),
 }
 
 #[skip_serializing_none]
@@ -17,7 +17,18 @@ use lemmy_db_schema_file::{
     my_person_actions_join,
     my_post_actions_join,
   },
-  schema::{comment, community, instance, modlog, notification, person, post, private_message},
+  schema::{
+    ban_appeal,
+    comment,
+    community,
+    instance,
+    modlog,
+    modmail_thread,
+    notification,
+    person,
+    post,
+    private_message,
+  },
 };
 
 #[diesel::dsl::auto_type(no_type_alias)]
@@ -54,6 +65,8 @@ pub fn notification_joins(person_id: PersonId, instance_id: InstanceId) -> _ {
     .left_join(instance::table)
     .left_join(image_details_join())
     .left_join(private_message::table)
+    .left_join(modmail_thread::table)
+    .left_join(ban_appeal::table)
     .left_join(creator_community_actions_join())
     .left_join(creator_local_user_admin_join())
     .left_join(creator_home_instance_actions_join())
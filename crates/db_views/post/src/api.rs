@@ -1,7 +1,16 @@
 use crate::PostView;
+use chrono::{DateTime, Utc};
 use lemmy_db_schema::{
   PostFeatureType,
-  newtypes::{CommentId, CommunityId, CommunityTagId, LanguageId, MultiCommunityId, PostId},
+  newtypes::{
+    CommentId,
+    CommunityId,
+    CommunityTagId,
+    LanguageId,
+    MultiCommunityId,
+    PollChoiceId,
+    PostId,
+  },
 };
 use lemmy_db_schema_file::{
   PersonId,
@@ -35,6 +44,12 @@ pub struct CreatePost {
   pub tags: Option<Vec<CommunityTagId>>,
   /// Time when this post should be scheduled. Null means publish immediately.
   pub scheduled_publish_time_at: Option<i64>,
+  /// Attach a poll with these choices to the post.
+  pub poll_choices: Option<Vec<String>>,
+  /// Allow voting for more than one poll choice.
+  pub poll_multiple_choice: Option<bool>,
+  /// Time when the poll closes, as unix timestamp. Null means it stays open.
+  pub poll_end_at: Option<i64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -47,6 +62,15 @@ pub struct CreatePostLike {
   pub is_upvote: Option<bool>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Vote in the poll of a post. Votes can't be changed afterwards.
+pub struct VotePoll {
+  pub post_id: PostId,
+  pub choice_ids: Vec<PollChoiceId>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
@@ -176,6 +200,41 @@ pub struct LinkMetadata {
   pub content_type: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Get the edit history of a post. Only mods and admins can see it, unless the site makes edit
+/// history public.
+pub struct GetPostHistory {
+  pub post_id: PostId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct GetPostHistoryResponse {
+  /// All versions of the post, oldest first. The last one is the current version. Empty if the
+  /// post was removed or deleted, unless you are a mod or the creator.
+  pub revisions: Vec<PostRevision>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// A version of a post.
+pub struct PostRevision {
+  pub name: String,
+  pub body: Option<String>,
+  pub url: Option<DbUrl>,
+  /// When this version was published, or the time of the edit which created it.
+  pub published_at: DateTime<Utc>,
+  /// Unified diff of the title against the previous version, if it changed.
+  pub name_diff: Option<String>,
+  /// Unified diff of the body against the previous version, if it changed.
+  pub body_diff: Option<String>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
@@ -31,7 +31,16 @@ use lemmy_db_schema::{
   },
   utils::{
     limit_fetch,
-    queries::filters::{filter_blocked, filter_private_or_followed, filter_unlisted_or_followed},
+    queries::{
+      filters::{filter_blocked, filter_private_or_followed, filter_unlisted_or_followed},
+      search::{
+        TsvectorExpressionMethods,
+        post_search_vector,
+        search_query,
+        search_query_any_language,
+        ts_rank,
+      },
+    },
   },
 };
 use lemmy_db_schema_file::{
@@ -51,7 +60,7 @@ use lemmy_db_schema_file::{
     my_person_actions_join,
     my_post_actions_join,
   },
-  schema::{community, person, post, post_actions, post_community_tag},
+  schema::{community, person, post, post_actions, post_community_tag, post_search},
 };
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
@@ -60,10 +69,19 @@ use lemmy_diesel_utils::{
     PagedResponse,
     PaginationCursor,
     PaginationCursorConversion,
+    offset_from_cursor,
     paginate_response,
+    paginate_response_by_offset,
   },
   traits::Crud,
-  utils::{CoalesceKey, Commented, fuzzy_search, now, seconds_to_pg_interval},
+  utils::{
+    CoalesceKey,
+    Commented,
+    full_text_search_query,
+    fuzzy_search,
+    now,
+    seconds_to_pg_interval,
+  },
 };
 use lemmy_utils::{
   error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
@@ -310,6 +328,8 @@ pub struct PostQuery<'a> {
   pub search_term: Option<String>,
   pub search_title_only: Option<bool>,
   pub search_url_only: Option<bool>,
+  /// Order search results by relevance, only used with [LocalSite.full_text_search].
+  pub search_sort_by_relevance: Option<bool>,
   pub page_cursor: Option<PaginationCursor>,
   pub tag_id: Option<CommunityTagId>,
   /// For backwards compat with API v3 (not available on API v4).
@@ -452,18 +472,39 @@ impl PostQuery<'_> {
     }
 
     // The search term
+    let search_url_only = self.search_url_only.unwrap_or_default();
+    let full_text_query = self
+      .search_term
+      .as_deref()
+      .filter(|_| local_site.full_text_search && !search_url_only)
+      .and_then(|t| full_text_search_query(t, self.search_title_only.unwrap_or_default()));
     if let Some(search_term) = self.search_term {
       let searcher = fuzzy_search(&search_term);
 
       let name_or_title_filter = post::name.ilike(searcher.clone());
 
       // A url / cross-post search
-      query = if self.search_url_only.unwrap_or_default() {
+      query = if search_url_only {
         // Parse and normalize the url, removing tracking parameters (same logic which is used
         // when creating a new post).
         let normalized_url = Url::parse(&search_term).map(|u| clean_url(&u).to_string())?;
 
         query.filter(post::url.eq(normalized_url))
+      } else if let Some(full_text_query) = &full_text_query {
+        // The first filter can use the search index, the second one checks each post against
+        // the query for its own language.
+        query
+          .filter(
+            post::id.eq_any(
+              post_search::table.select(post_search::post_id).filter(
+                post_search::search_vector
+                  .matches(search_query_any_language(full_text_query.clone())),
+              ),
+            ),
+          )
+          .filter(
+            post_search_vector().matches(search_query(full_text_query.clone(), post::language_id)),
+          )
       } else if self.search_title_only.unwrap_or_default() {
         query.filter(name_or_title_filter)
       } else {
@@ -558,6 +599,29 @@ impl PostQuery<'_> {
       query = query.filter(tags.eq(tag_id));
     }
 
+    // Relevance can't be used as a cursor key, so these results are paginated with an offset
+    if self.search_sort_by_relevance.unwrap_or_default()
+      && let Some(full_text_query) = full_text_query
+    {
+      let offset = offset_from_cursor(&self.page_cursor)?;
+      let rank = ts_rank(
+        post_search_vector(),
+        search_query(full_text_query, post::language_id),
+      );
+      let query = query
+        .order_by(rank.desc())
+        .then_order_by(post::id.desc())
+        .offset(offset);
+
+      let conn = &mut get_conn(pool).await?;
+      let res = Commented::new(query)
+        .text("PostQuery::list by relevance")
+        .load::<PostView>(conn)
+        .await
+        .with_lemmy_type(LemmyErrorType::NotFound)?;
+      return paginate_response_by_offset(res, limit, offset);
+    }
+
     // Only sort by ascending for Old
     let sort = self.sort.unwrap_or(PostSortType::Hot);
     let sort_direction = asc_if(sort == PostSortType::Old);
@@ -4,6 +4,7 @@ use lemmy_db_schema::source::{
   community_tag::CommunityTagsView,
   images::ImageDetails,
   person::{Person, PersonActions},
+  poll::PollView,
   post::{Post, PostActions},
 };
 use serde::{Deserialize, Serialize};
@@ -26,6 +27,7 @@ use {
     local_user_can_mod_post,
     post_community_tags_fragment,
     post_creator_is_admin,
+    post_poll_fragment,
   },
 };
 
@@ -70,6 +72,12 @@ pub struct PostView {
     )
   )]
   pub tags: CommunityTagsView,
+  #[cfg_attr(feature = "full",
+    diesel(
+      select_expression = post_poll_fragment()
+    )
+  )]
+  pub poll: Option<PollView>,
   #[cfg_attr(feature = "full",
     diesel(
       select_expression = local_user_can_mod_post()
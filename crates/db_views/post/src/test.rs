@@ -2441,3 +2441,74 @@ async fn search(data: &mut Data) -> LemmyResult<()> {
 
   Ok(())
 }
+
+#[test_context(Data)]
+#[tokio::test]
+#[serial]
+async fn full_text_search(data: &mut Data) -> LemmyResult<()> {
+  let pool = &data.pool();
+  let pool = &mut pool.into();
+  // Enabling full text search fills the search vectors of the existing posts
+  let form = LocalSiteUpdateForm {
+    full_text_search: Some(true),
+    ..Default::default()
+  };
+  let local_site = LocalSite::update(pool, &form).await?;
+
+  // Stemming should match the plural in the title
+  let search_stemmed = PostQuery {
+    search_term: Some("tag".into()),
+    local_user: Some(&data.tegan.local_user),
+    ..Default::default()
+  }
+  .list(pool, &data.site, &local_site)
+  .await?;
+  assert_eq!(vec![POST_WITH_TAGS], names(&search_stemmed));
+
+  // The body isn't searched with title only
+  let search_title_only = PostQuery {
+    search_term: Some("body".into()),
+    search_title_only: Some(true),
+    ..Default::default()
+  }
+  .list(pool, &data.site, &local_site)
+  .await?;
+  assert!(search_title_only.is_empty());
+
+  // Excluded terms
+  let search_excluded = PostQuery {
+    search_term: Some("post -tags -bot".into()),
+    sort: Some(PostSortType::New),
+    ..Default::default()
+  }
+  .list(pool, &data.site, &local_site)
+  .await?;
+  assert_eq!(vec![POST, POST_BY_BLOCKED_PERSON], names(&search_excluded));
+
+  // Relevance results are paginated with an offset, make sure each post is included once
+  let mut page_cursor = None;
+  let mut listed = vec![];
+  loop {
+    let page = PostQuery {
+      search_term: Some("post".into()),
+      search_sort_by_relevance: Some(true),
+      limit: Some(1),
+      page_cursor,
+      ..Default::default()
+    }
+    .list(pool, &data.site, &local_site)
+    .await?;
+    listed.extend(page.items.iter().map(|p| p.post.name.clone()));
+    page_cursor = page.next_page;
+    if page_cursor.is_none() {
+      break;
+    }
+  }
+  listed.sort();
+  assert_eq!(
+    vec![POST, POST_BY_BLOCKED_PERSON, POST_BY_BOT, POST_WITH_TAGS],
+    listed
+  );
+
+  Ok(())
+}
@@ -5,6 +5,7 @@ use lemmy_db_schema::source::{
   community_tag::CommunityTagsView,
   images::ImageDetails,
   person::{Person, PersonActions},
+  poll::PollView,
   post::{Post, PostActions},
 };
 use lemmy_db_views_comment::CommentView;
@@ -24,6 +25,7 @@ use {
     creator_local_home_community_banned,
     local_user_can_mod,
     post_community_tags_fragment,
+    post_poll_fragment,
   },
 };
 
@@ -54,6 +56,8 @@ pub struct PostCommentCombinedViewInternal {
   pub item_creator_is_admin: bool,
   #[diesel(select_expression = post_community_tags_fragment())]
   pub tags: CommunityTagsView,
+  #[diesel(select_expression = post_poll_fragment())]
+  pub poll: Option<PollView>,
   #[diesel(select_expression = local_user_can_mod())]
   pub can_mod: bool,
   #[diesel(select_expression = creator_local_home_community_banned())]
@@ -117,6 +121,7 @@ impl InternalToCombinedView for PostCommentCombinedViewInternal {
         person_actions: v.person_actions,
         creator_is_admin: v.item_creator_is_admin,
         tags: v.tags,
+        poll: v.poll,
         can_mod: v.can_mod,
         creator_banned: v.creator_banned,
         creator_ban_expires_at: v.creator_ban_expires_at,
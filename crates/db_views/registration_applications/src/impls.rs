@@ -265,6 +265,9 @@ mod tests {
         show_upvote_percentage: sara_local_user.show_upvote_percentage,
         show_person_votes: sara_local_user.show_person_votes,
         invited_by_local_user_id: sara_local_user.invited_by_local_user_id,
+        email_digest_frequency: sara_local_user.email_digest_frequency,
+        email_digest_top_posts: sara_local_user.email_digest_top_posts,
+        last_email_digest_at: sara_local_user.last_email_digest_at,
       },
       creator: Person {
         id: sara_person.id,
@@ -289,6 +292,8 @@ mod tests {
         post_score: 0,
         comment_count: 0,
         comment_score: 0,
+        also_known_as: vec![],
+        moved_to: None,
       },
       admin: None,
     };
@@ -363,6 +368,8 @@ mod tests {
       post_score: 0,
       comment_count: 0,
       comment_score: 0,
+      also_known_as: vec![],
+      moved_to: None,
     });
     assert_eq!(read_sara_app_view_after_approve, expected_sara_app_view);
 
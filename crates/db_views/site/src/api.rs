@@ -1,36 +1,64 @@
-use crate::{ResolveObjectView, SiteView};
+use crate::{FederationQueueView, ResolveObjectView, SiteView};
 #[cfg(feature = "full")]
 use activitypub_federation::protocol::helpers::deserialize_skip_error;
+use chrono::{DateTime, Utc};
 use lemmy_db_schema::{
+  QueuedEmailStatus,
+  SearchSortType,
   SearchType,
-  newtypes::{CommunityId, LanguageId, MultiCommunityId, OAuthProviderId, TaglineId},
+  newtypes::{
+    ActivityId,
+    ApiTokenId,
+    CommunityId,
+    EmailTemplateId,
+    LanguageId,
+    MultiCommunityId,
+    NotificationId,
+    OAuthProviderId,
+    PasskeyId,
+    PostId,
+    ReceivedActivityLogId,
+    TaglineId,
+    WebhookId,
+  },
   source::{
+    api_token::ApiToken,
     comment::Comment,
     community::Community,
+    email_template::EmailTemplate,
+    federation_send_error::FederationSendError,
     instance::Instance,
     language::Language,
     local_site_url_blocklist::LocalSiteUrlBlocklist,
     local_user::LocalUser,
     login_token::LoginToken,
+    oauth_client::OAuthClient,
     oauth_provider::{AdminOAuthProvider, PublicOAuthProvider},
+    passkey::Passkey,
     person::Person,
     post::Post,
     private_message::PrivateMessage,
     tagline::Tagline,
+    webhook::Webhook,
   },
 };
 use lemmy_db_schema_file::{
   InstanceId,
   PersonId,
   enums::{
+    ApiTokenScope,
     CommentSortType,
+    EmailDigestFrequency,
+    EmailTemplateType,
     FederationMode,
     ImageMode,
     ListingType,
+    NotificationType,
     PostListingMode,
     PostSortType,
     RegistrationMode,
     VoteShow,
+    WebhookEventType,
   },
 };
 use lemmy_db_views_comment::CommentView;
@@ -38,6 +66,7 @@ use lemmy_db_views_community::{CommunityView, MultiCommunityView};
 use lemmy_db_views_community_follower::CommunityFollowerView;
 use lemmy_db_views_community_moderator::CommunityModeratorView;
 use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_notification::NotificationView;
 use lemmy_db_views_person::PersonView;
 use lemmy_db_views_post::PostView;
 use lemmy_diesel_utils::{pagination::PaginationCursor, sensitive::SensitiveString};
@@ -69,6 +98,103 @@ pub struct AdminBlockInstanceParams {
   pub expires_at: Option<i64>,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Lists the outgoing federation queues, for admins.
+pub struct ListFederationQueues {
+  pub domain_filter: Option<String>,
+  /// Only show queues which are failing or paused.
+  pub failing_only: Option<bool>,
+  pub page_cursor: Option<PaginationCursor>,
+  pub limit: Option<i64>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// An outgoing federation queue, and how far it is behind.
+pub struct FederationQueueStatus {
+  pub queue: FederationQueueView,
+  /// Number of activities which were not sent to the instance yet. This includes activities
+  /// which are not relevant for the instance and will be skipped.
+  pub activities_behind: i64,
+  /// Seconds since the last sent activity was published, if the queue is behind.
+  pub seconds_behind: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Pauses or resumes sending activities to an instance.
+pub struct AdminPauseFederationQueue {
+  pub instance_id: InstanceId,
+  pub paused: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Retries sending to an instance immediately, instead of waiting for the retry delay. This
+/// also works for instances which are considered dead.
+pub struct AdminRetryFederationQueue {
+  pub instance_id: InstanceId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Skips activities which can't be sent to an instance. Sending continues after the given
+/// activity.
+pub struct AdminSkipFederationQueue {
+  pub instance_id: InstanceId,
+  pub activity_id: ActivityId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Lists the most recent errors when sending to an instance.
+pub struct ListFederationSendErrors {
+  pub instance_id: InstanceId,
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct ListFederationSendErrorsResponse {
+  pub errors: Vec<FederationSendError>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Searches the log of incoming activities, for admins.
+pub struct ListReceivedActivities {
+  /// ActivityPub id of the actor who sent the activity.
+  pub actor_id: Option<String>,
+  pub domain: Option<String>,
+  /// The activity type, for example `Create` or `Follow`.
+  pub kind: Option<String>,
+  /// Only show activities which couldn't be processed.
+  pub failed_only: Option<bool>,
+  pub page_cursor: Option<PaginationCursor>,
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Processes a logged incoming activity again, for example after a bug was fixed.
+pub struct ReplayReceivedActivity {
+  pub id: ReceivedActivityLogId,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
@@ -170,6 +296,10 @@ pub struct CreateSite {
   pub image_allow_video_uploads: Option<bool>,
   pub image_upload_disabled: Option<bool>,
   pub max_invites_per_user_allowed: Option<i32>,
+  pub full_text_search: Option<bool>,
+  pub public_edit_history: Option<bool>,
+  pub require_2fa_for_mods_and_admins: Option<bool>,
+  pub federation_authorized_fetch: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -200,6 +330,122 @@ pub struct EditOAuthProvider {
   pub enabled: Option<bool>,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Registers an app which uses Lemmy as OAuth2 authorization server, following RFC 7591.
+pub struct RegisterOAuthClient {
+  pub client_name: String,
+  pub redirect_uris: Vec<Url>,
+  pub client_uri: Option<Url>,
+  /// Space separated scopes which the client may request. Defaults to `read`.
+  pub scope: Option<String>,
+  /// Either `none` for public clients like mobile apps, or `client_secret_post` for clients
+  /// which can keep a secret. Defaults to `none`.
+  pub token_endpoint_auth_method: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct RegisterOAuthClientResponse {
+  pub client_id: String,
+  /// Only returned once, and only for confidential clients.
+  pub client_secret: Option<SensitiveString>,
+  pub client_name: String,
+  pub redirect_uris: Vec<String>,
+  pub client_uri: Option<String>,
+  pub scope: String,
+  pub token_endpoint_auth_method: String,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// The parameters of an OAuth2 authorization request, which the client sends to the consent
+/// screen of the frontend. Only the authorization code flow with PKCE (S256) is supported.
+pub struct OAuthAuthorize {
+  pub response_type: String,
+  pub client_id: String,
+  pub redirect_uri: String,
+  /// Space separated scopes. Defaults to all scopes of the client.
+  pub scope: Option<String>,
+  pub state: Option<String>,
+  pub code_challenge: String,
+  pub code_challenge_method: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// The app and the scopes to show on the consent screen.
+pub struct OAuthAuthorizeResponse {
+  pub client: OAuthClient,
+  pub scopes: Vec<ApiTokenScope>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// The decision of the user on the consent screen.
+pub struct OAuthConsent {
+  #[serde(flatten)]
+  pub authorize: OAuthAuthorize,
+  pub approve: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct OAuthConsentResponse {
+  /// The frontend redirects the user here, with the authorization code or an error.
+  pub redirect_uri: Url,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// An OAuth2 token request, with `grant_type` either `authorization_code` or `refresh_token`. Sent
+/// form encoded.
+pub struct OAuthToken {
+  pub grant_type: String,
+  pub client_id: String,
+  pub client_secret: Option<SensitiveString>,
+  pub code: Option<SensitiveString>,
+  pub redirect_uri: Option<String>,
+  pub code_verifier: Option<String>,
+  pub refresh_token: Option<SensitiveString>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct OAuthTokenResponse {
+  /// An API token which is limited to the granted scopes.
+  pub access_token: SensitiveString,
+  pub token_type: String,
+  /// Seconds until the access token expires.
+  pub expires_in: i64,
+  pub refresh_token: SensitiveString,
+  pub scope: String,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Revokes an access or refresh token, following RFC 7009. Sent form encoded.
+pub struct OAuthRevoke {
+  pub token: SensitiveString,
+  pub token_type_hint: Option<String>,
+  pub client_id: String,
+  pub client_secret: Option<SensitiveString>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
@@ -307,6 +553,18 @@ pub struct EditSite {
   pub image_allow_video_uploads: Option<bool>,
   pub image_upload_disabled: Option<bool>,
   pub max_invites_per_user_allowed: Option<i32>,
+  /// Use Postgres full text search for posts and comments instead of fuzzy matching. Enabling it
+  /// builds the search index for all existing posts and comments, which can take a while on large
+  /// instances.
+  pub full_text_search: Option<bool>,
+  /// Show the edit history of posts and comments to everyone, instead of only to mods and admins.
+  pub public_edit_history: Option<bool>,
+  /// Moderators and admins need to set up TOTP or a passkey before they can log in.
+  pub require_2fa_for_mods_and_admins: Option<bool>,
+  /// Only serve Activitypub objects to fetches with a valid HTTP signature from an instance which
+  /// is allowed to federate with us. Enabling this also signs outgoing fetches, which only takes
+  /// effect after a restart.
+  pub federation_authorized_fetch: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -401,6 +659,17 @@ pub struct DeleteAccount {
   pub delete_content: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Move your account to another account, which needs to list this account in `also_known_as`.
+/// Your followers and moderator roles are transferred to the new account.
+pub struct MoveAccount {
+  /// The new account, for example `name@example.com`.
+  pub target: String,
+  pub password: SensitiveString,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
@@ -425,6 +694,236 @@ pub struct ListLoginsResponse {
   pub logins: Vec<LoginToken>,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Create a personal access token for a bot or third-party app. It is sent as bearer token like
+/// a login token, but can only be used for the actions of its scopes.
+pub struct CreateApiToken {
+  pub name: String,
+  pub scopes: Vec<ApiTokenScope>,
+  pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct CreateApiTokenResponse {
+  pub api_token: ApiToken,
+  /// The token itself is only returned once, and can't be retrieved later.
+  pub token: SensitiveString,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Revoke one of your API tokens.
+pub struct DeleteApiToken {
+  pub id: ApiTokenId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct ListApiTokensResponse {
+  pub api_tokens: Vec<ApiToken>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Options for `navigator.credentials.create()` to register a new passkey.
+pub struct BeginPasskeyRegistrationResponse {
+  pub public_key: PasskeyCreationOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[serde(rename_all = "camelCase")]
+/// `PublicKeyCredentialCreationOptionsJSON` from the WebAuthn spec.
+pub struct PasskeyCreationOptions {
+  pub rp: PasskeyRelyingParty,
+  pub user: PasskeyUser,
+  pub challenge: String,
+  pub pub_key_cred_params: Vec<PasskeyCredentialParameters>,
+  pub timeout: i64,
+  pub exclude_credentials: Vec<PasskeyCredentialDescriptor>,
+  pub authenticator_selection: PasskeyAuthenticatorSelection,
+  pub attestation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct PasskeyRelyingParty {
+  pub id: String,
+  pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[serde(rename_all = "camelCase")]
+pub struct PasskeyUser {
+  /// Base64url encoded user handle, which is returned on passwordless login.
+  pub id: String,
+  pub name: String,
+  pub display_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct PasskeyCredentialParameters {
+  #[serde(rename = "type")]
+  pub type_: String,
+  /// COSE algorithm identifier.
+  pub alg: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct PasskeyCredentialDescriptor {
+  #[serde(rename = "type")]
+  pub type_: String,
+  pub id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[serde(rename_all = "camelCase")]
+pub struct PasskeyAuthenticatorSelection {
+  pub resident_key: String,
+  pub user_verification: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Finishes the registration of a passkey, with the result of `navigator.credentials.create()`.
+pub struct FinishPasskeyRegistration {
+  pub name: String,
+  pub credential: PasskeyAttestation,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// `RegistrationResponseJSON` from the WebAuthn spec. Unused fields are omitted.
+pub struct PasskeyAttestation {
+  pub id: String,
+  pub response: PasskeyAttestationResponse,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[serde(rename_all = "camelCase")]
+pub struct PasskeyAttestationResponse {
+  #[serde(rename = "clientDataJSON")]
+  pub client_data_json: String,
+  pub attestation_object: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Rename one of your passkeys.
+pub struct EditPasskey {
+  pub id: PasskeyId,
+  pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Delete one of your passkeys.
+pub struct DeletePasskey {
+  pub id: PasskeyId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct PasskeyResponse {
+  pub passkey: Passkey,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct ListPasskeysResponse {
+  pub passkeys: Vec<Passkey>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Gets a challenge for passkey login. Leave out the user for passwordless login, or give it to
+/// use the passkey as second factor for `Login`.
+pub struct BeginPasskeyLogin {
+  pub username_or_email: Option<SensitiveString>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Options for `navigator.credentials.get()`.
+pub struct BeginPasskeyLoginResponse {
+  pub public_key: PasskeyRequestOptions,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[serde(rename_all = "camelCase")]
+/// `PublicKeyCredentialRequestOptionsJSON` from the WebAuthn spec.
+pub struct PasskeyRequestOptions {
+  pub challenge: String,
+  pub rp_id: String,
+  pub timeout: i64,
+  pub allow_credentials: Vec<PasskeyCredentialDescriptor>,
+  pub user_verification: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// `AuthenticationResponseJSON` from the WebAuthn spec. Unused fields are omitted.
+pub struct PasskeyAssertion {
+  pub id: String,
+  pub response: PasskeyAssertionResponse,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+#[serde(rename_all = "camelCase")]
+pub struct PasskeyAssertionResponse {
+  #[serde(rename = "clientDataJSON")]
+  pub client_data_json: String,
+  pub authenticator_data: String,
+  pub signature: String,
+  pub user_handle: Option<String>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Passwordless login with a passkey, which needs to verify the user.
+pub struct PasskeyLogin {
+  pub credential: PasskeyAssertion,
+  /// If this is true the login is valid forever, otherwise it expires after one week.
+  pub stay_logged_in: Option<bool>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
@@ -438,6 +937,9 @@ pub struct Login {
   pub password: SensitiveString,
   /// May be required, if totp is enabled for their account.
   pub totp_2fa_token: Option<String>,
+  /// Required if the account has passkeys, unless a totp token is given. The challenge comes from
+  /// `BeginPasskeyLogin`.
+  pub passkey: Option<PasskeyAssertion>,
   /// If this is true the login is valid forever, otherwise it expires after one week.
   pub stay_logged_in: Option<bool>,
 }
@@ -534,12 +1036,19 @@ pub struct SaveUserSettings {
   pub bio: Option<String>,
   /// Your matrix user id. Ex: @my_user:matrix.org
   pub matrix_user_id: Option<String>,
+  /// Your other accounts, for example `name@example.com`. These accounts are allowed to move to
+  /// this account. At most 10.
+  pub also_known_as: Option<Vec<String>>,
   /// Whether to show or hide avatars.
   pub show_avatars: Option<bool>,
   /// Whether to show media in the UI.
   pub show_media: Option<bool>,
   /// Sends notifications to your email.
   pub send_notifications_to_email: Option<bool>,
+  /// Send notification emails immediately, or as a periodic digest.
+  pub email_digest_frequency: Option<EmailDigestFrequency>,
+  /// Include top posts from subscribed communities in the digest email.
+  pub email_digest_top_posts: Option<bool>,
   /// Whether this account is a bot account. Users can hide these accounts easily if they wish.
   pub bot_account: Option<bool>,
   /// Whether to show bot accounts.
@@ -615,6 +1124,15 @@ pub struct VerifyEmail {
   pub token: String,
 }
 
+#[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Stop notification emails, using the token from the link in a digest email. Doesn't require
+/// login.
+pub struct UnsubscribeEmail {
+  pub token: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
@@ -657,6 +1175,149 @@ pub struct EditTagline {
   pub content: String,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Create a webhook. Without a community it receives events from the whole site, and can only be
+/// created by admins.
+pub struct CreateWebhook {
+  pub url: Url,
+  pub community_id: Option<CommunityId>,
+  pub events: Vec<WebhookEventType>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Edit a webhook.
+pub struct EditWebhook {
+  pub id: WebhookId,
+  pub url: Option<Url>,
+  pub events: Option<Vec<WebhookEventType>>,
+  pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Delete a webhook.
+pub struct DeleteWebhook {
+  pub id: WebhookId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Fetches the webhooks of a community, or the webhooks created by the user if no community is
+/// given.
+pub struct ListWebhooks {
+  pub community_id: Option<CommunityId>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct ListWebhooksResponse {
+  pub webhooks: Vec<Webhook>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct WebhookResponse {
+  pub webhook: Webhook,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct CreateWebhookResponse {
+  pub webhook: Webhook,
+  /// The secret for verifying payload signatures is only returned once, and can't be retrieved
+  /// later.
+  pub secret: SensitiveString,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Fetches the delivery log of a webhook.
+pub struct ListWebhookDeliveries {
+  pub webhook_id: WebhookId,
+  pub page_cursor: Option<PaginationCursor>,
+  pub limit: Option<i64>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Fetches the outgoing email queue, for admins.
+pub struct ListQueuedEmails {
+  pub status: Option<QueuedEmailStatus>,
+  pub page_cursor: Option<PaginationCursor>,
+  pub limit: Option<i64>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Creates an email template, or replaces the existing one with the same type and language.
+pub struct SaveEmailTemplate {
+  pub template_type: EmailTemplateType,
+  /// Interface language code like `de`. If empty, the template is used for all languages which
+  /// don't have their own template.
+  pub language: Option<String>,
+  pub subject: String,
+  /// HTML body of the email.
+  pub body: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Deletes an email template, so that the default email is used again.
+pub struct DeleteEmailTemplate {
+  pub id: EmailTemplateId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct EmailTemplateResponse {
+  pub email_template: EmailTemplate,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct ListEmailTemplatesResponse {
+  pub email_templates: Vec<EmailTemplate>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Renders an email template with sample data, without saving it.
+pub struct PreviewEmailTemplate {
+  pub template_type: EmailTemplateType,
+  pub subject: String,
+  pub body: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct PreviewEmailTemplateResponse {
+  pub subject: String,
+  pub body: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[cfg_attr(feature = "plugins", derive(FromBytes))]
 #[cfg_attr(feature = "plugins", encoding(Json))]
@@ -701,6 +1362,8 @@ pub struct Search {
   pub creator_id: Option<PersonId>,
   pub creator_username: Option<String>,
   pub type_: Option<SearchType>,
+  /// How to sort post and comment results. If not given, they are sorted by new.
+  pub sort: Option<SearchSortType>,
   /// Filter to within a given time range, in seconds.
   /// IE 60 would give results for the past minute.
   pub time_range_seconds: Option<i32>,
@@ -831,6 +1494,78 @@ pub struct UnreadCountsResponse {
   pub registration_application_count: Option<i64>,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Opens a stream of server-sent events. New notifications and changes of the unread counts are
+/// always sent. The stream is closed after logout, when the token is revoked or when the user is
+/// banned.
+pub struct ListenEvents {
+  /// Also send new posts and comments in the communities you follow. Only content which was
+  /// created on this instance is sent.
+  pub followed_communities: Option<bool>,
+  /// Also send new comments in this post. Only comments which were created on this instance are
+  /// sent.
+  pub post_id: Option<PostId>,
+}
+
+/// The data of a server-sent event.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+#[serde(tag = "type_", rename_all = "snake_case")]
+pub enum StreamEvent {
+  Notification(Box<NotificationView>),
+  UnreadCounts(UnreadCountsResponse),
+  Post(Box<PostView>),
+  Comment(Box<CommentView>),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Registers a web push subscription, with the values from `PushSubscription.toJSON()`.
+pub struct CreatePushSubscription {
+  pub endpoint: Url,
+  /// Base64url encoded public key of the subscription.
+  pub p256dh: String,
+  /// Base64url encoded authentication secret of the subscription.
+  pub auth: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Removes a web push subscription.
+pub struct DeletePushSubscription {
+  pub endpoint: Url,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct GetVapidPublicKeyResponse {
+  /// Base64url encoded key, to be passed as `applicationServerKey` to `PushManager.subscribe()`.
+  pub public_key: String,
+}
+
+/// The decrypted payload of a web push message. Push services limit the size of messages, so it
+/// only contains a preview of the notification.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct PushNotification {
+  pub notification_id: NotificationId,
+  pub kind: NotificationType,
+  /// Name of the user who caused the notification.
+  pub creator_name: Option<String>,
+  /// Beginning of the text, for example of the comment or private message.
+  pub preview: Option<String>,
+  pub url: Option<Url>,
+}
+
 /// Used for delete user plugin hooks
 #[derive(Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
@@ -1,11 +1,19 @@
 use crate::{
   FederatedInstanceView,
+  FederationQueueView,
   SiteView,
-  api::{GetFederatedInstances, GetFederatedInstancesKind, UserSettingsBackup},
+  api::{
+    GetFederatedInstances,
+    GetFederatedInstancesKind,
+    ListFederationQueues,
+    UserSettingsBackup,
+  },
 };
 use diesel::{
+  BoolExpressionMethods,
   ExpressionMethods,
   JoinOnDsl,
+  NullableExpressionMethods,
   OptionalExtension,
   PgTextExpressionMethods,
   QueryDsl,
@@ -29,6 +37,7 @@ use lemmy_db_schema_file::{
   schema::{
     federation_allowlist,
     federation_blocklist,
+    federation_queue_control,
     federation_queue_state,
     instance,
     local_site,
@@ -203,17 +212,75 @@ impl PaginationCursorConversion for FederatedInstanceView {
   }
 }
 
+impl FederationQueueView {
+  #[diesel::dsl::auto_type(no_type_alias)]
+  fn joins() -> _ {
+    instance::table
+      .inner_join(federation_queue_state::table)
+      .left_join(federation_queue_control::table)
+  }
+
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    data: ListFederationQueues,
+  ) -> LemmyResult<PagedResponse<Self>> {
+    let limit = limit_fetch(data.limit, None)?;
+    let mut query = Self::joins()
+      .select(Self::as_select())
+      .limit(limit)
+      .into_boxed();
+
+    if let Some(domain_filter) = &data.domain_filter {
+      query = query.filter(instance::domain.ilike(fuzzy_search(domain_filter)))
+    }
+    if data.failing_only.unwrap_or_default() {
+      query = query.filter(
+        federation_queue_state::fail_count
+          .gt(0)
+          .or(federation_queue_control::paused.nullable().eq(true)),
+      )
+    }
+
+    let pq = Self::paginate(query, &data.page_cursor, SortDirection::Asc, pool)
+      .await?
+      .then_order_by(key::id);
+
+    let conn = &mut get_conn(pool).await?;
+    let res = pq
+      .get_results(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)?;
+    paginate_response(res, limit, data.page_cursor)
+  }
+}
+
+impl PaginationCursorConversion for FederationQueueView {
+  type PaginatedType = Instance;
+  fn to_cursor(&self) -> CursorData {
+    CursorData::new_id(self.instance.id.0)
+  }
+
+  async fn from_cursor(
+    cursor: CursorData,
+    pool: &mut DbPool<'_>,
+  ) -> LemmyResult<Self::PaginatedType> {
+    Instance::read(pool, InstanceId(cursor.id()?)).await
+  }
+}
+
 #[cfg(test)]
 #[expect(clippy::indexing_slicing)]
 mod tests {
   use crate::{
     FederatedInstanceView,
-    api::{GetFederatedInstances, GetFederatedInstancesKind},
+    FederationQueueView,
+    api::{GetFederatedInstances, GetFederatedInstancesKind, ListFederationQueues},
   };
   use lemmy_db_schema::{
     assert_length,
     source::{
       federation_allowlist::{FederationAllowList, FederationAllowListForm},
+      federation_queue_control::FederationQueueControl,
       federation_queue_state::FederationQueueState,
       instance::Instance,
       site::{Site, SiteInsertForm},
@@ -277,4 +344,47 @@ mod tests {
     Instance::delete_all(pool).await?;
     Ok(())
   }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_federation_queue_list() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance0 = Instance::read_or_create(pool, "example0.com").await?;
+    let instance1 = Instance::read_or_create(pool, "example1.com").await?;
+    // no queue state, so not included
+    Instance::read_or_create(pool, "example2.com").await?;
+    for (instance, fail_count) in [(&instance0, 0), (&instance1, 3)] {
+      let queue_state = FederationQueueState {
+        instance_id: instance.id,
+        fail_count,
+        last_successful_id: None,
+        last_successful_published_time_at: None,
+        last_retry_at: None,
+      };
+      FederationQueueState::upsert(pool, &queue_state).await?;
+    }
+
+    let mut data = ListFederationQueues::default();
+    let list = FederationQueueView::list(pool, data.clone()).await?;
+    assert_length!(2, list);
+    assert_eq!(instance0.id, list[0].instance.id);
+    assert!(list[0].control.is_none());
+
+    // only instance1 is failing
+    data.failing_only = Some(true);
+    let list = FederationQueueView::list(pool, data.clone()).await?;
+    assert_length!(1, list);
+    assert_eq!(instance1.id, list[0].instance.id);
+
+    // paused instances are included as well
+    FederationQueueControl::set_paused(pool, instance0.id, true).await?;
+    let list = FederationQueueView::list(pool, data).await?;
+    assert_length!(2, list);
+    assert_eq!(Some(true), list[0].control.as_ref().map(|c| c.paused));
+
+    Instance::delete_all(pool).await?;
+    Ok(())
+  }
 }
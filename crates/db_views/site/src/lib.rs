@@ -3,6 +3,7 @@ use diesel::{Queryable, Selectable};
 use lemmy_db_schema::source::{
   federation_allowlist::FederationAllowList,
   federation_blocklist::FederationBlockList,
+  federation_queue_control::FederationQueueControl,
   federation_queue_state::FederationQueueState,
   instance::Instance,
   local_site::LocalSite,
@@ -56,6 +57,22 @@ pub struct FederatedInstanceView {
   pub allowed: Option<FederationAllowList>,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// The outgoing federation queue of an instance, for admins.
+pub struct FederationQueueView {
+  #[cfg_attr(feature = "full", diesel(embed))]
+  pub instance: Instance,
+  #[cfg_attr(feature = "full", diesel(embed))]
+  pub queue_state: FederationQueueState,
+  #[cfg_attr(feature = "full", diesel(embed))]
+  pub control: Option<FederationQueueControl>,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(export))]
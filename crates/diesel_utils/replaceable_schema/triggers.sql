@@ -394,6 +394,168 @@ CREATE TRIGGER change_values
     BEFORE INSERT ON private_message
     FOR EACH ROW
     EXECUTE FUNCTION r.private_message_change_values ();
+CREATE FUNCTION r.modmail_thread_change_values ()
+    RETURNS TRIGGER
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    -- Set local ap_id
+    IF NEW.local THEN
+        NEW.ap_id = coalesce(NEW.ap_id, r.local_url ('/modmail/' || NEW.id::text));
+    END IF;
+    RETURN NEW;
+END
+$$;
+CREATE TRIGGER change_values
+    BEFORE INSERT ON modmail_thread
+    FOR EACH ROW
+    EXECUTE FUNCTION r.modmail_thread_change_values ();
+CREATE FUNCTION r.modmail_message_change_values ()
+    RETURNS TRIGGER
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    -- Set local ap_id
+    IF NEW.local THEN
+        NEW.ap_id = coalesce(NEW.ap_id, r.local_url ('/modmail_message/' || NEW.id::text));
+    END IF;
+    RETURN NEW;
+END
+$$;
+CREATE TRIGGER change_values
+    BEFORE INSERT ON modmail_message
+    FOR EACH ROW
+    EXECUTE FUNCTION r.modmail_message_change_values ();
+-- Keep full text search vectors up to date, only while full text search is enabled for the site
+CREATE FUNCTION r.post_search_update ()
+    RETURNS TRIGGER
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    IF EXISTS (
+        SELECT
+        FROM
+            local_site
+        WHERE
+            full_text_search) THEN
+        INSERT INTO post_search (post_id, search_vector)
+            VALUES (NEW.id, search_vector (NEW.language_id, NEW.name, NEW.body))
+        ON CONFLICT (post_id)
+            DO UPDATE SET
+                search_vector = excluded.search_vector;
+    END IF;
+    RETURN NULL;
+END
+$$;
+CREATE TRIGGER search_update
+    AFTER INSERT OR UPDATE OF name, body, language_id ON post
+    FOR EACH ROW
+    EXECUTE FUNCTION r.post_search_update ();
+CREATE FUNCTION r.comment_search_update ()
+    RETURNS TRIGGER
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    IF EXISTS (
+        SELECT
+        FROM
+            local_site
+        WHERE
+            full_text_search) THEN
+        INSERT INTO comment_search (comment_id, search_vector)
+            VALUES (NEW.id, search_vector (NEW.language_id, NULL, NEW.content))
+        ON CONFLICT (comment_id)
+            DO UPDATE SET
+                search_vector = excluded.search_vector;
+    END IF;
+    RETURN NULL;
+END
+$$;
+CREATE TRIGGER search_update
+    AFTER INSERT OR UPDATE OF content, language_id ON comment
+    FOR EACH ROW
+    EXECUTE FUNCTION r.comment_search_update ();
+-- Fill the search vectors of all existing posts and comments when full text search is enabled, and
+-- drop them again when it's disabled. This way instances which don't use full text search don't pay
+-- for the vectors on every write, at the cost of one slow update when enabling it.
+CREATE FUNCTION r.local_site_full_text_search_update ()
+    RETURNS TRIGGER
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    IF NEW.full_text_search THEN
+        INSERT INTO post_search (post_id, search_vector)
+        SELECT
+            id,
+            search_vector (language_id, name, body)
+        FROM
+            post
+        ON CONFLICT (post_id)
+            DO UPDATE SET
+                search_vector = excluded.search_vector;
+        INSERT INTO comment_search (comment_id, search_vector)
+        SELECT
+            id,
+            search_vector (language_id, NULL, content)
+        FROM
+            comment
+        ON CONFLICT (comment_id)
+            DO UPDATE SET
+                search_vector = excluded.search_vector;
+    ELSE
+        DELETE FROM post_search;
+        DELETE FROM comment_search;
+    END IF;
+    RETURN NULL;
+END
+$$;
+CREATE TRIGGER full_text_search_update
+    AFTER UPDATE OF full_text_search ON local_site
+    FOR EACH ROW
+    WHEN (OLD.full_text_search IS DISTINCT FROM NEW.full_text_search)
+    EXECUTE FUNCTION r.local_site_full_text_search_update ();
+-- Keep the previous version of posts and comments when their text is edited. Text which is
+-- overwritten because of deletion must not be kept around, so the history is cleared instead.
+CREATE FUNCTION r.post_history_insert ()
+    RETURNS TRIGGER
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    IF NEW.deleted THEN
+        DELETE FROM post_history
+        WHERE post_id = NEW.id;
+    ELSE
+        INSERT INTO post_history (post_id, name, body, url, published_at)
+            VALUES (OLD.id, OLD.name, OLD.body, OLD.url, coalesce(OLD.updated_at, OLD.published_at));
+    END IF;
+    RETURN NULL;
+END
+$$;
+CREATE TRIGGER history_insert
+    AFTER UPDATE OF name, body, url ON post
+    FOR EACH ROW
+    WHEN (OLD.name IS DISTINCT FROM NEW.name OR OLD.body IS DISTINCT FROM NEW.body OR OLD.url IS DISTINCT FROM NEW.url)
+    EXECUTE FUNCTION r.post_history_insert ();
+CREATE FUNCTION r.comment_history_insert ()
+    RETURNS TRIGGER
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    IF NEW.deleted THEN
+        DELETE FROM comment_history
+        WHERE comment_id = NEW.id;
+    ELSE
+        INSERT INTO comment_history (comment_id, content, published_at)
+            VALUES (OLD.id, OLD.content, coalesce(OLD.updated_at, OLD.published_at));
+    END IF;
+    RETURN NULL;
+END
+$$;
+CREATE TRIGGER history_insert
+    AFTER UPDATE OF content ON comment
+    FOR EACH ROW
+    WHEN (OLD.content IS DISTINCT FROM NEW.content)
+    EXECUTE FUNCTION r.comment_history_insert ();
 -- Combined tables triggers
 -- These insert (published_at, item_id) into X_combined tables
 -- Reports (comment_report, post_report, private_message_report)
@@ -1121,3 +1283,68 @@ CREATE TRIGGER modlog_bulk_action_parent_delete
     FOR EACH ROW
     WHEN (OLD.bulk_action_parent_id IS NOT NULL)
     EXECUTE FUNCTION r.modlog_child_count_decrement ();
+-- Publish events for real-time clients on the `lemmy_realtime` channel. The payloads only contain
+-- ids, and each server process reads the objects with the permissions of its connected users.
+-- Only local posts and comments are published, otherwise every process would wake up for each
+-- federated comment, even if no client is connected.
+CREATE FUNCTION r.realtime_notification_insert ()
+    RETURNS TRIGGER
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    PERFORM
+        pg_notify('lemmy_realtime', json_build_object('type', 'notification', 'id', NEW.id, 'recipient_id', NEW.recipient_id)::text);
+    RETURN NULL;
+END
+$$;
+CREATE TRIGGER realtime_insert
+    AFTER INSERT ON notification
+    FOR EACH ROW
+    EXECUTE FUNCTION r.realtime_notification_insert ();
+-- Identical payloads in the same transaction are only delivered once, so marking all
+-- notifications as read sends a single event per recipient.
+CREATE FUNCTION r.realtime_notification_read ()
+    RETURNS TRIGGER
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    PERFORM
+        pg_notify('lemmy_realtime', json_build_object('type', 'unread_counts', 'recipient_id', NEW.recipient_id)::text);
+    RETURN NULL;
+END
+$$;
+CREATE TRIGGER realtime_read
+    AFTER UPDATE OF read ON notification
+    FOR EACH ROW
+    WHEN (OLD.read != NEW.read)
+    EXECUTE FUNCTION r.realtime_notification_read ();
+CREATE FUNCTION r.realtime_post_insert ()
+    RETURNS TRIGGER
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    PERFORM
+        pg_notify('lemmy_realtime', json_build_object('type', 'post', 'id', NEW.id, 'community_id', NEW.community_id)::text);
+    RETURN NULL;
+END
+$$;
+CREATE TRIGGER realtime_insert
+    AFTER INSERT ON post
+    FOR EACH ROW
+    WHEN (NEW.local)
+    EXECUTE FUNCTION r.realtime_post_insert ();
+CREATE FUNCTION r.realtime_comment_insert ()
+    RETURNS TRIGGER
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    PERFORM
+        pg_notify('lemmy_realtime', json_build_object('type', 'comment', 'id', NEW.id, 'post_id', NEW.post_id)::text);
+    RETURN NULL;
+END
+$$;
+CREATE TRIGGER realtime_insert
+    AFTER INSERT ON comment
+    FOR EACH ROW
+    WHEN (NEW.local)
+    EXECUTE FUNCTION r.realtime_comment_insert ();
@@ -287,3 +287,62 @@ BEGIN
 END;
 $$;
 
+
+-- Converts a query in `to_tsquery` syntax into a tsquery which matches search vectors created by
+-- `search_vector`, using both the language of the searched content and unchanged words.
+CREATE FUNCTION r.search_query (query text, language_id int)
+    RETURNS tsquery
+    LANGUAGE sql
+    STABLE PARALLEL SAFE RETURN to_tsquery('simple', query) || to_tsquery(text_search_config ((
+            SELECT
+                code
+            FROM language
+            WHERE
+                id = language_id)), query);
+
+-- Same as `r.search_query`, but matches content in any language. This is used with the search
+-- index, because the language of each row isn't known before the index lookup.
+CREATE FUNCTION r.search_query_any_language (query text)
+    RETURNS tsquery
+    LANGUAGE plpgsql
+    STABLE PARALLEL SAFE
+    AS $$
+DECLARE
+    result tsquery := to_tsquery('simple', query);
+    config regconfig;
+BEGIN
+    FOR config IN
+    SELECT DISTINCT
+        text_search_config (code)
+    FROM
+        language LOOP
+            result := result || to_tsquery(config, query);
+        END LOOP;
+    RETURN result;
+END
+$$;
+
+
+-- The poll of a post as json, with its choices and the choices which the given person voted for.
+-- Returns null if the post has no poll.
+CREATE FUNCTION r.post_poll (post_id int, person_id int)
+    RETURNS json
+    LANGUAGE sql
+    STABLE PARALLEL SAFE RETURN (
+        SELECT
+            json_build_object('poll', row_to_json(p), 'choices', coalesce((
+                    SELECT
+                        json_agg(pc ORDER BY pc.id)
+                    FROM poll_choice pc
+                    WHERE
+                        pc.post_id = p.post_id), '[]'), 'my_votes', coalesce((
+                    SELECT
+                        json_agg(pv.poll_choice_id)
+                    FROM poll_vote pv
+                    WHERE
+                        pv.post_id = p.post_id
+                        AND pv.person_id = post_poll.person_id), '[]'))
+        FROM
+            poll p
+        WHERE
+            p.post_id = post_poll.post_id);
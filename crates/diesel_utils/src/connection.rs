@@ -14,7 +14,7 @@ use diesel_async::{
   },
   scoped_futures::ScopedBoxFuture,
 };
-use futures_util::{FutureExt, future::BoxFuture};
+use futures_util::{FutureExt, StreamExt, future::BoxFuture, stream::poll_fn};
 use lemmy_utils::{
   error::{LemmyError, LemmyResult},
   settings::SETTINGS,
@@ -37,6 +37,12 @@ use std::{
   sync::Arc,
   time::Duration,
 };
+use tokio::{
+  io::{AsyncRead, AsyncWrite},
+  sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel},
+};
+use tokio_postgres::{AsyncMessage, Client, Connection, NoTls};
+use tokio_postgres_rustls::MakeRustlsConnect;
 use tracing::error;
 
 pub type ActualDbPool = Pool<AsyncPgConnection>;
@@ -202,14 +208,7 @@ fn establish_connection(config: &str) -> BoxFuture<'_, ConnectionResult<AsyncPgC
   let fut = async {
     // We only support TLS with sslmode=require currently
     let conn = if config.contains("sslmode=require") {
-      let rustls_config = DangerousClientConfigBuilder {
-        cfg: ClientConfig::builder(),
-      }
-      .with_custom_certificate_verifier(Arc::new(NoCertVerifier {}))
-      .with_no_client_auth();
-
-      let tls = tokio_postgres_rustls::MakeRustlsConnect::new(rustls_config);
-      let (client, conn) = tokio_postgres::connect(config, tls)
+      let (client, conn) = tokio_postgres::connect(config, make_tls_connect())
         .await
         .map_err(|e| ConnectionError::BadConnection(e.to_string()))?;
       tokio::spawn(async move {
@@ -227,6 +226,78 @@ fn establish_connection(config: &str) -> BoxFuture<'_, ConnectionResult<AsyncPgC
   fut.boxed()
 }
 
+fn make_tls_connect() -> MakeRustlsConnect {
+  let rustls_config = DangerousClientConfigBuilder {
+    cfg: ClientConfig::builder(),
+  }
+  .with_custom_certificate_verifier(Arc::new(NoCertVerifier {}))
+  .with_no_client_auth();
+
+  MakeRustlsConnect::new(rustls_config)
+}
+
+/// A separate database connection which receives the payloads sent with `NOTIFY` on a channel.
+/// It can't be taken from the pool, because `LISTEN` only applies to the session which ran it.
+pub struct DbListener {
+  // Closes the connection when dropped
+  _client: Client,
+  receiver: UnboundedReceiver<String>,
+}
+
+impl DbListener {
+  pub async fn connect(channel: &str) -> LemmyResult<Self> {
+    let config = SETTINGS.get_database_url_with_options()?;
+    let (sender, receiver) = unbounded_channel();
+    // The connection has to be polled while `LISTEN` runs, so it is spawned first
+    let client = if config.contains("sslmode=require") {
+      let (client, conn) = tokio_postgres::connect(&config, make_tls_connect()).await?;
+      forward_notifications(conn, sender);
+      client
+    } else {
+      let (client, conn) = tokio_postgres::connect(&config, NoTls).await?;
+      forward_notifications(conn, sender);
+      client
+    };
+    client
+      .batch_execute(&format!("LISTEN \"{}\"", channel.replace('"', "\"\"")))
+      .await?;
+
+    Ok(DbListener {
+      _client: client,
+      receiver,
+    })
+  }
+
+  /// Waits for the next payload. Returns `None` if the connection was lost.
+  pub async fn recv(&mut self) -> Option<String> {
+    self.receiver.recv().await
+  }
+}
+
+fn forward_notifications<S, T>(mut conn: Connection<S, T>, sender: UnboundedSender<String>)
+where
+  S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+  T: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+  tokio::spawn(async move {
+    let mut messages = poll_fn(move |cx| conn.poll_message(cx));
+    while let Some(message) = messages.next().await {
+      match message {
+        Ok(AsyncMessage::Notification(n)) => {
+          if sender.send(n.payload().to_owned()).is_err() {
+            break;
+          }
+        }
+        Ok(_) => {}
+        Err(e) => {
+          error!("Database listener failed: {e}");
+          break;
+        }
+      }
+    }
+  });
+}
+
 #[derive(Debug)]
 struct NoCertVerifier {}
 
@@ -318,6 +318,58 @@ where
   })
 }
 
+/// Add prev/next cursors to a query result which was paginated with a plain offset. This is only
+/// used for sort orders which can't be expressed with cursor keys, like search relevance.
+#[cfg(feature = "full")]
+pub fn paginate_response_by_offset<
+  #[cfg(feature = "ts-rs")] T: ts_rs::TS,
+  #[cfg(not(feature = "ts-rs"))] T,
+>(
+  data: Vec<T>,
+  limit: i64,
+  offset: i64,
+) -> LemmyResult<PagedResponse<T>> {
+  let make_cursor = |offset: i64| -> LemmyResult<PaginationCursor> {
+    PaginationCursor::from_internal(PaginationCursorInternal {
+      back: false,
+      data: CursorData::new_with_prefix(OFFSET_CURSOR_PREFIX, offset.try_into()?),
+      recovery: false,
+    })
+  };
+  let next_page = if i64::try_from(data.len())? < limit {
+    None
+  } else {
+    Some(make_cursor(offset + limit)?)
+  };
+  let prev_page = if offset > 0 {
+    Some(make_cursor((offset - limit).max(0))?)
+  } else {
+    None
+  };
+  Ok(PagedResponse {
+    items: data,
+    next_page,
+    prev_page,
+  })
+}
+
+/// Marks offset cursors, so that a cursor from a different sort order isn't read as an offset.
+#[cfg(feature = "full")]
+const OFFSET_CURSOR_PREFIX: char = 'o';
+
+/// Read the offset from a cursor created by [paginate_response_by_offset].
+#[cfg(feature = "full")]
+pub fn offset_from_cursor(cursor: &Option<PaginationCursor>) -> LemmyResult<i64> {
+  let Some(cursor) = cursor else {
+    return Ok(0);
+  };
+  let (prefix, offset) = cursor.clone().into_internal()?.data.id_and_prefix()?;
+  if prefix != OFFSET_CURSOR_PREFIX {
+    return Err(LemmyErrorType::CouldntParsePaginationToken.into());
+  }
+  Ok(offset.into())
+}
+
 #[cfg(test)]
 mod test {
   use super::*;
@@ -346,6 +398,27 @@ mod test {
     Ok(())
   }
 
+  #[test]
+  fn test_offset_cursor() -> LemmyResult<()> {
+    let first = paginate_response_by_offset(vec![1, 2], 2, 0)?;
+    assert!(first.prev_page.is_none());
+    assert_eq!(offset_from_cursor(&first.next_page)?, 2);
+
+    let last = paginate_response_by_offset(vec![3], 2, 2)?;
+    assert!(last.next_page.is_none());
+    assert_eq!(offset_from_cursor(&last.prev_page)?, 0);
+    assert_eq!(offset_from_cursor(&None)?, 0);
+
+    // Cursors of other sort orders are rejected
+    let id_cursor = PaginationCursor::from_internal(PaginationCursorInternal {
+      back: false,
+      data: CursorData::new_id(5),
+      recovery: false,
+    })?;
+    assert!(offset_from_cursor(&Some(id_cursor)).is_err());
+    Ok(())
+  }
+
   #[test]
   fn test_internal_format() -> LemmyResult<()> {
     assert_eq!(
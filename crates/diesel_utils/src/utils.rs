@@ -150,6 +150,64 @@ pub fn fuzzy_search(q: &str) -> String {
   format!("%{replaced}%")
 }
 
+/// Converts a search term into `to_tsquery` syntax for full text search. Words are combined with
+/// AND, `"exact phrases"` have to appear in the given order, `-word` excludes results and `word*`
+/// does a prefix match. Returns `None` if the term doesn't contain any searchable words.
+///
+/// If `title_only` is set, only words with weight A (post titles) can match.
+pub fn full_text_search_query(q: &str, title_only: bool) -> Option<String> {
+  let weight = if title_only { "A" } else { "" };
+  let mut terms = Vec::new();
+  let mut chars = q.chars().peekable();
+  loop {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+    let Some(&first) = chars.peek() else {
+      break;
+    };
+    let negated = first == '-';
+    if negated {
+      chars.next();
+    }
+    let token: String = if chars.next_if_eq(&'"').is_some() {
+      chars.by_ref().take_while(|c| *c != '"').collect()
+    } else {
+      let mut word = String::new();
+      while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+        word.push(c);
+      }
+      word
+    };
+
+    let prefix = token.ends_with('*');
+    let words: Vec<&str> = token
+      .split(|c: char| !c.is_alphanumeric())
+      .filter(|w| !w.is_empty())
+      .collect();
+    let last = words.len().saturating_sub(1);
+    let lexemes: Vec<String> = words
+      .iter()
+      .enumerate()
+      .map(|(i, w)| {
+        let star = if prefix && i == last { "*" } else { "" };
+        if star.is_empty() && weight.is_empty() {
+          (*w).to_string()
+        } else {
+          format!("{w}:{star}{weight}")
+        }
+      })
+      .collect();
+
+    let term = match lexemes.len() {
+      0 => continue,
+      1 => lexemes.join(""),
+      _ => format!("({})", lexemes.join(" <-> ")),
+    };
+    terms.push(if negated { format!("!{term}") } else { term });
+  }
+
+  (!terms.is_empty()).then(|| terms.join(" & "))
+}
+
 /// Takes an API optional text input, and converts it to an optional diesel DB update.
 pub fn diesel_string_update(opt: Option<&str>) -> Option<Option<String>> {
   match opt {
@@ -281,6 +339,27 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_full_text_search_query() {
+    assert_eq!(
+      full_text_search_query("rust borrow", false),
+      Some("rust & borrow".to_string())
+    );
+    assert_eq!(
+      full_text_search_query("\"borrow checker\" -java asyn*", false),
+      Some("(borrow <-> checker) & !java & asyn:*".to_string())
+    );
+    assert_eq!(
+      full_text_search_query("-\"foo bar\" it's", true),
+      Some("!(foo:A <-> bar:A) & (it:A <-> s:A)".to_string())
+    );
+    assert_eq!(
+      full_text_search_query("a&b|c \"unclosed", false),
+      Some("(a <-> b <-> c) & unclosed".to_string())
+    );
+    assert_eq!(full_text_search_query(" !!! - \"\" * ", false), None);
+  }
+
   #[test]
   fn test_diesel_option_overwrite() {
     assert_eq!(diesel_string_update(None), None);
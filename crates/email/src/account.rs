@@ -1,10 +1,15 @@
-use crate::{send::send_email, user_email, user_language};
+use crate::{
+  send::send_email,
+  template::{TemplateValue, apply_email_template},
+  user_email,
+  user_language,
+};
 use lemmy_db_schema::source::{
   email_verification::{EmailVerification, EmailVerificationForm},
   local_site::LocalSite,
   password_reset_request::PasswordResetRequest,
 };
-use lemmy_db_schema_file::enums::RegistrationMode;
+use lemmy_db_schema_file::enums::{EmailTemplateType, RegistrationMode};
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_diesel_utils::{connection::DbPool, sensitive::SensitiveString};
 use lemmy_utils::{
@@ -26,8 +31,25 @@ pub async fn send_password_reset_email(
   let protocol_and_hostname = settings.get_protocol_and_hostname();
   let reset_link = format!("{}/password_change/{}", protocol_and_hostname, &token);
   let email = user_email(user)?;
-  let body = lang.password_reset_body(reset_link, &user.person.name);
-  send_email(subject, email, user.person.name.clone(), body, settings);
+  let body = lang.password_reset_body(&reset_link, &user.person.name);
+  let (subject, body) = apply_email_template(
+    EmailTemplateType::PasswordReset,
+    user,
+    vec![("reset_link", TemplateValue::Text(reset_link))],
+    (subject, body),
+    pool,
+    settings,
+  )
+  .await?;
+  send_email(
+    subject,
+    email,
+    user.person.name.clone(),
+    body,
+    pool,
+    settings,
+  )
+  .await?;
 
   // Insert the row after successful send, to avoid using daily reset limit while
   // email sending is broken.
@@ -61,12 +83,29 @@ pub async fn send_verification_email(
 
   // If an application is required, use a translation that includes that warning.
   let body = if local_site.registration_mode == RegistrationMode::RequireApplication {
-    lang.verify_email_body_with_application(&settings.hostname, &user.person.name, verify_link)
+    lang.verify_email_body_with_application(&settings.hostname, &user.person.name, &verify_link)
   } else {
-    lang.verify_email_body(&settings.hostname, &user.person.name, verify_link)
+    lang.verify_email_body(&settings.hostname, &user.person.name, &verify_link)
   };
+  let (subject, body) = apply_email_template(
+    EmailTemplateType::VerifyEmail,
+    user,
+    vec![("verify_link", TemplateValue::Text(verify_link))],
+    (subject, body),
+    pool,
+    settings,
+  )
+  .await?;
 
-  send_email(subject, new_email, user.person.name.clone(), body, settings);
+  send_email(
+    subject,
+    new_email,
+    user.person.name.clone(),
+    body,
+    pool,
+    settings,
+  )
+  .await?;
   Ok(())
 }
 
@@ -89,39 +128,77 @@ pub async fn send_verification_email_if_required(
   }
 }
 
-pub fn send_application_approved_email(
+pub async fn send_application_approved_email(
   user: &LocalUserView,
+  pool: &mut DbPool<'_>,
   settings: &'static Settings,
 ) -> LemmyResult<()> {
   let lang = user_language(&user.local_user);
   let subject = lang.registration_approved_subject(&user.person.name);
   let email = user_email(user)?;
   let body = lang.registration_approved_body(&settings.hostname);
-  send_email(subject, email, user.person.name.clone(), body, settings);
+  let (subject, body) = apply_email_template(
+    EmailTemplateType::ApplicationApproved,
+    user,
+    vec![],
+    (subject, body),
+    pool,
+    settings,
+  )
+  .await?;
+  send_email(
+    subject,
+    email,
+    user.person.name.clone(),
+    body,
+    pool,
+    settings,
+  )
+  .await?;
   Ok(())
 }
 
-pub fn send_application_denied_email(
+pub async fn send_application_denied_email(
   user: &LocalUserView,
   deny_reason: Option<String>,
+  pool: &mut DbPool<'_>,
   settings: &'static Settings,
 ) -> LemmyResult<()> {
   let lang = user_language(&user.local_user);
   let subject = lang.registration_denied_subject(&user.person.name);
   let email = user_email(user)?;
-  let body = match deny_reason {
-    Some(deny_reason) => {
-      let markdown = markdown_to_html(&deny_reason);
-      lang.registration_denied_reason_body(&settings.hostname, &markdown)
-    }
+  let deny_reason = deny_reason.map(|r| markdown_to_html(&r));
+  let body = match &deny_reason {
+    Some(deny_reason) => lang.registration_denied_reason_body(&settings.hostname, deny_reason),
     None => lang.registration_denied_body(&settings.hostname),
   };
-  send_email(subject, email, user.person.name.clone(), body, settings);
+  let (subject, body) = apply_email_template(
+    EmailTemplateType::ApplicationDenied,
+    user,
+    vec![(
+      "deny_reason",
+      TemplateValue::Html(deny_reason.unwrap_or_default()),
+    )],
+    (subject, body),
+    pool,
+    settings,
+  )
+  .await?;
+  send_email(
+    subject,
+    email,
+    user.person.name.clone(),
+    body,
+    pool,
+    settings,
+  )
+  .await?;
   Ok(())
 }
 
-pub fn send_email_verified_email(
+pub async fn send_email_verified_email(
   user: &LocalUserView,
+  pool: &mut DbPool<'_>,
   settings: &'static Settings,
 ) -> LemmyResult<()> {
   let lang = user_language(&user.local_user);
@@ -133,7 +210,9 @@ pub fn send_email_verified_email(
     email,
     user.person.name.clone(),
     body.to_string(),
+    pool,
     settings,
-  );
+  )
+  .await?;
   Ok(())
 }
@@ -1,4 +1,9 @@
-use crate::{send::send_email, user_language};
+use crate::{
+  send::send_email,
+  template::{TemplateValue, apply_email_template},
+  user_language,
+};
+use lemmy_db_schema_file::enums::EmailTemplateType;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_diesel_utils::connection::DbPool;
 use lemmy_utils::{error::LemmyResult, settings::structs::Settings};
@@ -22,7 +27,7 @@ pub async fn send_new_applicant_email_to_admins(
     if let Some(email) = admin.local_user.email {
       let subject = lang.new_application_subject(&settings.hostname, applicant_username);
       let body = lang.new_application_body(applications_link);
-      send_email(subject, email, admin.person.name, body, settings);
+      send_email(subject, email, admin.person.name, body, pool, settings).await?;
     }
   }
   Ok(())
@@ -42,11 +47,31 @@ pub async fn send_new_report_email_to_admins(
 
   for admin in admins {
     let lang = user_language(&admin.local_user);
-    if let Some(email) = admin.local_user.email {
+    if let Some(email) = admin.local_user.email.clone() {
       let subject =
         lang.new_report_subject(&settings.hostname, reported_username, reporter_username);
       let body = lang.new_report_body(reports_link);
-      send_email(subject, email, admin.person.name, body, settings);
+      let variables = vec![
+        (
+          "reporter",
+          TemplateValue::Text(reporter_username.to_string()),
+        ),
+        (
+          "reported",
+          TemplateValue::Text(reported_username.to_string()),
+        ),
+        ("reports_link", TemplateValue::Text(reports_link.clone())),
+      ];
+      let (subject, body) = apply_email_template(
+        EmailTemplateType::NewReport,
+        &admin,
+        variables,
+        (subject, body),
+        pool,
+        settings,
+      )
+      .await?;
+      send_email(subject, email, admin.person.name, body, pool, settings).await?;
     }
   }
   Ok(())
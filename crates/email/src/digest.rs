@@ -0,0 +1,112 @@
+use crate::{escape_html, inbox_link, send::send_email};
+use lemmy_db_schema_file::enums::NotificationType;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::connection::DbPool;
+use lemmy_utils::{
+  error::LemmyResult,
+  settings::structs::Settings,
+  utils::markdown::markdown_to_html,
+};
+use std::fmt::Write;
+
+/// A notification which is listed in the digest email.
+pub struct DigestNotification {
+  pub kind: NotificationType,
+  pub creator_name: String,
+  /// Markdown content of the comment or private message.
+  pub content: String,
+  pub link: String,
+}
+
+/// A popular post from one of the user's subscribed communities.
+pub struct DigestPost {
+  pub title: String,
+  pub community_name: String,
+  pub score: i32,
+  pub link: String,
+}
+
+/// Sends a single email which summarizes unread notifications since the last digest. Nothing is
+/// sent if there is nothing new.
+pub async fn send_digest_email(
+  local_user_view: &LocalUserView,
+  notifications: &[DigestNotification],
+  top_posts: &[DigestPost],
+  unsubscribe_link: &str,
+  pool: &mut DbPool<'_>,
+  settings: &'static Settings,
+) -> LemmyResult<()> {
+  if notifications.is_empty() && top_posts.is_empty() {
+    return Ok(());
+  }
+  let Some(email) = local_user_view.local_user.email.clone() else {
+    return Ok(());
+  };
+
+  // TODO: use translations once the digest strings are added to lemmy-translations
+  let subject = format!(
+    "{} new notifications on {}",
+    notifications.len(),
+    settings.hostname
+  );
+  let body = digest_body(notifications, top_posts, unsubscribe_link, settings);
+  send_email(
+    subject,
+    email,
+    local_user_view.person.name.clone(),
+    body,
+    pool,
+    settings,
+  )
+  .await
+}
+
+fn digest_body(
+  notifications: &[DigestNotification],
+  top_posts: &[DigestPost],
+  unsubscribe_link: &str,
+  settings: &Settings,
+) -> String {
+  let mut body = String::new();
+  for (kind, heading) in [
+    (NotificationType::Reply, "Replies"),
+    (NotificationType::Mention, "Mentions"),
+    (NotificationType::PrivateMessage, "Private messages"),
+  ] {
+    let mut items = notifications.iter().filter(|n| n.kind == kind).peekable();
+    if items.peek().is_none() {
+      continue;
+    }
+    let _ = write!(body, "<h2>{heading}</h2>");
+    for n in items {
+      let _ = write!(
+        body,
+        "<h3><a href=\"{}\">{}</a></h3>{}",
+        n.link,
+        escape_html(&n.creator_name),
+        markdown_to_html(&n.content)
+      );
+    }
+  }
+  if !top_posts.is_empty() {
+    body.push_str("<h2>Top posts</h2><ul>");
+    for p in top_posts {
+      let _ = write!(
+        body,
+        "<li><a href=\"{}\">{}</a> in {} ({} points)</li>",
+        p.link,
+        escape_html(&p.title),
+        escape_html(&p.community_name),
+        p.score
+      );
+    }
+    body.push_str("</ul>");
+  }
+  let _ = write!(
+    body,
+    "<p><a href=\"{}\">Go to your inbox</a></p>\
+     <p><a href=\"{unsubscribe_link}\">Unsubscribe from these emails</a></p>",
+    inbox_link(settings)
+  );
+  body
+}
@@ -10,8 +10,12 @@ use translations::Lang;
 
 pub mod account;
 pub mod admin;
+pub mod digest;
 pub mod notifications;
 mod send;
+pub mod template;
+
+pub use send::send_queued_emails;
 
 /// Avoid warnings for unused 0.19 translations
 #[expect(mismatched_lifetime_syntaxes)]
@@ -23,6 +27,14 @@ fn inbox_link(settings: &Settings) -> String {
   format!("{}/inbox", settings.get_protocol_and_hostname())
 }
 
+fn escape_html(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
+
 #[expect(clippy::expect_used)]
 pub fn user_language(local_user: &LocalUser) -> Lang {
   let lang_id = LanguageId::new(&local_user.interface_language);
@@ -1,9 +1,19 @@
-use crate::{inbox_link, send::send_email, user_language};
+use crate::{
+  escape_html,
+  inbox_link,
+  send::send_email,
+  template::{TemplateValue, apply_email_template},
+  user_language,
+};
 use lemmy_db_schema::source::{comment::Comment, community::Community, person::Person, post::Post};
-use lemmy_db_schema_file::enums::ModlogKind;
+use lemmy_db_schema_file::enums::{EmailDigestFrequency, EmailTemplateType, ModlogKind};
 use lemmy_db_views_local_user::LocalUserView;
-use lemmy_diesel_utils::dburl::DbUrl;
-use lemmy_utils::{settings::structs::Settings, utils::markdown::markdown_to_html};
+use lemmy_diesel_utils::{connection::DbPool, dburl::DbUrl};
+use lemmy_utils::{
+  error::LemmyResult,
+  settings::structs::Settings,
+  utils::markdown::markdown_to_html,
+};
 
 pub enum NotificationEmailData<'a> {
   Mention {
@@ -33,33 +43,55 @@ pub enum NotificationEmailData<'a> {
     reason: Option<&'a str>,
     is_revert: bool,
   },
+  /// Uses the texts for mod actions, with the kind of the appealed ban.
+  BanAppealDenied {
+    kind: ModlogKind,
+    reason: Option<&'a str>,
+  },
 }
 
-pub fn send_notification_email(
+pub async fn send_notification_email(
   local_user_view: LocalUserView,
   link: DbUrl,
-  data: NotificationEmailData,
+  data: NotificationEmailData<'_>,
+  pool: &mut DbPool<'_>,
   settings: &'static Settings,
-) {
-  if local_user_view.banned || !local_user_view.local_user.send_notifications_to_email {
-    return;
+) -> LemmyResult<()> {
+  // Banned users still need to hear about mod actions, eg when their ban appeal was denied
+  let is_mod_action = matches!(
+    data,
+    NotificationEmailData::ModAction { .. } | NotificationEmailData::BanAppealDenied { .. }
+  );
+  if (local_user_view.banned && !is_mod_action)
+    || !local_user_view.local_user.send_notifications_to_email
+  {
+    return Ok(());
+  }
+  // Other notifications are collected into a digest email by a scheduled task. Mod actions are
+  // rare and important, so they are always sent right away.
+  if local_user_view.local_user.email_digest_frequency != EmailDigestFrequency::Immediate
+    && !is_mod_action
+  {
+    return Ok(());
   }
 
   let inbox_link = inbox_link(settings);
   let lang = user_language(&local_user_view.local_user);
-  let (subject, body) = match data {
+  let (subject, body, content) = match data {
     NotificationEmailData::Mention { content, person } => {
       let content = markdown_to_html(&content);
       (
         lang.notification_mentioned_by_subject(&person.name),
         lang.notification_mentioned_by_body(&link, &content, &inbox_link, &person.name),
+        content,
       )
     }
     NotificationEmailData::PostSubscribed { post, comment } => {
       let content = markdown_to_html(&comment.content);
       (
         lang.notification_post_subscribed_subject(&post.name),
-        lang.notification_post_subscribed_body(&content, &link, inbox_link),
+        lang.notification_post_subscribed_body(&content, &link, &inbox_link),
+        content,
       )
     }
     NotificationEmailData::CommunitySubscribed { post, community } => {
@@ -73,7 +105,8 @@ pub fn send_notification_email(
           &post.name,
           community.title.as_ref().unwrap_or(&community.name),
         ),
-        lang.notification_community_subscribed_body(&content, &link, inbox_link),
+        lang.notification_community_subscribed_body(&content, &link, &inbox_link),
+        content,
       )
     }
     NotificationEmailData::Reply {
@@ -86,13 +119,14 @@ pub fn send_notification_email(
       (
         lang.notification_comment_reply_subject(&person.name),
         lang.notification_comment_reply_body(
-          link,
+          &link,
           &content,
           &inbox_link,
           &parent_comment.content,
           &post.name,
           &person.name,
         ),
+        content,
       )
     }
     NotificationEmailData::Reply {
@@ -104,7 +138,8 @@ pub fn send_notification_email(
       let content = markdown_to_html(&comment.content);
       (
         lang.notification_post_reply_subject(&person.name),
-        lang.notification_post_reply_body(link, &content, &inbox_link, &post.name, &person.name),
+        lang.notification_post_reply_body(&link, &content, &inbox_link, &post.name, &person.name),
+        content,
       )
     }
     NotificationEmailData::PrivateMessage { sender, content } => {
@@ -112,7 +147,8 @@ pub fn send_notification_email(
       let content = markdown_to_html(content);
       (
         lang.notification_private_message_subject(sender_name),
-        lang.notification_private_message_body(inbox_link, &content, sender_name),
+        lang.notification_private_message_body(&inbox_link, &content, sender_name),
+        content,
       )
     }
     NotificationEmailData::ModAction {
@@ -125,24 +161,53 @@ pub fn send_notification_email(
       if is_revert {
         (
           lang.notification_mod_action_subject(kind).clone(),
-          lang.notification_mod_action_body(reason, inbox_link),
+          lang.notification_mod_action_body(reason, &inbox_link),
+          escape_html(reason),
         )
       } else {
         (
           lang.notification_mod_action_reverted_subject(kind).clone(),
-          lang.notification_mod_action_reverted_body(reason, inbox_link),
+          lang.notification_mod_action_reverted_body(reason, &inbox_link),
+          escape_html(reason),
         )
       }
     }
+    NotificationEmailData::BanAppealDenied { kind, reason } => {
+      let reason = reason.unwrap_or_default();
+      (
+        lang.notification_mod_action_subject(kind).clone(),
+        lang.notification_mod_action_body(reason, &inbox_link),
+        escape_html(reason),
+      )
+    }
   };
 
+  let variables = vec![
+    ("summary", TemplateValue::Text(subject.clone())),
+    ("content", TemplateValue::Html(content)),
+    ("link", TemplateValue::Text(link.to_string())),
+    ("inbox_link", TemplateValue::Text(inbox_link)),
+  ];
+  let (subject, body) = apply_email_template(
+    EmailTemplateType::Notification,
+    &local_user_view,
+    variables,
+    (subject, body),
+    pool,
+    settings,
+  )
+  .await?;
+
   if let Some(user_email) = local_user_view.local_user.email {
     send_email(
       subject,
       user_email,
       local_user_view.person.name,
       body,
+      pool,
       settings,
-    );
+    )
+    .await?;
   }
+  Ok(())
 }
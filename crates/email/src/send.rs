@@ -1,6 +1,12 @@
-use lemmy_diesel_utils::sensitive::SensitiveString;
+use chrono::Utc;
+use lemmy_db_schema::source::queued_email::{
+  QueuedEmail,
+  QueuedEmailInsertForm,
+  QueuedEmailUpdateForm,
+};
+use lemmy_diesel_utils::{connection::DbPool, sensitive::SensitiveString, traits::Crud};
 use lemmy_utils::{
-  error::{LemmyErrorExt, LemmyErrorType},
+  error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
   settings::structs::Settings,
   spawn_try_task,
 };
@@ -11,58 +17,150 @@ use lettre::{
   message::{Mailbox, MultiPart},
   transport::smtp::extension::ClientId,
 };
-use std::{str::FromStr, sync::OnceLock};
+use std::{cmp::min, str::FromStr, sync::OnceLock, time::Duration};
+use tracing::warn;
 use uuid::Uuid;
 
 type AsyncSmtpTransport = lettre::AsyncSmtpTransport<lettre::Tokio1Executor>;
 
-pub(crate) fn send_email(
+/// Give up sending an email after this many failed attempts.
+const MAX_ATTEMPTS: i32 = 10;
+/// While an email is being sent, its next attempt is moved this far into the future so that it
+/// isn't picked up again by [send_queued_emails].
+const SEND_LEASE: Duration = Duration::from_secs(5 * 60);
+
+/// Stores the email in the queue and tries to send it right away. If that fails, it is retried by
+/// the scheduled task [send_queued_emails].
+pub(crate) async fn send_email(
   subject: String,
   to_email: SensitiveString,
   to_username: String,
   html: String,
+  pool: &mut DbPool<'_>,
   settings: &'static Settings,
-) {
-  spawn_try_task(async move {
-    static MAILER: OnceLock<AsyncSmtpTransport> = OnceLock::new();
-    let email_config = settings.email.clone().ok_or(LemmyErrorType::NoEmailSetup)?;
-
-    #[expect(clippy::expect_used)]
-    let mailer = MAILER.get_or_init(|| {
-      AsyncSmtpTransport::from_url(&email_config.connection)
-        .expect("init email transport")
-        .hello_name(ClientId::Domain(settings.hostname.clone()))
-        .build()
-    });
-
-    // use usize::MAX as the line wrap length, since lettre handles the wrapping for us
-    let plain_text = html2text::from_read(html.as_bytes(), usize::MAX)?;
-
-    let smtp_from_address = &email_config.smtp_from_address;
-
-    let email = Message::builder()
-      .from(
-        smtp_from_address
-          .parse()
-          .with_lemmy_type(LemmyErrorType::InvalidEmailAddress(
-            smtp_from_address.into(),
-          ))?,
-      )
-      .to(Mailbox::new(
-        Some(to_username.clone()),
-        Address::from_str(&to_email)
-          .with_lemmy_type(LemmyErrorType::InvalidEmailAddress(to_email.into_inner()))?,
-      ))
-      .message_id(Some(format!("<{}@{}>", Uuid::new_v4(), settings.hostname)))
-      .subject(subject)
-      .multipart(MultiPart::alternative_plain_html(plain_text, html.clone()))
-      .with_lemmy_type(LemmyErrorType::EmailSendFailed)?;
-
-    mailer
-      .send(email)
-      .await
-      .with_lemmy_type(LemmyErrorType::EmailSendFailed)?;
-
-    Ok(())
-  })
+) -> LemmyResult<()> {
+  if settings.email.is_none() {
+    warn!("{}", LemmyErrorType::NoEmailSetup);
+    return Ok(());
+  }
+
+  // Inside of a transaction the email only becomes visible after commit, so it is left for the
+  // scheduled task. Otherwise it is sent immediately while holding the lease, and the scheduled
+  // task only picks it up if that fails.
+  let actual_pool = match pool {
+    DbPool::Pool(actual_pool) => Some((*actual_pool).clone()),
+    DbPool::Conn(_) => None,
+  };
+  let mut form = QueuedEmailInsertForm::new(to_email, to_username, subject, html.into());
+  if actual_pool.is_some() {
+    form.next_attempt_at = Some(Utc::now() + SEND_LEASE);
+  }
+  let email = QueuedEmail::create(pool, &form).await?;
+
+  if let Some(actual_pool) = actual_pool {
+    spawn_try_task(async move { deliver(email, &mut (&actual_pool).into(), settings).await });
+  }
+  Ok(())
+}
+
+/// Sends emails whose previous attempt failed. The emails are claimed first, so that they aren't
+/// sent twice if another server process runs this at the same time.
+pub async fn send_queued_emails(pool: &mut DbPool<'_>, settings: &Settings) -> LemmyResult<()> {
+  if settings.email.is_none() {
+    return Ok(());
+  }
+  for email in QueuedEmail::claim_pending(pool, Utc::now() + SEND_LEASE).await? {
+    deliver(email, pool, settings).await?;
+  }
+  Ok(())
+}
+
+/// Sends the email and stores the result. If it fails, the next attempt is scheduled with
+/// exponential backoff.
+async fn deliver(
+  email: QueuedEmail,
+  pool: &mut DbPool<'_>,
+  settings: &Settings,
+) -> LemmyResult<()> {
+  let res = send_smtp(&email, settings).await;
+
+  let attempts = email.attempts + 1;
+  let form = match res {
+    Ok(()) => QueuedEmailUpdateForm {
+      attempts: Some(attempts),
+      next_attempt_at: Some(None),
+      last_error: Some(None),
+      sent_at: Some(Some(Utc::now())),
+    },
+    Err(e) => {
+      warn!("Failed to send email to {}: {e}", email.to_name);
+      let next_attempt_at =
+        (attempts < MAX_ATTEMPTS).then(|| Utc::now() + email_retry_delay(attempts));
+      QueuedEmailUpdateForm {
+        attempts: Some(attempts),
+        next_attempt_at: Some(next_attempt_at),
+        last_error: Some(Some(e.to_string())),
+        sent_at: None,
+      }
+    }
+  };
+  QueuedEmail::update(pool, email.id, &form).await?;
+  Ok(())
+}
+
+async fn send_smtp(email: &QueuedEmail, settings: &Settings) -> LemmyResult<()> {
+  static MAILER: OnceLock<AsyncSmtpTransport> = OnceLock::new();
+  let email_config = settings.email.clone().ok_or(LemmyErrorType::NoEmailSetup)?;
+
+  #[expect(clippy::expect_used)]
+  let mailer = MAILER.get_or_init(|| {
+    AsyncSmtpTransport::from_url(&email_config.connection)
+      .expect("init email transport")
+      .hello_name(ClientId::Domain(settings.hostname.clone()))
+      .build()
+  });
+
+  // use usize::MAX as the line wrap length, since lettre handles the wrapping for us
+  let plain_text = html2text::from_read(email.html.as_bytes(), usize::MAX)?;
+
+  let smtp_from_address = &email_config.smtp_from_address;
+
+  let message = Message::builder()
+    .from(
+      smtp_from_address
+        .parse()
+        .with_lemmy_type(LemmyErrorType::InvalidEmailAddress(
+          smtp_from_address.into(),
+        ))?,
+    )
+    .to(Mailbox::new(
+      Some(email.to_name.clone()),
+      Address::from_str(&email.to_email).with_lemmy_type(LemmyErrorType::InvalidEmailAddress(
+        email.to_email.to_string(),
+      ))?,
+    ))
+    .message_id(Some(format!("<{}@{}>", Uuid::new_v4(), settings.hostname)))
+    .subject(email.subject.clone())
+    .multipart(MultiPart::alternative_plain_html(
+      plain_text,
+      email.html.to_string(),
+    ))
+    .with_lemmy_type(LemmyErrorType::EmailSendFailed)?;
+
+  mailer
+    .send(message)
+    .await
+    .with_lemmy_type(LemmyErrorType::EmailSendFailed)?;
+
+  Ok(())
+}
+
+/// How long to wait before retrying a failed email. Starts at one minute and doubles after each
+/// attempt, with a maximum of one day.
+fn email_retry_delay(attempts: i32) -> Duration {
+  let minutes = 2_u64.saturating_pow(attempts.saturating_sub(1).try_into().unwrap_or(0));
+  min(
+    Duration::from_secs(60 * 60 * 24),
+    Duration::from_secs(60_u64.saturating_mul(minutes)),
+  )
 }
@@ -0,0 +1,192 @@
+use crate::{escape_html, translations::Lang, user_language};
+use lemmy_db_schema::source::email_template::EmailTemplate;
+use lemmy_db_schema_file::enums::EmailTemplateType;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::connection::DbPool;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  settings::structs::Settings,
+};
+use rosetta_i18n::{Language, LanguageId};
+use tracing::warn;
+
+/// Value of a template variable. Text is escaped in the HTML body, while HTML is inserted as is.
+/// HTML values are generated by Lemmy, for example rendered markdown.
+pub(crate) enum TemplateValue {
+  Text(String),
+  Html(String),
+}
+
+type TemplateVariables = Vec<(&'static str, TemplateValue)>;
+
+/// Renders the admin defined template for this email type and the recipient's language, or
+/// returns the default subject and body if there is none. The variables `username` and `hostname`
+/// are added automatically.
+pub(crate) async fn apply_email_template(
+  template_type: EmailTemplateType,
+  user: &LocalUserView,
+  mut variables: TemplateVariables,
+  default: (String, String),
+  pool: &mut DbPool<'_>,
+  settings: &Settings,
+) -> LemmyResult<(String, String)> {
+  let lang = user_language(&user.local_user);
+  let language = lang.language_id();
+  let Some(template) =
+    EmailTemplate::read_for_language(pool, template_type, language.value()).await?
+  else {
+    return Ok(default);
+  };
+
+  variables.push(("username", TemplateValue::Text(user.person.name.clone())));
+  variables.push(("hostname", TemplateValue::Text(settings.hostname.clone())));
+  let rendered = render_template(&template.subject, &variables, false)
+    .and_then(|subject| Ok((subject, render_template(&template.body, &variables, true)?)));
+  // Templates are checked when saving, so this only fails if a variable was removed later
+  Ok(rendered.unwrap_or_else(|e| {
+    warn!("Failed to render email template {}: {e}", template.id.0);
+    default
+  }))
+}
+
+/// Renders the template with sample data, so that admins can see how it looks.
+pub fn render_template_preview(
+  template_type: EmailTemplateType,
+  subject: &str,
+  body: &str,
+  settings: &Settings,
+) -> LemmyResult<(String, String)> {
+  let variables = sample_variables(template_type, settings);
+  Ok((
+    render_template(subject, &variables, false)?,
+    render_template(body, &variables, true)?,
+  ))
+}
+
+/// Checks the template syntax and variables, and that the language is one which emails can be
+/// sent in.
+pub fn validate_email_template(
+  template_type: EmailTemplateType,
+  language: Option<&str>,
+  subject: &str,
+  body: &str,
+  settings: &Settings,
+) -> LemmyResult<()> {
+  if let Some(language) = language
+    && Lang::from_language_id(&LanguageId::new(language)).is_none()
+  {
+    return Err(LemmyErrorType::LanguageNotAllowed.into());
+  }
+  render_template_preview(template_type, subject, body, settings)?;
+  Ok(())
+}
+
+fn sample_variables(template_type: EmailTemplateType, settings: &Settings) -> TemplateVariables {
+  let protocol_and_hostname = settings.get_protocol_and_hostname();
+  let mut variables = vec![
+    ("username", TemplateValue::Text("example_user".to_string())),
+    ("hostname", TemplateValue::Text(settings.hostname.clone())),
+  ];
+  variables.extend(match template_type {
+    EmailTemplateType::VerifyEmail => vec![(
+      "verify_link",
+      TemplateValue::Text(format!("{protocol_and_hostname}/verify_email/example")),
+    )],
+    EmailTemplateType::PasswordReset => vec![(
+      "reset_link",
+      TemplateValue::Text(format!("{protocol_and_hostname}/password_change/example")),
+    )],
+    EmailTemplateType::ApplicationApproved => vec![],
+    EmailTemplateType::ApplicationDenied => vec![(
+      "deny_reason",
+      TemplateValue::Html("<p>Please tell us more about yourself.</p>".to_string()),
+    )],
+    EmailTemplateType::NewReport => vec![
+      ("reporter", TemplateValue::Text("reporter_user".to_string())),
+      ("reported", TemplateValue::Text("reported_user".to_string())),
+      (
+        "reports_link",
+        TemplateValue::Text(format!("{protocol_and_hostname}/reports")),
+      ),
+    ],
+    EmailTemplateType::Notification => vec![
+      (
+        "summary",
+        TemplateValue::Text("Reply from example_user".to_string()),
+      ),
+      (
+        "content",
+        TemplateValue::Html("<p>Example comment</p>".to_string()),
+      ),
+      (
+        "link",
+        TemplateValue::Text(format!("{protocol_and_hostname}/comment/1")),
+      ),
+      (
+        "inbox_link",
+        TemplateValue::Text(format!("{protocol_and_hostname}/inbox")),
+      ),
+    ],
+  });
+  variables
+}
+
+/// Replaces each `{{ name }}` with the value of the variable. There is no other syntax, so
+/// templates can't run any code.
+fn render_template(
+  template: &str,
+  variables: &[(&str, TemplateValue)],
+  is_html: bool,
+) -> LemmyResult<String> {
+  let mut output = String::with_capacity(template.len());
+  let mut rest = template;
+  while let Some((before, after)) = rest.split_once("{{") {
+    output.push_str(before);
+    let (name, after) = after
+      .split_once("}}")
+      .ok_or_else(|| LemmyErrorType::InvalidEmailTemplate("Missing closing }}".to_string()))?;
+    let name = name.trim();
+    let value = variables
+      .iter()
+      .find(|(n, _)| *n == name)
+      .map(|(_, v)| v)
+      .ok_or_else(|| LemmyErrorType::InvalidEmailTemplate(format!("Unknown variable {name}")))?;
+    match value {
+      TemplateValue::Text(text) if is_html => output.push_str(&escape_html(text)),
+      TemplateValue::Text(text) | TemplateValue::Html(text) => output.push_str(text),
+    }
+    rest = after;
+  }
+  output.push_str(rest);
+  Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pretty_assertions::assert_eq;
+
+  #[test]
+  fn test_render_template() -> LemmyResult<()> {
+    let variables = vec![
+      ("name", TemplateValue::Text("<b>joe</b>".to_string())),
+      ("content", TemplateValue::Html("<p>hi</p>".to_string())),
+    ];
+    assert_eq!(
+      "Hello &lt;b&gt;joe&lt;/b&gt;: <p>hi</p>",
+      render_template("Hello {{name}}: {{ content }}", &variables, true)?
+    );
+    assert_eq!(
+      "Hello <b>joe</b>",
+      render_template("Hello {{ name }}", &variables, false)?
+    );
+    assert_eq!(
+      "No variables } {",
+      render_template("No variables } {", &variables, true)?
+    );
+
+    assert!(render_template("{{ unknown }}", &variables, true).is_err());
+    assert!(render_template("{{ name", &variables, true).is_err());
+    Ok(())
+  }
+}
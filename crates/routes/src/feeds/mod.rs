@@ -454,8 +454,10 @@ fn create_reply_and_mention_items(
             lang,
           ))
         }
-        // skip modlog items
-        NotificationData::ModAction(_) => None,
+        // skip modlog, modmail and ban appeal items
+        NotificationData::ModAction(_)
+        | NotificationData::Modmail(_)
+        | NotificationData::BanAppeal(_) => None,
       }
     })
     .collect::<LemmyResult<Vec<Item>>>()?;
@@ -656,6 +658,22 @@ fn create_modlog_items(
           ),
           settings,
         ),
+        ModlogKind::ModAutomodAction => build_modlog_item(
+          r,
+          &modlog_url,
+          if r.target_comment.is_some() {
+            format!(
+              "Automod action on comment {} by {}",
+              &&target_comment_content, &&target_person_name
+            )
+          } else {
+            format!(
+              "Automod action on post {} by {}",
+              &&target_post_name, &&target_person_name
+            )
+          },
+          settings,
+        ),
       }
     })
     .collect::<LemmyResult<Vec<Item>>>()?;
@@ -714,7 +732,8 @@ fn build_item(
     NotificationType::Reply => lang.reply_from_x(creator.name.clone()),
     NotificationType::Subscribed => lang.subscribed().to_string(),
     NotificationType::PrivateMessage => lang.private_message_from_x(creator.name.clone()),
-    NotificationType::ModAction => lang.mod_action().to_string(),
+    NotificationType::ModAction | NotificationType::BanAppeal => lang.mod_action().to_string(),
+    NotificationType::Modmail => format!("Modmail from {}", creator.name),
   };
   Ok(Item {
     title: Some(title),
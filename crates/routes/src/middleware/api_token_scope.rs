@@ -0,0 +1,99 @@
+use actix_web::{
+  Error,
+  HttpMessage,
+  body::MessageBody,
+  dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready},
+};
+use core::future::Ready;
+use lemmy_db_schema_file::enums::ApiTokenScope;
+use lemmy_db_views_local_user::RequiredApiTokenScope;
+use std::future::ready;
+
+/// Sets the scope which API tokens need for the wrapped routes. It is checked when the handler
+/// reads the local user view, so that an inner middleware can override the scope of an outer
+/// one. For optional auth it is checked by the session middleware after the handler ran. Login
+/// tokens are not affected.
+#[derive(Clone, Copy)]
+pub struct ApiTokenScopeMiddleware {
+  read: Option<ApiTokenScope>,
+  write: Option<ApiTokenScope>,
+}
+
+impl ApiTokenScopeMiddleware {
+  /// Reading needs the read scope, and everything else the given scope.
+  pub fn new(scope: ApiTokenScope) -> Self {
+    ApiTokenScopeMiddleware {
+      read: Some(ApiTokenScope::Read),
+      write: Some(scope),
+    }
+  }
+
+  /// All requests need the given scope, including reading.
+  pub fn all(scope: ApiTokenScope) -> Self {
+    ApiTokenScopeMiddleware {
+      read: Some(scope),
+      write: Some(scope),
+    }
+  }
+
+  /// Reading needs the read scope, and other requests are not allowed with API tokens.
+  pub fn read_only() -> Self {
+    ApiTokenScopeMiddleware {
+      read: Some(ApiTokenScope::Read),
+      write: None,
+    }
+  }
+
+  /// The routes can't be used with API tokens at all, for example to manage the account.
+  pub fn deny() -> Self {
+    ApiTokenScopeMiddleware {
+      read: None,
+      write: None,
+    }
+  }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for ApiTokenScopeMiddleware
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+  B: MessageBody,
+{
+  type Response = ServiceResponse<B>;
+  type Error = Error;
+  type Transform = ApiTokenScopeService<S>;
+  type InitError = ();
+  type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+  fn new_transform(&self, service: S) -> Self::Future {
+    ready(Ok(ApiTokenScopeService {
+      service,
+      scopes: *self,
+    }))
+  }
+}
+
+pub struct ApiTokenScopeService<S> {
+  service: S,
+  scopes: ApiTokenScopeMiddleware,
+}
+
+impl<S, B> Service<ServiceRequest> for ApiTokenScopeService<S>
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+{
+  type Response = ServiceResponse<B>;
+  type Error = Error;
+  type Future = S::Future;
+
+  forward_ready!(service);
+
+  fn call(&self, req: ServiceRequest) -> Self::Future {
+    let scope = if req.method().is_safe() {
+      self.scopes.read
+    } else {
+      self.scopes.write
+    };
+    req.extensions_mut().insert(RequiredApiTokenScope(scope));
+    self.service.call(req)
+  }
+}
@@ -9,8 +9,14 @@ use core::future::Ready;
 use futures_util::future::LocalBoxFuture;
 use lemmy_api_utils::{
   context::LemmyContext,
-  utils::{local_user_view_from_jwt, read_auth_token},
+  utils::{
+    API_TOKEN_PREFIX,
+    local_user_view_from_api_token,
+    local_user_view_from_jwt,
+    read_auth_token,
+  },
 };
+use lemmy_db_views_local_user::{RequiredApiTokenScope, impls::check_api_token_scope};
 use std::{future::ready, rc::Rc};
 
 #[derive(Clone)]
@@ -67,10 +73,20 @@ where
     Box::pin(async move {
       let jwt = read_auth_token(req.request())?;
 
-      if let Some(jwt) = &jwt {
-        // Ignore any invalid auth so the site can still be used
-        // This means it is be impossible to get any error message for invalid jwt. Need
-        // to use `/api/v4/account/validate_auth` for that.
+      // Ignore any invalid auth so the site can still be used
+      // This means it is be impossible to get any error message for invalid jwt. Need
+      // to use `/api/v4/account/validate_auth` for that.
+      if let Some(jwt) = &jwt
+        && jwt.starts_with(API_TOKEN_PREFIX)
+      {
+        // The scopes are checked against the route when reading the local user view
+        let auth = local_user_view_from_api_token(jwt, &context).await.ok();
+        if let Some((local_user_view, scopes)) = auth {
+          let mut extensions = req.extensions_mut();
+          extensions.insert(local_user_view);
+          extensions.insert(scopes);
+        }
+      } else if let Some(jwt) = &jwt {
         let local_user_view = local_user_view_from_jwt(jwt, &context).await.ok();
         if let Some(local_user_view) = local_user_view {
           req.extensions_mut().insert(local_user_view);
@@ -79,6 +95,16 @@ where
 
       let mut res = svc.call(req).await?;
 
+      // Handlers with optional auth treat the request as anonymous if the local user view can't
+      // be read. Return the error instead, so that API tokens with a missing scope don't silently
+      // get anonymous results.
+      {
+        let extensions = res.request().extensions();
+        if extensions.get::<RequiredApiTokenScope>().is_some() {
+          check_api_token_scope(&extensions)?;
+        }
+      }
+
       // Add cache-control header if none is present
       if !res.headers().contains_key(CACHE_CONTROL) {
         // If user is authenticated, mark as private. Otherwise cache
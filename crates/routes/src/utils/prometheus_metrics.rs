@@ -1,13 +1,14 @@
 use actix_web::{App, HttpServer, rt::System, web};
 use actix_web_prom::{PrometheusMetrics, PrometheusMetricsBuilder};
 use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::queued_email::QueuedEmail;
 use lemmy_utils::{
   error::{LemmyErrorType, LemmyResult},
   settings::structs::PrometheusConfig,
 };
 use prometheus::{Encoder, Gauge, Opts, TextEncoder, default_registry};
 use std::{sync::Arc, thread};
-use tracing::error;
+use tracing::{error, warn};
 
 /// Creates a middleware that populates http metrics for each path, method, and status code
 pub fn new_prometheus_metrics() -> LemmyResult<PrometheusMetrics> {
@@ -22,6 +23,7 @@ pub fn new_prometheus_metrics() -> LemmyResult<PrometheusMetrics> {
 struct PromContext {
   lemmy: LemmyContext,
   db_pool_metrics: DbPoolMetrics,
+  email_queue_metrics: EmailQueueMetrics,
 }
 
 struct DbPoolMetrics {
@@ -30,10 +32,16 @@ struct DbPoolMetrics {
   available: Gauge,
 }
 
+struct EmailQueueMetrics {
+  pending: Gauge,
+  failed: Gauge,
+}
+
 pub fn serve_prometheus(config: PrometheusConfig, lemmy_context: LemmyContext) -> LemmyResult<()> {
   let context = Arc::new(PromContext {
     lemmy: lemmy_context,
     db_pool_metrics: create_db_pool_metrics()?,
+    email_queue_metrics: create_email_queue_metrics()?,
   });
 
   // spawn thread that blocks on handling requests
@@ -62,6 +70,7 @@ pub fn serve_prometheus(config: PrometheusConfig, lemmy_context: LemmyContext) -
 async fn metrics(context: web::Data<Arc<PromContext>>) -> LemmyResult<String> {
   // collect metrics
   collect_db_pool_metrics(&context);
+  collect_email_queue_metrics(&context).await;
 
   let mut buffer = Vec::new();
   let encoder = TextEncoder::new();
@@ -113,3 +122,34 @@ fn collect_db_pool_metrics(context: &PromContext) {
     .available
     .set(pool_status.available as f64);
 }
+
+// create lemmy_email_queue_* metrics and register them with the default registry
+fn create_email_queue_metrics() -> LemmyResult<EmailQueueMetrics> {
+  let metrics = EmailQueueMetrics {
+    pending: Gauge::with_opts(Opts::new(
+      "lemmy_email_queue_pending",
+      "Number of emails waiting to be sent",
+    ))?,
+    failed: Gauge::with_opts(Opts::new(
+      "lemmy_email_queue_failed",
+      "Number of emails which could not be sent after all retries",
+    ))?,
+  };
+
+  default_registry().register(Box::new(metrics.pending.clone()))?;
+  default_registry().register(Box::new(metrics.failed.clone()))?;
+
+  Ok(metrics)
+}
+
+/// try_from does not support conversion from i64 to f64
+#[expect(clippy::as_conversions)]
+async fn collect_email_queue_metrics(context: &PromContext) {
+  match QueuedEmail::count_pending_and_failed(&mut context.lemmy.pool()).await {
+    Ok((pending, failed)) => {
+      context.email_queue_metrics.pending.set(pending as f64);
+      context.email_queue_metrics.failed.set(failed as f64);
+    }
+    Err(e) => warn!("Failed to count queued emails: {e}"),
+  }
+}
@@ -1,6 +1,6 @@
 use crate::nodeinfo::{NodeInfo, NodeInfoWellKnown};
 use activitypub_federation::config::Data;
-use chrono::{DateTime, TimeZone, Utc};
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
 use clokwerk::{AsyncScheduler, TimeUnits as CTimeUnits};
 use diesel::{
   BoolExpressionMethods,
@@ -16,18 +16,27 @@ use diesel::{
 };
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use diesel_uplete::uplete;
+use lemmy_api_crud::recurring_post::publish::publish_recurring_posts;
 use lemmy_api_utils::{
+  automod::{AutomodContent, run_automod},
   context::LemmyContext,
+  email_digest::send_email_digests,
   plugins::plugin_hook_after,
   send_activity::{ActivityChannel, SendActivityData},
   utils::send_webmention,
+  webhooks::{WebhookData, retry_webhook_deliveries, send_webhooks},
 };
 use lemmy_db_schema::{
   source::{
     community::Community,
     instance::{Instance, InstanceForm},
     local_user::LocalUser,
+    oauth_client::OAuthAuthorizationCode,
+    passkey::PasskeyChallenge,
+    poll::Poll,
     post::{Post, PostUpdateForm},
+    queued_email::QueuedEmail,
+    received_activity_log::ReceivedActivityLog,
   },
   utils::DELETED_REPLACEMENT_TEXT,
 };
@@ -46,6 +55,7 @@ use lemmy_db_schema_file::schema::{
   received_activity,
   sent_activity,
   site,
+  webhook_delivery,
 };
 use lemmy_db_views_site::SiteView;
 use lemmy_diesel_utils::{
@@ -53,6 +63,7 @@ use lemmy_diesel_utils::{
   traits::Crud,
   utils::{functions::coalesce, now},
 };
+use lemmy_email::send_queued_emails;
 use lemmy_utils::{
   DB_BATCH_SIZE,
   error::{LemmyErrorType, LemmyResult},
@@ -66,13 +77,32 @@ pub async fn setup(context: Data<LemmyContext>) -> LemmyResult<()> {
   // https://github.com/mdsherry/clokwerk/issues/38
   let mut scheduler = AsyncScheduler::with_tz(Utc);
 
-  // Every 1 minute run plugin hooks
-  scheduler.every(CTimeUnits::minutes(1)).run(async move || {
-    plugin_hook_after("scheduled_task_1_min", &());
+  let context_1 = context.clone();
+  // Every 1 minute retry failed webhook deliveries and emails, federate poll vote counts, and run
+  // plugin hooks
+  scheduler.every(CTimeUnits::minutes(1)).run(move || {
+    let context = context_1.clone();
+
+    async move {
+      send_poll_updates(&context)
+        .await
+        .inspect_err(|e| warn!("Failed to send poll updates: {e}"))
+        .ok();
+      retry_webhook_deliveries(&context)
+        .await
+        .inspect_err(|e| warn!("Failed to retry webhook deliveries: {e}"))
+        .ok();
+      send_queued_emails(&mut context.pool(), context.settings())
+        .await
+        .inspect_err(|e| warn!("Failed to send queued emails: {e}"))
+        .ok();
+      plugin_hook_after("scheduled_task_1_min", &());
+    }
   });
 
   let context_1 = context.clone();
-  // Every 10 minutes update hot ranks, delete expired captchas and publish scheduled posts
+  // Every 10 minutes update hot ranks, delete expired captchas, publish scheduled and recurring
+  // posts and send email digests
   scheduler.every(CTimeUnits::minutes(10)).run(move || {
     let context = context_1.clone();
 
@@ -85,6 +115,14 @@ pub async fn setup(context: Data<LemmyContext>) -> LemmyResult<()> {
         .await
         .inspect_err(|e| warn!("Failed to publish scheduled posts: {e}"))
         .ok();
+      publish_recurring_posts(&context)
+        .await
+        .inspect_err(|e| warn!("Failed to publish recurring posts: {e}"))
+        .ok();
+      send_email_digests(&context)
+        .await
+        .inspect_err(|e| warn!("Failed to send email digests: {e}"))
+        .ok();
       plugin_hook_after("scheduled_task_10_mins", &());
     }
   });
@@ -95,6 +133,8 @@ pub async fn setup(context: Data<LemmyContext>) -> LemmyResult<()> {
   // - Expired bans
   // - Expired instance blocks
   // - Expired invitations
+  // - Expired OAuth authorization codes and passkey challenges
+  // - Old entries in the inbox log
   scheduler.every(CTimeUnits::hour(1)).run(move || {
     let context = context_1.clone();
 
@@ -115,6 +155,24 @@ pub async fn setup(context: Data<LemmyContext>) -> LemmyResult<()> {
         .await
         .inspect_err(|e| warn!("Failed to delete expired invitations: {e}"))
         .ok();
+      OAuthAuthorizationCode::delete_expired(&mut context.pool())
+        .await
+        .inspect_err(|e| warn!("Failed to delete expired authorization codes: {e}"))
+        .ok();
+      PasskeyChallenge::delete_expired(&mut context.pool())
+        .await
+        .inspect_err(|e| warn!("Failed to delete expired passkey challenges: {e}"))
+        .ok();
+      let max_logged_activities = context
+        .settings()
+        .inbox_log
+        .as_ref()
+        .map(|c| c.max_activities)
+        .unwrap_or_default();
+      ReceivedActivityLog::delete_old(&mut context.pool(), max_logged_activities)
+        .await
+        .inspect_err(|e| warn!("Failed to delete old inbox log entries: {e}"))
+        .ok();
       plugin_hook_after("scheduled_task_1_hour", &());
     }
   });
@@ -127,6 +185,8 @@ pub async fn setup(context: Data<LemmyContext>) -> LemmyResult<()> {
   // - Delete old denied users
   // - Update instance software
   // - Delete old outgoing activities
+  // - Delete old webhook deliveries
+  // - Delete old queued emails
   scheduler.every(CTimeUnits::days(1)).run(move || {
     let context = context_1.reset_request_count();
 
@@ -155,6 +215,14 @@ pub async fn setup(context: Data<LemmyContext>) -> LemmyResult<()> {
         .await
         .inspect_err(|e| warn!("Failed to clear old activities: {e}"))
         .ok();
+      clear_old_webhook_deliveries(&mut context.pool())
+        .await
+        .inspect_err(|e| warn!("Failed to clear old webhook deliveries: {e}"))
+        .ok();
+      QueuedEmail::delete_old(&mut context.pool())
+        .await
+        .inspect_err(|e| warn!("Failed to clear old queued emails: {e}"))
+        .ok();
       plugin_hook_after("scheduled_task_daily", &());
     }
   });
@@ -312,6 +380,19 @@ async fn clear_old_activities(pool: &mut DbPool<'_>) -> LemmyResult<()> {
   Ok(())
 }
 
+/// Clear webhook deliveries which are finished, either successfully or by giving up
+async fn clear_old_webhook_deliveries(pool: &mut DbPool<'_>) -> LemmyResult<()> {
+  let conn = &mut get_conn(pool).await?;
+  diesel::delete(
+    webhook_delivery::table
+      .filter(webhook_delivery::next_attempt_at.is_null())
+      .filter(webhook_delivery::published_at.lt(now() - IntervalDsl::days(7))),
+  )
+  .execute(conn)
+  .await?;
+  Ok(())
+}
+
 async fn delete_old_denied_users(pool: &mut DbPool<'_>) -> LemmyResult<()> {
   LocalUser::delete_old_denied_local_users(pool).await?;
   info!("Done.");
@@ -587,6 +668,23 @@ async fn delete_invitations_when_expired(pool: &mut DbPool<'_>) -> LemmyResult<(
   Ok(())
 }
 
+const POLL_UPDATE_DELAY: TimeDelta = TimeDelta::minutes(1);
+
+/// Sends the changed vote counts of local polls. Votes often arrive in bursts, so a poll is only
+/// updated once its first unfederated vote is [POLL_UPDATE_DELAY] old, together with all votes
+/// since then.
+async fn send_poll_updates(context: &Data<LemmyContext>) -> LemmyResult<()> {
+  let post_ids = Poll::take_pending_updates(&mut context.pool(), POLL_UPDATE_DELAY).await?;
+  for post_id in post_ids {
+    let post = Post::read(&mut context.pool(), post_id).await?;
+    if post.deleted || post.removed {
+      continue;
+    }
+    ActivityChannel::submit_activity(SendActivityData::UpdatePost(post), context)?;
+  }
+  Ok(())
+}
+
 /// Find all unpublished posts with scheduled date in the future, and publish them.
 async fn publish_scheduled_posts(context: &Data<LemmyContext>) -> LemmyResult<()> {
   let pool = &mut context.pool();
@@ -630,6 +728,8 @@ async fn publish_scheduled_posts(context: &Data<LemmyContext>) -> LemmyResult<()
     // send out post via federation and webmention
     let send_activity = SendActivityData::CreatePost(post.clone());
     ActivityChannel::submit_activity(send_activity, context)?;
+    send_webhooks(WebhookData::NewPost(post.id), Some(community.id), context);
+    run_automod(AutomodContent::Post(post.clone()), context);
     send_webmention(post, &community, context.clone());
   }
   Ok(())
@@ -12,6 +12,8 @@ use lemmy_api::sitemap::get_sitemap;
 use lemmy_api_utils::{
   context::LemmyContext,
   plugins::LemmyPlugins,
+  push::init_vapid_key,
+  realtime::listen_for_realtime_events,
   request::client_builder,
   send_activity::ActivityChannel,
   utils::local_site_rate_limit_to_rate_limit_config,
@@ -19,10 +21,17 @@ use lemmy_api_utils::{
 use lemmy_apub::{
   FEDERATION_HTTP_FETCH_LIMIT,
   VerifyUrlData,
-  collections::fetch_community_collections,
+  collections::{fetch_community_collections, import_community_history},
+  http::replay_received_activity,
 };
 use lemmy_apub_activities::handle_outgoing_activities;
-use lemmy_apub_objects::objects::{community::FETCH_COMMUNITY_COLLECTIONS, instance::ApubSite};
+use lemmy_apub_objects::{
+  objects::{
+    community::{FETCH_COMMUNITY_COLLECTIONS, IMPORT_COMMUNITY_HISTORY},
+    instance::ApubSite,
+  },
+  utils::functions::REPLAY_RECEIVED_ACTIVITY,
+};
 use lemmy_apub_send::{Opts, SendManager};
 use lemmy_db_schema::source::secret::Secret;
 use lemmy_db_views_site::SiteView;
@@ -184,6 +193,7 @@ pub async fn start_lemmy_server(args: CmdArgs) -> LemmyResult<()> {
 
   // Initialize the secrets
   let secret = Secret::init(&mut (&pool).into()).await?;
+  let secret = init_vapid_key(secret, &mut (&pool).into()).await?;
 
   // Make sure the local site is set up.
   let site_view = setup_local_site(&mut (&pool).into(), &SETTINGS).await?;
@@ -196,7 +206,7 @@ pub async fn start_lemmy_server(args: CmdArgs) -> LemmyResult<()> {
   // Set up the rate limiter
   let rate_limit_config =
     local_site_rate_limit_to_rate_limit_config(&site_view.local_site_rate_limit);
-  let rate_limit_cell = RateLimit::new(rate_limit_config);
+  let rate_limit_cell = RateLimit::new(rate_limit_config, SETTINGS.rate_limit_redis.as_ref())?;
 
   println!(
     "Starting HTTP server at {}:{}",
@@ -230,7 +240,11 @@ pub async fn start_lemmy_server(args: CmdArgs) -> LemmyResult<()> {
     .debug(cfg!(debug_assertions))
     .http_signature_compat(true)
     .url_verifier(Box::new(VerifyUrlData(context.inner_pool().clone())));
-  if site_view.local_site.federation_signed_fetch {
+  // Instances with authorized fetch usually require the same from others. The federation config
+  // can't be changed later, so changing either setting requires a restart.
+  if site_view.local_site.federation_signed_fetch
+    || site_view.local_site.federation_authorized_fetch
+  {
     let site: ApubSite = site_view.site.clone().into();
     federation_config_builder.signed_fetch_actor(&site);
   }
@@ -239,6 +253,12 @@ pub async fn start_lemmy_server(args: CmdArgs) -> LemmyResult<()> {
   FETCH_COMMUNITY_COLLECTIONS
     .set(fetch_community_collections)
     .map_err(|_e| LemmyErrorType::Unknown("couldnt set function pointer".into()))?;
+  IMPORT_COMMUNITY_HISTORY
+    .set(import_community_history)
+    .map_err(|_e| LemmyErrorType::Unknown("couldnt set function pointer".into()))?;
+  REPLAY_RECEIVED_ACTIVITY
+    .set(replay_received_activity)
+    .map_err(|_e| LemmyErrorType::Unknown("couldnt set function pointer".into()))?;
 
   let request_data = federation_config.to_request_data();
   let outgoing_activities_task =
@@ -254,6 +274,9 @@ pub async fn start_lemmy_server(args: CmdArgs) -> LemmyResult<()> {
       startup_server_handle.stop(true).await;
     }
 
+    // Pushes events from the database to clients connected to this server
+    tokio::task::spawn(listen_for_realtime_events(context.clone()));
+
     Some(create_http_server(
       federation_config.clone(),
       SETTINGS.clone(),
@@ -369,6 +392,11 @@ fn create_http_server(
     app
       .configure(|cfg| lemmy_api_routes::config(cfg, &rate_limit))
       .configure(|cfg| lemmy_api_routes_v3::config(cfg, &rate_limit))
+      .configure(|cfg| {
+        if SETTINGS.mastodon_api {
+          lemmy_api_routes_mastodon::config(cfg, &rate_limit);
+        }
+      })
       .configure(|cfg| {
         if site_view.local_site.federation_enabled {
           lemmy_apub::http::routes::config(cfg);
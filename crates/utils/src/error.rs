@@ -111,6 +111,16 @@ pub enum LemmyErrorType {
   OauthAuthorizationInvalid,
   OauthLoginFailed,
   OauthRegistrationClosed,
+  /// The OAuth client is unknown, or its secret is wrong.
+  OauthInvalidClient,
+  /// The authorization code or refresh token is invalid or expired, or was issued to another
+  /// client.
+  OauthInvalidGrant,
+  /// The authorization request is missing a parameter or has an invalid redirect uri or PKCE
+  /// challenge.
+  OauthInvalidRequest,
+  OauthInvalidScope,
+  OauthUnsupportedGrantType,
   NotFound,
   PostScheduleTimeMustBeInFuture,
   TooManyScheduledPosts,
@@ -124,6 +134,54 @@ pub enum LemmyErrorType {
   MultiCommunityEntryLimitReached,
   TooManyRequests,
   ResolveObjectFailed(String),
+  /// A poll needs between 2 and 20 unique choices, each up to 200 characters long.
+  InvalidPollChoices,
+  PollEnded,
+  AlreadyVotedInPoll,
+  InvalidPollVote,
+  /// Automod rules are only available for local communities. They need at least one condition, and
+  /// the field which is required by their action.
+  InvalidAutomodRule,
+  /// The post, comment or mod action linked to a modmail thread belongs to another community.
+  InvalidModmailThread,
+  /// Only active bans from the site or from a local community can be appealed, and each appeal can
+  /// only be resolved once.
+  InvalidBanAppeal,
+  /// A draft needs exactly one of a community, post or recipient, and a parent comment only
+  /// together with its post.
+  InvalidDraft,
+  /// Recurring posts are only available for local communities, and monthly ones need to start on
+  /// one of the first 28 days of a month.
+  InvalidRecurringPost,
+  /// API tokens need at least one scope, and can't be used to manage the account.
+  InvalidApiToken,
+  /// The API token which was used for this request doesn't have the necessary scope.
+  MissingApiTokenScope,
+  /// The passkey response couldn't be verified or its challenge has expired, or the name of the
+  /// passkey is invalid.
+  InvalidPasskey,
+  /// The user has passkeys, so a passkey is needed as second factor for password login.
+  MissingPasskey,
+  /// The site requires moderators and admins to set up TOTP or a passkey before logging in.
+  TwoFactorRequired,
+  /// The web push subscription needs an https endpoint and valid keys.
+  InvalidPushSubscription,
+  /// The link from a digest email to stop notification emails is invalid.
+  InvalidUnsubscribeToken,
+  /// The email template has a syntax error or uses an unknown variable.
+  InvalidEmailTemplate(String),
+  /// The new account for an account move doesn't list the old account as alias, or one of the
+  /// accounts was already moved.
+  InvalidAccountMove,
+  /// The new community for a community move doesn't list the old community as alias, or one of
+  /// the communities was already moved.
+  InvalidCommunityMove,
+  /// The logged activity wasn't stored in full or its signature wasn't verified, so it can't be
+  /// replayed.
+  CantReplayActivity,
+  /// The request has no equivalent in Lemmy, for example following a user or creating a status
+  /// which isn't a reply.
+  UnsupportedMastodonRequest,
   #[serde(untagged)]
   #[cfg_attr(feature = "ts-rs", ts(skip))]
   UntranslatedError(Option<UntranslatedError>),
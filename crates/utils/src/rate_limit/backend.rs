@@ -1,65 +1,50 @@
-//! The content in this file is mostly copy-pasted from library code:
-//! https://github.com/jacob-pro/actix-extensible-rate-limit/blob/master/src/backend/memory.rs
-
-use crate::rate_limit::{ActionType, BucketConfig, input::LemmyInput};
-use actix_extensible_rate_limit::backend::{
-  Backend,
-  Decision,
-  SimpleOutput,
-  memory::DEFAULT_GC_INTERVAL_SECONDS,
+use crate::{
+  error::LemmyResult,
+  rate_limit::{ActionType, BucketConfig, input::LemmyInput},
 };
-use actix_web::rt::{task::JoinHandle, time::Instant};
-use dashmap::DashMap;
+use actix_extensible_rate_limit::backend::{Backend, Decision, SimpleOutput};
+use actix_web::rt::time::Instant;
 use enum_map::EnumMap;
+use futures::future::BoxFuture;
 use std::{
   convert::Infallible,
   sync::{Arc, RwLock},
   time::Duration,
 };
+use tracing::warn;
+
+/// Storage for the rate limit buckets. Each bucket counts the requests for one [LemmyInput] within
+/// a fixed time window.
+pub trait RateLimitStore: Send + Sync {
+  /// Counts a request. Returns the number of requests in the current window, and the time when
+  /// the window ends. A new window of the given length is started if there is none.
+  fn increment(
+    &self,
+    input: LemmyInput,
+    interval: Duration,
+  ) -> BoxFuture<'_, LemmyResult<(u64, Instant)>>;
+
+  /// Removes a request from the current window again.
+  fn decrement(&self, input: LemmyInput) -> BoxFuture<'_, LemmyResult<()>>;
+}
 
-/// A Fixed Window rate limiter [Backend] that uses [Dashmap](dashmap::DashMap) to store keys
-/// in memory.
+/// A Fixed Window rate limiter [Backend] which keeps its buckets in a [RateLimitStore].
 #[derive(Clone)]
 pub struct LemmyBackend {
-  map: Arc<DashMap<LemmyInput, Value>>,
-  gc_handle: Option<Arc<JoinHandle<()>>>,
+  store: Arc<dyn RateLimitStore>,
   pub(super) configs: Arc<RwLock<EnumMap<ActionType, BucketConfig>>>,
 }
 
-struct Value {
-  ttl: Instant,
-  count: u64,
-}
-
 impl LemmyBackend {
-  pub(crate) fn new(configs: EnumMap<ActionType, BucketConfig>, enable_gc: bool) -> Self {
-    let map = Arc::new(DashMap::<LemmyInput, Value>::new());
-    let gc_handle = enable_gc.then(|| {
-      Arc::new(LemmyBackend::garbage_collector(
-        map.clone(),
-        Duration::from_secs(DEFAULT_GC_INTERVAL_SECONDS),
-      ))
-    });
+  pub(crate) fn new(
+    configs: EnumMap<ActionType, BucketConfig>,
+    store: Arc<dyn RateLimitStore>,
+  ) -> Self {
     LemmyBackend {
-      map,
-      gc_handle,
+      store,
       configs: Arc::new(RwLock::new(configs)),
     }
   }
-
-  fn garbage_collector(map: Arc<DashMap<LemmyInput, Value>>, interval: Duration) -> JoinHandle<()> {
-    assert!(
-      interval.as_secs_f64() > 0f64,
-      "GC interval must be non-zero"
-    );
-    tokio::spawn(async move {
-      loop {
-        let now = Instant::now();
-        map.retain(|_k, v| v.ttl > now);
-        tokio::time::sleep_until(now + interval).await;
-      }
-    })
-  }
 }
 
 impl Backend<LemmyInput> for LemmyBackend {
@@ -67,7 +52,6 @@ impl Backend<LemmyInput> for LemmyBackend {
   type RollbackToken = LemmyInput;
   type Error = Infallible;
 
-  #[expect(clippy::expect_used)]
   async fn request(
     &self,
     input: LemmyInput,
@@ -78,31 +62,14 @@ impl Backend<LemmyInput> for LemmyBackend {
     let max_requests: u64 = config.max_requests.into();
     let interval = Duration::from_secs(config.interval.into());
 
-    let now = Instant::now();
-    let mut count = 1;
-    let mut expiry = now
-      .checked_add(interval)
-      .expect("Interval unexpectedly large");
-    self
-      .map
-      .entry(input)
-      .and_modify(|v| {
-        // If this bucket hasn't yet expired, increment and extract the count/expiry
-        if v.ttl > now {
-          v.count += 1;
-          count = v.count;
-          expiry = v.ttl;
-        } else {
-          // If this bucket has expired we will reset the count to 1 and set a new TTL.
-          v.ttl = expiry;
-          v.count = count;
-        }
-      })
-      .or_insert_with(|| Value {
-        // If the bucket doesn't exist, create it with a count of 1, and set the TTL.
-        ttl: expiry,
-        count,
-      });
+    let (count, expiry) = match self.store.increment(input, interval).await {
+      Ok(bucket) => bucket,
+      Err(e) => {
+        // Better let requests through than reject all of them while the store is unavailable
+        warn!("Failed to update rate limit: {e}");
+        (1, Instant::now() + interval)
+      }
+    };
     let allow = count <= max_requests;
     let output = SimpleOutput {
       limit: max_requests,
@@ -113,28 +80,20 @@ impl Backend<LemmyInput> for LemmyBackend {
   }
 
   async fn rollback(&self, token: Self::RollbackToken) -> Result<(), Self::Error> {
-    self.map.entry(token).and_modify(|v| {
-      v.count = v.count.saturating_sub(1);
-    });
+    self
+      .store
+      .decrement(token)
+      .await
+      .inspect_err(|e| warn!("Failed to roll back rate limit: {e}"))
+      .ok();
     Ok(())
   }
 }
 
-impl Drop for LemmyBackend {
-  fn drop(&mut self) {
-    if let Some(handle) = &self.gc_handle {
-      handle.abort();
-    }
-  }
-}
-
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::{
-    error::LemmyResult,
-    rate_limit::{ActionType, input::raw_ip_key},
-  };
+  use crate::rate_limit::{ActionType, input::raw_ip_key, memory_store::MemoryStore};
   use enum_map::enum_map;
 
   const MINUTE_SECS: u32 = 60;
@@ -173,10 +132,18 @@ mod tests {
     }
   }
 
+  fn memory_backend(
+    configs: EnumMap<ActionType, BucketConfig>,
+    enable_gc: bool,
+  ) -> (LemmyBackend, Arc<MemoryStore>) {
+    let store = Arc::new(MemoryStore::new(enable_gc));
+    (LemmyBackend::new(configs, store.clone()), store)
+  }
+
   #[actix_web::test]
   async fn test_allow_deny() -> LemmyResult<()> {
     tokio::time::pause();
-    let backend = LemmyBackend::new(test_config(MINUTE_SECS, 5), true);
+    let (backend, _) = memory_backend(test_config(MINUTE_SECS, 5), true);
     let key = raw_ip_key(Some("127.0.0.2"));
     let input = LemmyInput(key, ActionType::Message);
     for _ in 0..5 {
@@ -193,7 +160,7 @@ mod tests {
   #[actix_web::test]
   async fn test_reset() -> LemmyResult<()> {
     tokio::time::pause();
-    let backend = LemmyBackend::new(test_config(MINUTE_SECS, 1), false);
+    let (backend, store) = memory_backend(test_config(MINUTE_SECS, 1), false);
     let input = LemmyInput(raw_ip_key(Some("127.0.0.3")), ActionType::Message);
     // Make first request, should be allowed
     let (decision, _, _) = backend.request(input).await?;
@@ -204,7 +171,7 @@ mod tests {
     // Advance time and try again, should now be allowed
     tokio::time::advance(MINUTE).await;
     // We want to be sure the key hasn't been garbage collected, and we are testing the expiry logic
-    assert!(backend.map.contains_key(&input));
+    assert!(store.map.contains_key(&input));
     let (decision, _, _) = backend.request(input).await?;
     assert!(decision.is_allowed());
     Ok(())
@@ -213,25 +180,25 @@ mod tests {
   #[actix_web::test]
   async fn test_garbage_collection() -> LemmyResult<()> {
     tokio::time::pause();
-    let backend = LemmyBackend::new(test_config(MINUTE_SECS, 1), true);
+    let (backend, store) = memory_backend(test_config(MINUTE_SECS, 1), true);
     let key1 = LemmyInput(raw_ip_key(Some("127.0.0.4")), ActionType::Message);
     let key2 = LemmyInput(raw_ip_key(Some("127.0.0.5")), ActionType::Post);
     backend.request(key1).await?;
     backend.request(key2).await?;
-    assert!(backend.map.contains_key(&key1));
-    assert!(backend.map.contains_key(&key2));
+    assert!(store.map.contains_key(&key1));
+    assert!(store.map.contains_key(&key2));
     // Advance time such that the garbage collector runs,
     // expired KEY1 should be cleaned, but KEY2 should remain.
     tokio::time::advance(MINUTE).await;
-    assert!(!backend.map.contains_key(&key1));
-    assert!(backend.map.contains_key(&key2));
+    assert!(!store.map.contains_key(&key1));
+    assert!(store.map.contains_key(&key2));
     Ok(())
   }
 
   #[actix_web::test]
   async fn test_output() -> LemmyResult<()> {
     tokio::time::pause();
-    let backend = LemmyBackend::new(test_config(MINUTE_SECS, 2), true);
+    let (backend, _) = memory_backend(test_config(MINUTE_SECS, 2), true);
     let key = raw_ip_key(Some("127.0.0.6"));
     let input = LemmyInput(key, ActionType::Message);
     // First of 2 should be allowed.
@@ -258,7 +225,7 @@ mod tests {
   #[actix_web::test]
   async fn test_rollback() -> LemmyResult<()> {
     tokio::time::pause();
-    let backend = LemmyBackend::new(test_config(MINUTE_SECS, 5), true);
+    let (backend, _) = memory_backend(test_config(MINUTE_SECS, 5), true);
     let key = raw_ip_key(Some("127.0.0.7"));
     let input = LemmyInput(key, ActionType::Message);
     let (_, output, rollback) = backend.request(input).await?;
@@ -1,5 +1,6 @@
 use crate::rate_limit::ActionType;
 use std::{
+  fmt::{Display, Formatter},
   future::Ready,
   net::{IpAddr, Ipv4Addr, SocketAddr},
   str::FromStr,
@@ -8,6 +9,13 @@ use std::{
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub struct LemmyInput(pub(crate) RateLimitIpAddr, pub(crate) ActionType);
 
+/// A unique key for the bucket, consisting of action type and ip address.
+impl Display for LemmyInput {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}:{}", self.1, self.0)
+  }
+}
+
 pub(crate) type LemmyInputFuture = Ready<Result<LemmyInput, actix_web::Error>>;
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
@@ -16,6 +24,15 @@ pub(crate) enum RateLimitIpAddr {
   V6([u16; 4]),
 }
 
+impl Display for RateLimitIpAddr {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      RateLimitIpAddr::V4(addr) => write!(f, "{addr}"),
+      RateLimitIpAddr::V6([a, b, c, d]) => write!(f, "{a:x}:{b:x}:{c:x}:{d:x}::/64"),
+    }
+  }
+}
+
 #[expect(clippy::expect_used)]
 impl From<IpAddr> for RateLimitIpAddr {
   fn from(value: IpAddr) -> Self {
@@ -69,4 +86,15 @@ mod tests {
     );
     Ok(())
   }
+
+  #[test]
+  fn test_input_key() {
+    let input = LemmyInput(raw_ip_key(Some("142.250.187.206")), ActionType::Post);
+    assert_eq!("Post:142.250.187.206", input.to_string());
+    let input = LemmyInput(
+      raw_ip_key(Some("2a00:1450:4009:81f::200e")),
+      ActionType::Message,
+    );
+    assert_eq!("Message:2a00:1450:4009:81f::/64", input.to_string());
+  }
 }
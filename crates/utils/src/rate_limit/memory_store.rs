@@ -0,0 +1,102 @@
+//! The content in this file is mostly copy-pasted from library code:
+//! https://github.com/jacob-pro/actix-extensible-rate-limit/blob/master/src/backend/memory.rs
+
+use crate::{
+  error::LemmyResult,
+  rate_limit::{backend::RateLimitStore, input::LemmyInput},
+};
+use actix_extensible_rate_limit::backend::memory::DEFAULT_GC_INTERVAL_SECONDS;
+use actix_web::rt::{task::JoinHandle, time::Instant};
+use dashmap::DashMap;
+use futures::future::BoxFuture;
+use std::{sync::Arc, time::Duration};
+
+/// A [RateLimitStore] that uses [Dashmap](dashmap::DashMap) to store keys in memory. The limits
+/// only apply to the current process.
+pub(crate) struct MemoryStore {
+  pub(super) map: Arc<DashMap<LemmyInput, Value>>,
+  gc_handle: Option<JoinHandle<()>>,
+}
+
+pub(super) struct Value {
+  ttl: Instant,
+  count: u64,
+}
+
+impl MemoryStore {
+  pub(crate) fn new(enable_gc: bool) -> Self {
+    let map = Arc::new(DashMap::<LemmyInput, Value>::new());
+    let gc_handle = enable_gc.then(|| {
+      MemoryStore::garbage_collector(
+        map.clone(),
+        Duration::from_secs(DEFAULT_GC_INTERVAL_SECONDS),
+      )
+    });
+    MemoryStore { map, gc_handle }
+  }
+
+  fn garbage_collector(map: Arc<DashMap<LemmyInput, Value>>, interval: Duration) -> JoinHandle<()> {
+    assert!(
+      interval.as_secs_f64() > 0f64,
+      "GC interval must be non-zero"
+    );
+    tokio::spawn(async move {
+      loop {
+        let now = Instant::now();
+        map.retain(|_k, v| v.ttl > now);
+        tokio::time::sleep_until(now + interval).await;
+      }
+    })
+  }
+}
+
+impl RateLimitStore for MemoryStore {
+  #[expect(clippy::expect_used)]
+  fn increment(
+    &self,
+    input: LemmyInput,
+    interval: Duration,
+  ) -> BoxFuture<'_, LemmyResult<(u64, Instant)>> {
+    let now = Instant::now();
+    let mut count = 1;
+    let mut expiry = now
+      .checked_add(interval)
+      .expect("Interval unexpectedly large");
+    self
+      .map
+      .entry(input)
+      .and_modify(|v| {
+        // If this bucket hasn't yet expired, increment and extract the count/expiry
+        if v.ttl > now {
+          v.count += 1;
+          count = v.count;
+          expiry = v.ttl;
+        } else {
+          // If this bucket has expired we will reset the count to 1 and set a new TTL.
+          v.ttl = expiry;
+          v.count = count;
+        }
+      })
+      .or_insert_with(|| Value {
+        // If the bucket doesn't exist, create it with a count of 1, and set the TTL.
+        ttl: expiry,
+        count,
+      });
+    Box::pin(async move { Ok((count, expiry)) })
+  }
+
+  fn decrement(&self, input: LemmyInput) -> BoxFuture<'_, LemmyResult<()>> {
+    self.map.entry(input).and_modify(|v| {
+      v.count = v.count.saturating_sub(1);
+    });
+    Box::pin(async { Ok(()) })
+  }
+}
+
+impl Drop for MemoryStore {
+  fn drop(&mut self) {
+    if let Some(handle) = &self.gc_handle {
+      handle.abort();
+    }
+  }
+}
@@ -1,15 +1,26 @@
-use crate::rate_limit::{
-  backend::LemmyBackend,
-  input::{LemmyInput, LemmyInputFuture, raw_ip_key},
+use crate::{
+  error::LemmyResult,
+  rate_limit::{
+    backend::LemmyBackend,
+    input::{LemmyInputFuture, raw_ip_key},
+    memory_store::MemoryStore,
+    redis_store::RedisStore,
+  },
+  settings::structs::RateLimitRedisConfig,
 };
 use actix_extensible_rate_limit::{RateLimiter, backend::SimpleOutput};
 use actix_web::dev::ServiceRequest;
 use enum_map::{EnumMap, enum_map};
-use std::future::ready;
+use std::{future::ready, sync::Arc};
 use strum::{AsRefStr, Display};
 
 mod backend;
 mod input;
+mod memory_store;
+mod redis_store;
+
+pub use backend::RateLimitStore;
+pub use input::LemmyInput;
 
 #[derive(Debug, enum_map::Enum, Copy, Clone, Display, AsRefStr, Eq, PartialEq, Hash)]
 pub enum ActionType {
@@ -34,14 +45,29 @@ pub struct RateLimit {
 }
 
 impl RateLimit {
-  pub fn new(configs: EnumMap<ActionType, BucketConfig>) -> Self {
+  /// Keeps the rate limits in Redis if it is configured, and otherwise in memory.
+  pub fn new(
+    configs: EnumMap<ActionType, BucketConfig>,
+    redis_config: Option<&RateLimitRedisConfig>,
+  ) -> LemmyResult<Self> {
+    let store: Arc<dyn RateLimitStore> = match redis_config {
+      Some(redis_config) => Arc::new(RedisStore::new(redis_config)?),
+      None => Arc::new(MemoryStore::new(true)),
+    };
+    Ok(Self::with_store(configs, store))
+  }
+
+  pub fn with_store(
+    configs: EnumMap<ActionType, BucketConfig>,
+    store: Arc<dyn RateLimitStore>,
+  ) -> Self {
     Self {
-      backend: LemmyBackend::new(configs, true),
+      backend: LemmyBackend::new(configs, store),
     }
   }
 
   pub fn with_debug_config() -> Self {
-    Self::new(enum_map! {
+    let configs = enum_map! {
       ActionType::Message => BucketConfig {
         max_requests: 180,
         interval: 60,
@@ -70,7 +96,8 @@ impl RateLimit {
         max_requests: 1,
         interval: 24 * 60 * 60,
       },
-    })
+    };
+    Self::with_store(configs, Arc::new(MemoryStore::new(true)))
   }
 
   #[expect(clippy::expect_used)]
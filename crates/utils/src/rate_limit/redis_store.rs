@@ -0,0 +1,179 @@
+use crate::{
+  error::LemmyResult,
+  rate_limit::{backend::RateLimitStore, input::LemmyInput},
+  settings::structs::RateLimitRedisConfig,
+};
+use actix_web::rt::time::Instant;
+use futures::future::BoxFuture;
+use redis::{Client, Script, aio::ConnectionManager};
+use std::{sync::LazyLock, time::Duration};
+use tokio::sync::OnceCell;
+
+/// Increments the counter, and starts a new window if the key doesn't exist or has no expiry.
+/// Returns the new count and the remaining milliseconds of the window.
+static INCREMENT: LazyLock<Script> = LazyLock::new(|| {
+  Script::new(
+    r"
+    local count = redis.call('INCR', KEYS[1])
+    if count == 1 or redis.call('PTTL', KEYS[1]) < 0 then
+      redis.call('PEXPIRE', KEYS[1], ARGV[1])
+    end
+    return {count, redis.call('PTTL', KEYS[1])}
+    ",
+  )
+});
+
+/// Decrements the counter, unless the window has ended already.
+static DECREMENT: LazyLock<Script> = LazyLock::new(|| {
+  Script::new(
+    r"
+    if redis.call('EXISTS', KEYS[1]) == 1 then
+      redis.call('DECR', KEYS[1])
+    end
+    ",
+  )
+});
+
+/// A [RateLimitStore] which keeps the buckets in Redis or Valkey, so that the limits are shared
+/// between all Lemmy processes which use the same server.
+pub(crate) struct RedisStore {
+  client: Client,
+  /// Connecting is async, so it happens on the first request.
+  connection: OnceCell<ConnectionManager>,
+  key_prefix: String,
+}
+
+impl RedisStore {
+  pub(crate) fn new(config: &RateLimitRedisConfig) -> LemmyResult<Self> {
+    Ok(RedisStore {
+      client: Client::open(config.url.as_str())?,
+      connection: OnceCell::new(),
+      key_prefix: config.key_prefix.clone(),
+    })
+  }
+
+  async fn connection(&self) -> LemmyResult<ConnectionManager> {
+    let connection = self
+      .connection
+      .get_or_try_init(|| self.client.get_connection_manager())
+      .await?;
+    Ok(connection.clone())
+  }
+
+  fn key(&self, input: LemmyInput) -> String {
+    format!("{}:{input}", self.key_prefix)
+  }
+}
+
+impl RateLimitStore for RedisStore {
+  fn increment(
+    &self,
+    input: LemmyInput,
+    interval: Duration,
+  ) -> BoxFuture<'_, LemmyResult<(u64, Instant)>> {
+    Box::pin(async move {
+      let mut connection = self.connection().await?;
+      let (count, ttl): (u64, i64) = INCREMENT
+        .key(self.key(input))
+        .arg(u64::try_from(interval.as_millis())?)
+        .invoke_async(&mut connection)
+        .await?;
+      let ttl = Duration::from_millis(u64::try_from(ttl).unwrap_or_default());
+      Ok((count, Instant::now() + ttl))
+    })
+  }
+
+  fn decrement(&self, input: LemmyInput) -> BoxFuture<'_, LemmyResult<()>> {
+    Box::pin(async move {
+      let mut connection = self.connection().await?;
+      DECREMENT
+        .key(self.key(input))
+        .invoke_async::<()>(&mut connection)
+        .await?;
+      Ok(())
+    })
+  }
+}
+
+/// These tests need a Redis compatible server, whose URL is given with the environment variable
+/// `LEMMY_TEST_REDIS_URL`, for example `redis://localhost:6379`. They are skipped otherwise.
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rate_limit::{ActionType, input::raw_ip_key};
+  use chrono::Utc;
+  use std::env;
+
+  fn test_store() -> LemmyResult<Option<RedisStore>> {
+    let Ok(url) = env::var("LEMMY_TEST_REDIS_URL") else {
+      return Ok(None);
+    };
+    // Use new keys for each run, so that earlier runs don't affect the counts
+    let config = RateLimitRedisConfig {
+      url,
+      key_prefix: format!("lemmy_rate_limit_test_{}", Utc::now().timestamp_micros()),
+    };
+    Ok(Some(RedisStore::new(&config)?))
+  }
+
+  #[tokio::test]
+  async fn test_redis_increment() -> LemmyResult<()> {
+    let Some(store) = test_store()? else {
+      return Ok(());
+    };
+    let interval = Duration::from_secs(60);
+    let input = LemmyInput(raw_ip_key(Some("127.0.0.2")), ActionType::Message);
+    let other_input = LemmyInput(raw_ip_key(Some("127.0.0.2")), ActionType::Post);
+
+    let (count, reset) = store.increment(input, interval).await?;
+    assert_eq!(1, count);
+    assert!(reset <= Instant::now() + interval);
+    let (count, second_reset) = store.increment(input, interval).await?;
+    assert_eq!(2, count);
+    // The window is not extended by later requests
+    assert!(second_reset <= reset);
+
+    // Other action types have their own bucket
+    let (count, _) = store.increment(other_input, interval).await?;
+    assert_eq!(1, count);
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_redis_decrement() -> LemmyResult<()> {
+    let Some(store) = test_store()? else {
+      return Ok(());
+    };
+    let interval = Duration::from_secs(60);
+    let input = LemmyInput(raw_ip_key(Some("127.0.0.3")), ActionType::Comment);
+
+    store.increment(input, interval).await?;
+    store.increment(input, interval).await?;
+    store.decrement(input).await?;
+    let (count, _) = store.increment(input, interval).await?;
+    assert_eq!(2, count);
+
+    // Rolling back a request without a window doesn't create one
+    let other_input = LemmyInput(raw_ip_key(Some("127.0.0.4")), ActionType::Comment);
+    store.decrement(other_input).await?;
+    let (count, _) = store.increment(other_input, interval).await?;
+    assert_eq!(1, count);
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_redis_window_expires() -> LemmyResult<()> {
+    let Some(store) = test_store()? else {
+      return Ok(());
+    };
+    let interval = Duration::from_millis(100);
+    let input = LemmyInput(raw_ip_key(Some("127.0.0.5")), ActionType::Search);
+
+    store.increment(input, interval).await?;
+    store.increment(input, interval).await?;
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    let (count, _) = store.increment(input, interval).await?;
+    assert_eq!(1, count);
+    Ok(())
+  }
+}
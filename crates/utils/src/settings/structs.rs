@@ -42,6 +42,10 @@ pub struct Settings {
   #[doku(skip)]
   pub opentelemetry_url: Option<Url>,
   pub federation: FederationWorkerConfig,
+  /// Store incoming activities together with the result of processing them, so that admins can
+  /// inspect and replay them. Disabled by default.
+  #[doku(example = "Some(Default::default())")]
+  pub inbox_log: Option<InboxLogConfig>,
   // Prometheus configuration.
   #[doku(example = "Some(Default::default())")]
   pub prometheus: Option<PrometheusConfig>,
@@ -52,8 +56,17 @@ pub struct Settings {
   cors_origin: Vec<String>,
   /// Print logs in JSON format. You can also disable ANSI colors in logs with env var `NO_COLOR`.
   pub json_logging: bool,
+  /// Serve a subset of the Mastodon client API under `/api/v1`, so that Mastodon apps can be used
+  /// to browse, vote, reply and follow communities. There is no Mastodon OAuth flow, so users need
+  /// to create an API token and enter it in apps which allow that.
+  pub mastodon_api: bool,
   /// Data for loading Lemmy plugins
   pub plugins: Vec<PluginSettings>,
+  /// Store rate limits in Redis or Valkey instead of in memory. This is necessary when running
+  /// multiple Lemmy processes behind a load balancer, as otherwise each process enforces the
+  /// limits separately.
+  #[doku(example = "Some(Default::default())")]
+  pub rate_limit_redis: Option<RateLimitRedisConfig>,
 }
 
 impl Settings {
@@ -149,6 +162,19 @@ pub struct PrometheusConfig {
   pub port: u16,
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone, SmartDefault, Document)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimitRedisConfig {
+  /// Connection URL of the Redis or Valkey server
+  #[default("redis://localhost:6379")]
+  #[doku(example = "redis://localhost:6379")]
+  pub url: String,
+  /// Prefix for the rate limit keys, so that multiple instances can use the same server
+  #[default("lemmy_rate_limit")]
+  #[doku(example = "lemmy_rate_limit")]
+  pub key_prefix: String,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, SmartDefault, Document)]
 #[serde(default, deny_unknown_fields)]
 // named federation"worker"config to disambiguate from the activitypub library configuration
@@ -160,6 +186,19 @@ pub struct FederationWorkerConfig {
   pub concurrent_sends_per_instance: i8,
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone, SmartDefault, Document)]
+#[serde(default, deny_unknown_fields)]
+pub struct InboxLogConfig {
+  /// Maximum number of activities to keep, older ones are deleted every few minutes
+  #[default(10000)]
+  #[doku(example = "10000")]
+  pub max_activities: i64,
+  /// Content of activities larger than this many bytes is not stored
+  #[default(65536)]
+  #[doku(example = "65536")]
+  pub max_activity_size: usize,
+}
+
 /// See the extism docs for more details: https://extism.org/docs/concepts/manifest
 #[derive(Debug, Deserialize, Serialize, Clone, SmartDefault, Document)]
 #[serde(default, deny_unknown_fields)]
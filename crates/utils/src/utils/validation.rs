@@ -27,6 +27,9 @@ const MIN_LENGTH_BLOCKING_KEYWORD: usize = 3;
 const MAX_LENGTH_BLOCKING_KEYWORD: usize = 50;
 const ACTOR_NAME_MAX_LENGTH: usize = 20;
 pub const DISPLAY_NAME_MAX_LENGTH: usize = 50;
+const POLL_CHOICES_MAX: usize = 20;
+const POLL_CHOICE_MAX_LENGTH: usize = 200;
+const ALSO_KNOWN_AS_MAX: usize = 10;
 
 fn has_newline(name: &str) -> bool {
   name.contains('\n')
@@ -100,6 +103,22 @@ pub fn is_valid_post_title(title: &str) -> LemmyResult<()> {
   }
 }
 
+/// Checks that a poll has at least two choices, which are unique and not too long.
+pub fn is_valid_poll_choices(choices: &[String]) -> LemmyResult<()> {
+  let valid_choice = |c: &String| {
+    let length = c.trim().chars().count();
+    (1..=POLL_CHOICE_MAX_LENGTH).contains(&length) && !has_newline(c)
+  };
+  let check = (2..=POLL_CHOICES_MAX).contains(&choices.len())
+    && choices.iter().all(valid_choice)
+    && choices.iter().map(|c| c.trim()).all_unique();
+  if !check {
+    Err(LemmyErrorType::InvalidPollChoices.into())
+  } else {
+    Ok(())
+  }
+}
+
 /// This could be post bodies, comments, notes, or any description field
 pub fn is_valid_body_field(body: &str, post: bool) -> LemmyResult<()> {
   if post {
@@ -243,6 +262,14 @@ pub fn check_blocking_keywords_are_valid(blocking_keywords: &Vec<String>) -> Lem
   Ok(())
 }
 
+/// Limits the number of aliases which can be set for a person or community.
+pub fn check_also_known_as_count(len: usize) -> LemmyResult<()> {
+  if len > ALSO_KNOWN_AS_MAX {
+    return Err(LemmyErrorType::TooManyItems.into());
+  }
+  Ok(())
+}
+
 fn build_url_str_without_scheme(url_str: &str) -> LemmyResult<String> {
   // Parse and check for errors
   let mut url = Url::parse(url_str).or_else(|e| {
@@ -334,10 +361,23 @@ mod tests {
   use crate::{
     error::{LemmyErrorType, LemmyResult},
     utils::validation::{
-      BIO_MAX_LENGTH, SITE_NAME_MAX_LENGTH, SITE_SUMMARY_MAX_LENGTH, URL_MAX_LENGTH,
-      build_and_check_regex, check_urls_are_valid, is_url_blocked, is_valid_actor_name,
-      is_valid_bio_field, is_valid_display_name, is_valid_matrix_id, is_valid_post_title,
-      is_valid_url, site_name_length_check, summary_length_check, truncate_for_db,
+      BIO_MAX_LENGTH,
+      SITE_NAME_MAX_LENGTH,
+      SITE_SUMMARY_MAX_LENGTH,
+      URL_MAX_LENGTH,
+      build_and_check_regex,
+      check_urls_are_valid,
+      is_url_blocked,
+      is_valid_actor_name,
+      is_valid_bio_field,
+      is_valid_display_name,
+      is_valid_matrix_id,
+      is_valid_poll_choices,
+      is_valid_post_title,
+      is_valid_url,
+      site_name_length_check,
+      summary_length_check,
+      truncate_for_db,
     },
   };
   use pretty_assertions::assert_eq;
@@ -412,6 +452,19 @@ Line3",
     assert!(is_valid_post_title("\u{1f3f3}\u{fe0f}\u{200d}\u{26a7}\u{fe0f}").is_ok());
   }
 
+  #[test]
+  fn test_valid_poll_choices() {
+    let choices = |c: &[&str]| c.iter().map(ToString::to_string).collect::<Vec<_>>();
+    assert!(is_valid_poll_choices(&choices(&["yes", "no"])).is_ok());
+    assert!(is_valid_poll_choices(&choices(&["yes"])).is_err());
+    assert!(is_valid_poll_choices(&choices(&["yes", " yes "])).is_err());
+    assert!(is_valid_poll_choices(&choices(&["yes", "  "])).is_err());
+    assert!(is_valid_poll_choices(&choices(&["yes", "n\no"])).is_err());
+    assert!(is_valid_poll_choices(&choices(&["yes", &"n".repeat(201)])).is_err());
+    let too_many = (0..21).map(|i| i.to_string()).collect::<Vec<_>>();
+    assert!(is_valid_poll_choices(&too_many).is_err());
+  }
+
   #[test]
   fn test_valid_matrix_id() {
     assert!(is_valid_matrix_id("@dess:matrix.org").is_ok());
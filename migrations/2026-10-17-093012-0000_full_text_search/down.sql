@@ -0,0 +1,7 @@
+ALTER TABLE local_site
+    DROP COLUMN full_text_search;
+
+DROP TABLE post_search, comment_search;
+
+DROP FUNCTION search_vector, text_search_config;
+
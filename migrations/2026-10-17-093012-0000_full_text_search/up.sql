@@ -0,0 +1,83 @@
+-- Full text search vectors for posts and comments. These are kept in separate tables so they
+-- don't need to be loaded together with the post or comment. Full text search is disabled by
+-- default, so they start out empty. Triggers in the replaceable schema fill them for all existing
+-- rows when it's enabled for the site, and keep them up to date only while it stays enabled.
+CREATE TABLE post_search (
+    post_id int PRIMARY KEY REFERENCES post ON UPDATE CASCADE ON DELETE CASCADE,
+    search_vector tsvector NOT NULL
+);
+
+CREATE TABLE comment_search (
+    comment_id int PRIMARY KEY REFERENCES comment ON UPDATE CASCADE ON DELETE CASCADE,
+    search_vector tsvector NOT NULL
+);
+
+-- Returns the text search configuration (stemmer and stop words) for a language code, or `simple`
+-- if Postgres doesn't have one for the language. This and `search_vector` are defined here instead
+-- of in the replaceable schema, because changing them requires recomputing all stored vectors.
+CREATE FUNCTION text_search_config (language_code text)
+    RETURNS regconfig
+    LANGUAGE sql
+    STABLE PARALLEL SAFE RETURN coalesce((
+        SELECT
+            c.oid::regconfig
+        FROM (
+            VALUES ('ar', 'arabic'),
+            ('ca', 'catalan'),
+            ('da', 'danish'),
+            ('de', 'german'),
+            ('el', 'greek'),
+            ('en', 'english'),
+            ('es', 'spanish'),
+            ('eu', 'basque'),
+            ('fi', 'finnish'),
+            ('fr', 'french'),
+            ('ga', 'irish'),
+            ('hi', 'hindi'),
+            ('hu', 'hungarian'),
+            ('hy', 'armenian'),
+            ('id', 'indonesian'),
+            ('it', 'italian'),
+            ('lt', 'lithuanian'),
+            ('nb', 'norwegian'),
+            ('ne', 'nepali'),
+            ('nl', 'dutch'),
+            ('nn', 'norwegian'),
+            ('no', 'norwegian'),
+            ('pt', 'portuguese'),
+            ('ro', 'romanian'),
+            ('ru', 'russian'),
+            ('sr', 'serbian'),
+            ('sv', 'swedish'),
+            ('ta', 'tamil'),
+            ('tr', 'turkish'),
+            ('yi', 'yiddish')) AS m (code, cfgname)
+        JOIN pg_ts_config AS c ON c.cfgname = m.cfgname
+    WHERE
+        m.code = language_code), 'simple'::regconfig);
+
+-- The search vector of a post or comment. Words are included both stemmed according to the
+-- language of the content, and unchanged so that exact matches work regardless of the language.
+-- Title words get a higher weight than body words.
+CREATE FUNCTION search_vector (language_id int, title text, body text)
+    RETURNS tsvector
+    LANGUAGE sql
+    STABLE PARALLEL SAFE RETURN (
+        SELECT
+            setweight(to_tsvector(config, coalesce(title, '')), 'A') || setweight(to_tsvector(config, coalesce(body, '')), 'B') || setweight(to_tsvector('simple', coalesce(title, '')), 'A') || setweight(to_tsvector('simple', coalesce(body, '')), 'B')
+        FROM (
+            SELECT
+                text_search_config ((
+                    SELECT
+                        code
+                    FROM language
+                    WHERE
+                        id = language_id)) AS config) AS c);
+
+CREATE INDEX idx_post_search_vector ON post_search USING gin (search_vector);
+
+CREATE INDEX idx_comment_search_vector ON comment_search USING gin (search_vector);
+
+ALTER TABLE local_site
+    ADD COLUMN full_text_search boolean NOT NULL DEFAULT FALSE;
+
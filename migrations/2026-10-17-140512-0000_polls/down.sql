@@ -0,0 +1,5 @@
+ALTER TABLE post_actions
+    DROP COLUMN poll_voted_at;
+
+DROP TABLE poll_vote, poll_choice, poll;
+
@@ -0,0 +1,37 @@
+-- Polls are posts with a list of choices which users can vote on. They federate as
+-- ActivityPub `Question` objects.
+CREATE TABLE poll (
+    post_id int PRIMARY KEY REFERENCES post ON UPDATE CASCADE ON DELETE CASCADE,
+    multiple_choice boolean NOT NULL DEFAULT FALSE,
+    end_at timestamptz,
+    voter_count int NOT NULL DEFAULT 0,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    -- For local polls, when the vote counts changed without being federated yet
+    pending_update_at timestamptz
+);
+
+CREATE TABLE poll_choice (
+    id serial PRIMARY KEY,
+    post_id int NOT NULL REFERENCES poll ON UPDATE CASCADE ON DELETE CASCADE,
+    name text NOT NULL,
+    vote_count int NOT NULL DEFAULT 0,
+    UNIQUE (post_id, name)
+);
+
+CREATE TABLE poll_vote (
+    poll_choice_id int NOT NULL REFERENCES poll_choice ON UPDATE CASCADE ON DELETE CASCADE,
+    person_id int NOT NULL REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    post_id int NOT NULL REFERENCES poll ON UPDATE CASCADE ON DELETE CASCADE,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (person_id, poll_choice_id)
+);
+
+CREATE INDEX idx_poll_vote_post_person ON poll_vote (post_id, person_id);
+
+CREATE INDEX idx_poll_pending_update ON poll (pending_update_at)
+WHERE
+    pending_update_at IS NOT NULL;
+
+ALTER TABLE post_actions
+    ADD COLUMN poll_voted_at timestamptz;
+
@@ -0,0 +1,5 @@
+ALTER TABLE local_site
+    DROP COLUMN public_edit_history;
+
+DROP TABLE post_history, comment_history;
+
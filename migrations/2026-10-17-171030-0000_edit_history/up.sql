@@ -0,0 +1,25 @@
+-- Previous versions of posts and comments. A new row is inserted by a trigger in the replaceable
+-- schema whenever the text of a post or comment changes.
+CREATE TABLE post_history (
+    id serial PRIMARY KEY,
+    post_id int NOT NULL REFERENCES post ON UPDATE CASCADE ON DELETE CASCADE,
+    name text NOT NULL,
+    body text,
+    url text,
+    published_at timestamptz NOT NULL
+);
+
+CREATE INDEX idx_post_history_post ON post_history (post_id);
+
+CREATE TABLE comment_history (
+    id serial PRIMARY KEY,
+    comment_id int NOT NULL REFERENCES comment ON UPDATE CASCADE ON DELETE CASCADE,
+    content text NOT NULL,
+    published_at timestamptz NOT NULL
+);
+
+CREATE INDEX idx_comment_history_comment ON comment_history (comment_id);
+
+ALTER TABLE local_site
+    ADD COLUMN public_edit_history boolean NOT NULL DEFAULT FALSE;
+
@@ -0,0 +1,45 @@
+CREATE TYPE webhook_event_type_enum AS enum (
+    'NewPost',
+    'NewComment',
+    'NewReport',
+    'ModAction',
+    'NewRegistrationApplication'
+);
+
+-- HTTPS endpoints which receive signed JSON payloads for site events. Webhooks without a
+-- community_id are site-wide and can only be managed by admins.
+CREATE TABLE webhook (
+    id serial PRIMARY KEY,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    creator_id int NOT NULL REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    url text NOT NULL,
+    secret text NOT NULL,
+    events webhook_event_type_enum[] NOT NULL,
+    enabled boolean NOT NULL DEFAULT TRUE,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz
+);
+
+CREATE INDEX idx_webhook_community ON webhook (community_id);
+
+-- Each event sent to a webhook. Failed deliveries are retried with exponential backoff until
+-- next_attempt_at is cleared.
+CREATE TABLE webhook_delivery (
+    id serial PRIMARY KEY,
+    webhook_id int NOT NULL REFERENCES webhook ON UPDATE CASCADE ON DELETE CASCADE,
+    event webhook_event_type_enum NOT NULL,
+    payload text NOT NULL,
+    attempts int NOT NULL DEFAULT 0,
+    next_attempt_at timestamptz DEFAULT now(),
+    last_status int,
+    last_error text,
+    delivered_at timestamptz,
+    published_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_webhook_delivery_webhook ON webhook_delivery (webhook_id, id);
+
+CREATE INDEX idx_webhook_delivery_pending ON webhook_delivery (next_attempt_at)
+WHERE
+    next_attempt_at IS NOT NULL;
+
@@ -0,0 +1,5 @@
+-- The new value is used in the modlog check constraint, which has to happen in a separate
+-- transaction.
+ALTER TYPE modlog_kind
+    ADD VALUE 'ModAutomodAction';
+
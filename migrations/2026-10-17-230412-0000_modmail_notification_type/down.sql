@@ -0,0 +1,33 @@
+-- reverting an enum value addition is not supported by postgres, so recreate the type without it
+ALTER TYPE notification_type_enum RENAME TO notification_type_enum__;
+
+CREATE TYPE notification_type_enum AS ENUM (
+    'Mention',
+    'Reply',
+    'Subscribed',
+    'PrivateMessage',
+    'ModAction'
+);
+
+ALTER TABLE notification
+    DROP CONSTRAINT IF EXISTS notification_check;
+
+ALTER TABLE notification
+    ALTER COLUMN kind DROP DEFAULT,
+    ALTER COLUMN kind TYPE notification_type_enum
+    USING kind::text::notification_type_enum,
+    ALTER COLUMN kind SET DEFAULT 'Reply';
+
+DROP TYPE notification_type_enum__;
+
+ALTER TABLE notification
+    ADD CHECK (((kind = 'Mention'
+        OR kind = 'Reply')
+        AND num_nonnulls (post_id, comment_id, community_id) >= 2)
+        OR (kind = 'PrivateMessage'
+        AND num_nonnulls (private_message_id) = 1)
+        OR (kind = 'Subscribed'
+        AND num_nonnulls (post_id) = 1)
+        OR (kind = 'ModAction'
+        AND num_nonnulls (modlog_id, post_id, comment_id, community_id, instance_id) >= 2));
+
@@ -0,0 +1,22 @@
+DELETE FROM notification
+WHERE kind = 'Modmail';
+
+ALTER TABLE notification
+    DROP CONSTRAINT notification_check,
+    DROP COLUMN modmail_thread_id;
+
+ALTER TABLE notification
+    ADD CHECK (((kind = 'Mention'
+        OR kind = 'Reply')
+        AND num_nonnulls (post_id, comment_id, community_id) >= 2)
+        OR (kind = 'PrivateMessage'
+        AND num_nonnulls (private_message_id) = 1)
+        OR (kind = 'Subscribed'
+        AND num_nonnulls (post_id) = 1)
+        OR (kind = 'ModAction'
+        AND num_nonnulls (modlog_id, post_id, comment_id, community_id, instance_id) >= 2));
+
+DROP TABLE modmail_message;
+
+DROP TABLE modmail_thread;
+
@@ -0,0 +1,56 @@
+-- Conversations between a user and the mod team of a community
+CREATE TABLE modmail_thread (
+    id serial PRIMARY KEY,
+    community_id int NOT NULL REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    creator_id int NOT NULL REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    subject text NOT NULL,
+    -- Optional links to the content which the thread is about
+    post_id int REFERENCES post ON UPDATE CASCADE ON DELETE SET NULL,
+    comment_id int REFERENCES comment ON UPDATE CASCADE ON DELETE SET NULL,
+    modlog_id int REFERENCES modlog ON UPDATE CASCADE ON DELETE SET NULL,
+    archived boolean NOT NULL DEFAULT FALSE,
+    ap_id text UNIQUE NOT NULL,
+    local boolean NOT NULL DEFAULT TRUE,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz
+);
+
+CREATE INDEX idx_modmail_thread_community ON modmail_thread (community_id, published_at DESC);
+
+CREATE INDEX idx_modmail_thread_creator ON modmail_thread (creator_id, published_at DESC);
+
+CREATE TABLE modmail_message (
+    id serial PRIMARY KEY,
+    thread_id int NOT NULL REFERENCES modmail_thread ON UPDATE CASCADE ON DELETE CASCADE,
+    creator_id int NOT NULL REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    -- Messages from the mod team are shown without the name of the individual moderator
+    from_mod_team boolean NOT NULL,
+    content text NOT NULL,
+    ap_id text UNIQUE NOT NULL,
+    local boolean NOT NULL DEFAULT TRUE,
+    published_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_modmail_message_thread ON modmail_message (thread_id, published_at);
+
+ALTER TABLE notification
+    ADD COLUMN modmail_thread_id int REFERENCES modmail_thread ON UPDATE CASCADE ON DELETE CASCADE,
+    DROP CONSTRAINT notification_check;
+
+ALTER TABLE notification
+    ADD CHECK (((kind = 'Mention'
+        OR kind = 'Reply')
+        AND num_nonnulls (post_id, comment_id, community_id) >= 2)
+        OR (kind = 'PrivateMessage'
+        AND num_nonnulls (private_message_id) = 1)
+        OR (kind = 'Subscribed'
+        AND num_nonnulls (post_id) = 1)
+        OR (kind = 'ModAction'
+        AND num_nonnulls (modlog_id, post_id, comment_id, community_id, instance_id) >= 2)
+        OR (kind = 'Modmail'
+        AND num_nonnulls (modmail_thread_id, community_id) = 2));
+
+CREATE INDEX idx_notification_modmail_thread ON notification (modmail_thread_id)
+WHERE
+    modmail_thread_id IS NOT NULL;
+
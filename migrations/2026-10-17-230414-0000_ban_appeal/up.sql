@@ -0,0 +1,22 @@
+-- Appeals by banned users against a ban from a community or from the site
+CREATE TABLE ban_appeal (
+    id serial PRIMARY KEY,
+    -- The ban which is appealed. Each ban can only be appealed once.
+    modlog_id int UNIQUE NOT NULL REFERENCES modlog ON UPDATE CASCADE ON DELETE CASCADE,
+    creator_id int NOT NULL REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    -- Null for site bans, which are handled by admins
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    reason text NOT NULL,
+    resolver_id int REFERENCES person ON UPDATE CASCADE ON DELETE SET NULL,
+    approved boolean NOT NULL DEFAULT FALSE,
+    deny_reason text,
+    resolved_at timestamptz,
+    published_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_ban_appeal_community ON ban_appeal (community_id, published_at DESC);
+
+CREATE INDEX idx_ban_appeal_unresolved ON ban_appeal (published_at)
+WHERE
+    resolved_at IS NULL;
+
@@ -0,0 +1,27 @@
+-- Unpublished posts, comments and private messages. Drafts are stored separately from the real
+-- content tables, so that they never federate or show up in any listing.
+CREATE TABLE draft (
+    id serial PRIMARY KEY,
+    creator_id int NOT NULL REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    -- The target of the draft: a community for posts, a post for comments, or a person for
+    -- private messages
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    post_id int REFERENCES post ON UPDATE CASCADE ON DELETE CASCADE,
+    parent_id int REFERENCES comment ON UPDATE CASCADE ON DELETE CASCADE,
+    recipient_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    name text,
+    url text,
+    body text,
+    alt_text text,
+    nsfw boolean,
+    language_id int REFERENCES
+    LANGUAGE ON UPDATE CASCADE ON DELETE SET NULL,
+    custom_thumbnail text,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz,
+    CHECK (num_nonnulls (community_id, post_id, recipient_id) = 1),
+    CHECK (parent_id IS NULL OR post_id IS NOT NULL)
+);
+
+CREATE INDEX idx_draft_creator ON draft (creator_id, published_at DESC);
+
@@ -0,0 +1,4 @@
+DROP TABLE recurring_post;
+
+DROP TYPE recurring_post_frequency_enum;
+
@@ -0,0 +1,35 @@
+CREATE TYPE recurring_post_frequency_enum AS enum (
+    'Daily',
+    'Weekly',
+    'Monthly'
+);
+
+-- Templates for posts which are published in a community on a fixed schedule, for example weekly
+-- discussion threads. The title and body may contain placeholders which are filled in when the
+-- post is published.
+CREATE TABLE recurring_post (
+    id serial PRIMARY KEY,
+    community_id int NOT NULL REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE,
+    -- The moderator who created the template, and who is the creator of the published posts
+    creator_id int NOT NULL REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    name text NOT NULL,
+    body text,
+    nsfw boolean NOT NULL DEFAULT FALSE,
+    language_id int REFERENCES
+    LANGUAGE ON UPDATE CASCADE ON DELETE SET NULL,
+    frequency recurring_post_frequency_enum NOT NULL,
+    next_publish_at timestamptz NOT NULL,
+    -- Feature each published post in the community, and unfeature the previous one
+    feature boolean NOT NULL DEFAULT FALSE,
+    last_post_id int REFERENCES post ON UPDATE CASCADE ON DELETE SET NULL,
+    enabled boolean NOT NULL DEFAULT TRUE,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz
+);
+
+CREATE INDEX idx_recurring_post_community ON recurring_post (community_id);
+
+CREATE INDEX idx_recurring_post_next_publish ON recurring_post (next_publish_at)
+WHERE
+    enabled;
+
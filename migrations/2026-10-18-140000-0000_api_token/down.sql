@@ -0,0 +1,4 @@
+DROP TABLE api_token;
+
+DROP TYPE api_token_scope_enum;
+
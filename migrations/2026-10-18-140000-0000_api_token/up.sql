@@ -0,0 +1,24 @@
+CREATE TYPE api_token_scope_enum AS enum (
+    'Read',
+    'Post',
+    'Vote',
+    'Moderate',
+    'Admin',
+    'PrivateMessage'
+);
+
+-- Personal access tokens for bots and third-party apps. Unlike login tokens they are limited to
+-- the given scopes, and only a hash of the token is stored.
+CREATE TABLE api_token (
+    id serial PRIMARY KEY,
+    local_user_id int NOT NULL REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE,
+    name text NOT NULL,
+    token_hash text NOT NULL UNIQUE,
+    scopes api_token_scope_enum[] NOT NULL,
+    expires_at timestamptz,
+    last_used_at timestamptz,
+    published_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_api_token_local_user ON api_token (local_user_id);
+
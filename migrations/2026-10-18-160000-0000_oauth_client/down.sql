@@ -0,0 +1,8 @@
+ALTER TABLE api_token
+    DROP COLUMN oauth_client_id,
+    DROP COLUMN refresh_token_hash;
+
+DROP TABLE oauth_authorization_code;
+
+DROP TABLE oauth_client;
+
@@ -0,0 +1,33 @@
+-- Third-party apps which use Lemmy as OAuth2 authorization server. They are registered
+-- dynamically, and need PKCE for every authorization. Public clients have no secret.
+CREATE TABLE oauth_client (
+    id serial PRIMARY KEY,
+    client_id text NOT NULL UNIQUE,
+    client_secret_hash text,
+    name text NOT NULL,
+    client_uri text,
+    redirect_uris text[] NOT NULL,
+    -- The scopes which the client may request
+    scopes api_token_scope_enum[] NOT NULL,
+    published_at timestamptz NOT NULL DEFAULT now()
+);
+
+-- Authorization codes are only valid for a few minutes, and deleted once they are exchanged for
+-- tokens.
+CREATE TABLE oauth_authorization_code (
+    code_hash text PRIMARY KEY,
+    oauth_client_id int NOT NULL REFERENCES oauth_client ON UPDATE CASCADE ON DELETE CASCADE,
+    local_user_id int NOT NULL REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE,
+    redirect_uri text NOT NULL,
+    scopes api_token_scope_enum[] NOT NULL,
+    code_challenge text NOT NULL,
+    expires_at timestamptz NOT NULL,
+    published_at timestamptz NOT NULL DEFAULT now()
+);
+
+-- Access tokens which are issued to OAuth clients are API tokens, which can be renewed with the
+-- refresh token.
+ALTER TABLE api_token
+    ADD COLUMN oauth_client_id int REFERENCES oauth_client ON UPDATE CASCADE ON DELETE CASCADE,
+    ADD COLUMN refresh_token_hash text UNIQUE;
+
@@ -0,0 +1,6 @@
+ALTER TABLE local_site
+    DROP COLUMN require_2fa_for_mods_and_admins;
+
+DROP TABLE passkey_challenge;
+
+DROP TABLE passkey;
@@ -0,0 +1,24 @@
+-- WebAuthn credentials, which can be used as second factor or for passwordless login.
+CREATE TABLE passkey (
+    id serial PRIMARY KEY,
+    local_user_id int NOT NULL REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE,
+    name text NOT NULL,
+    credential_id text NOT NULL UNIQUE,
+    public_key bytea NOT NULL,
+    sign_count bigint NOT NULL DEFAULT 0,
+    last_used_at timestamptz,
+    published_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_passkey_local_user ON passkey (local_user_id);
+
+-- Challenges for passkey registration and login. The user is empty for passwordless login.
+CREATE TABLE passkey_challenge (
+    challenge text PRIMARY KEY,
+    local_user_id int REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE,
+    expires_at timestamptz NOT NULL,
+    published_at timestamptz NOT NULL DEFAULT now()
+);
+
+ALTER TABLE local_site
+    ADD COLUMN require_2fa_for_mods_and_admins boolean NOT NULL DEFAULT FALSE;
@@ -0,0 +1,15 @@
+-- Private key for signing web push requests (VAPID), generated by the server on startup.
+ALTER TABLE secret
+    ADD COLUMN vapid_private_key text;
+
+-- Web push subscriptions of browsers and apps, as returned by `PushManager.subscribe()`.
+CREATE TABLE push_subscription (
+    id serial PRIMARY KEY,
+    local_user_id int NOT NULL REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE,
+    endpoint text NOT NULL UNIQUE,
+    p256dh text NOT NULL,
+    auth text NOT NULL,
+    published_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_push_subscription_local_user ON push_subscription (local_user_id);
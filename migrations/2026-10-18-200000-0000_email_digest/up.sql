@@ -0,0 +1,13 @@
+-- Lets users receive notification emails as a periodic digest instead of one email per event
+CREATE TYPE email_digest_frequency_enum AS enum (
+    'Immediate',
+    'Hourly',
+    'Daily',
+    'Weekly'
+);
+
+ALTER TABLE local_user
+    ADD COLUMN email_digest_frequency email_digest_frequency_enum DEFAULT 'Immediate' NOT NULL,
+    ADD COLUMN email_digest_top_posts boolean DEFAULT FALSE NOT NULL,
+    ADD COLUMN last_email_digest_at timestamptz DEFAULT now() NOT NULL;
+
@@ -0,0 +1,18 @@
+-- Outgoing emails are stored before sending, and retried with backoff if the mail server is unavailable
+CREATE TABLE queued_email (
+    id serial PRIMARY KEY,
+    to_email text NOT NULL,
+    to_name text NOT NULL,
+    subject text NOT NULL,
+    html text NOT NULL,
+    attempts int NOT NULL DEFAULT 0,
+    next_attempt_at timestamptz DEFAULT now(),
+    last_error text,
+    sent_at timestamptz,
+    published_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_queued_email_pending ON queued_email (next_attempt_at)
+WHERE
+    next_attempt_at IS NOT NULL;
+
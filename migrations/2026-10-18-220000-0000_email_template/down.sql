@@ -0,0 +1,4 @@
+DROP TABLE email_template;
+
+DROP TYPE email_template_type_enum;
+
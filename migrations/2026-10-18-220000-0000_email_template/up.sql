@@ -0,0 +1,23 @@
+-- Lets admins override the subject and body of emails, optionally per interface language
+CREATE TYPE email_template_type_enum AS enum (
+    'VerifyEmail',
+    'PasswordReset',
+    'ApplicationApproved',
+    'ApplicationDenied',
+    'NewReport',
+    'Notification'
+);
+
+CREATE TABLE email_template (
+    id serial PRIMARY KEY,
+    template_type email_template_type_enum NOT NULL,
+    -- Interface language code, or null for the template which applies to all other languages
+    language text,
+    subject text NOT NULL,
+    body text NOT NULL,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    updated_at timestamptz
+);
+
+CREATE UNIQUE INDEX idx_email_template_type_language ON email_template (template_type, coalesce(language, ''));
+
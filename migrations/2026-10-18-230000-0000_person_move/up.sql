@@ -0,0 +1,5 @@
+-- Other accounts of the same person, used to verify account moves (ActivityPub alsoKnownAs)
+ALTER TABLE person
+    ADD COLUMN also_known_as text[] NOT NULL DEFAULT '{}',
+    ADD COLUMN moved_to varchar(255);
+
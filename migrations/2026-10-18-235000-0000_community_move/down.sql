@@ -0,0 +1,4 @@
+ALTER TABLE community
+    DROP COLUMN also_known_as,
+    DROP COLUMN moved_to;
+
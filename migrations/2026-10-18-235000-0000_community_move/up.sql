@@ -0,0 +1,5 @@
+-- Old communities which are allowed to move to this community (ActivityPub alsoKnownAs)
+ALTER TABLE community
+    ADD COLUMN also_known_as text[] NOT NULL DEFAULT '{}',
+    ADD COLUMN moved_to varchar(255);
+
@@ -0,0 +1,4 @@
+DROP TABLE federation_send_error;
+
+DROP TABLE federation_queue_control;
+
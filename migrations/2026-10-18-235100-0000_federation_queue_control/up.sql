@@ -0,0 +1,21 @@
+-- Admin requests for the outgoing federation queue of an instance. They are applied by the
+-- federation send manager, which then clears retry_requested_at and skip_to_id.
+CREATE TABLE federation_queue_control (
+    instance_id int PRIMARY KEY REFERENCES instance ON UPDATE CASCADE ON DELETE CASCADE,
+    paused boolean NOT NULL DEFAULT FALSE,
+    retry_requested_at timestamptz,
+    skip_to_id bigint,
+    updated_at timestamptz NOT NULL DEFAULT now()
+);
+
+-- Most recent errors when sending activities, only the last few are kept per instance
+CREATE TABLE federation_send_error (
+    id serial PRIMARY KEY,
+    instance_id int NOT NULL REFERENCES instance ON UPDATE CASCADE ON DELETE CASCADE,
+    activity_id bigint NOT NULL,
+    error text NOT NULL,
+    published_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_federation_send_error_instance ON federation_send_error (instance_id, id DESC);
+
@@ -0,0 +1,2 @@
+DROP TABLE received_activity_log;
+
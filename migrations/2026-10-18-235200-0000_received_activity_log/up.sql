@@ -0,0 +1,19 @@
+-- Raw incoming activities with the result of processing them, for debugging federation issues.
+-- Only filled if enabled in the config, and trimmed regularly.
+CREATE TABLE received_activity_log (
+    id serial PRIMARY KEY,
+    ap_id text,
+    actor_id text,
+    domain text,
+    kind text,
+    -- Empty if the activity was too large
+    data text,
+    signature_verified boolean NOT NULL,
+    success boolean NOT NULL,
+    error text,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    replayed_at timestamptz
+);
+
+CREATE INDEX idx_received_activity_log_domain ON received_activity_log (domain);
+
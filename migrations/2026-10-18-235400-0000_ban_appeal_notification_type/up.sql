@@ -0,0 +1,5 @@
+-- The new value is used in the notification check constraint, which has to happen in a separate
+-- transaction.
+ALTER TYPE notification_type_enum
+    ADD VALUE 'BanAppeal';
+
@@ -0,0 +1,20 @@
+DELETE FROM notification
+WHERE kind = 'BanAppeal';
+
+ALTER TABLE notification
+    DROP CONSTRAINT notification_check,
+    DROP COLUMN ban_appeal_id;
+
+ALTER TABLE notification
+    ADD CHECK (((kind = 'Mention'
+        OR kind = 'Reply')
+        AND num_nonnulls (post_id, comment_id, community_id) >= 2)
+        OR (kind = 'PrivateMessage'
+        AND num_nonnulls (private_message_id) = 1)
+        OR (kind = 'Subscribed'
+        AND num_nonnulls (post_id) = 1)
+        OR (kind = 'ModAction'
+        AND num_nonnulls (modlog_id, post_id, comment_id, community_id, instance_id) >= 2)
+        OR (kind = 'Modmail'
+        AND num_nonnulls (modmail_thread_id, community_id) = 2));
+
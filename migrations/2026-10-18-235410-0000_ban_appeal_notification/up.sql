@@ -0,0 +1,24 @@
+-- Notifies a banned user that their ban appeal was denied
+ALTER TABLE notification
+    ADD COLUMN ban_appeal_id int REFERENCES ban_appeal ON UPDATE CASCADE ON DELETE CASCADE,
+    DROP CONSTRAINT notification_check;
+
+ALTER TABLE notification
+    ADD CHECK (((kind = 'Mention'
+        OR kind = 'Reply')
+        AND num_nonnulls (post_id, comment_id, community_id) >= 2)
+        OR (kind = 'PrivateMessage'
+        AND num_nonnulls (private_message_id) = 1)
+        OR (kind = 'Subscribed'
+        AND num_nonnulls (post_id) = 1)
+        OR (kind = 'ModAction'
+        AND num_nonnulls (modlog_id, post_id, comment_id, community_id, instance_id) >= 2)
+        OR (kind = 'Modmail'
+        AND num_nonnulls (modmail_thread_id, community_id) = 2)
+        OR (kind = 'BanAppeal'
+        AND num_nonnulls (ban_appeal_id) = 1));
+
+CREATE INDEX idx_notification_ban_appeal ON notification (ban_appeal_id)
+WHERE
+    ban_appeal_id IS NOT NULL;
+